@@ -11,6 +11,7 @@ use std::mem::size_of;
 use test::Bencher;
 
 use rand::{Rng, FromEntropy, XorShiftRng};
+use rand::prng::Hc128Rng;
 use rand::distributions::*;
 
 macro_rules! distr_int {
@@ -73,23 +74,44 @@ macro_rules! distr {
     }
 }
 
+macro_rules! distr_arr {
+    ($fnn:ident, $ty:ty, $distr:expr) => {
+        #[bench]
+        fn $fnn(b: &mut Bencher) {
+            let mut rng = XorShiftRng::from_entropy();
+            let distr = $distr;
+
+            b.iter(|| {
+                let mut accum = 0.0;
+                for _ in 0..::RAND_BENCH_N {
+                    let x: $ty = distr.sample(&mut rng);
+                    accum += x[0];
+                }
+                accum
+            });
+            b.bytes = size_of::<$ty>() as u64 * ::RAND_BENCH_N;
+        }
+    }
+}
+
 // uniform
 distr_int!(distr_uniform_i8, i8, Uniform::new(20i8, 100));
 distr_int!(distr_uniform_i16, i16, Uniform::new(-500i16, 2000));
 distr_int!(distr_uniform_i32, i32, Uniform::new(-200_000_000i32, 800_000_000));
 distr_int!(distr_uniform_i64, i64, Uniform::new(3i64, 123_456_789_123));
-#[cfg(feature = "i128_support")]
+#[cfg(any(feature = "i128_support", rustc_1_26))]
 distr_int!(distr_uniform_i128, i128, Uniform::new(-123_456_789_123i128, 123_456_789_123_456_789));
 
 distr_float!(distr_uniform_f32, f32, Uniform::new(2.26f32, 2.319));
 distr_float!(distr_uniform_f64, f64, Uniform::new(2.26f64, 2.319));
+distr!(distr_uniform_char, char, Uniform::new_inclusive('a', 'z'));
 
 // standard
 distr_int!(distr_standard_i8, i8, Standard);
 distr_int!(distr_standard_i16, i16, Standard);
 distr_int!(distr_standard_i32, i32, Standard);
 distr_int!(distr_standard_i64, i64, Standard);
-#[cfg(feature = "i128_support")]
+#[cfg(any(feature = "i128_support", rustc_1_26))]
 distr_int!(distr_standard_i128, i128, Standard);
 
 distr!(distr_standard_bool, bool, Standard);
@@ -102,17 +124,97 @@ distr_float!(distr_open01_f32, f32, Open01);
 distr_float!(distr_open01_f64, f64, Open01);
 distr_float!(distr_openclosed01_f32, f32, OpenClosed01);
 distr_float!(distr_openclosed01_f64, f64, OpenClosed01);
+distr_float!(distr_high_precision01_f32, f32, HighPrecision01);
+distr_float!(distr_high_precision01_f64, f64, HighPrecision01);
 
 // distributions
+distr_float!(distr_exp1, f64, Exp1);
+distr_float!(distr_exp1_f32, f32, Exp1F32);
+distr_float!(distr_exp1_f32_via_f64, f32, Exp1.map(|x: f64| x as f32));
 distr_float!(distr_exp, f64, Exp::new(1.23 * 4.56));
+distr_float!(distr_standard_normal, f64, StandardNormal);
+distr_float!(distr_standard_normal_f32, f32, StandardNormalF32);
+distr_float!(distr_standard_normal_f32_via_f64, f32, StandardNormal.map(|x: f64| x as f32));
 distr_float!(distr_normal, f64, Normal::new(-1.23, 4.56));
 distr_float!(distr_log_normal, f64, LogNormal::new(-1.23, 4.56));
+distr_float!(distr_skew_normal, f64, SkewNormal::new(-1.23, 4.56, 2.0));
+distr_float!(distr_truncated_normal, f64, TruncatedNormal::new(-1.23, 4.56, 0.0, 10.0));
+distr_float!(distr_truncated_normal_tail, f64, TruncatedNormal::new(0.0, 1.0, 5.0, 6.0));
+distr_float!(distr_inverse_gaussian, f64, InverseGaussian::new(1.23, 4.56));
 distr_float!(distr_gamma_large_shape, f64, Gamma::new(10., 1.0));
 distr_float!(distr_gamma_small_shape, f64, Gamma::new(0.1, 1.0));
+distr_float!(distr_beta_small_param, f64, Beta::new(0.1, 0.1));
+distr_float!(distr_beta_large_param, f64, Beta::new(2.0, 5.0));
 distr_float!(distr_cauchy, f64, Cauchy::new(4.2, 6.9));
+distr_float!(distr_cauchy_f32, f32, Cauchy::new(4.2, 6.9));
+distr_float!(distr_gumbel, f64, Gumbel::new(4.2, 6.9));
+distr_float!(distr_gumbel_f32, f32, Gumbel::new(4.2, 6.9));
+distr_float!(distr_laplace, f64, Laplace::new(4.2, 6.9));
+distr_float!(distr_logistic, f64, Logistic::new(4.2, 6.9));
+distr_float!(distr_pareto, f64, Pareto::new(1.0, 2.0));
+distr_float!(distr_weibull, f64, Weibull::new(1.0, 2.0));
+distr_float!(distr_frechet, f64, Frechet::new(0.0, 1.0, 2.0));
+distr_float!(distr_triangular, f64, Triangular::new(0.0, 0.5, 1.0));
+distr_float!(distr_pert, f64, Pert::new(0.0, 0.5, 1.0));
+distr_float!(distr_von_mises, f64, VonMises::new(0.5, 2.0));
 distr_int!(distr_binomial, u64, Binomial::new(20, 0.7));
+distr_int!(distr_binomial_small, u64, Binomial::new(1000000, 1e-6));
+distr_int!(distr_binomial_btpe_1e3, u64, Binomial::new(1000, 0.3));
+distr_int!(distr_binomial_btpe_1e7, u64, Binomial::new(10_000_000, 0.3));
+distr_int!(distr_binomial_btpe_1e12, u64, Binomial::new(1_000_000_000_000, 0.3));
+distr_int!(distr_hypergeometric_small, u64, Hypergeometric::new(52, 4, 5));
+distr_int!(distr_hypergeometric_large, u64, Hypergeometric::new(100_000_000, 10_000_000, 40_000));
+distr_int!(distr_geometric, u64, Geometric::new(0.3));
+distr_int!(distr_negative_binomial, u64, NegativeBinomial::new(2.5, 0.3));
+distr_int!(distr_zipf, u64, Zipf::new(1_000_000_000, 1.1));
+distr_int!(distr_zeta, u64, Zeta::new(2.0));
 distr_int!(distr_poisson, u64, Poisson::new(4.0));
-distr!(distr_bernoulli, bool, Bernoulli::new(0.18));
+distr!(distr_bernoulli, bool, Bernoulli::new(0.18).unwrap());
+distr_arr!(distr_unit_circle, [f64; 2], UnitCircle);
+distr_arr!(distr_unit_sphere_surface, [f64; 3], UnitSphereSurface);
+distr_arr!(distr_unit_ball, [f64; 3], UnitBall);
+distr_arr!(distr_unit_quaternion, [f64; 4], UnitQuaternion);
+
+
+// fill a buffer, sampling each element or with `sample_fill`
+macro_rules! distr_fill {
+    ($fnn_loop:ident, $fnn_fill:ident, $ty:ty, $rng:ident, $distr:expr) => {
+        #[bench]
+        fn $fnn_loop(b: &mut Bencher) {
+            let mut rng = $rng::from_entropy();
+            let distr = $distr;
+            let mut buf = vec![<$ty>::default(); ::RAND_BENCH_N as usize];
+
+            b.iter(|| {
+                for x in buf.iter_mut() {
+                    *x = distr.sample(&mut rng);
+                }
+                buf[::RAND_BENCH_N as usize - 1]
+            });
+            b.bytes = size_of::<$ty>() as u64 * ::RAND_BENCH_N;
+        }
+
+        #[bench]
+        fn $fnn_fill(b: &mut Bencher) {
+            let mut rng = $rng::from_entropy();
+            let distr = $distr;
+            let mut buf = vec![<$ty>::default(); ::RAND_BENCH_N as usize];
+
+            b.iter(|| {
+                distr.sample_fill(&mut rng, &mut buf);
+                buf[::RAND_BENCH_N as usize - 1]
+            });
+            b.bytes = size_of::<$ty>() as u64 * ::RAND_BENCH_N;
+        }
+    }
+}
+
+distr_fill!(fill_loop_standard_u64, fill_standard_u64, u64, XorShiftRng, Standard);
+distr_fill!(fill_loop_standard_f64, fill_standard_f64, f64, XorShiftRng, Standard);
+distr_fill!(fill_loop_uniform_u64, fill_uniform_u64, u64, XorShiftRng, Uniform::new(3u64, 123_456_789_123));
+distr_fill!(fill_loop_standard_u64_hc128, fill_standard_u64_hc128, u64, Hc128Rng, Standard);
+distr_fill!(fill_loop_standard_f64_hc128, fill_standard_f64_hc128, f64, Hc128Rng, Standard);
+distr_fill!(fill_loop_uniform_u64_hc128, fill_uniform_u64_hc128, u64, Hc128Rng, Uniform::new(3u64, 123_456_789_123));
 
 
 // construct and sample from a range
@@ -141,8 +243,12 @@ gen_range_int!(gen_range_i8, i8, -20i8, 100);
 gen_range_int!(gen_range_i16, i16, -500i16, 2000);
 gen_range_int!(gen_range_i32, i32, -200_000_000i32, 800_000_000);
 gen_range_int!(gen_range_i64, i64, 3i64, 123_456_789_123);
-#[cfg(feature = "i128_support")]
+#[cfg(any(feature = "i128_support", rustc_1_26))]
 gen_range_int!(gen_range_i128, i128, -12345678901234i128, 123_456_789_123_456_789);
+// ranges of about a sixth of the type, where a zone rounded to a power of
+// two would reject a third of the values
+gen_range_int!(gen_range_u32_sixth, u32, 0u32, 0x2aaa_aaab);
+gen_range_int!(gen_range_u64_sixth, u64, 0u64, 0x2aaa_aaaa_aaaa_aaab);
 
 #[bench]
 fn dist_iter(b: &mut Bencher) {
@@ -10,9 +10,9 @@ use std::mem::size_of;
 use test::{black_box, Bencher};
 
 use rand::prelude::*;
-use rand::prng::{XorShiftRng, Hc128Rng, IsaacRng, Isaac64Rng, ChaChaRng};
+use rand::prng::{XorShiftRng, Xoroshiro128StarStarRng, Pcg64, Hc128Rng, IsaacRng, Isaac64Rng, ChaChaRng};
 use rand::prng::hc128::Hc128Core;
-use rand::rngs::adapter::ReseedingRng;
+use rand::rngs::adapter::{ReseedingRng, ThresholdMode};
 use rand::rngs::{OsRng, JitterRng, EntropyRng};
 
 macro_rules! gen_bytes {
@@ -33,6 +33,13 @@ macro_rules! gen_bytes {
 }
 
 gen_bytes!(gen_bytes_xorshift, XorShiftRng::from_entropy());
+gen_bytes!(gen_bytes_xoroshiro, Xoroshiro128StarStarRng::from_entropy());
+gen_bytes!(gen_bytes_pcg64, Pcg64::from_entropy());
+// Run with and without `--features simd` to compare the vectorised ChaCha. With
+// `simd`, these fills of 1024 bytes use AVX2 if it is available, and
+// `gen_u32_chacha*` and `gen_u64_chacha*` below use SSE2.
+gen_bytes!(gen_bytes_chacha8, ChaChaRng::new_with_rounds(thread_rng().gen(), 8));
+gen_bytes!(gen_bytes_chacha12, ChaChaRng::new_with_rounds(thread_rng().gen(), 12));
 gen_bytes!(gen_bytes_chacha20, ChaChaRng::from_entropy());
 gen_bytes!(gen_bytes_hc128, Hc128Rng::from_entropy());
 gen_bytes!(gen_bytes_isaac, IsaacRng::from_entropy());
@@ -59,6 +66,10 @@ macro_rules! gen_uint {
 }
 
 gen_uint!(gen_u32_xorshift, u32, XorShiftRng::from_entropy());
+gen_uint!(gen_u32_xoroshiro, u32, Xoroshiro128StarStarRng::from_entropy());
+gen_uint!(gen_u32_pcg64, u32, Pcg64::from_entropy());
+gen_uint!(gen_u32_chacha8, u32, ChaChaRng::new_with_rounds(thread_rng().gen(), 8));
+gen_uint!(gen_u32_chacha12, u32, ChaChaRng::new_with_rounds(thread_rng().gen(), 12));
 gen_uint!(gen_u32_chacha20, u32, ChaChaRng::from_entropy());
 gen_uint!(gen_u32_hc128, u32, Hc128Rng::from_entropy());
 gen_uint!(gen_u32_isaac, u32, IsaacRng::from_entropy());
@@ -68,6 +79,10 @@ gen_uint!(gen_u32_small, u32, SmallRng::from_entropy());
 gen_uint!(gen_u32_os, u32, OsRng::new().unwrap());
 
 gen_uint!(gen_u64_xorshift, u64, XorShiftRng::from_entropy());
+gen_uint!(gen_u64_xoroshiro, u64, Xoroshiro128StarStarRng::from_entropy());
+gen_uint!(gen_u64_pcg64, u64, Pcg64::from_entropy());
+gen_uint!(gen_u64_chacha8, u64, ChaChaRng::new_with_rounds(thread_rng().gen(), 8));
+gen_uint!(gen_u64_chacha12, u64, ChaChaRng::new_with_rounds(thread_rng().gen(), 12));
 gen_uint!(gen_u64_chacha20, u64, ChaChaRng::from_entropy());
 gen_uint!(gen_u64_hc128, u64, Hc128Rng::from_entropy());
 gen_uint!(gen_u64_isaac, u64, IsaacRng::from_entropy());
@@ -101,10 +116,14 @@ macro_rules! init_gen {
 }
 
 init_gen!(init_xorshift, XorShiftRng);
+init_gen!(init_xoroshiro, Xoroshiro128StarStarRng);
+init_gen!(init_pcg64, Pcg64);
 init_gen!(init_hc128, Hc128Rng);
 init_gen!(init_isaac, IsaacRng);
 init_gen!(init_isaac64, Isaac64Rng);
 init_gen!(init_chacha, ChaChaRng);
+init_gen!(init_std, StdRng);
+init_gen!(init_small, SmallRng);
 
 #[bench]
 fn init_jitter(b: &mut Bencher) {
@@ -134,11 +153,14 @@ fn reseeding_hc128_bytes(b: &mut Bencher) {
 
 macro_rules! reseeding_uint {
     ($fnn:ident, $ty:ty) => {
+        reseeding_uint!($fnn, $ty, |rng| rng);
+    };
+    ($fnn:ident, $ty:ty, $config:expr) => {
         #[bench]
         fn $fnn(b: &mut Bencher) {
-            let mut rng = ReseedingRng::new(Hc128Core::from_entropy(),
-                                            RESEEDING_THRESHOLD,
-                                            EntropyRng::new());
+            let mut rng = $config(ReseedingRng::new(Hc128Core::from_entropy(),
+                                                    RESEEDING_THRESHOLD,
+                                                    EntropyRng::new()));
             b.iter(|| {
                 let mut accum: $ty = 0;
                 for _ in 0..RAND_BENCH_N {
@@ -153,6 +175,13 @@ macro_rules! reseeding_uint {
 
 reseeding_uint!(reseeding_hc128_u32, u32);
 reseeding_uint!(reseeding_hc128_u64, u64);
+// Fork detection and counting calls add a check to every call, which the
+// default configuration used by `thread_rng` (see `thread_rng_u32`) avoids.
+#[cfg(unix)]
+reseeding_uint!(reseeding_hc128_fork_detection_u32, u32,
+                |rng: ReseedingRng<_, _>| rng.with_fork_detection());
+reseeding_uint!(reseeding_hc128_calls_u32, u32,
+                |rng: ReseedingRng<_, _>| rng.with_threshold_mode(ThresholdMode::Calls));
 
 
 macro_rules! threadrng_uint {
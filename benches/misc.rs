@@ -36,10 +36,32 @@ fn misc_gen_bool_var(b: &mut Bencher) {
     })
 }
 
+#[bench]
+fn misc_gen_bool_fair(b: &mut Bencher) {
+    let mut rng = StdRng::from_rng(&mut thread_rng()).unwrap();
+    b.iter(|| {
+        let mut accum = true;
+        for _ in 0..::RAND_BENCH_N {
+            accum ^= rng.gen::<bool>();
+        }
+        accum
+    })
+}
+
+#[bench]
+fn misc_gen_bools(b: &mut Bencher) {
+    let mut rng = StdRng::from_rng(&mut thread_rng()).unwrap();
+    let mut buf = [false; RAND_BENCH_N as usize];
+    b.iter(|| {
+        rng.gen_bools(&mut buf);
+        buf[0]
+    })
+}
+
 #[bench]
 fn misc_bernoulli_const(b: &mut Bencher) {
     let mut rng = StdRng::from_rng(&mut thread_rng()).unwrap();
-    let d = rand::distributions::Bernoulli::new(0.18);
+    let d = rand::distributions::Bernoulli::new(0.18).unwrap();
     b.iter(|| {
         // Can be evaluated at compile time.
         let mut accum = true;
@@ -57,7 +79,7 @@ fn misc_bernoulli_var(b: &mut Bencher) {
         let mut accum = true;
         let mut p = 0.18;
         for _ in 0..::RAND_BENCH_N {
-            let d = rand::distributions::Bernoulli::new(p);
+            let d = rand::distributions::Bernoulli::new(p).unwrap();
             accum ^= rng.sample(d);
             p += 0.0001;
         }
@@ -85,12 +107,36 @@ sample_binomial!(misc_binomial_100, 100, 0.99);
 sample_binomial!(misc_binomial_1000, 1000, 0.01);
 sample_binomial!(misc_binomial_1e12, 1000_000_000_000, 0.2);
 
+#[bench]
+fn misc_gen_index_var(b: &mut Bencher) {
+    let mut rng = SmallRng::from_rng(thread_rng()).unwrap();
+    b.iter(|| {
+        let mut accum = 0;
+        for i in 1..(::RAND_BENCH_N as usize + 1) {
+            accum ^= rng.gen_index(i);
+        }
+        accum
+    })
+}
+
+#[bench]
+fn misc_gen_range_var(b: &mut Bencher) {
+    let mut rng = SmallRng::from_rng(thread_rng()).unwrap();
+    b.iter(|| {
+        let mut accum = 0;
+        for i in 1..(::RAND_BENCH_N as usize + 1) {
+            accum ^= rng.gen_range(0, i);
+        }
+        accum
+    })
+}
+
 #[bench]
 fn misc_shuffle_100(b: &mut Bencher) {
     let mut rng = SmallRng::from_rng(thread_rng()).unwrap();
     let x : &mut [usize] = &mut [1; 100];
     b.iter(|| {
-        rng.shuffle(x);
+        x.shuffle(&mut rng);
         x[0]
     })
 }
@@ -122,6 +168,47 @@ fn misc_sample_slice_ref_10_of_100(b: &mut Bencher) {
     })
 }
 
+// Allocating and non-allocating ways to sample a few elements repeatedly
+#[bench]
+fn misc_sample_slice_8_of_100(b: &mut Bencher) {
+    let mut rng = SmallRng::from_rng(thread_rng()).unwrap();
+    let x : &[usize] = &[1; 100];
+    b.iter(|| {
+        sample_slice(&mut rng, x, 8)
+    })
+}
+
+#[bench]
+fn misc_sample_slice_fill_8_of_100(b: &mut Bencher) {
+    let mut rng = SmallRng::from_rng(thread_rng()).unwrap();
+    let x : &[usize] = &[1; 100];
+    let mut out = [0usize; 8];
+    b.iter(|| {
+        sample_slice_fill(&mut rng, x, &mut out);
+        out[0]
+    })
+}
+
+#[bench]
+fn misc_sample_slice_fill_40_of_100(b: &mut Bencher) {
+    let mut rng = SmallRng::from_rng(thread_rng()).unwrap();
+    let x : &[usize] = &[1; 100];
+    let mut out = [0usize; 40];
+    b.iter(|| {
+        sample_slice_fill(&mut rng, x, &mut out);
+        out[0]
+    })
+}
+
+#[bench]
+fn misc_choose_multiple_8_of_100(b: &mut Bencher) {
+    let mut rng = SmallRng::from_rng(thread_rng()).unwrap();
+    let x : &[usize] = &[1; 100];
+    b.iter(|| {
+        x.choose_multiple(&mut rng, 8).sum::<usize>()
+    })
+}
+
 macro_rules! sample_indices {
     ($name:ident, $amount:expr, $length:expr) => {
         #[bench]
@@ -138,6 +225,36 @@ sample_indices!(misc_sample_indices_10_of_1k, 10, 1000);
 sample_indices!(misc_sample_indices_50_of_1k, 50, 1000);
 sample_indices!(misc_sample_indices_100_of_1k, 100, 1000);
 
+// `index::sample` on both sides of the crossover points between its
+// algorithms. Floyd's algorithm is used for up to 48 indices unless `length`
+// is small; beyond that the in-place shuffle is used if `length` is less than
+// 130 times `amount`, and rejection sampling otherwise.
+macro_rules! sample_index {
+    ($name:ident, $amount:expr, $length:expr) => {
+        #[bench]
+        fn $name(b: &mut Bencher) {
+            let mut rng = SmallRng::from_rng(thread_rng()).unwrap();
+            b.iter(|| {
+                index::sample(&mut rng, $length, $amount)
+            })
+        }
+    }
+}
+
+// Floyd's algorithm and the in-place shuffle for small amounts
+sample_index!(misc_sample_index_20_of_1k_floyd, 20, 1000);
+sample_index!(misc_sample_index_40_of_1k_inplace, 40, 1000);
+sample_index!(misc_sample_index_40_of_1m_floyd, 40, 1_000_000);
+// Floyd's algorithm and rejection sampling
+sample_index!(misc_sample_index_48_of_1m_floyd, 48, 1_000_000);
+sample_index!(misc_sample_index_64_of_1m_rejection, 64, 1_000_000);
+// The in-place shuffle and rejection sampling
+sample_index!(misc_sample_index_64_of_10k_rejection, 64, 10_000);
+sample_index!(misc_sample_index_100_of_10k_inplace, 100, 10_000);
+sample_index!(misc_sample_index_3k_of_1m_rejection, 3000, 1_000_000);
+sample_index!(misc_sample_index_10k_of_1m_inplace, 10_000, 1_000_000);
+sample_index!(misc_sample_index_900_of_1k_inplace, 900, 1000);
+
 #[bench]
 fn gen_1k_iter_repeat(b: &mut Bencher) {
     use std::iter;
@@ -0,0 +1,46 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::env;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rustc-check-cfg=cfg(rustc_1_26)");
+    println!("cargo:rustc-check-cfg=cfg(rustc_1_28)");
+    println!("cargo:rustc-check-cfg=cfg(rustc_1_55)");
+    // Set with `RUSTFLAGS="--cfg chacha_scalar"` to test the scalar ChaCha
+    // implementation with the `simd` feature.
+    println!("cargo:rustc-check-cfg=cfg(chacha_scalar)");
+    let minor = rustc_minor_version().unwrap_or(0);
+    // `i128` and `u128` are stable since Rust 1.26. Older compilers need the
+    // `i128_support` feature together with a nightly compiler.
+    if minor >= 26 {
+        println!("cargo:rustc-cfg=rustc_1_26");
+    }
+    // The `NonZero*` integer types are stable since Rust 1.28.
+    if minor >= 28 {
+        println!("cargo:rustc-cfg=rustc_1_28");
+    }
+    // Const generics are stable since Rust 1.51, and `array::map` since 1.55.
+    if minor >= 55 {
+        println!("cargo:rustc-cfg=rustc_1_55");
+    }
+}
+
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC")?;
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = String::from_utf8(output.stdout).ok()?;
+    let mut pieces = version.split('.');
+    if pieces.next() != Some("rustc 1") {
+        return None;
+    }
+    pieces.next()?.parse().ok()
+}
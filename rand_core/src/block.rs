@@ -170,6 +170,25 @@ impl<R: BlockRngCore> BlockRng<R> {
         self.core.generate(&mut self.results);
         self.index = index;
     }
+
+    /// Get the buffer of generated results.
+    ///
+    /// Only the results from `index()` onwards are still to be returned.
+    /// Together with `core` this exposes the full state of the RNG, for
+    /// example to save it and later `restore` it.
+    pub fn results(&self) -> &R::Results {
+        &self.results
+    }
+
+    /// Replace the result buffer and set the index into it, as previously
+    /// obtained from `results()` and `index()`.
+    ///
+    /// Panics if `index` is larger than the size of the result buffer.
+    pub fn restore(&mut self, results: R::Results, index: usize) {
+        assert!(index <= results.as_ref().len());
+        self.results = results;
+        self.index = index;
+    }
 }
 
 impl<R: BlockRngCore<Item=u32>> RngCore for BlockRng<R>
@@ -378,6 +397,36 @@ impl<R: BlockRngCore> BlockRng64<R> {
         self.index = index;
         self.half_used = false;
     }
+
+    /// Get the buffer of generated results.
+    ///
+    /// Only the results from `index()` onwards are still to be returned, with
+    /// the exception of the upper half of the previous result if
+    /// `half_used()`.
+    pub fn results(&self) -> &R::Results {
+        &self.results
+    }
+
+    /// Returns `true` if only the lower half of the previous result has been
+    /// consumed by `next_u32`.
+    pub fn half_used(&self) -> bool {
+        self.half_used
+    }
+
+    /// Replace the result buffer and set the index into it, as previously
+    /// obtained from `results()`, `index()` and `half_used()`.
+    ///
+    /// Panics if `index` is larger than the size of the result buffer, or if
+    /// `half_used` is set while `index` is zero.
+    pub fn restore(&mut self, results: R::Results, index: usize,
+                   half_used: bool)
+    {
+        assert!(index <= results.as_ref().len());
+        assert!(index > 0 || !half_used);
+        self.results = results;
+        self.index = index;
+        self.half_used = half_used;
+    }
 }
 
 impl<R: BlockRngCore<Item=u64>> RngCore for BlockRng64<R>
@@ -18,6 +18,15 @@ use std::error::Error as stdError;
 use std::io;
 
 /// Error kind which can be matched over.
+///
+/// New kinds may be added in the future, so rather than matching over the
+/// kind, code deciding whether to retry should use the classification of
+/// [`severity`], [`is_recoverable`] and [`should_wait`], which covers every
+/// kind.
+///
+/// [`severity`]: #method.severity
+/// [`is_recoverable`]: #method.is_recoverable
+/// [`should_wait`]: #method.should_wait
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
 pub enum ErrorKind {
     /// Feature is not available; not recoverable.
@@ -54,16 +63,55 @@ pub enum ErrorKind {
 impl ErrorKind {
     /// True if this kind of error may resolve itself on retry.
     /// 
-    /// See also `should_wait()`.
+    /// This is the same as `is_recoverable()`. See also `should_wait()`.
     pub fn should_retry(self) -> bool {
-        self != ErrorKind::Unavailable
+        self.is_recoverable()
     }
     
     /// True if we should retry but wait before retrying
     /// 
     /// This implies `should_retry()` is true.
     pub fn should_wait(self) -> bool {
-        self == ErrorKind::NotReady
+        self.severity() == ErrorSeverity::Wait
+    }
+
+    /// True if this kind of error may resolve itself on retry, i.e. for all
+    /// kinds except `Unavailable`.
+    ///
+    /// A robust retry loop retries recoverable errors a limited number of
+    /// times, waiting first if `should_wait()` is true; see `severity()` for
+    /// a finer classification.
+    pub fn is_recoverable(self) -> bool {
+        self.severity() != ErrorSeverity::Permanent
+    }
+
+    /// True if this kind of error will not resolve itself on retry, i.e.
+    /// only for `Unavailable`.
+    ///
+    /// This is the opposite of `is_recoverable()`.
+    pub fn is_permanent(self) -> bool {
+        !self.is_recoverable()
+    }
+
+    /// How severe this kind of error is, which determines how to handle it.
+    ///
+    /// ```
+    /// use rand_core::{ErrorKind, ErrorSeverity};
+    ///
+    /// assert_eq!(ErrorKind::Transient.severity(), ErrorSeverity::Transient);
+    /// assert_eq!(ErrorKind::NotReady.severity(), ErrorSeverity::Wait);
+    /// assert_eq!(ErrorKind::Unexpected.severity(), ErrorSeverity::Uncertain);
+    /// assert_eq!(ErrorKind::Unavailable.severity(), ErrorSeverity::Permanent);
+    /// assert!(ErrorSeverity::Transient < ErrorSeverity::Permanent);
+    /// ```
+    pub fn severity(self) -> ErrorSeverity {
+        match self {
+            ErrorKind::Unavailable => ErrorSeverity::Permanent,
+            ErrorKind::Unexpected => ErrorSeverity::Uncertain,
+            ErrorKind::Transient => ErrorSeverity::Transient,
+            ErrorKind::NotReady => ErrorSeverity::Wait,
+            ErrorKind::__Nonexhaustive => unreachable!(),
+        }
     }
     
     /// A description of this error kind
@@ -78,6 +126,24 @@ impl ErrorKind {
     }
 }
 
+/// How severe an error is, as returned by [`ErrorKind::severity`].
+///
+/// The variants are ordered from least to most severe, and each suggests a
+/// way to handle the error when retrying.
+///
+/// [`ErrorKind::severity`]: enum.ErrorKind.html#method.severity
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Copy, Clone)]
+pub enum ErrorSeverity {
+    /// The error will likely be resolved by retrying immediately.
+    Transient,
+    /// The error will likely be resolved by retrying after waiting a little.
+    Wait,
+    /// The cause of the error is unknown; it may be resolved by retrying a
+    /// few times, but this is not likely.
+    Uncertain,
+    /// The error will not be resolved by retrying.
+    Permanent,
+}
 
 /// Error type of random number generators
 /// 
@@ -177,3 +243,32 @@ impl From<Error> for io::Error {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{ErrorKind, ErrorSeverity};
+
+    #[test]
+    fn test_error_kind_classification() {
+        // kind, severity, recoverable, should_wait
+        let kinds = [
+            (ErrorKind::Unavailable, ErrorSeverity::Permanent, false, false),
+            (ErrorKind::Unexpected, ErrorSeverity::Uncertain, true, false),
+            (ErrorKind::Transient, ErrorSeverity::Transient, true, false),
+            (ErrorKind::NotReady, ErrorSeverity::Wait, true, true),
+        ];
+        for &(kind, severity, recoverable, wait) in kinds.iter() {
+            assert_eq!(kind.severity(), severity);
+            assert_eq!(kind.is_recoverable(), recoverable);
+            assert_eq!(kind.is_permanent(), !recoverable);
+            assert_eq!(kind.should_retry(), recoverable);
+            assert_eq!(kind.should_wait(), wait);
+            // Waiting implies retrying
+            assert!(!kind.should_wait() || kind.should_retry());
+        }
+
+        assert!(ErrorSeverity::Transient < ErrorSeverity::Wait);
+        assert!(ErrorSeverity::Wait < ErrorSeverity::Uncertain);
+        assert!(ErrorSeverity::Uncertain < ErrorSeverity::Permanent);
+    }
+}
@@ -55,7 +55,7 @@ use core::convert::AsMut;
 
 #[cfg(all(feature="alloc", not(feature="std")))] use alloc::boxed::Box;
 
-pub use error::{ErrorKind, Error};
+pub use error::{ErrorKind, ErrorSeverity, Error};
 
 
 mod error;
@@ -0,0 +1,246 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! `#[derive(Distribution)]` and `#[derive(SampleUniform)]` for the [rand]
+//! library.
+//!
+//! This crate should not be used directly; enable the `derive` feature of
+//! rand and use the derive macros re-exported as
+//! `rand::distributions::Distribution` and
+//! `rand::distributions::uniform::SampleUniform`.
+//!
+//! # `Distribution`
+//!
+//! Deriving `Distribution` for a struct implements `Distribution<Struct>`
+//! for `Standard`, sampling each field from `Standard` in the order of
+//! declaration. A field can be sampled from another distribution with the
+//! `#[rand(dist = "...")]` attribute, where the string is an expression
+//! evaluating to the distribution; it is evaluated for each sample.
+//!
+//! ```ignore
+//! #[derive(Distribution)]
+//! struct Input {
+//!     flag: bool,
+//!     #[rand(dist = "Uniform::new(0, 10)")]
+//!     len: usize,
+//! }
+//! ```
+//!
+//! # `SampleUniform`
+//!
+//! Deriving `SampleUniform` for a struct with a single field makes
+//! `Uniform`, `Rng::gen_range` and related methods work for the struct, by
+//! sampling the field from its own uniform back-end. This is meant for
+//! newtypes around numbers. The back-end is a new tuple struct named after
+//! the struct with the prefix `Uniform`, with the same visibility. Structs
+//! with generic parameters are not supported.
+//!
+//! ```ignore
+//! /// An amount in cents.
+//! #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, SampleUniform)]
+//! pub struct Money(i64);
+//!
+//! // Also generated:
+//! // pub struct UniformMoney(UniformInt<i64>);
+//! let price = rng.gen_range(Money(100), Money(1000));
+//! ```
+//!
+//! [rand]: https://crates.io/crates/rand
+
+#![doc(html_logo_url = "https://www.rust-lang.org/logos/rust-logo-128x128-blk.png",
+       html_favicon_url = "https://www.rust-lang.org/favicon.ico",
+       html_root_url = "https://docs.rs/rand_derive/0.1.0")]
+
+#![deny(missing_docs)]
+#![deny(missing_debug_implementations)]
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use] extern crate quote;
+extern crate syn;
+
+use proc_macro2::TokenStream;
+use syn::{Data, DeriveInput, Fields, Lit, Meta, NestedMeta};
+use syn::spanned::Spanned;
+
+/// Implement `Distribution<T>` for `Standard` for a struct `T`.
+///
+/// See the [crate documentation](index.html).
+#[proc_macro_derive(Distribution, attributes(rand))]
+pub fn derive_distribution(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Implement `SampleUniform` for a struct with a single field.
+///
+/// See the [crate documentation](index.html).
+#[proc_macro_derive(SampleUniform)]
+pub fn derive_sample_uniform(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    match expand_sample_uniform(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream, syn::Error> {
+    let name = &input.ident;
+    let fields = match input.data {
+        Data::Struct(ref data) => &data.fields,
+        _ => return Err(syn::Error::new(input.span(),
+                "#[derive(Distribution)] is only supported for structs")),
+    };
+
+    let mut generics = input.generics.clone();
+    let mut samples = Vec::new();
+    for field in fields.iter() {
+        let sample = match field_distribution(field)? {
+            Some(dist) => quote! { ::rand::Rng::sample(rng, #dist) },
+            None => {
+                let ty = &field.ty;
+                generics.make_where_clause().predicates.push(syn::parse_quote! {
+                    ::rand::distributions::Standard: ::rand::distributions::Distribution<#ty>
+                });
+                quote! { ::rand::Rng::gen(rng) }
+            }
+        };
+        samples.push(sample);
+    }
+
+    // Struct expressions evaluate their fields in the order written, so the
+    // fields are sampled in the order of declaration.
+    let body = match *fields {
+        Fields::Named(ref fields) => {
+            let names = fields.named.iter().map(|field| &field.ident);
+            quote! { #name { #(#names: #samples),* } }
+        }
+        Fields::Unnamed(_) => quote! { #name ( #(#samples),* ) },
+        Fields::Unit => quote! { #name },
+    };
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::rand::distributions::Distribution<#name #ty_generics>
+            for ::rand::distributions::Standard #where_clause
+        {
+            #[allow(unused_variables)]
+            fn sample<R: ::rand::Rng + ?Sized>(&self, rng: &mut R) -> #name #ty_generics {
+                #body
+            }
+        }
+    })
+}
+
+fn expand_sample_uniform(input: &DeriveInput) -> Result<TokenStream, syn::Error> {
+    let name = &input.ident;
+    let fields = match input.data {
+        Data::Struct(ref data) if data.fields.iter().count() == 1 => &data.fields,
+        _ => return Err(syn::Error::new(input.span(),
+                "#[derive(SampleUniform)] is only supported for structs with a \
+                 single field")),
+    };
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(input.generics.span(),
+                "#[derive(SampleUniform)] is not supported for generic structs"));
+    }
+
+    let field = fields.iter().next().unwrap();
+    let ty = &field.ty;
+    let (access, construct) = match field.ident {
+        Some(ref ident) => (quote! { #ident }, quote! { #name { #ident: x } }),
+        None => {
+            let index = syn::Index::from(0);
+            (quote! { #index }, quote! { #name(x) })
+        }
+    };
+
+    let vis = &input.vis;
+    let sampler = syn::Ident::new(&format!("Uniform{}", name), name.span());
+    let doc = format!("The `UniformSampler` back-end for `{}`.", name);
+    let inner = quote! {
+        <#ty as ::rand::distributions::uniform::SampleUniform>::Sampler
+    };
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(Clone, Copy, Debug)]
+        #vis struct #sampler(#inner);
+
+        impl ::rand::distributions::uniform::SampleUniform for #name {
+            type Sampler = #sampler;
+        }
+
+        impl ::rand::distributions::uniform::UniformSampler for #sampler {
+            type X = #name;
+
+            #[inline]
+            fn new(low: #name, high: #name) -> #sampler {
+                #sampler(<#inner as ::rand::distributions::uniform::UniformSampler>
+                         ::new(low.#access, high.#access))
+            }
+
+            #[inline]
+            fn new_inclusive(low: #name, high: #name) -> #sampler {
+                #sampler(<#inner as ::rand::distributions::uniform::UniformSampler>
+                         ::new_inclusive(low.#access, high.#access))
+            }
+
+            #[inline]
+            fn sample<R: ::rand::Rng + ?Sized>(&self, rng: &mut R) -> #name {
+                let x = <#inner as ::rand::distributions::uniform::UniformSampler>
+                    ::sample(&self.0, rng);
+                #construct
+            }
+
+            #[inline]
+            fn sample_single<R: ::rand::Rng + ?Sized>(low: #name, high: #name, rng: &mut R)
+                -> #name
+            {
+                let x = <#inner as ::rand::distributions::uniform::UniformSampler>
+                    ::sample_single(low.#access, high.#access, rng);
+                #construct
+            }
+        }
+    })
+}
+
+/// Parse the `#[rand(dist = "...")]` attribute of a field, if any.
+fn field_distribution(field: &syn::Field) -> Result<Option<TokenStream>, syn::Error> {
+    let mut dist = None;
+    for attr in field.attrs.iter().filter(|attr| attr.path.is_ident("rand")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => return Err(syn::Error::new(meta.span(),
+                    "expected `#[rand(dist = \"...\")]`")),
+        };
+        for nested in list.nested.iter() {
+            match *nested {
+                NestedMeta::Meta(Meta::NameValue(ref nv)) if nv.path.is_ident("dist") => {
+                    let expr = match nv.lit {
+                        Lit::Str(ref s) => s.parse::<syn::Expr>()?,
+                        ref lit => return Err(syn::Error::new(lit.span(),
+                                "expected a string containing an expression")),
+                    };
+                    if dist.is_some() {
+                        return Err(syn::Error::new(nv.span(),
+                                "duplicate `dist` attribute"));
+                    }
+                    dist = Some(quote! { #expr });
+                }
+                ref other => return Err(syn::Error::new(other.span(),
+                        "unknown attribute, expected `dist = \"...\"`")),
+            }
+        }
+    }
+    Ok(dist)
+}
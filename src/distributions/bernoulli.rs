@@ -11,6 +11,7 @@
 
 use Rng;
 use distributions::Distribution;
+use core::fmt;
 
 /// The Bernoulli distribution.
 ///
@@ -21,7 +22,7 @@ use distributions::Distribution;
 /// ```rust
 /// use rand::distributions::{Bernoulli, Distribution};
 ///
-/// let d = Bernoulli::new(0.3);
+/// let d = Bernoulli::new(0.3).unwrap();
 /// let v = d.sample(&mut rand::thread_rng());
 /// println!("{} is from a Bernoulli distribution", v);
 /// ```
@@ -37,12 +38,64 @@ pub struct Bernoulli {
     p_int: u64,
 }
 
+// To sample from the Bernoulli distribution we use a method that compares a
+// random `u64` value `v < (p * 2^64)`.
+//
+// If `p == 1.0`, the integer `v` to compare against can not represented as a
+// `u64`. We manually set it to `u64::MAX` instead (2^64 - 1 instead of 2^64).
+// Note that  value of `p < 1.0` can never result in `u64::MAX`, because an
+// `f64` only has 53 bits of precision, and the next largest value of `p` will
+// result in `2^64 - 2048`.
+//
+// Also there is a 100% theoretical concern: if someone consistenly wants to
+// generate `true` using the Bernoulli distribution (i.e. by using a probability
+// of `1.0`), just using `u64::MAX` is not enough. On average it would return
+// false once every 2^64 iterations. Some people apparently care about this
+// case.
+//
+// That is why we special-case `u64::MAX` to always return `true`, without using
+// the RNG, and pay the performance price for all uses that *are* reasonable.
+// Luckily, if `new()` and `sample` are close, the compiler can optimize out the
+// extra check.
+const ALWAYS_TRUE: u64 = ::core::u64::MAX;
+
+// This is just `2.0.powi(64)`, but written this way because it is not available
+// in `no_std` mode.
+const SCALE: f64 = 2.0 * (1u64 << 63) as f64;
+
+/// Error type returned from `Bernoulli::new` and `Bernoulli::from_ratio`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BernoulliError {
+    /// `p < 0`, `p > 1` or `p` is NaN.
+    InvalidProbability,
+}
+
+impl BernoulliError {
+    fn msg(&self) -> &str {
+        match *self {
+            BernoulliError::InvalidProbability =>
+                "Bernoulli probability not in the range [0, 1]",
+        }
+    }
+}
+
+impl fmt::Display for BernoulliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+#[cfg(feature="std")]
+impl ::std::error::Error for BernoulliError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
 impl Bernoulli {
     /// Construct a new `Bernoulli` with the given probability of success `p`.
     ///
-    /// # Panics
-    ///
-    /// If `p < 0` or `p > 1`.
+    /// Returns an error if `p < 0`, `p > 1` or `p` is NaN.
     ///
     /// # Precision
     ///
@@ -53,19 +106,46 @@ impl Bernoulli {
     /// a multiple of 2<sup>-64</sup>. (Note that not all multiples of
     /// 2<sup>-64</sup> in `[0, 1]` can be represented as a `f64`.)
     #[inline]
-    pub fn new(p: f64) -> Bernoulli {
-        assert!((p >= 0.0) & (p <= 1.0), "Bernoulli::new not called with 0 <= p <= 0");
-        // Technically, this should be 2^64 or `u64::MAX + 1` because we compare
-        // using `<` when sampling. However, `u64::MAX` rounds to an `f64`
-        // larger than `u64::MAX` anyway.
-        const MAX_P_INT: f64 = ::core::u64::MAX as f64;
-        let p_int = if p < 1.0 {
-            (p * MAX_P_INT) as u64
-        } else {
-            // Avoid overflow: `MAX_P_INT` cannot be represented as u64.
-            ::core::u64::MAX
-        };
-        Bernoulli { p_int }
+    pub fn new(p: f64) -> Result<Bernoulli, BernoulliError> {
+        // Written this way so that NaN is rejected as well.
+        if !(p >= 0.0 && p < 1.0) {
+            if p == 1.0 {
+                return Ok(Bernoulli { p_int: ALWAYS_TRUE });
+            }
+            return Err(BernoulliError::InvalidProbability);
+        }
+        Ok(Bernoulli { p_int: (p * SCALE) as u64 })
+    }
+
+    /// Construct a new `Bernoulli` with the probability of success of
+    /// `numerator`-in-`denominator`. I.e. `new_ratio(2, 3)` will return
+    /// a `Bernoulli` with a 2-in-3 chance, or about 67%, of returning `true`.
+    ///
+    /// Unlike [`new`], the threshold is computed with integer arithmetic, so
+    /// the result is exactly `numerator / denominator` rounded down to a
+    /// multiple of 2<sup>-64</sup>.
+    ///
+    /// Returns an error if `denominator == 0` or `numerator > denominator`.
+    ///
+    /// [`new`]: struct.Bernoulli.html#method.new
+    #[inline]
+    pub fn from_ratio(numerator: u32, denominator: u32)
+        -> Result<Bernoulli, BernoulliError>
+    {
+        if denominator == 0 || numerator > denominator {
+            return Err(BernoulliError::InvalidProbability);
+        }
+        if numerator == denominator {
+            return Ok(Bernoulli { p_int: ALWAYS_TRUE });
+        }
+        // Long division computing `floor(numerator * 2^64 / denominator)` in
+        // two 32-bit steps. Since `numerator < denominator`, neither step can
+        // overflow.
+        let d = u64::from(denominator);
+        let n = u64::from(numerator) << 32;
+        let hi = n / d;
+        let lo = ((n % d) << 32) / d;
+        Ok(Bernoulli { p_int: (hi << 32) | lo })
     }
 }
 
@@ -73,11 +153,11 @@ impl Distribution<bool> for Bernoulli {
     #[inline]
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> bool {
         // Make sure to always return true for p = 1.0.
-        if self.p_int == ::core::u64::MAX {
+        if self.p_int == ALWAYS_TRUE {
             return true;
         }
-        let r: u64 = rng.gen();
-        r < self.p_int
+        let v: u64 = rng.gen();
+        v < self.p_int
     }
 }
 
@@ -90,8 +170,8 @@ mod test {
     #[test]
     fn test_trivial() {
         let mut r = ::test::rng(1);
-        let always_false = Bernoulli::new(0.0);
-        let always_true = Bernoulli::new(1.0);
+        let always_false = Bernoulli::new(0.0).unwrap();
+        let always_true = Bernoulli::new(1.0).unwrap();
         for _ in 0..5 {
             assert_eq!(r.sample::<bool, _>(&always_false), false);
             assert_eq!(r.sample::<bool, _>(&always_true), true);
@@ -103,7 +183,7 @@ mod test {
     #[test]
     fn test_average() {
         const P: f64 = 0.3;
-        let d = Bernoulli::new(P);
+        let d = Bernoulli::new(P).unwrap();
         const N: u32 = 10_000_000;
 
         let mut sum: u32 = 0;
@@ -117,4 +197,53 @@ mod test {
 
         assert!((avg - P).abs() < 1e-3);
     }
+
+    #[test]
+    fn test_invalid() {
+        use super::BernoulliError;
+        assert_eq!(Bernoulli::new(-0.1).unwrap_err(),
+                   BernoulliError::InvalidProbability);
+        assert_eq!(Bernoulli::new(1.1).unwrap_err(),
+                   BernoulliError::InvalidProbability);
+        assert!(Bernoulli::new(::core::f64::NAN).is_err());
+        assert!(Bernoulli::from_ratio(2, 1).is_err());
+        assert!(Bernoulli::from_ratio(0, 0).is_err());
+    }
+
+    #[test]
+    fn test_from_ratio() {
+        // 1/3 of 2^64, rounded down
+        let d = Bernoulli::from_ratio(1, 3).unwrap();
+        assert_eq!(d.p_int, 0x5555_5555_5555_5555);
+        let d = Bernoulli::from_ratio(2, 3).unwrap();
+        assert_eq!(d.p_int, 0xAAAA_AAAA_AAAA_AAAA);
+        assert_eq!(Bernoulli::from_ratio(0, 7).unwrap().p_int, 0);
+        assert_eq!(Bernoulli::from_ratio(7, 7).unwrap().p_int, ::core::u64::MAX);
+
+        let mut r = ::test::rng(3);
+        let d = Bernoulli::from_ratio(1, 3).unwrap();
+        const N: u32 = 1_000_000;
+        let mut sum: u32 = 0;
+        for _ in 0..N {
+            if d.sample(&mut r) {
+                sum += 1;
+            }
+        }
+        let avg = (sum as f64) / (N as f64);
+        assert!((avg - 1.0 / 3.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_gen_bool_equivalence() {
+        // `gen_bool` uses the same threshold, so with the same RNG the
+        // results must match exactly.
+        for &p in [0.0, 0.1, 0.3, 0.5, 0.77, 1.0].iter() {
+            let d = Bernoulli::new(p).unwrap();
+            let mut r1 = ::test::rng(4);
+            let mut r2 = ::test::rng(4);
+            for _ in 0..1000 {
+                assert_eq!(d.sample(&mut r1), r2.gen_bool(p));
+            }
+        }
+    }
 }
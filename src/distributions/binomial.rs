@@ -11,14 +11,19 @@
 //! The binomial distribution.
 
 use Rng;
-use distributions::{Distribution, Bernoulli, Cauchy};
-use distributions::log_gamma::log_gamma;
+use distributions::{Distribution, Uniform};
+use distributions::error::{DistributionError, unwrap_new, check_probability};
 
 /// The binomial distribution `Binomial(n, p)`.
 ///
 /// This distribution has density function:
 /// `f(k) = n!/(k! (n-k)!) p^k (1-p)^(n-k)` for `k >= 0`.
 ///
+/// If `n * min(p, 1 - p) < 10`, samples are generated by inversion (the BINV
+/// algorithm), which takes `O(n * min(p, 1 - p))` time. Otherwise the BTPE
+/// rejection algorithm is used, which takes expected constant time regardless
+/// of `n`.
+///
 /// # Example
 ///
 /// ```
@@ -40,11 +45,17 @@ impl Binomial {
     /// Construct a new `Binomial` with the given shape parameters `n` (number
     /// of trials) and `p` (probability of success).
     ///
-    /// Panics if `p < 0` or `p > 1`.
+    /// Panics if `p` is not in `[0, 1]`.
     pub fn new(n: u64, p: f64) -> Binomial {
-        assert!(p >= 0.0, "Binomial::new called with p < 0");
-        assert!(p <= 1.0, "Binomial::new called with p > 1");
-        Binomial { n, p }
+        unwrap_new(Binomial::try_new(n, p), "Binomial::new")
+    }
+
+    /// Construct a new `Binomial` with the given shape parameters `n` (number
+    /// of trials) and `p` (probability of success), returning an error if `p`
+    /// is not in `[0, 1]`.
+    pub fn try_new(n: u64, p: f64) -> Result<Binomial, DistributionError> {
+        check_probability(p, "p")?;
+        Ok(Binomial { n, p })
     }
 }
 
@@ -56,75 +67,213 @@ impl Distribution<u64> for Binomial {
         } else if self.p == 1.0 {
             return self.n;
         }
-        
-        // For low n, it is faster to sample directly. For both methods,
-        // performance is independent of p. On Intel Haswell CPU this method
-        // appears to be faster for approx n < 300.
-        if self.n < 300 {
-            let mut result = 0;
-            let d = Bernoulli::new(self.p);
-            for _ in 0 .. self.n {
-                result += rng.sample(d) as u32;
-            }
-            return result as u64;
-        }
-        
-        // binomial distribution is symmetrical with respect to p -> 1-p, k -> n-k
-        // switch p so that it is less than 0.5 - this allows for lower expected values
-        // we will just invert the result at the end
+
+        // The binomial distribution is symmetrical with respect to p -> 1-p,
+        // k -> n-k. Switch p so that it is less than 0.5 - this allows for
+        // lower expected values. We will just invert the result at the end.
         let p = if self.p <= 0.5 {
             self.p
         } else {
             1.0 - self.p
         };
 
-        // prepare some cached values
-        let float_n = self.n as f64;
-        let ln_fact_n = log_gamma(float_n + 1.0);
-        let pc = 1.0 - p;
-        let log_p = p.ln();
-        let log_pc = pc.ln();
-        let expected = self.n as f64 * p;
-        let sq = (expected * (2.0 * pc)).sqrt();
-
-        let mut lresult;
-
-        // we use the Cauchy distribution as the comparison distribution
-        // f(x) ~ 1/(1+x^2)
-        let cauchy = Cauchy::new(0.0, 1.0);
-        loop {
-            let mut comp_dev: f64;
+        let result;
+        let q = 1. - p;
+
+        // For small n * min(p, 1 - p), the BINV algorithm based on the inverse
+        // transformation of the binomial distribution is efficient. Otherwise,
+        // the BTPE algorithm is used.
+        //
+        // Voratas Kachitvichyanukul and Bruce W. Schmeiser. 1988. Binomial
+        // random variate generation. Commun. ACM 31, 2 (February 1988),
+        // 216-222. http://dx.doi.org/10.1145/42372.42381
+
+        // Threshold for preferring the BINV algorithm. The paper suggests 10,
+        // Ranlib uses 30, and GSL uses 14.
+        const BINV_THRESHOLD: f64 = 10.;
+
+        if (self.n as f64) * p < BINV_THRESHOLD {
+            // Use the BINV algorithm.
+
+            // Because of rounding, the remaining probability mass may not be
+            // used up; restart in the (rare) case that `x` exceeds this value,
+            // which has a probability below 1e-40 for `np < 10`.
+            const BINV_MAX_X: u64 = 110;
+
+            let s = p / q;
+            let a = ((self.n + 1) as f64) * s;
+            let r0 = q.powf(self.n as f64);
+            result = 'outer: loop {
+                let mut r = r0;
+                let mut u: f64 = rng.gen();
+                let mut x = 0;
+                while u > r {
+                    u -= r;
+                    x += 1;
+                    if x > BINV_MAX_X {
+                        continue 'outer;
+                    }
+                    r *= a / (x as f64) - s;
+                }
+                break x;
+            };
+        } else {
+            // Use the BTPE algorithm.
+
+            // Threshold for using the squeeze algorithm. This can be freely
+            // chosen based on performance. Ranlib and GSL use 20.
+            const SQUEEZE_THRESHOLD: f64 = 20.;
+
+            // Step 0: Calculate constants as functions of `n` and `p`.
+            // Integers are kept as `f64`, so any `n` can be used without
+            // overflow.
+            let n = self.n as f64;
+            let np = n * p;
+            let npq = np * q;
+            let f_m = np + p;
+            let m = f_m.trunc();
+            // radius of triangle region, since height=1 also area of region
+            let p1 = (2.195 * npq.sqrt() - 4.6 * q).floor() + 0.5;
+            // tip of triangle
+            let x_m = m + 0.5;
+            // left edge of triangle
+            let x_l = x_m - p1;
+            // right edge of triangle
+            let x_r = x_m + p1;
+            let c = 0.134 + 20.5 / (15.3 + m);
+            // p1 + area of parallelogram region
+            let p2 = p1 * (1. + 2. * c);
+
+            fn lambda(a: f64) -> f64 {
+                a * (1. + 0.5 * a)
+            }
+
+            let lambda_l = lambda((f_m - x_l) / (f_m - x_l * p));
+            let lambda_r = lambda((x_r - f_m) / (x_r * q));
+            // p1 + area of left tail
+            let p3 = p2 + c / lambda_l;
+            // p1 + area of right tail
+            let p4 = p3 + c / lambda_r;
+
+            // return value
+            let mut y: f64;
+
+            let gen_u = Uniform::new(0., p4);
+            let gen_v = Uniform::new(0., 1.);
+
             loop {
-                // draw from the Cauchy distribution
-                comp_dev = rng.sample(cauchy);
-                // shift the peak of the comparison ditribution
-                lresult = expected + sq * comp_dev;
-                // repeat the drawing until we are in the range of possible values
-                if lresult >= 0.0 && lresult < float_n + 1.0 {
+                // Step 1: Generate `u` for selecting the region. If region 1 is
+                // selected, generate a triangularly distributed variate.
+                let u = gen_u.sample(rng);
+                let mut v = gen_v.sample(rng);
+                if u <= p1 {
+                    y = (x_m - p1 * v + u).trunc();
                     break;
                 }
-            }
 
-            // the result should be discrete
-            lresult = lresult.floor();
+                if u <= p2 {
+                    // Step 2: Region 2, parallelograms. Check if region 2 is
+                    // used. If so, generate `y`.
+                    let x = x_l + (u - p1) / c;
+                    v = v * c + 1.0 - (x - x_m).abs() / p1;
+                    if v > 1. {
+                        continue;
+                    } else {
+                        y = x.trunc();
+                    }
+                } else if u <= p3 {
+                    // Step 3: Region 3, left exponential tail.
+                    y = (x_l + v.ln() / lambda_l).trunc();
+                    if y < 0. {
+                        continue;
+                    } else {
+                        v *= (u - p2) * lambda_l;
+                    }
+                } else {
+                    // Step 4: Region 4, right exponential tail.
+                    y = (x_r - v.ln() / lambda_r).trunc();
+                    if y > n {
+                        continue;
+                    } else {
+                        v *= (u - p3) * lambda_r;
+                    }
+                }
+
+                // Step 5: Acceptance/rejection comparison.
+
+                // Step 5.0: Test for appropriate method of evaluating f(y).
+                let k = (y - m).abs();
+                if !(k > SQUEEZE_THRESHOLD && k < 0.5 * npq - 1.) {
+                    // Step 5.1: Evaluate f(y) via the recursive relationship.
+                    // Start the search from the mode.
+                    let s = p / q;
+                    let a = s * (n + 1.);
+                    let mut f = 1.0;
+                    let steps = k as u64;
+                    if m < y {
+                        for i in 1..(steps + 1) {
+                            f *= a / (m + i as f64) - s;
+                        }
+                    } else if m > y {
+                        for i in 1..(steps + 1) {
+                            f /= a / (y + i as f64) - s;
+                        }
+                    }
+                    if v > f {
+                        continue;
+                    } else {
+                        break;
+                    }
+                }
+
+                // Step 5.2: Squeezing. Check the value of ln(v) against upper
+                // and lower bound of ln(f(y)).
+                let rho = (k / npq) * ((k * (k / 3. + 0.625) + 1. / 6.) / npq + 0.5);
+                let t = -0.5 * k * k / npq;
+                let alpha = v.ln();
+                if alpha < t - rho {
+                    break;
+                }
+                if alpha > t + rho {
+                    continue;
+                }
+
+                // Step 5.3: Final acceptance/rejection test.
+                let x1 = y + 1.;
+                let f1 = m + 1.;
+                let z = n + 1. - m;
+                let w = n - y + 1.;
+
+                fn stirling(a: f64) -> f64 {
+                    let a2 = a * a;
+                    (13860. - (462. - (132. - (99. - 140. / a2) / a2) / a2) / a2) / a / 166320.
+                }
 
-            let log_binomial_dist = ln_fact_n - log_gamma(lresult+1.0) -
-                log_gamma(float_n - lresult + 1.0) + lresult*log_p + (float_n - lresult)*log_pc;
-            // this is the binomial probability divided by the comparison probability
-            // we will generate a uniform random value and if it is larger than this,
-            // we interpret it as a value falling out of the distribution and repeat
-            let comparison_coeff = (log_binomial_dist.exp() * sq) * (1.2 * (1.0 + comp_dev*comp_dev));
+                if alpha > x_m * (f1 / x1).ln()
+                    + (n - m + 0.5) * (z / w).ln()
+                    + (y - m) * (w * p / (x1 * q)).ln()
+                    // We use the signs from the GSL implementation, which are
+                    // different than the ones in the reference. According to
+                    // the GSL authors, the new signs were verified to be
+                    // correct by one of the original designers of the
+                    // algorithm.
+                    + stirling(f1) + stirling(z) - stirling(x1) - stirling(w)
+                {
+                    continue;
+                }
 
-            if comparison_coeff >= rng.gen() {
                 break;
             }
+            assert!(y >= 0.);
+            // `n` may have been rounded up in the conversion to `f64`.
+            result = ::core::cmp::min(y as u64, self.n);
         }
 
-        // invert the result for p < 0.5
+        // Invert the result for p < 0.5.
         if p != self.p {
-            self.n - lresult as u64
+            self.n - result
         } else {
-            lresult as u64
+            result
         }
     }
 }
@@ -134,6 +283,7 @@ mod test {
     use Rng;
     use distributions::Distribution;
     use super::Binomial;
+    use distributions::DistributionError::*;
 
     fn test_binomial_mean_and_variance<R: Rng>(n: u64, p: f64, rng: &mut R) {
         let binomial = Binomial::new(n, p);
@@ -143,14 +293,18 @@ mod test {
 
         let mut results = [0.0; 1000];
         for i in results.iter_mut() { *i = binomial.sample(rng) as f64; }
+        assert!(results.iter().all(|&x| x <= n as f64));
 
         let mean = results.iter().sum::<f64>() / results.len() as f64;
-        assert!((mean as f64 - expected_mean).abs() < expected_mean / 50.0);
+        assert!((mean as f64 - expected_mean).abs() < expected_mean / 50.0,
+                "mean {} != {} for n = {}, p = {}", mean, expected_mean, n, p);
 
         let variance =
             results.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>()
             / results.len() as f64;
-        assert!((variance - expected_variance).abs() < expected_variance / 10.0);
+        assert!((variance - expected_variance).abs() < expected_variance / 10.0,
+                "variance {} != {} for n = {}, p = {}",
+                variance, expected_variance, n, p);
     }
 
     #[test]
@@ -161,6 +315,35 @@ mod test {
         test_binomial_mean_and_variance(40, 0.5, &mut rng);
         test_binomial_mean_and_variance(20, 0.7, &mut rng);
         test_binomial_mean_and_variance(20, 0.5, &mut rng);
+        // BINV for small `n * min(p, 1 - p)`
+        test_binomial_mean_and_variance(1000, 0.005, &mut rng);
+        test_binomial_mean_and_variance(1000, 0.995, &mut rng);
+        test_binomial_mean_and_variance(10_000_000, 5e-7, &mut rng);
+        // BTPE
+        test_binomial_mean_and_variance(1000, 0.01, &mut rng);
+        test_binomial_mean_and_variance(200, 0.5, &mut rng);
+        test_binomial_mean_and_variance(10_000_000, 0.3, &mut rng);
+        test_binomial_mean_and_variance(10_000_000, 0.9, &mut rng);
+        test_binomial_mean_and_variance(::std::u32::MAX as u64 * 4, 0.01, &mut rng);
+    }
+
+    #[test]
+    fn test_binomial_large_n() {
+        // The BTPE algorithm must not overflow around `i64::MAX`.
+        let mut rng = ::test::rng(468);
+        let max = ::std::u64::MAX;
+        for &n in [::std::i64::MAX as u64, ::std::i64::MAX as u64 + 1, max].iter() {
+            for &p in [0.5, 0.3, 0.9].iter() {
+                let binomial = Binomial::new(n, p);
+                let mean = n as f64 * p;
+                let std_dev = (mean * (1.0 - p)).sqrt();
+                for _ in 0..100 {
+                    let x = binomial.sample(&mut rng);
+                    assert!((x as f64 - mean).abs() < 6.0 * std_dev,
+                            "{} is far from the mean {}", x, mean);
+                }
+            }
+        }
     }
 
     #[test]
@@ -168,6 +351,10 @@ mod test {
         let mut rng = ::test::rng(352);
         assert_eq!(rng.sample(Binomial::new(20, 0.0)), 0);
         assert_eq!(rng.sample(Binomial::new(20, 1.0)), 20);
+        assert_eq!(rng.sample(Binomial::new(10_000_000, 0.0)), 0);
+        assert_eq!(rng.sample(Binomial::new(10_000_000, 1.0)), 10_000_000);
+        assert_eq!(rng.sample(Binomial::new(0, 0.3)), 0);
+        assert_eq!(rng.sample(Binomial::new(0, 0.7)), 0);
     }
 
     #[test]
@@ -175,4 +362,14 @@ mod test {
     fn test_binomial_invalid_lambda_neg() {
         Binomial::new(20, -10.0);
     }
+
+
+    #[test]
+    fn test_binomial_try_new() {
+        assert!(Binomial::try_new(0, 0.0).is_ok());
+        assert!(Binomial::try_new(20, 1.0).is_ok());
+        assert_eq!(Binomial::try_new(20, -10.0).err(), Some(OutOfRange("p")));
+        assert_eq!(Binomial::try_new(20, 1.5).err(), Some(OutOfRange("p")));
+        assert_eq!(Binomial::try_new(20, ::std::f64::NAN).err(), Some(OutOfRange("p")));
+    }
 }
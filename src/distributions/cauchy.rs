@@ -12,6 +12,7 @@
 
 use Rng;
 use distributions::Distribution;
+use distributions::error::{DistributionError, unwrap_new, check_finite, check_positive};
 use std::f64::consts::PI;
 
 /// The Cauchy distribution `Cauchy(median, scale)`.
@@ -19,13 +20,29 @@ use std::f64::consts::PI;
 /// This distribution has a density function:
 /// `f(x) = 1 / (pi * scale * (1 + ((x - median) / scale)^2))`
 ///
+/// The mean and variance of this distribution are undefined; the median and
+/// mode are `median`, and the quartiles are `median ± scale`.
+///
+/// Samples are generated as `median + scale * tan(pi * u)` with `u` uniform
+/// in `[0, 1)`. Since `pi / 2` is not exactly representable, `tan` never
+/// returns an infinite value or NaN, so the samples are always finite. As a
+/// consequence, the tails are truncated: the standard Cauchy samples are
+/// bounded by about `±1.6e16` (reached for `u` close to 0.5), and samples
+/// further into the tails than the resolution of `u` allows are never
+/// generated.
+///
+/// `Cauchy` implements both `Distribution<f64>` and `Distribution<f32>`, so
+/// the type of the sample may need to be annotated. `f32` samples are
+/// computed in double precision and rounded; samples beyond the range of
+/// `f32` become infinite.
+///
 /// # Example
 ///
 /// ```
 /// use rand::distributions::{Cauchy, Distribution};
 ///
 /// let cau = Cauchy::new(2.0, 5.0);
-/// let v = cau.sample(&mut rand::thread_rng());
+/// let v: f64 = cau.sample(&mut rand::thread_rng());
 /// println!("{} is from a Cauchy(2, 5) distribution", v);
 /// ```
 #[derive(Clone, Copy, Debug)]
@@ -37,13 +54,21 @@ pub struct Cauchy {
 impl Cauchy {
     /// Construct a new `Cauchy` with the given shape parameters
     /// `median` the peak location and `scale` the scale factor.
-    /// Panics if `scale <= 0`.
+    /// Panics if `scale <= 0` or if either parameter is not finite.
     pub fn new(median: f64, scale: f64) -> Cauchy {
-        assert!(scale > 0.0, "Cauchy::new called with scale factor <= 0");
-        Cauchy {
+        unwrap_new(Cauchy::try_new(median, scale), "Cauchy::new")
+    }
+
+    /// Construct a new `Cauchy` with the given shape parameters
+    /// `median` the peak location and `scale` the scale factor, returning an
+    /// error if `scale <= 0` or if either parameter is not finite.
+    pub fn try_new(median: f64, scale: f64) -> Result<Cauchy, DistributionError> {
+        check_finite(median, "median")?;
+        check_positive(scale, "scale")?;
+        Ok(Cauchy {
             median,
             scale
-        }
+        })
     }
 }
 
@@ -60,10 +85,21 @@ impl Distribution<f64> for Cauchy {
     }
 }
 
+impl Distribution<f32> for Cauchy {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+        // The tangent loses too much precision in single precision, so
+        // compute the sample as `f64` and round it.
+        let x: f64 = self.sample(rng);
+        x as f32
+    }
+}
+
 #[cfg(test)]
 mod test {
     use distributions::Distribution;
+    use rngs::mock::StepRng;
     use super::Cauchy;
+    use distributions::DistributionError::*;
 
     fn median(mut numbers: &mut [f64]) -> f64 {
         sort(&mut numbers);
@@ -94,7 +130,8 @@ mod test {
         let mut rng = ::test::rng(123);
         let mut sum = 0.0;
         for _ in 0..1000 {
-            sum += cauchy.sample(&mut rng);
+            let x: f64 = cauchy.sample(&mut rng);
+            sum += x;
         }
         let mean = sum / 1000.0;
         println!("Cauchy mean: {}", mean);
@@ -102,6 +139,58 @@ mod test {
         assert!((mean - 10.0).abs() > 0.5); // not 100% certain, but probable enough
     }
 
+    #[test]
+    fn test_cauchy_quartiles() {
+        let cauchy = Cauchy::new(-3.0, 2.0);
+        let mut rng = ::test::rng(124);
+        const N: usize = 20_000;
+        let mut numbers = vec![0.0f64; N];
+        for x in numbers.iter_mut() {
+            *x = cauchy.sample(&mut rng);
+        }
+        sort(&mut numbers);
+        // the quartiles are at `median ± scale`
+        let q1 = numbers[N / 4];
+        let q3 = numbers[3 * N / 4];
+        assert!((q1 - -5.0).abs() < 0.1, "first quartile {} != -5", q1);
+        assert!((q3 - -1.0).abs() < 0.1, "third quartile {} != -1", q3);
+        assert!((q3 - q1 - 4.0).abs() < 0.15);
+    }
+
+    #[test]
+    fn test_cauchy_quartiles_f32() {
+        let cauchy = Cauchy::new(1.0, 0.5);
+        let mut rng = ::test::rng(125);
+        const N: usize = 20_000;
+        let mut numbers = vec![0.0f32; N];
+        for x in numbers.iter_mut() {
+            *x = cauchy.sample(&mut rng);
+        }
+        numbers.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert!((numbers[N / 2] - 1.0).abs() < 0.03);
+        assert!((numbers[N / 4] - 0.5).abs() < 0.03);
+        assert!((numbers[3 * N / 4] - 1.5).abs() < 0.03);
+    }
+
+    #[test]
+    fn test_cauchy_extreme_tails() {
+        let cauchy = Cauchy::new(0.0, 1.0);
+        // `u` exactly 0 gives the median
+        let x: f64 = cauchy.sample(&mut StepRng::new(0, 0));
+        assert_eq!(x, 0.0);
+        // `u` exactly 0.5, and the largest `u` below 1
+        for &bits in [1u64 << 63, ::core::u64::MAX].iter() {
+            let x: f64 = cauchy.sample(&mut StepRng::new(bits, 0));
+            assert!(x.is_finite());
+            let x: f32 = cauchy.sample(&mut StepRng::new(bits, 0));
+            assert!(!x.is_nan());
+        }
+        // `u` just below and above 0.5 give samples far in opposite tails
+        let below: f64 = cauchy.sample(&mut StepRng::new((1u64 << 63) - (1 << 11), 0));
+        let above: f64 = cauchy.sample(&mut StepRng::new((1u64 << 63) + (1 << 11), 0));
+        assert!(below > 1e15 && above < -1e15);
+    }
+
     #[test]
     #[should_panic]
     fn test_cauchy_invalid_scale_zero() {
@@ -113,4 +202,14 @@ mod test {
     fn test_cauchy_invalid_scale_neg() {
         Cauchy::new(0.0, -10.0);
     }
+
+
+    #[test]
+    fn test_cauchy_try_new() {
+        assert!(Cauchy::try_new(-10.0, 1e-300).is_ok());
+        assert_eq!(Cauchy::try_new(0.0, 0.0).err(), Some(NonPositive("scale")));
+        assert_eq!(Cauchy::try_new(0.0, -10.0).err(), Some(NonPositive("scale")));
+        assert_eq!(Cauchy::try_new(0.0, ::std::f64::INFINITY).err(), Some(NonFinite("scale")));
+        assert_eq!(Cauchy::try_new(::std::f64::NAN, 1.0).err(), Some(NonFinite("median")));
+    }
 }
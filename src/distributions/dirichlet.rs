@@ -0,0 +1,249 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Dirichlet distribution.
+
+use Rng;
+use distributions::{Distribution, Gamma, Open01};
+use distributions::error::{DistributionError, unwrap_new, check_positive};
+
+/// The Dirichlet distribution `Dirichlet(alpha)`.
+///
+/// The Dirichlet distribution is a family of continuous multivariate
+/// probability distributions parameterized by a vector alpha of positive reals.
+/// It is a multivariate generalization of the beta distribution. Samples are
+/// vectors of non-negative numbers which sum to 1.
+///
+/// Samples are generated by drawing independent `Gamma(alpha[i], 1)` values
+/// and normalizing them. When any `alpha[i] < 1`, the Gamma samples may
+/// underflow (possibly all of them at once), so the logarithms of the Gamma
+/// samples are used instead; components may still be exactly 0, but the
+/// result always sums to 1.
+///
+/// # Example
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand::distributions::Dirichlet;
+///
+/// let dirichlet = Dirichlet::new(vec![1.0, 2.0, 3.0]);
+/// let samples = dirichlet.sample(&mut rand::thread_rng());
+/// println!("{:?} is from a Dirichlet([1.0, 2.0, 3.0]) distribution", samples);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Dirichlet {
+    repr: DirichletRepr,
+}
+
+#[derive(Clone, Debug)]
+enum DirichletRepr {
+    // All `alpha[i] >= 1`: normalize the Gamma samples directly.
+    Large(Vec<Gamma>),
+    // Some `alpha[i] < 1`: `ln Gamma(a) = ln Gamma(a + 1) + ln(U) / a` for
+    // these components, and samples are normalized in log space. Components
+    // with `alpha >= 1` have an `inv_alpha` of 0.
+    Small(Vec<(Gamma, f64)>),
+}
+
+impl Dirichlet {
+    /// Construct a new `Dirichlet` with the given alpha parameter `alpha`.
+    ///
+    /// Panics if `alpha.len() < 2` or any element of `alpha` is not finite
+    /// and strictly positive.
+    #[inline]
+    pub fn new<V: Into<Vec<f64>>>(alpha: V) -> Dirichlet {
+        unwrap_new(Dirichlet::try_new(alpha), "Dirichlet::new")
+    }
+
+    /// Construct a new `Dirichlet` with the given alpha parameter `alpha`,
+    /// returning an error if `alpha.len() < 2` or any element of `alpha` is
+    /// not finite and strictly positive.
+    pub fn try_new<V: Into<Vec<f64>>>(alpha: V) -> Result<Dirichlet, DistributionError> {
+        let alpha = alpha.into();
+        if alpha.len() < 2 {
+            return Err(DistributionError::OutOfRange("alpha"));
+        }
+        for &a in alpha.iter() {
+            check_positive(a, "alpha")?;
+        }
+
+        let repr = if alpha.iter().all(|&a| a >= 1.0) {
+            DirichletRepr::Large(alpha.iter().map(|&a| Gamma::new(a, 1.0)).collect())
+        } else {
+            DirichletRepr::Small(alpha.iter().map(|&a| {
+                if a < 1.0 {
+                    (Gamma::new(a + 1.0, 1.0), 1.0 / a)
+                } else {
+                    (Gamma::new(a, 1.0), 0.0)
+                }
+            }).collect())
+        };
+        Ok(Dirichlet { repr })
+    }
+
+    /// Construct a new `Dirichlet` with the given shape parameter `alpha` and
+    /// `size`, i.e. the symmetric distribution with all `size` parameters
+    /// equal to `alpha`.
+    ///
+    /// Panics if `alpha` is not finite and strictly positive, or if
+    /// `size < 2`.
+    #[inline]
+    pub fn new_with_param(alpha: f64, size: usize) -> Dirichlet {
+        unwrap_new(Dirichlet::try_new_with_param(alpha, size),
+                   "Dirichlet::new_with_param")
+    }
+
+    /// Construct a new symmetric `Dirichlet` with the given shape parameter
+    /// `alpha` and `size`, returning an error if `alpha` is not finite and
+    /// strictly positive, or if `size < 2`.
+    pub fn try_new_with_param(alpha: f64, size: usize)
+        -> Result<Dirichlet, DistributionError>
+    {
+        if size < 2 {
+            return Err(DistributionError::OutOfRange("size"));
+        }
+        Dirichlet::try_new(vec![alpha; size])
+    }
+}
+
+impl Distribution<Vec<f64>> for Dirichlet {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        match self.repr {
+            DirichletRepr::Large(ref gammas) => {
+                let mut samples: Vec<f64> =
+                    gammas.iter().map(|g| g.sample(rng)).collect();
+                let sum: f64 = samples.iter().sum();
+                let inv_sum = 1.0 / sum;
+                for x in samples.iter_mut() {
+                    *x *= inv_sum;
+                }
+                samples
+            }
+            DirichletRepr::Small(ref gammas) => {
+                let mut samples: Vec<f64> = gammas.iter().map(|&(ref g, inv_alpha)| {
+                    let mut ln_x = g.sample(rng).ln();
+                    if inv_alpha != 0.0 {
+                        let u: f64 = rng.sample(Open01);
+                        ln_x += u.ln() * inv_alpha;
+                    }
+                    ln_x
+                }).collect();
+                // Scale by the largest sample, which becomes exactly 1, so the
+                // sum can neither be 0 nor overflow.
+                let max = samples.iter().cloned().fold(::std::f64::NEG_INFINITY, f64::max);
+                let mut sum = 0.0;
+                for x in samples.iter_mut() {
+                    *x = (*x - max).exp();
+                    sum += *x;
+                }
+                let inv_sum = 1.0 / sum;
+                for x in samples.iter_mut() {
+                    *x *= inv_sum;
+                }
+                samples
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Dirichlet;
+    use distributions::Distribution;
+    use distributions::DistributionError::*;
+
+    fn check_means(alpha: &[f64], seed: u64) {
+        let d = Dirichlet::new(alpha);
+        let n = alpha.len();
+        let alpha_sum: f64 = alpha.iter().sum();
+        let mut rng = ::test::rng(seed);
+        let mut sums = vec![0.0; n];
+        const N: usize = 20_000;
+        for _ in 0..N {
+            let samples = d.sample(&mut rng);
+            assert_eq!(samples.len(), n);
+            let total: f64 = samples.iter().sum();
+            assert!((total - 1.0).abs() < 1e-12);
+            for (s, x) in sums.iter_mut().zip(samples.iter()) {
+                *s += *x;
+            }
+        }
+        for i in 0..n {
+            let mean = sums[i] / N as f64;
+            let expected = alpha[i] / alpha_sum;
+            assert!((mean - expected).abs() < 0.01,
+                    "component {}: mean {} != {}", i, mean, expected);
+        }
+    }
+
+    #[test]
+    fn test_dirichlet() {
+        check_means(&[1.0, 2.0, 3.0], 221);
+        check_means(&[0.5, 2.0, 0.25, 1.0], 222);
+    }
+
+    #[test]
+    fn test_dirichlet_with_param() {
+        let alpha = 0.5f64;
+        let size = 2;
+        let d = Dirichlet::new_with_param(alpha, size);
+        let mut rng = ::test::rng(223);
+        let n = 5000;
+        let mut sum = 0.0;
+        for _ in 0..n {
+            let samples = d.sample(&mut rng);
+            sum += samples[0];
+        }
+        let avg = sum / n as f64;
+        assert!((avg - 0.5).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_dirichlet_tiny_alpha() {
+        // Nearly all Gamma samples underflow to 0 here.
+        let d = Dirichlet::new_with_param(1e-4, 10);
+        let mut rng = ::test::rng(224);
+        for _ in 0..1000 {
+            let samples = d.sample(&mut rng);
+            assert!(samples.iter().all(|x| x.is_finite() && *x >= 0.0));
+            let total: f64 = samples.iter().sum();
+            assert!((total - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dirichlet_invalid_length() {
+        Dirichlet::new_with_param(0.5f64, 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dirichlet_invalid_alpha() {
+        Dirichlet::new_with_param(0.0f64, 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dirichlet_infinite_alpha() {
+        Dirichlet::new(vec![1.0, ::std::f64::INFINITY]);
+    }
+
+
+    #[test]
+    fn test_dirichlet_try_new() {
+        assert!(Dirichlet::try_new(vec![0.1, 1e300]).is_ok());
+        assert_eq!(Dirichlet::try_new(vec![1.0]).err(), Some(OutOfRange("alpha")));
+        assert_eq!(Dirichlet::try_new(vec![1.0, 0.0]).err(), Some(NonPositive("alpha")));
+        assert_eq!(Dirichlet::try_new(vec![::std::f64::NAN, 1.0]).err(), Some(NonFinite("alpha")));
+        assert_eq!(Dirichlet::try_new_with_param(0.5, 1).err(), Some(OutOfRange("size")));
+        assert_eq!(Dirichlet::try_new_with_param(-0.5, 2).err(), Some(NonPositive("alpha")));
+    }
+}
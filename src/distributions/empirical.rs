@@ -0,0 +1,296 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The empirical distribution.
+
+use Rng;
+use distributions::Distribution;
+use core::cmp::Ordering;
+use core::fmt;
+
+#[cfg(not(feature="std"))] use alloc::Vec;
+
+/// The largest deviation of the last cumulative probability from 1 accepted
+/// by `Empirical::from_cdf`, to allow for rounding errors.
+const CDF_TOLERANCE: f64 = 1e-9;
+
+/// An empirical distribution, given by observed samples or by points of its
+/// cumulative distribution function (CDF).
+///
+/// Samples are generated by the inverse transform method: a uniform value
+/// `u` in `[0, 1)` is drawn, and the smallest point with a cumulative
+/// probability larger than `u` is found with a binary search.
+///
+/// By default the distribution is discrete, and only the given values are
+/// sampled: each value with the probability by which the CDF increases at it.
+/// With [`with_interpolation`], the CDF is instead interpolated linearly
+/// between the points, which gives a continuous distribution between them.
+/// This is suitable for histograms, where the points are the bin edges.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, Empirical};
+///
+/// let mut rng = rand::thread_rng();
+///
+/// // resample observed data
+/// let observed = [3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0];
+/// let empirical = Empirical::from_samples(&observed).unwrap();
+/// let v = empirical.sample(&mut rng);
+/// assert!(observed.contains(&v));
+///
+/// // a histogram: 20% of requests are below 1 kB, 70% below 10 kB and all
+/// // below 100 kB
+/// let sizes = Empirical::from_cdf(&[(0.0, 0.0), (1.0, 0.2), (10.0, 0.7),
+///                                   (100.0, 1.0)]).unwrap()
+///     .with_interpolation();
+/// let size = sizes.sample(&mut rng);
+/// assert!(size >= 0.0 && size <= 100.0);
+/// ```
+///
+/// [`with_interpolation`]: #method.with_interpolation
+#[derive(Clone, Debug)]
+pub struct Empirical {
+    values: Vec<f64>,
+    cdf: Vec<f64>,
+    interpolate: bool,
+}
+
+impl Empirical {
+    /// Create the empirical distribution of the given samples, in which each
+    /// sample has the same probability.
+    ///
+    /// Returns an error if there are no samples, or if any sample is not
+    /// finite.
+    pub fn from_samples(samples: &[f64]) -> Result<Empirical, EmpiricalError> {
+        if samples.is_empty() {
+            return Err(EmpiricalError::NoPoints);
+        }
+        if samples.iter().any(|x| !x.is_finite()) {
+            return Err(EmpiricalError::NonFiniteValue);
+        }
+        let mut values = samples.to_vec();
+        values.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+        let n = values.len() as f64;
+        let mut cdf: Vec<f64> = (1..values.len() + 1).map(|i| i as f64 / n).collect();
+        // `i / n` may round to less than 1 for the last point.
+        *cdf.last_mut().unwrap() = 1.0;
+        Ok(Empirical { values, cdf, interpolate: false })
+    }
+
+    /// Create an empirical distribution from points `(value, cumulative
+    /// probability)` of its CDF.
+    ///
+    /// The values must be finite and strictly increasing. The cumulative
+    /// probabilities must be non-decreasing and within `[0, 1]`, and the last
+    /// one must be 1 (up to a rounding error of `1e-9`). The first value is
+    /// sampled with its cumulative probability, so this should be 0 if it is
+    /// the lower bound of a histogram.
+    pub fn from_cdf(points: &[(f64, f64)]) -> Result<Empirical, EmpiricalError> {
+        if points.is_empty() {
+            return Err(EmpiricalError::NoPoints);
+        }
+        let mut values = Vec::with_capacity(points.len());
+        let mut cdf = Vec::with_capacity(points.len());
+        for (i, &(value, p)) in points.iter().enumerate() {
+            if !value.is_finite() {
+                return Err(EmpiricalError::NonFiniteValue);
+            }
+            if !(p >= 0.0 && p <= 1.0 + CDF_TOLERANCE) {
+                return Err(EmpiricalError::InvalidProbability);
+            }
+            if i > 0 && (value <= values[i - 1] || p < cdf[i - 1]) {
+                return Err(EmpiricalError::NotMonotone);
+            }
+            values.push(value);
+            cdf.push(p);
+        }
+        let last = cdf.last_mut().unwrap();
+        if (*last - 1.0).abs() > CDF_TOLERANCE {
+            return Err(EmpiricalError::NotNormalized);
+        }
+        *last = 1.0;
+        Ok(Empirical { values, cdf, interpolate: false })
+    }
+
+    /// Interpolate the CDF linearly between the points, so that values
+    /// between the points are sampled as well.
+    ///
+    /// Only the first point is sampled with a positive probability, namely
+    /// its cumulative probability.
+    pub fn with_interpolation(mut self) -> Empirical {
+        self.interpolate = true;
+        self
+    }
+}
+
+impl Distribution<f64> for Empirical {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let u: f64 = rng.gen();
+        // Find the first point with a cumulative probability above `u`. The
+        // last one is 1, so there always is one.
+        let i = self.cdf.binary_search_by(
+            |p| if *p <= u { Ordering::Less } else { Ordering::Greater })
+            .unwrap_err();
+        if !self.interpolate || i == 0 {
+            return self.values[i];
+        }
+        let (p0, p1) = (self.cdf[i - 1], self.cdf[i]);
+        let (x0, x1) = (self.values[i - 1], self.values[i]);
+        x0 + (u - p0) / (p1 - p0) * (x1 - x0)
+    }
+}
+
+/// Error type returned from `Empirical::from_samples` and
+/// `Empirical::from_cdf`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmpiricalError {
+    /// No samples or points were given.
+    NoPoints,
+
+    /// A value is infinite or NaN.
+    NonFiniteValue,
+
+    /// A cumulative probability is not within `[0, 1]`.
+    InvalidProbability,
+
+    /// The values are not strictly increasing, or the cumulative
+    /// probabilities are decreasing.
+    NotMonotone,
+
+    /// The last cumulative probability is not 1.
+    NotNormalized,
+}
+
+impl EmpiricalError {
+    fn msg(&self) -> &str {
+        match *self {
+            EmpiricalError::NoPoints => "No points found",
+            EmpiricalError::NonFiniteValue => "Value is not finite",
+            EmpiricalError::InvalidProbability =>
+                "Cumulative probability not in the range [0, 1]",
+            EmpiricalError::NotMonotone => "CDF is not monotonically increasing",
+            EmpiricalError::NotNormalized => "CDF does not reach 1",
+        }
+    }
+}
+
+#[cfg(feature="std")]
+impl ::std::error::Error for EmpiricalError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl fmt::Display for EmpiricalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::{Distribution, Exp};
+    use super::{Empirical, EmpiricalError};
+
+    #[test]
+    fn test_empirical_from_samples() {
+        // Resampling the samples of a known distribution approximately
+        // reproduces its quantiles.
+        let mut rng = ::test::rng(511);
+        const M: usize = 20_000;
+        let exp = Exp::new(1.0);
+        let observed: Vec<f64> = (0..M).map(|_| exp.sample(&mut rng)).collect();
+        for &interpolate in [false, true].iter() {
+            let mut empirical = Empirical::from_samples(&observed).unwrap();
+            if interpolate {
+                empirical = empirical.with_interpolation();
+            }
+            const N: usize = 50_000;
+            let samples: Vec<f64> = (0..N).map(|_| empirical.sample(&mut rng)).collect();
+            if !interpolate {
+                assert!(samples.iter().all(|x| empirical.values.binary_search_by(
+                    |v| v.partial_cmp(x).unwrap()).is_ok()));
+            }
+            for &p in [0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99].iter() {
+                let q = -(1.0 - p as f64).ln();
+                let freq = samples.iter().filter(|&&x| x <= q).count() as f64 / N as f64;
+                // errors from both the observed and the resampled values
+                let err = 5.0 * (p * (1.0 - p) * (1.0 / M as f64 + 1.0 / N as f64)).sqrt();
+                assert!((freq - p).abs() < err, "P(X <= {}) = {} != {}", q, freq, p);
+            }
+        }
+    }
+
+    #[test]
+    fn test_empirical_from_cdf() {
+        let mut rng = ::test::rng(512);
+        const N: usize = 50_000;
+
+        // discrete
+        let empirical = Empirical::from_cdf(&[(1.0, 0.2), (2.0, 0.5), (5.0, 1.0)]).unwrap();
+        let mut counts = [0usize; 3];
+        for _ in 0..N {
+            match empirical.sample(&mut rng) {
+                x if x == 1.0 => counts[0] += 1,
+                x if x == 2.0 => counts[1] += 1,
+                x if x == 5.0 => counts[2] += 1,
+                x => panic!("unexpected value {}", x),
+            }
+        }
+        for (&count, &p) in counts.iter().zip([0.2, 0.3, 0.5].iter()) {
+            let err = 5.0 * (N as f64 * p * (1.0 - p)).sqrt();
+            assert!((count as f64 - N as f64 * p).abs() < err);
+        }
+
+        // interpolated histogram, with the quantiles at the bin edges and
+        // in between
+        let empirical = Empirical::from_cdf(&[(0.0, 0.0), (1.0, 0.5), (3.0, 1.0)])
+            .unwrap().with_interpolation();
+        let samples: Vec<f64> = (0..N).map(|_| empirical.sample(&mut rng)).collect();
+        assert!(samples.iter().all(|&x| x >= 0.0 && x < 3.0));
+        for &(q, p) in [(0.5, 0.25), (1.0, 0.5), (2.0, 0.75), (2.8, 0.95)].iter() {
+            let freq = samples.iter().filter(|&&x| x <= q).count() as f64 / N as f64;
+            let err = 5.0 * (p * (1.0 - p) / N as f64).sqrt();
+            assert!((freq - p).abs() < err, "P(X <= {}) = {} != {}", q, freq, p);
+        }
+
+        // rounding errors in the total are accepted
+        let empirical = Empirical::from_cdf(&[(1.0, 0.1), (2.0, 0.1 + 0.2),
+                                              (3.0, 0.1 + 0.2 + 0.7)]).unwrap();
+        assert!(empirical.sample(&mut rng) <= 3.0);
+    }
+
+    #[test]
+    fn test_empirical_invalid() {
+        let nan = ::std::f64::NAN;
+        assert_eq!(Empirical::from_samples(&[]).unwrap_err(), EmpiricalError::NoPoints);
+        assert_eq!(Empirical::from_samples(&[1.0, nan]).unwrap_err(),
+                   EmpiricalError::NonFiniteValue);
+        assert_eq!(Empirical::from_cdf(&[]).unwrap_err(), EmpiricalError::NoPoints);
+        assert_eq!(Empirical::from_cdf(&[(2.0, 0.5), (1.0, 1.0)]).unwrap_err(),
+                   EmpiricalError::NotMonotone);
+        assert_eq!(Empirical::from_cdf(&[(1.0, 0.5), (1.0, 1.0)]).unwrap_err(),
+                   EmpiricalError::NotMonotone);
+        assert_eq!(Empirical::from_cdf(&[(1.0, 0.6), (2.0, 0.5), (3.0, 1.0)]).unwrap_err(),
+                   EmpiricalError::NotMonotone);
+        assert_eq!(Empirical::from_cdf(&[(1.0, 0.5), (2.0, 0.9)]).unwrap_err(),
+                   EmpiricalError::NotNormalized);
+        assert_eq!(Empirical::from_cdf(&[(1.0, -0.1), (2.0, 1.0)]).unwrap_err(),
+                   EmpiricalError::InvalidProbability);
+        assert_eq!(Empirical::from_cdf(&[(1.0, nan), (2.0, 1.0)]).unwrap_err(),
+                   EmpiricalError::InvalidProbability);
+        assert_eq!(Empirical::from_cdf(&[(1.0, 0.5), (2.0, 1.5)]).unwrap_err(),
+                   EmpiricalError::InvalidProbability);
+        assert_eq!(Empirical::from_cdf(&[(nan, 0.5), (2.0, 1.0)]).unwrap_err(),
+                   EmpiricalError::NonFiniteValue);
+    }
+}
@@ -0,0 +1,180 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The error type of the `try_new` constructors of distributions.
+//!
+//! Distributions with numeric parameters have a panicking `new` constructor
+//! and a `try_new` constructor returning `DistributionError`. Distributions
+//! which are constructed from a probability or from collections of weights,
+//! samples or elements keep their own error types instead, because those
+//! describe problems with the input as a whole which are not about a single
+//! named parameter:
+//!
+//! - `Bernoulli::new`, `Bernoulli::from_ratio` and `Maybe::new` return
+//!   `BernoulliError`. `Bernoulli::new` already returned a `Result` before
+//!   `DistributionError` was added, so there is no separate `try_new`.
+//! - `Multinomial::try_new` and `Mixture::new` return `WeightedError`, like
+//!   `WeightedIndex::new`, since they validate weights the same way.
+//! - `Empirical::from_samples` and `Empirical::from_cdf` return
+//!   `EmpiricalError`, which also reports unsorted and unnormalized input.
+//! - `Slice::new` returns `EmptySlice`, its only failure.
+
+use core::fmt;
+
+/// Error type returned from the `try_new` constructors of distributions,
+/// describing which parameter is invalid and why.
+///
+/// The panicking `new` constructors panic with the message of this error.
+/// Distributions constructed from weights, samples or a slice, and
+/// `Bernoulli`, use their own error types such as `WeightedError`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Normal, DistributionError};
+///
+/// assert_eq!(Normal::try_new(0.0, -1.0).err(),
+///            Some(DistributionError::Negative("std_dev")));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DistributionError {
+    /// The named parameter is NaN or infinite.
+    NonFinite(&'static str),
+    /// The named parameter is zero or negative.
+    NonPositive(&'static str),
+    /// The named parameter is negative.
+    Negative(&'static str),
+    /// The named parameter is outside the range of valid values, for example
+    /// a probability not in `[0, 1]`.
+    OutOfRange(&'static str),
+    /// The lower bound is not below the upper bound (or, for an inclusive
+    /// range, is above the upper bound).
+    EmptyRange,
+}
+
+impl fmt::Display for DistributionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DistributionError::NonFinite(param) =>
+                write!(f, "`{}` is not finite", param),
+            DistributionError::NonPositive(param) =>
+                write!(f, "`{}` is not positive", param),
+            DistributionError::Negative(param) =>
+                write!(f, "`{}` is negative", param),
+            DistributionError::OutOfRange(param) =>
+                write!(f, "`{}` is out of range", param),
+            DistributionError::EmptyRange =>
+                write!(f, "the range is empty"),
+        }
+    }
+}
+
+#[cfg(feature="std")]
+impl ::std::error::Error for DistributionError {
+    fn description(&self) -> &str {
+        match *self {
+            DistributionError::NonFinite(_) => "parameter is not finite",
+            DistributionError::NonPositive(_) => "parameter is not positive",
+            DistributionError::Negative(_) => "parameter is negative",
+            DistributionError::OutOfRange(_) => "parameter is out of range",
+            DistributionError::EmptyRange => "the range is empty",
+        }
+    }
+}
+
+/// Unwrap the result of a `try_new` constructor, panicking with a message
+/// naming the panicking constructor.
+pub(crate) fn unwrap_new<T>(result: Result<T, DistributionError>,
+                            constructor: &str) -> T {
+    match result {
+        Ok(distr) => distr,
+        Err(err) => panic!("{} called with invalid parameters: {}",
+                           constructor, err),
+    }
+}
+
+/// Check that `x` is finite.
+#[cfg(feature="std")]
+pub(crate) fn check_finite(x: f64, param: &'static str)
+    -> Result<(), DistributionError>
+{
+    if x.is_finite() { Ok(()) } else { Err(DistributionError::NonFinite(param)) }
+}
+
+/// Check that `x` is finite and positive.
+#[cfg(feature="std")]
+pub(crate) fn check_positive(x: f64, param: &'static str)
+    -> Result<(), DistributionError>
+{
+    check_finite(x, param)?;
+    if x > 0.0 { Ok(()) } else { Err(DistributionError::NonPositive(param)) }
+}
+
+/// Check that `x` is finite and not negative.
+#[cfg(feature="std")]
+pub(crate) fn check_non_negative(x: f64, param: &'static str)
+    -> Result<(), DistributionError>
+{
+    check_finite(x, param)?;
+    if x >= 0.0 { Ok(()) } else { Err(DistributionError::Negative(param)) }
+}
+
+/// Check that `p` is a probability in `[0, 1]`.
+#[cfg(feature="std")]
+pub(crate) fn check_probability(p: f64, param: &'static str)
+    -> Result<(), DistributionError>
+{
+    // Written this way so that NaN is rejected as well.
+    if !(p >= 0.0 && p <= 1.0) {
+        return Err(DistributionError::OutOfRange(param));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(feature="std")]
+    #[test]
+    fn test_checks() {
+        assert_eq!(check_finite(-1.0, "x"), Ok(()));
+        for &x in [::core::f64::NAN, ::core::f64::INFINITY,
+                   ::core::f64::NEG_INFINITY].iter() {
+            assert_eq!(check_finite(x, "x"), Err(DistributionError::NonFinite("x")));
+        }
+
+        assert_eq!(check_positive(1e-300, "x"), Ok(()));
+        for &x in [0.0, -0.0, -1.0].iter() {
+            assert_eq!(check_positive(x, "x"), Err(DistributionError::NonPositive("x")));
+        }
+        assert_eq!(check_positive(::core::f64::NAN, "x"),
+                   Err(DistributionError::NonFinite("x")));
+
+        assert_eq!(check_non_negative(0.0, "x"), Ok(()));
+        assert_eq!(check_non_negative(-1e-300, "x"),
+                   Err(DistributionError::Negative("x")));
+        assert_eq!(check_non_negative(::core::f64::INFINITY, "x"),
+                   Err(DistributionError::NonFinite("x")));
+
+        for &p in [0.0, 0.5, 1.0].iter() {
+            assert_eq!(check_probability(p, "p"), Ok(()));
+        }
+        for &p in [-0.1, 1.1, ::core::f64::NAN].iter() {
+            assert_eq!(check_probability(p, "p"), Err(DistributionError::OutOfRange("p")));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Test::new called with invalid parameters: `x` is not positive")]
+    fn test_unwrap_new() {
+        unwrap_new::<()>(Err(DistributionError::NonPositive("x")), "Test::new");
+    }
+}
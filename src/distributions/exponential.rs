@@ -11,7 +11,8 @@
 //! The exponential distribution.
 
 use {Rng};
-use distributions::{ziggurat, ziggurat_tables, Distribution};
+use distributions::{ziggurat, ziggurat_f32, ziggurat_tables, Distribution, Open01};
+use distributions::error::{DistributionError, unwrap_new, check_positive};
 
 /// Samples floating-point numbers according to the exponential distribution,
 /// with rate parameter `λ = 1`. This is equivalent to `Exp::new(1.0)` or
@@ -28,6 +29,8 @@ use distributions::{ziggurat, ziggurat_tables, Distribution};
 /// Samples*](https://www.doornik.com/research/ziggurat.pdf). Nuffield
 /// College, Oxford
 ///
+/// See [`Exp1F32`] to sample `f32` values.
+///
 /// # Example
 /// ```
 /// use rand::prelude::*;
@@ -36,6 +39,8 @@ use distributions::{ziggurat, ziggurat_tables, Distribution};
 /// let val: f64 = SmallRng::from_entropy().sample(Exp1);
 /// println!("{}", val);
 /// ```
+///
+/// [`Exp1F32`]: struct.Exp1F32.html
 #[derive(Clone, Copy, Debug)]
 pub struct Exp1;
 
@@ -59,10 +64,54 @@ impl Distribution<f64> for Exp1 {
     }
 }
 
+/// Samples `f32` values according to the exponential distribution, with rate
+/// parameter `λ = 1`.
+///
+/// This is the single-precision counterpart of [`Exp1`]. It uses its own
+/// Ziggurat tables rounded to `f32` and consumes one `u32` per iteration,
+/// which is faster than sampling an `f64` and converting it.
+///
+/// # Example
+/// ```
+/// use rand::prelude::*;
+/// use rand::distributions::Exp1F32;
+///
+/// let val: f32 = SmallRng::from_entropy().sample(Exp1F32);
+/// println!("{}", val);
+/// ```
+///
+/// [`Exp1`]: struct.Exp1.html
+#[derive(Clone, Copy, Debug)]
+pub struct Exp1F32;
+
+impl Distribution<f32> for Exp1F32 {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+        #[inline]
+        fn pdf(x: f32) -> f32 {
+            (-x).exp()
+        }
+        #[inline]
+        fn zero_case<R: Rng + ?Sized>(rng: &mut R, _u: f32) -> f32 {
+            // With only 24 bits of precision a sample of exactly 0 is not
+            // negligible, so avoid it to never return infinity.
+            let u: f32 = rng.sample(Open01);
+            ziggurat_tables::ZIG_EXP_R_F32 - u.ln()
+        }
+
+        ziggurat_f32(rng, false,
+                     &ziggurat_tables::ZIG_EXP_X_F32,
+                     &ziggurat_tables::ZIG_EXP_F_F32,
+                     pdf, zero_case)
+    }
+}
+
 /// The exponential distribution `Exp(lambda)`.
 ///
 /// This distribution has density function: `f(x) = lambda *
-/// exp(-lambda * x)` for `x > 0`.
+/// exp(-lambda * x)` for `x > 0`, with rate `lambda`. Its mean is
+/// `1 / lambda`; use [`Exp::from_mean`] to construct it from the mean
+/// instead. Samples are those of [`Exp1`] scaled by `1 / lambda`.
 ///
 /// # Example
 ///
@@ -72,7 +121,15 @@ impl Distribution<f64> for Exp1 {
 /// let exp = Exp::new(2.0);
 /// let v = exp.sample(&mut rand::thread_rng());
 /// println!("{} is from a Exp(2) distribution", v);
+///
+/// // Waiting times of a process with 30 events per hour on average, in minutes
+/// let wait = Exp::from_mean(2.0);
+/// let minutes = wait.sample(&mut rand::thread_rng());
+/// println!("waited {} minutes", minutes);
 /// ```
+///
+/// [`Exp::from_mean`]: #method.from_mean
+/// [`Exp1`]: struct.Exp1.html
 #[derive(Clone, Copy, Debug)]
 pub struct Exp {
     /// `lambda` stored as `1/lambda`, since this is what we scale by.
@@ -80,12 +137,42 @@ pub struct Exp {
 }
 
 impl Exp {
-    /// Construct a new `Exp` with the given shape parameter
-    /// `lambda`. Panics if `lambda <= 0`.
+    /// Construct a new `Exp` with the given rate parameter
+    /// `lambda`. Panics if `lambda <= 0` or `lambda` is NaN.
     #[inline]
     pub fn new(lambda: f64) -> Exp {
-        assert!(lambda > 0.0, "Exp::new called with `lambda` <= 0");
-        Exp { lambda_inverse: 1.0 / lambda }
+        unwrap_new(Exp::try_new(lambda), "Exp::new")
+    }
+
+    /// Construct a new `Exp` with the given rate parameter `lambda`,
+    /// returning an error if `lambda <= 0` or `lambda` is NaN.
+    ///
+    /// An infinite `lambda` is allowed; all samples are then 0.
+    #[inline]
+    pub fn try_new(lambda: f64) -> Result<Exp, DistributionError> {
+        if lambda.is_nan() {
+            return Err(DistributionError::NonFinite("lambda"));
+        }
+        if lambda <= 0.0 {
+            return Err(DistributionError::NonPositive("lambda"));
+        }
+        Ok(Exp { lambda_inverse: 1.0 / lambda })
+    }
+
+    /// Construct a new `Exp` with the given `mean`, i.e. with rate
+    /// `lambda = 1 / mean`. Panics if `mean <= 0` or `mean` is not finite.
+    #[inline]
+    pub fn from_mean(mean: f64) -> Exp {
+        unwrap_new(Exp::try_from_mean(mean), "Exp::from_mean")
+    }
+
+    /// Construct a new `Exp` with the given `mean`, i.e. with rate
+    /// `lambda = 1 / mean`, returning an error if `mean <= 0` or `mean` is
+    /// not finite.
+    #[inline]
+    pub fn try_from_mean(mean: f64) -> Result<Exp, DistributionError> {
+        check_positive(mean, "mean")?;
+        Ok(Exp { lambda_inverse: mean })
     }
 }
 
@@ -98,8 +185,33 @@ impl Distribution<f64> for Exp {
 
 #[cfg(test)]
 mod test {
+    use Rng;
     use distributions::Distribution;
-    use super::Exp;
+    use super::{Exp1F32, Exp};
+
+    #[test]
+    fn test_exp1_f32() {
+        let mut rng = ::test::rng(222);
+        const N: usize = 1_000_000;
+        let samples: Vec<f32> = (0..N).map(|_| rng.sample(Exp1F32)).collect();
+        assert!(samples.iter().all(|&x| x >= 0.0 && x.is_finite()));
+
+        let mean = samples.iter().map(|&x| x as f64).sum::<f64>() / N as f64;
+        assert!((mean - 1.0).abs() < 5.0 / (N as f64).sqrt(), "mean {} != 1", mean);
+        // The variance of `(X - 1)^2` is 8.
+        let var = samples.iter().map(|&x| (x as f64 - mean).powi(2))
+            .sum::<f64>() / N as f64;
+        assert!((var - 1.0).abs() < 5.0 * (8.0 / N as f64).sqrt(),
+                "variance {} != 1", var);
+
+        // The tail beyond the base layer is sampled by `zero_case`.
+        let r = 7.6971173f32;
+        let p = (-r as f64).exp();
+        let tail = samples.iter().filter(|&&x| x > r).count();
+        let freq = tail as f64 / N as f64;
+        assert!((freq - p).abs() < 5.0 * (p / N as f64).sqrt(),
+                "P(X > R) = {} != {}", freq, p);
+    }
 
     #[test]
     fn test_exp() {
@@ -109,6 +221,31 @@ mod test {
             assert!(exp.sample(&mut rng) >= 0.0);
         }
     }
+    #[test]
+    fn test_exp_mean() {
+        let mut rng = ::test::rng(224);
+        const N: usize = 100_000;
+        for &lambda in [0.01, 1.0, 3.5, 1e6].iter() {
+            for exp in [Exp::new(lambda), Exp::from_mean(1.0 / lambda)].iter() {
+                let mean = (0..N).map(|_| -> f64 { exp.sample(&mut rng) })
+                    .sum::<f64>() / N as f64;
+                // The standard deviation equals the mean.
+                let expected = 1.0 / lambda;
+                assert!((mean - expected).abs() < 5.0 * expected / (N as f64).sqrt(),
+                        "mean {} != {}", mean, expected);
+            }
+        }
+
+        // Both constructors scale the same samples of `Exp1`.
+        let mut rng1 = ::test::rng(225);
+        let mut rng2 = ::test::rng(225);
+        for _ in 0..100 {
+            let x: f64 = Exp::new(0.5).sample(&mut rng1);
+            let y: f64 = Exp::from_mean(2.0).sample(&mut rng2);
+            assert_eq!(x, y);
+        }
+    }
+
     #[test]
     #[should_panic]
     fn test_exp_invalid_lambda_zero() {
@@ -119,4 +256,31 @@ mod test {
     fn test_exp_invalid_lambda_neg() {
         Exp::new(-10.0);
     }
+    #[test]
+    fn test_exp_try_new() {
+        use distributions::DistributionError::*;
+        assert!(Exp::try_new(1e-300).is_ok());
+        assert!(Exp::try_new(::std::f64::INFINITY).is_ok());
+        assert_eq!(Exp::try_new(0.0).err(), Some(NonPositive("lambda")));
+        assert_eq!(Exp::try_new(-10.0).err(), Some(NonPositive("lambda")));
+        assert_eq!(Exp::try_new(::std::f64::NEG_INFINITY).err(), Some(NonPositive("lambda")));
+        assert_eq!(Exp::try_new(::std::f64::NAN).err(), Some(NonFinite("lambda")));
+    }
+
+    #[test]
+    fn test_exp_try_from_mean() {
+        use distributions::DistributionError::*;
+        assert!(Exp::try_from_mean(1e-300).is_ok());
+        assert!(Exp::try_from_mean(1e300).is_ok());
+        assert_eq!(Exp::try_from_mean(0.0).err(), Some(NonPositive("mean")));
+        assert_eq!(Exp::try_from_mean(-2.0).err(), Some(NonPositive("mean")));
+        assert_eq!(Exp::try_from_mean(::std::f64::INFINITY).err(), Some(NonFinite("mean")));
+        assert_eq!(Exp::try_from_mean(::std::f64::NAN).err(), Some(NonFinite("mean")));
+    }
+
+    #[test]
+    #[should_panic(expected = "Exp::from_mean called with invalid parameters")]
+    fn test_exp_from_mean_invalid() {
+        Exp::from_mean(0.0);
+    }
 }
@@ -67,6 +67,46 @@ pub struct OpenClosed01;
 #[derive(Clone, Copy, Debug)]
 pub struct Open01;
 
+/// A distribution to sample floating point numbers uniformly in the half-open
+/// interval `[0, 1)`, with the full precision of the floating point type.
+///
+/// [`Standard`] generates values of the form `n * ε/2`, so the smallest
+/// non-zero value is `ε/2` and all values close to zero are spaced equally
+/// far apart. `HighPrecision01` instead can generate every representable value
+/// in `[0, 1)`, including the smallest subnormal numbers. Each value `x`
+/// occurs with a probability equal to the distance to the next representable
+/// value, as if a uniformly distributed real number was rounded down. This
+/// matters for transformations which amplify values close to zero, like
+/// `ln(x)`, where the coarse granularity of `Standard` truncates the tail.
+///
+/// The fraction bits are taken from the least significant bits of one random
+/// `u32` (for `f32`) or `u64` (for `f64`). The exponent follows from the
+/// number of leading zeros of a string of random bits, which starts with the
+/// remaining most significant bits and continues with more random numbers as
+/// long as those are all zero; this happens with a probability of `1/512` for
+/// `f32` and `1/4096` for `f64`. Additional random numbers are therefore
+/// rarely needed, but the extra bit manipulation makes sampling about 1.5
+/// times as slow as with [`Standard`].
+///
+/// See also: [`Standard`], [`Open01`], [`OpenClosed01`] and [`Uniform`]
+/// which sample with a fixed precision.
+///
+/// # Example
+/// ```
+/// use rand::{thread_rng, Rng};
+/// use rand::distributions::HighPrecision01;
+///
+/// let val: f64 = thread_rng().sample(HighPrecision01);
+/// println!("f64 from [0, 1): {}", val);
+/// ```
+///
+/// [`Standard`]: struct.Standard.html
+/// [`Open01`]: struct.Open01.html
+/// [`OpenClosed01`]: struct.OpenClosed01.html
+/// [`Uniform`]: uniform/struct.Uniform.html
+#[derive(Clone, Copy, Debug)]
+pub struct HighPrecision01;
+
 
 pub(crate) trait IntoFloat {
     type F;
@@ -107,6 +147,24 @@ macro_rules! float_impls {
                 let value: $uty = rng.gen();
                 scale * (value >> (float_size - precision)) as $ty
             }
+
+            fn sample_fill<R: Rng + ?Sized>(&self, rng: &mut R, out: &mut [$ty]) {
+                // Generate the integers in chunks with `Rng::fill`, converting
+                // them like above. Chunks are no larger than the rest of the
+                // slice, so the RNG is not advanced further than necessary.
+                let float_size = mem::size_of::<$ty>() * 8;
+                let precision = $fraction_bits + 1;
+                let scale = 1.0 / ((1 as $uty << precision) as $ty);
+
+                let mut buf = [0 as $uty; 32];
+                for chunk in out.chunks_mut(buf.len()) {
+                    let values = &mut buf[..chunk.len()];
+                    rng.fill(values);
+                    for (x, &value) in chunk.iter_mut().zip(values.iter()) {
+                        *x = scale * (value >> (float_size - precision)) as $ty;
+                    }
+                }
+            }
         }
 
         impl Distribution<$ty> for OpenClosed01 {
@@ -138,6 +196,37 @@ macro_rules! float_impls {
                 fraction.into_float_with_exponent(0) - (1.0 - EPSILON / 2.0)
             }
         }
+
+        impl Distribution<$ty> for HighPrecision01 {
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                // Transmute-based method with a geometrically distributed
+                // exponent; [0, 1) interval. The exponent uses the most
+                // significant bits, and more random numbers if needed.
+                let float_size = mem::size_of::<$ty>() as u32 * 8;
+                // With more leading zeros the result is subnormal.
+                let max_zeros = $exponent_bias - 1;
+
+                let value: $uty = rng.gen();
+                let fraction = value & ((1 << $fraction_bits) - 1);
+                let mut zeros =
+                    (value >> $fraction_bits).leading_zeros() - $fraction_bits;
+                if zeros == float_size - $fraction_bits {
+                    loop {
+                        let value: $uty = rng.gen();
+                        zeros += value.leading_zeros();
+                        if value != 0 || zeros >= max_zeros {
+                            break;
+                        }
+                    }
+                }
+                if zeros >= max_zeros {
+                    // The fraction of a subnormal number with a zero exponent
+                    // covers the remaining range `[0, MIN_POSITIVE)`.
+                    return <$ty>::from_bits(fraction);
+                }
+                fraction.into_float_with_exponent(-1 - zeros as i32)
+            }
+        }
     }
 }
 float_impls! { f32, u32, 23, 127 }
@@ -147,12 +236,44 @@ float_impls! { f64, u64, 52, 1023 }
 #[cfg(test)]
 mod tests {
     use Rng;
-    use distributions::{Open01, OpenClosed01};
+    use distributions::{Open01, OpenClosed01, HighPrecision01};
     use rngs::mock::StepRng;
 
     const EPSILON32: f32 = ::core::f32::EPSILON;
     const EPSILON64: f64 = ::core::f64::EPSILON;
 
+    #[test]
+    fn standard_fp_sample_fill() {
+        use distributions::{Distribution, Standard};
+
+        // With a block RNG, filling a slice gives the same values as sampling
+        // each element.
+        for &len in [0, 1, 5, 32, 75].iter() {
+            let mut rng1 = ::test::rng(817);
+            let mut rng2 = ::test::rng(817);
+            let mut filled = [0f32; 75];
+            Standard.sample_fill(&mut rng1, &mut filled[..len]);
+            let mut sampled = [0f32; 75];
+            for x in sampled[..len].iter_mut() {
+                *x = rng2.gen();
+            }
+            assert_eq!(&filled[..len], &sampled[..len]);
+
+            let mut filled = [0f64; 75];
+            Standard.sample_fill(&mut rng1, &mut filled[..len]);
+            let mut sampled = [0f64; 75];
+            for x in sampled[..len].iter_mut() {
+                *x = rng2.gen();
+            }
+            assert_eq!(&filled[..len], &sampled[..len]);
+            assert_eq!(rng1.gen::<u32>(), rng2.gen::<u32>());
+        }
+
+        let mut filled = [1f64; 3];
+        Standard.sample_fill(&mut StepRng::new(0, 1 << 11), &mut filled);
+        assert_eq!(filled, [0.0, EPSILON64 / 2.0, EPSILON64]);
+    }
+
     #[test]
     fn standard_fp_edge_cases() {
         let mut zeros = StepRng::new(0, 0);
@@ -203,4 +324,63 @@ mod tests {
         assert_eq!(max.sample::<f32, _>(Open01), 1.0 - EPSILON32 / 2.0);
         assert_eq!(max.sample::<f64, _>(Open01), 1.0 - EPSILON64 / 2.0);
     }
+
+    #[test]
+    fn high_precision01_edge_cases() {
+        // Zero is only reached after the exponent got to the subnormal range,
+        // which takes 16 zero `u64`s for `f64`.
+        let mut zeros = StepRng::new(0, 0);
+        assert_eq!(zeros.sample::<f32, _>(HighPrecision01), 0.0);
+        assert_eq!(zeros.sample::<f64, _>(HighPrecision01), 0.0);
+
+        let mut max = StepRng::new(!0, 0);
+        assert_eq!(max.sample::<f32, _>(HighPrecision01), 1.0 - EPSILON32 / 2.0);
+        assert_eq!(max.sample::<f64, _>(HighPrecision01), 1.0 - EPSILON64 / 2.0);
+
+        // The leading bits give the exponent, the trailing bits the fraction.
+        let mut half = StepRng::new(1 << 63 | 1, 0);
+        assert_eq!(half.sample::<f64, _>(HighPrecision01), 0.5 + EPSILON64 / 2.0);
+        let mut quarter = StepRng::new(1 << 30, 0);
+        assert_eq!(quarter.sample::<f32, _>(HighPrecision01), 0.25);
+
+        // Values below `2^-60` occur if the first 60 bits are zero: here 12
+        // from the first `u64`, and 50 from the second.
+        let mut small = StepRng::new(3, 1 << 13);
+        let x: f64 = small.sample(HighPrecision01);
+        assert_eq!(x, (1.0 + 3.0 * EPSILON64) * 2f64.powi(-63));
+    }
+
+    #[cfg(feature="std")]
+    #[test]
+    fn high_precision01_distribution() {
+        let mut rng = ::test::rng(601);
+        const N: usize = 1_000_000;
+        let samples_f64: Vec<f64> =
+            (0..N).map(|_| rng.sample(HighPrecision01)).collect();
+        let samples_f32: Vec<f64> =
+            (0..N).map(|_| rng.sample::<f32, _>(HighPrecision01) as f64).collect();
+        for samples in [samples_f64, samples_f32].iter() {
+            assert!(samples.iter().all(|&x| x >= 0.0 && x < 1.0));
+
+            // The probability of a value below `2^-k` is `2^-k`.
+            for k in 1..17 {
+                let p = 0.5f64.powi(k);
+                let below = samples.iter().filter(|&&x| x < p).count();
+                let freq = below as f64 / N as f64;
+                assert!((freq - p).abs() < 5.0 * (p * (1.0 - p) / N as f64).sqrt(),
+                        "P(X < 2^-{}) = {} != {}", k, freq, p);
+            }
+
+            // Large values are distributed as with `Standard`.
+            let mut buckets = [0usize; 32];
+            for &x in samples.iter() {
+                buckets[(x * 32.0) as usize] += 1;
+            }
+            let p = 1.0 / 32.0;
+            let err = 5.0 * (p * (1.0 - p) * N as f64).sqrt();
+            for &count in buckets.iter() {
+                assert!((count as f64 - p * N as f64).abs() < err);
+            }
+        }
+    }
 }
@@ -0,0 +1,125 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Fréchet distribution.
+
+use Rng;
+use distributions::{Distribution, Open01};
+use distributions::error::{DistributionError, unwrap_new, check_finite, check_positive};
+
+/// Samples floating-point numbers according to the Fréchet (type II extreme
+/// value) distribution `Frechet(location, scale, shape)`.
+///
+/// The cumulative distribution function is
+/// `F(x) = exp(-((x - location) / scale)^-shape)` for `x > location`. This
+/// distribution is heavy-tailed: the mean only exists for `shape > 1` and the
+/// variance only for `shape > 2`.
+///
+/// # Example
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand::distributions::Frechet;
+///
+/// let val: f64 = SmallRng::from_entropy().sample(Frechet::new(0., 1., 2.));
+/// println!("{}", val);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Frechet {
+    location: f64,
+    scale: f64,
+    inv_neg_shape: f64,
+}
+
+impl Frechet {
+    /// Construct a new `Frechet` distribution with given `location`, `scale`
+    /// and `shape`.
+    ///
+    /// # Panics
+    ///
+    /// `scale` and `shape` have to be non-zero and positive, and all
+    /// parameters have to be finite.
+    pub fn new(location: f64, scale: f64, shape: f64) -> Frechet {
+        unwrap_new(Frechet::try_new(location, scale, shape), "Frechet::new")
+    }
+
+    /// Construct a new `Frechet` distribution with given `location`, `scale`
+    /// and `shape`, returning an error if `scale` or `shape` is not positive
+    /// or if any parameter is not finite.
+    pub fn try_new(location: f64, scale: f64, shape: f64)
+        -> Result<Frechet, DistributionError>
+    {
+        check_finite(location, "location")?;
+        check_positive(scale, "scale")?;
+        check_positive(shape, "shape")?;
+        Ok(Frechet { location, scale, inv_neg_shape: -1.0 / shape })
+    }
+}
+
+impl Distribution<f64> for Frechet {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        // `u` is in `(0, 1)`: `u = 1` would result in infinity.
+        let u: f64 = rng.sample(Open01);
+        self.location + self.scale * (-u.ln()).powf(self.inv_neg_shape)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use distributions::Distribution;
+    use super::Frechet;
+    use distributions::DistributionError::*;
+
+    #[test]
+    #[should_panic]
+    fn invalid() {
+        Frechet::new(0., 1., 0.);
+    }
+
+    #[test]
+    fn sample() {
+        let d = Frechet::new(-1.0, 1.0, 2.0);
+        let mut r = ::test::rng(1);
+        for _ in 0..1000 {
+            let x = d.sample(&mut r);
+            assert!(x > -1.0 && x.is_finite());
+        }
+    }
+
+    #[test]
+    fn quantiles() {
+        let mut r = ::test::rng(2);
+        for &(location, scale, shape) in [(0.0, 1.0, 0.5), (2.0, 2.0, 1.5),
+                                          (-1.0, 0.5, 3.0)].iter() {
+            let d = Frechet::new(location, scale, shape);
+            const N: usize = 100_000;
+            let mut samples: Vec<f64> = (0..N).map(|_| d.sample(&mut r)).collect();
+            samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            for &p in [0.1, 0.5, 0.9].iter() {
+                let x = samples[(p * N as f64) as usize];
+                // the analytic CDF at the empirical quantile
+                let cdf = (-((x - location) / scale).powf(-shape)).exp();
+                assert!((cdf - p).abs() < 0.005,
+                        "quantile {}: CDF({}) = {}", p, x, cdf);
+            }
+        }
+    }
+
+
+    #[test]
+    fn try_new() {
+        assert!(Frechet::try_new(-1.0, 1e-300, 1e300).is_ok());
+        assert_eq!(Frechet::try_new(0., 1., 0.).err(), Some(NonPositive("shape")));
+        assert_eq!(Frechet::try_new(0., -1., 1.).err(), Some(NonPositive("scale")));
+        assert_eq!(Frechet::try_new(0., 1., ::std::f64::INFINITY).err(), Some(NonFinite("shape")));
+        assert_eq!(Frechet::try_new(0., ::std::f64::NAN, 1.).err(), Some(NonFinite("scale")));
+        assert_eq!(Frechet::try_new(::std::f64::INFINITY, 1., 1.).err(), Some(NonFinite("location")));
+    }
+}
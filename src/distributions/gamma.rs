@@ -16,6 +16,7 @@ use self::ChiSquaredRepr::*;
 use Rng;
 use distributions::normal::StandardNormal;
 use distributions::{Distribution, Exp, Open01};
+use distributions::error::{DistributionError, unwrap_new, check_positive};
 
 /// The Gamma distribution `Gamma(shape, scale)` distribution.
 ///
@@ -31,7 +32,14 @@ use distributions::{Distribution, Exp, Open01};
 /// The algorithm used is that described by Marsaglia & Tsang 2000[1],
 /// falling back to directly sampling from an Exponential for `shape
 /// == 1`, and using the boosting technique described in [1] for
-/// `shape < 1`.
+/// `shape < 1`. When the boost factor underflows, the boosted sample is
+/// computed in log space, so results are only rounded to zero if they are
+/// below the smallest positive `f64`.
+///
+/// Note that for very small shapes this happens often: with `shape = 1e-3`,
+/// about half of all samples are below `1e-300`, and close to that fraction
+/// are 0. Use the [`Dirichlet`] distribution rather than normalizing such
+/// samples yourself, as it works in log space throughout.
 ///
 /// # Example
 ///
@@ -47,6 +55,8 @@ use distributions::{Distribution, Exp, Open01};
 /// for Generating Gamma Variables" *ACM Trans. Math. Softw.* 26, 3
 /// (September 2000),
 /// 363-372. DOI:[10.1145/358407.358414](https://doi.acm.org/10.1145/358407.358414)
+///
+/// [`Dirichlet`]: struct.Dirichlet.html
 #[derive(Clone, Copy, Debug)]
 pub struct Gamma {
     repr: GammaRepr,
@@ -94,20 +104,27 @@ impl Gamma {
     /// Construct an object representing the `Gamma(shape, scale)`
     /// distribution.
     ///
-    /// Panics if `shape <= 0` or `scale <= 0`.
+    /// Panics if `shape <= 0` or `scale <= 0`, or if either is not finite.
     #[inline]
     pub fn new(shape: f64, scale: f64) -> Gamma {
-        assert!(shape > 0.0, "Gamma::new called with shape <= 0");
-        assert!(scale > 0.0, "Gamma::new called with scale <= 0");
+        unwrap_new(Gamma::try_new(shape, scale), "Gamma::new")
+    }
+
+    /// Construct an object representing the `Gamma(shape, scale)`
+    /// distribution, returning an error if `shape <= 0` or `scale <= 0`, or
+    /// if either is not finite.
+    pub fn try_new(shape: f64, scale: f64) -> Result<Gamma, DistributionError> {
+        check_positive(shape, "shape")?;
+        check_positive(scale, "scale")?;
 
         let repr = if shape == 1.0 {
-            One(Exp::new(1.0 / scale))
+            One(Exp::try_new(1.0 / scale)?)
         } else if shape < 1.0 {
             Small(GammaSmallShape::new_raw(shape, scale))
         } else {
             Large(GammaLargeShape::new_raw(shape, scale))
         };
-        Gamma { repr }
+        Ok(Gamma { repr })
     }
 }
 
@@ -143,26 +160,38 @@ impl Distribution<f64> for Gamma {
 impl Distribution<f64> for GammaSmallShape {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
         let u: f64 = rng.sample(Open01);
+        let x = self.large_shape.sample(rng);
 
-        self.large_shape.sample(rng) * u.powf(self.inv_shape)
+        let boost = u.powf(self.inv_shape);
+        if boost >= ::std::f64::MIN_POSITIVE {
+            x * boost
+        } else {
+            // The boost is subnormal or zero, which loses precision even if
+            // the product is representable. In log space, the result only
+            // underflows if it is too small itself.
+            (x.ln() + u.ln() * self.inv_shape).exp()
+        }
     }
 }
 impl Distribution<f64> for GammaLargeShape {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
         loop {
             let x = rng.sample(StandardNormal);
-            let v_cbrt = 1.0 + self.c * x;
-            if v_cbrt <= 0.0 { // a^3 <= 0 iff a <= 0
+            let t = self.c * x;
+            if t <= -1.0 { // v = (1 + t)^3 <= 0 iff t <= -1
                 continue
             }
 
-            let v = v_cbrt * v_cbrt * v_cbrt;
+            // `v - 1` and `ln(v)` are computed without cancellation, since
+            // for huge shapes `d` amplifies the rounding error of
+            // `1 - v + ln(v)`, which is `O(t^2)` with `t = O(d^-1/2)`.
+            let v_minus_1 = t * (3.0 + t * (3.0 + t));
             let u: f64 = rng.sample(Open01);
 
             let x_sqr = x * x;
             if u < 1.0 - 0.0331 * x_sqr * x_sqr ||
-                u.ln() < 0.5 * x_sqr + self.d * (1.0 - v + v.ln()) {
-                return self.d * v * self.scale
+                u.ln() < 0.5 * x_sqr + self.d * (3.0 * t.ln_1p() - v_minus_1) {
+                return self.d * (1.0 + v_minus_1) * self.scale
             }
         }
     }
@@ -176,13 +205,25 @@ impl Distribution<f64> for GammaLargeShape {
 /// `k`, this uses the equivalent characterisation
 /// `χ²(k) = Gamma(k/2, 2)`.
 ///
+/// The mean of this distribution is `k` and the variance is `2k`.
+///
+/// For `k == 1`, samples are generated directly as the square of a standard
+/// normal sample, since sampling `Gamma(1/2, 2)` is comparatively slow. For
+/// all other `k`, including small integers, samples are generated from
+/// `Gamma(k/2, 2)`: for `k >= 2` this needs about one normal and one uniform
+/// sample, which is cheaper than summing `k` squared normal samples.
+///
+/// `ChiSquared` implements both `Distribution<f64>` and `Distribution<f32>`,
+/// so the type of the sample may need to be annotated. `f32` samples are
+/// computed in double precision and rounded.
+///
 /// # Example
 ///
 /// ```
 /// use rand::distributions::{ChiSquared, Distribution};
 ///
 /// let chi = ChiSquared::new(11.0);
-/// let v = chi.sample(&mut rand::thread_rng());
+/// let v: f64 = chi.sample(&mut rand::thread_rng());
 /// println!("{} is from a χ²(11) distribution", v)
 /// ```
 #[derive(Clone, Copy, Debug)]
@@ -201,15 +242,21 @@ enum ChiSquaredRepr {
 
 impl ChiSquared {
     /// Create a new chi-squared distribution with degrees-of-freedom
-    /// `k`. Panics if `k < 0`.
+    /// `k`. Panics if `k <= 0` or `k` is not finite.
     pub fn new(k: f64) -> ChiSquared {
+        unwrap_new(ChiSquared::try_new(k), "ChiSquared::new")
+    }
+
+    /// Create a new chi-squared distribution with degrees-of-freedom
+    /// `k`, returning an error if `k <= 0` or `k` is not finite.
+    pub fn try_new(k: f64) -> Result<ChiSquared, DistributionError> {
+        check_positive(k, "k")?;
         let repr = if k == 1.0 {
             DoFExactlyOne
         } else {
-            assert!(k > 0.0, "ChiSquared::new called with `k` < 0");
-            DoFAnythingElse(Gamma::new(0.5 * k, 2.0))
+            DoFAnythingElse(Gamma::try_new(0.5 * k, 2.0)?)
         };
-        ChiSquared { repr }
+        Ok(ChiSquared { repr })
     }
 }
 impl Distribution<f64> for ChiSquared {
@@ -225,19 +272,34 @@ impl Distribution<f64> for ChiSquared {
     }
 }
 
+impl Distribution<f32> for ChiSquared {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+        let x: f64 = self.sample(rng);
+        x as f32
+    }
+}
+
 /// The Fisher F distribution `F(m, n)`.
 ///
 /// This distribution is equivalent to the ratio of two normalised
 /// chi-squared distributions, that is, `F(m,n) = (χ²(m)/m) /
 /// (χ²(n)/n)`.
 ///
+/// Samples are non-negative. The mean is `n / (n - 2)` for `n > 2`, and the
+/// variance is finite for `n > 4`.
+///
+/// `FisherF` implements both `Distribution<f64>` and `Distribution<f32>`, so
+/// the type of the sample may need to be annotated. `f32` samples are
+/// computed in double precision and rounded; samples beyond the range of
+/// `f32` become infinite.
+///
 /// # Example
 ///
 /// ```
 /// use rand::distributions::{FisherF, Distribution};
 ///
 /// let f = FisherF::new(2.0, 32.0);
-/// let v = f.sample(&mut rand::thread_rng());
+/// let v: f64 = f.sample(&mut rand::thread_rng());
 /// println!("{} is from an F(2, 32) distribution", v)
 /// ```
 #[derive(Clone, Copy, Debug)]
@@ -251,34 +313,65 @@ pub struct FisherF {
 
 impl FisherF {
     /// Create a new `FisherF` distribution, with the given
-    /// parameter. Panics if either `m` or `n` are not positive.
+    /// parameter. Panics if either `m` or `n` are not positive and finite.
     pub fn new(m: f64, n: f64) -> FisherF {
-        assert!(m > 0.0, "FisherF::new called with `m < 0`");
-        assert!(n > 0.0, "FisherF::new called with `n < 0`");
+        unwrap_new(FisherF::try_new(m, n), "FisherF::new")
+    }
+
+    /// Create a new `FisherF` distribution, with the given parameter,
+    /// returning an error if either `m` or `n` are not positive and finite.
+    pub fn try_new(m: f64, n: f64) -> Result<FisherF, DistributionError> {
+        check_positive(m, "m")?;
+        check_positive(n, "n")?;
 
-        FisherF {
-            numer: ChiSquared::new(m),
-            denom: ChiSquared::new(n),
+        Ok(FisherF {
+            numer: ChiSquared::try_new(m)?,
+            denom: ChiSquared::try_new(n)?,
             dof_ratio: n / m
-        }
+        })
     }
 }
 impl Distribution<f64> for FisherF {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
-        self.numer.sample(rng) / self.denom.sample(rng) * self.dof_ratio
+        let numer: f64 = self.numer.sample(rng);
+        let denom: f64 = self.denom.sample(rng);
+        numer / denom * self.dof_ratio
+    }
+}
+
+impl Distribution<f32> for FisherF {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+        let x: f64 = self.sample(rng);
+        x as f32
     }
 }
 
 /// The Student t distribution, `t(nu)`, where `nu` is the degrees of
 /// freedom.
 ///
+/// Samples are generated as `Z / sqrt(χ²(nu) / nu)`, where `Z` is a standard
+/// normal sample. The distribution is symmetric about 0; its mean is 0 for
+/// `nu > 1` and its variance is `nu / (nu - 2)` for `nu > 2`. As `nu` grows,
+/// the distribution approaches the standard normal distribution.
+///
+/// For `nu >= 2^53` the distance between the distribution functions of
+/// `t(nu)` and the standard normal distribution, which is of order `1 / nu`,
+/// is below the resolution of `f64`; samples are then generated directly as
+/// standard normal samples. With `nu == 1` this is the standard Cauchy
+/// distribution, which has no mean; individual samples are still finite.
+///
+/// `StudentT` implements both `Distribution<f64>` and `Distribution<f32>`, so
+/// the type of the sample may need to be annotated. `f32` samples are
+/// computed in double precision and rounded; samples beyond the range of
+/// `f32` become infinite.
+///
 /// # Example
 ///
 /// ```
 /// use rand::distributions::{StudentT, Distribution};
 ///
 /// let t = StudentT::new(11.0);
-/// let v = t.sample(&mut rand::thread_rng());
+/// let v: f64 = t.sample(&mut rand::thread_rng());
 /// println!("{} is from a t(11) distribution", v)
 /// ```
 #[derive(Clone, Copy, Debug)]
@@ -289,33 +382,161 @@ pub struct StudentT {
 
 impl StudentT {
     /// Create a new Student t distribution with `n` degrees of
-    /// freedom. Panics if `n <= 0`.
+    /// freedom. Panics if `n <= 0` or `n` is not finite.
     pub fn new(n: f64) -> StudentT {
-        assert!(n > 0.0, "StudentT::new called with `n <= 0`");
-        StudentT {
-            chi: ChiSquared::new(n),
+        unwrap_new(StudentT::try_new(n), "StudentT::new")
+    }
+
+    /// Create a new Student t distribution with `n` degrees of
+    /// freedom, returning an error if `n <= 0` or `n` is not finite.
+    pub fn try_new(n: f64) -> Result<StudentT, DistributionError> {
+        check_positive(n, "n")?;
+        Ok(StudentT {
+            chi: ChiSquared::try_new(n)?,
             dof: n
-        }
+        })
     }
 }
+/// The degrees of freedom from which `StudentT` samples from the standard
+/// normal distribution instead.
+const STUDENT_T_NORMAL_DOF: f64 = 9007199254740992.0; // 2^53
+
 impl Distribution<f64> for StudentT {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
         let norm = rng.sample(StandardNormal);
-        norm * (self.dof / self.chi.sample(rng)).sqrt()
+        if self.dof >= STUDENT_T_NORMAL_DOF {
+            return norm;
+        }
+        let chi: f64 = self.chi.sample(rng);
+        norm * (self.dof / chi).sqrt()
+    }
+}
+
+impl Distribution<f32> for StudentT {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+        let x: f64 = self.sample(rng);
+        x as f32
+    }
+}
+
+/// The Beta distribution with shape parameters `alpha` and `beta`.
+///
+/// The density function of this distribution is
+///
+/// ```text
+/// f(x) = x^(α - 1) * (1 - x)^(β - 1) / B(α, β)
+/// ```
+///
+/// where `B` is the Beta function and `α` and `β` are strictly positive.
+///
+/// If `α >= 1` or `β >= 1`, samples are generated as `X / (X + Y)` where
+/// `X ~ Gamma(α, 1)` and `Y ~ Gamma(β, 1)`. If both `α < 1` and `β < 1`,
+/// Jöhnk's algorithm is used, working with logarithms so that results stay
+/// accurate when the powers involved underflow.
+///
+/// Samples are in the range `[0, 1]`. While the density is zero at both
+/// boundaries, for very small `α` (or `β`) and a large other parameter the
+/// Gamma sample `X` (or `Y`) may underflow, in which case exactly `0.0` (or
+/// `1.0`) is returned.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, Beta};
+///
+/// let beta = Beta::new(2.0, 5.0);
+/// let v = beta.sample(&mut rand::thread_rng());
+/// println!("{} is from a Beta(2, 5) distribution", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Beta {
+    repr: BetaRepr,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum BetaRepr {
+    // Both shape parameters are < 1: use Jöhnk's algorithm.
+    Johnk {
+        inv_alpha: f64,
+        inv_beta: f64,
+    },
+    Gamma {
+        gamma_a: Gamma,
+        gamma_b: Gamma,
+    },
+}
+
+impl Beta {
+    /// Construct an object representing the `Beta(alpha, beta)`
+    /// distribution.
+    ///
+    /// Panics if `alpha` or `beta` is not finite and strictly positive.
+    pub fn new(alpha: f64, beta: f64) -> Beta {
+        unwrap_new(Beta::try_new(alpha, beta), "Beta::new")
+    }
+
+    /// Construct an object representing the `Beta(alpha, beta)`
+    /// distribution, returning an error if `alpha` or `beta` is not finite
+    /// and strictly positive.
+    pub fn try_new(alpha: f64, beta: f64) -> Result<Beta, DistributionError> {
+        check_positive(alpha, "alpha")?;
+        check_positive(beta, "beta")?;
+        let repr = if alpha < 1.0 && beta < 1.0 {
+            BetaRepr::Johnk {
+                inv_alpha: 1.0 / alpha,
+                inv_beta: 1.0 / beta,
+            }
+        } else {
+            BetaRepr::Gamma {
+                gamma_a: Gamma::try_new(alpha, 1.0)?,
+                gamma_b: Gamma::try_new(beta, 1.0)?,
+            }
+        };
+        Ok(Beta { repr })
+    }
+}
+
+impl Distribution<f64> for Beta {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        match self.repr {
+            BetaRepr::Johnk { inv_alpha, inv_beta } => {
+                loop {
+                    let u: f64 = rng.sample(Open01);
+                    let v: f64 = rng.sample(Open01);
+                    let log_x = u.ln() * inv_alpha;
+                    let log_y = v.ln() * inv_beta;
+                    // Accept if x + y <= 1. When both underflow this is
+                    // trivially true, but the ratio must then be computed
+                    // relative to the larger of the two.
+                    let log_max = if log_x > log_y { log_x } else { log_y };
+                    let x = (log_x - log_max).exp();
+                    let y = (log_y - log_max).exp();
+                    if log_max + (x + y).ln() <= 0.0 {
+                        return x / (x + y);
+                    }
+                }
+            }
+            BetaRepr::Gamma { ref gamma_a, ref gamma_b } => {
+                let x = gamma_a.sample(rng);
+                let y = gamma_b.sample(rng);
+                x / (x + y)
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod test {
     use distributions::Distribution;
-    use super::{ChiSquared, StudentT, FisherF};
+    use distributions::DistributionError::*;
+    use super::{Gamma, ChiSquared, StudentT, FisherF, Beta};
 
     #[test]
     fn test_chi_squared_one() {
         let chi = ChiSquared::new(1.0);
         let mut rng = ::test::rng(201);
         for _ in 0..1000 {
-            chi.sample(&mut rng);
+            let _: f64 = chi.sample(&mut rng);
         }
     }
     #[test]
@@ -323,7 +544,7 @@ mod test {
         let chi = ChiSquared::new(0.5);
         let mut rng = ::test::rng(202);
         for _ in 0..1000 {
-            chi.sample(&mut rng);
+            let _: f64 = chi.sample(&mut rng);
         }
     }
     #[test]
@@ -331,7 +552,30 @@ mod test {
         let chi = ChiSquared::new(30.0);
         let mut rng = ::test::rng(203);
         for _ in 0..1000 {
-            chi.sample(&mut rng);
+            let _: f64 = chi.sample(&mut rng);
+        }
+    }
+    #[test]
+    fn test_chi_squared_moments() {
+        let mut rng = ::test::rng(205);
+        for &k in [1.0, 2.0, 3.0, 10.0, 0.5, 2.5, 17.25].iter() {
+            let chi = ChiSquared::new(k);
+            const N: usize = 100_000;
+            let mut sum = 0.0;
+            let mut sum_sq = 0.0;
+            for _ in 0..N {
+                let x: f64 = chi.sample(&mut rng);
+                assert!(x >= 0.0);
+                sum += x;
+                sum_sq += x * x;
+            }
+            let mean = sum / N as f64;
+            let variance = sum_sq / N as f64 - mean * mean;
+            // the standard error of the mean is `sqrt(2k / N)`
+            assert!((mean - k).abs() < 5.0 * (2.0 * k / N as f64).sqrt(),
+                    "mean {} != {}", mean, k);
+            assert!((variance / (2.0 * k) - 1.0).abs() < 0.05,
+                    "variance {} != {}", variance, 2.0 * k);
         }
     }
     #[test]
@@ -339,22 +583,328 @@ mod test {
     fn test_chi_squared_invalid_dof() {
         ChiSquared::new(-1.0);
     }
+    #[test]
+    #[should_panic]
+    fn test_chi_squared_zero_dof() {
+        ChiSquared::new(0.0);
+    }
+    #[test]
+    #[should_panic]
+    fn test_chi_squared_infinite_dof() {
+        ChiSquared::new(::std::f64::INFINITY);
+    }
+
+    // Check the mean and variance of `n` samples against those of
+    // `Gamma(shape, scale)`, with a tolerance of 5 standard errors.
+    fn check_gamma_moments(shape: f64, scale: f64, n: usize, seed: u64) {
+        let gamma = Gamma::new(shape, scale);
+        let mut rng = ::test::rng(seed);
+        let samples: Vec<f64> = (0..n).map(|_| gamma.sample(&mut rng)).collect();
+        assert!(samples.iter().all(|&x| x >= 0.0 && x.is_finite()));
+
+        let mean = shape * scale;
+        let variance = shape * scale * scale;
+        let sample_mean = samples.iter().sum::<f64>() / n as f64;
+        let sample_variance = samples.iter().map(|x| (x - sample_mean).powi(2))
+            .sum::<f64>() / (n - 1) as f64;
+        // The kurtosis of the Gamma distribution is `3 + 6 / shape`.
+        let err_mean = 5.0 * (variance / n as f64).sqrt();
+        let err_variance = 5.0 * variance * ((2.0 + 6.0 / shape) / n as f64).sqrt();
+        assert!((sample_mean - mean).abs() < err_mean,
+                "shape {}: mean {} != {}", shape, sample_mean, mean);
+        assert!((sample_variance - variance).abs() < err_variance,
+                "shape {}: variance {} != {}", shape, sample_variance, variance);
+    }
+
+    #[test]
+    fn test_gamma_moments() {
+        check_gamma_moments(1e-3, 1.0, 1_000_000, 230);
+        check_gamma_moments(0.5, 2.0, 100_000, 231);
+        check_gamma_moments(10.0, 0.5, 100_000, 232);
+        check_gamma_moments(1e7, 1.0, 100_000, 233);
+        check_gamma_moments(1e15, 1e-15, 100_000, 234);
+    }
+
+    #[test]
+    fn test_gamma_tiny_shape() {
+        // `P(X <= x)` is about `x^shape / Γ(1 + shape)` for small `x`.
+        let shape = 1e-3;
+        let gamma = Gamma::new(shape, 1.0);
+        let mut rng = ::test::rng(235);
+        const N: usize = 100_000;
+        let (mut tiny, mut subnormal) = (0, 0);
+        for _ in 0..N {
+            let x = gamma.sample(&mut rng);
+            if x <= 1e-300 {
+                tiny += 1;
+                if x > 0.0 && x < ::std::f64::MIN_POSITIVE {
+                    subnormal += 1;
+                }
+            }
+        }
+        let p = (1e-300f64).powf(shape) / 0.999_423_772_484_595_5;
+        let freq = tiny as f64 / N as f64;
+        assert!((freq - p).abs() < 5.0 * (p * (1.0 - p) / N as f64).sqrt(),
+                "P(X <= 1e-300) = {} != {}", freq, p);
+        // The subnormal range `[2^-1074, 2^-1022)` has a probability of
+        // about `(2^-1022)^shape - (2^-1074)^shape`, i.e. 1.7%; these samples
+        // used to be computed imprecisely, or flushed to zero.
+        let p = (::std::f64::MIN_POSITIVE.powf(shape) - (5e-324f64).powf(shape))
+            / 0.999_423_772_484_595_5;
+        let freq = subnormal as f64 / N as f64;
+        assert!((freq - p).abs() < 5.0 * (p * (1.0 - p) / N as f64).sqrt(),
+                "P(X subnormal) = {} != {}", freq, p);
+
+        // With a shape of 0.1, the probability of a zero is about `1e-32`.
+        let gamma = Gamma::new(0.1, 1.0);
+        for _ in 0..N {
+            assert!(gamma.sample(&mut rng) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_gamma_try_new() {
+        assert!(Gamma::try_new(1.0, 1e-310).is_ok());
+        assert_eq!(Gamma::try_new(0.0, 1.0).err(), Some(NonPositive("shape")));
+        assert_eq!(Gamma::try_new(-1.0, 1.0).err(), Some(NonPositive("shape")));
+        assert_eq!(Gamma::try_new(::std::f64::NAN, 1.0).err(), Some(NonFinite("shape")));
+        assert_eq!(Gamma::try_new(2.0, 0.0).err(), Some(NonPositive("scale")));
+        assert_eq!(Gamma::try_new(2.0, ::std::f64::INFINITY).err(), Some(NonFinite("scale")));
+    }
+
+    #[test]
+    fn test_chi_squared_try_new() {
+        assert!(ChiSquared::try_new(1.0).is_ok());
+        assert!(ChiSquared::try_new(0.5).is_ok());
+        assert_eq!(ChiSquared::try_new(0.0).err(), Some(NonPositive("k")));
+        assert_eq!(ChiSquared::try_new(-1.0).err(), Some(NonPositive("k")));
+        assert_eq!(ChiSquared::try_new(::std::f64::INFINITY).err(), Some(NonFinite("k")));
+        assert_eq!(ChiSquared::try_new(::std::f64::NAN).err(), Some(NonFinite("k")));
+    }
 
     #[test]
     fn test_f() {
         let f = FisherF::new(2.0, 32.0);
         let mut rng = ::test::rng(204);
         for _ in 0..1000 {
-            f.sample(&mut rng);
+            let _: f64 = f.sample(&mut rng);
         }
     }
 
+    #[test]
+    fn test_f_mean() {
+        let mut rng = ::test::rng(208);
+        for &(m, n) in [(2.0, 32.0), (5.0, 10.0), (0.5, 8.0), (40.0, 6.0)].iter() {
+            let f = FisherF::new(m, n);
+            const N: usize = 100_000;
+            let mut sum = 0.0;
+            for _ in 0..N {
+                let x: f64 = f.sample(&mut rng);
+                assert!(x >= 0.0);
+                sum += x;
+            }
+            let mean = sum / N as f64;
+            let expected_mean = n / (n - 2.0);
+            let variance = 2.0 * n * n * (m + n - 2.0)
+                / (m * (n - 2.0) * (n - 2.0) * (n - 4.0));
+            assert!((mean - expected_mean).abs() < 5.0 * (variance / N as f64).sqrt(),
+                    "mean {} != {}", mean, expected_mean);
+        }
+    }
+    #[test]
+    #[should_panic]
+    fn test_f_invalid_dof() {
+        FisherF::new(2.0, 0.0);
+    }
+
+    #[test]
+    fn test_f_try_new() {
+        assert!(FisherF::try_new(2.0, 32.0).is_ok());
+        assert_eq!(FisherF::try_new(0.0, 1.0).err(), Some(NonPositive("m")));
+        assert_eq!(FisherF::try_new(1.0, -1.0).err(), Some(NonPositive("n")));
+        assert_eq!(FisherF::try_new(::std::f64::INFINITY, 1.0).err(), Some(NonFinite("m")));
+        assert_eq!(FisherF::try_new(1.0, ::std::f64::NAN).err(), Some(NonFinite("n")));
+    }
+
     #[test]
     fn test_t() {
         let t = StudentT::new(11.0);
         let mut rng = ::test::rng(205);
         for _ in 0..1000 {
-            t.sample(&mut rng);
+            let _: f64 = t.sample(&mut rng);
+        }
+    }
+
+    #[test]
+    fn test_t_symmetric() {
+        let mut rng = ::test::rng(206);
+        for &dof in [0.5, 1.0, 3.0, 30.0].iter() {
+            let t = StudentT::new(dof);
+            const N: usize = 20_000;
+            let mut samples: Vec<f64> = (0..N).map(|_| t.sample(&mut rng)).collect();
+            samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            // The median is 0 and the quartiles are symmetric about 0.
+            let positive = samples.iter().filter(|&&x| x > 0.0).count();
+            assert!((positive as f64 / N as f64 - 0.5).abs() < 0.02);
+            let (q1, q3) = (samples[N / 4], samples[3 * N / 4]);
+            assert!((q1 + q3).abs() < 0.05 * (q3 - q1),
+                    "quartiles {} and {} not symmetric", q1, q3);
+        }
+    }
+
+    #[test]
+    fn test_t_variance() {
+        let mut rng = ::test::rng(207);
+        // `nu / (nu - 2)`, approaching the standard normal for large `nu`
+        for &(dof, expected) in [(10.0, 1.25), (30.0, 30.0 / 28.0), (1e6, 1.0),
+                                 (1e20, 1.0)].iter() {
+            let t = StudentT::new(dof);
+            const N: usize = 100_000;
+            let mut sum = 0.0;
+            let mut sum_sq = 0.0;
+            for _ in 0..N {
+                let x: f64 = t.sample(&mut rng);
+                sum += x;
+                sum_sq += x * x;
+            }
+            let mean = sum / N as f64;
+            let variance = sum_sq / N as f64 - mean * mean;
+            assert!(mean.abs() < 0.02, "mean {} != 0", mean);
+            assert!((variance - expected).abs() < 0.04,
+                    "variance {} != {}", variance, expected);
+        }
+    }
+
+    #[test]
+    fn test_t_one_dof() {
+        // `t(1)` has no mean and an infinite variance, but each sample is
+        // finite.
+        let t = StudentT::new(1.0);
+        let mut rng = ::test::rng(209);
+        for _ in 0..100_000 {
+            let x: f64 = t.sample(&mut rng);
+            assert!(x.is_finite());
         }
     }
+
+    #[test]
+    fn test_t_huge_dof() {
+        use distributions::StandardNormal;
+        let t = StudentT::new(1e300);
+        let mut rng1 = ::test::rng(210);
+        let mut rng2 = ::test::rng(210);
+        for _ in 0..100 {
+            let x: f64 = t.sample(&mut rng1);
+            let n: f64 = StandardNormal.sample(&mut rng2);
+            assert_eq!(x, n);
+        }
+    }
+
+    #[test]
+    fn test_f32() {
+        const N: usize = 100_000;
+        let mut rng = ::test::rng(211);
+        let chi = ChiSquared::new(3.0);
+        let samples: Vec<f32> = (0..N).map(|_| chi.sample(&mut rng)).collect();
+        let mean = samples.iter().sum::<f32>() / N as f32;
+        assert!((mean - 3.0).abs() < 5.0 * (6.0 / N as f32).sqrt(),
+                "mean {} != 3", mean);
+
+        let f = FisherF::new(5.0, 10.0);
+        let samples: Vec<f32> = (0..N).map(|_| f.sample(&mut rng)).collect();
+        let mean = samples.iter().sum::<f32>() / N as f32;
+        let variance = 2.0 * 100.0 * 13.0 / (5.0 * 64.0 * 6.0);
+        assert!((mean - 1.25).abs() < 5.0 * (variance / N as f32).sqrt(),
+                "mean {} != 1.25", mean);
+
+        let t = StudentT::new(10.0);
+        let variance = (0..N).map(|_| {
+            let x: f32 = t.sample(&mut rng);
+            x * x
+        }).sum::<f32>() / N as f32;
+        assert!((variance - 1.25).abs() < 0.04, "variance {} != 1.25", variance);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_t_invalid_dof() {
+        StudentT::new(0.0);
+    }
+
+    #[test]
+    fn test_t_try_new() {
+        assert!(StudentT::try_new(11.0).is_ok());
+        assert_eq!(StudentT::try_new(0.0).err(), Some(NonPositive("n")));
+        assert_eq!(StudentT::try_new(-2.0).err(), Some(NonPositive("n")));
+        assert_eq!(StudentT::try_new(::std::f64::INFINITY).err(), Some(NonFinite("n")));
+    }
+
+    #[test]
+    fn test_beta() {
+        let beta = Beta::new(1.0, 2.0);
+        let mut rng = ::test::rng(206);
+        for _ in 0..1000 {
+            let x = beta.sample(&mut rng);
+            assert!(0.0 < x && x < 1.0);
+        }
+    }
+
+    #[test]
+    fn test_beta_moments() {
+        const N: usize = 200_000;
+        let params = [(2.0, 5.0), (1.0, 1.0), (0.5, 0.5), (0.5, 3.0),
+                      (0.1, 0.2), (0.01, 0.03)];
+        let mut rng = ::test::rng(207);
+        for &(a, b) in params.iter() {
+            let beta = Beta::new(a, b);
+            let mut sum = 0.0;
+            let mut sum_sq = 0.0;
+            for _ in 0..N {
+                let x = beta.sample(&mut rng);
+                assert!(x >= 0.0 && x <= 1.0);
+                sum += x;
+                sum_sq += x * x;
+            }
+            let mean = sum / N as f64;
+            let var = sum_sq / N as f64 - mean * mean;
+            let exp_mean = a / (a + b);
+            let exp_var = a * b / ((a + b) * (a + b) * (a + b + 1.0));
+            assert!((mean - exp_mean).abs() < 0.005,
+                    "Beta({}, {}): mean {} != {}", a, b, mean, exp_mean);
+            assert!((var - exp_var).abs() < 0.005,
+                    "Beta({}, {}): variance {} != {}", a, b, var, exp_var);
+        }
+    }
+
+    #[test]
+    fn test_beta_tiny_shapes() {
+        // Individual powers underflow here; results must still be valid.
+        let beta = Beta::new(1e-4, 1e-4);
+        let mut rng = ::test::rng(208);
+        for _ in 0..1000 {
+            let x = beta.sample(&mut rng);
+            assert!(x >= 0.0 && x <= 1.0);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_beta_invalid_dof() {
+        Beta::new(0., 0.);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_beta_infinite() {
+        Beta::new(::std::f64::INFINITY, 1.0);
+    }
+
+    #[test]
+    fn test_beta_try_new() {
+        assert!(Beta::try_new(0.5, 2.0).is_ok());
+        assert_eq!(Beta::try_new(0.0, 1.0).err(), Some(NonPositive("alpha")));
+        assert_eq!(Beta::try_new(1.0, -1.0).err(), Some(NonPositive("beta")));
+        assert_eq!(Beta::try_new(::std::f64::INFINITY, 1.0).err(), Some(NonFinite("alpha")));
+        assert_eq!(Beta::try_new(1.0, ::std::f64::NAN).err(), Some(NonFinite("beta")));
+    }
 }
@@ -0,0 +1,157 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The geometric distribution.
+
+use Rng;
+use distributions::{Distribution, OpenClosed01};
+use distributions::error::{DistributionError, unwrap_new};
+
+/// The geometric distribution `Geometric(p)`.
+///
+/// This is the distribution of the number of failures before the first
+/// success in a series of Bernoulli trials, each with probability of success
+/// `p`. Its probability mass function is `f(k) = (1 - p)^k p` for `k >= 0`,
+/// its mean is `(1 - p) / p` and its variance is `(1 - p) / p^2`.
+///
+/// Samples are generated by inversion as `floor(ln(u) / ln(1 - p))`, with `u`
+/// uniform in `(0, 1]`. For very small `p`, this may exceed the range of
+/// `u64`; such samples saturate at `u64::MAX`. This only happens with
+/// non-negligible probability when `p` is below about `1e-17`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Geometric, Distribution};
+///
+/// let geo = Geometric::new(0.25);
+/// let v = geo.sample(&mut rand::thread_rng());
+/// println!("{} failures before the first success", v);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Geometric {
+    // `1 / ln(1 - p)`, or 0 if `p == 1`
+    inv_ln_1_p: f64,
+}
+
+impl Geometric {
+    /// Construct a new `Geometric` with the given probability of success `p`.
+    ///
+    /// Panics if `p <= 0` or `p > 1`.
+    pub fn new(p: f64) -> Geometric {
+        unwrap_new(Geometric::try_new(p), "Geometric::new")
+    }
+
+    /// Construct a new `Geometric` with the given probability of success `p`,
+    /// returning an error if `p <= 0` or `p > 1`.
+    pub fn try_new(p: f64) -> Result<Geometric, DistributionError> {
+        if !(p > 0.0 && p <= 1.0) {
+            return Err(DistributionError::OutOfRange("p"));
+        }
+        let inv_ln_1_p = if p == 1.0 { 0.0 } else { 1.0 / (-p).ln_1p() };
+        Ok(Geometric { inv_ln_1_p })
+    }
+}
+
+impl Distribution<u64> for Geometric {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+        if self.inv_ln_1_p == 0.0 {
+            // `p == 1`
+            return 0;
+        }
+        let u: f64 = rng.sample(OpenClosed01);
+        let x = (u.ln() * self.inv_ln_1_p).floor();
+        if x >= ::core::u64::MAX as f64 {
+            ::core::u64::MAX
+        } else {
+            x as u64
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::Distribution;
+    use rngs::mock::StepRng;
+    use super::Geometric;
+    use distributions::DistributionError::*;
+
+    fn check_mean_variance(p: f64, seed: u64) {
+        let geo = Geometric::new(p);
+        let mut rng = ::test::rng(seed);
+        const N: usize = 100_000;
+        let mut sum = 0.0;
+        let mut sum_sq = 0.0;
+        for _ in 0..N {
+            let x = geo.sample(&mut rng) as f64;
+            sum += x;
+            sum_sq += x * x;
+        }
+        let mean = sum / N as f64;
+        let variance = sum_sq / N as f64 - mean * mean;
+        let expected_mean = (1.0 - p) / p;
+        let expected_variance = (1.0 - p) / (p * p);
+        assert!((mean - expected_mean).abs()
+                < 5.0 * (expected_variance / N as f64).sqrt() + 1e-9,
+                "mean {} != {}", mean, expected_mean);
+        assert!((variance / expected_variance - 1.0).abs() < 0.05,
+                "variance {} != {}", variance, expected_variance);
+    }
+
+    #[test]
+    fn test_geometric() {
+        check_mean_variance(0.5, 451);
+        check_mean_variance(0.3, 452);
+        check_mean_variance(0.95, 453);
+        check_mean_variance(1e-3, 454);
+        check_mean_variance(1e-9, 455);
+    }
+
+    #[test]
+    fn test_geometric_one() {
+        let mut rng = ::test::rng(456);
+        let geo = Geometric::new(1.0);
+        for _ in 0..100 {
+            assert_eq!(geo.sample(&mut rng), 0);
+        }
+    }
+
+    #[test]
+    fn test_geometric_saturate() {
+        // the smallest value of `u` gives the largest sample
+        let mut rng = StepRng::new(0, 0);
+        assert_eq!(Geometric::new(1e-300).sample(&mut rng), ::core::u64::MAX);
+        // `ln(2^-53) / ln(1 - 1e-9)`
+        let x = Geometric::new(1e-9).sample(&mut rng) as f64;
+        assert!((x - 53.0 * 2f64.ln() / 1e-9).abs() < 100.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_geometric_invalid_zero() {
+        Geometric::new(0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_geometric_invalid_nan() {
+        Geometric::new(::std::f64::NAN);
+    }
+
+
+    #[test]
+    fn test_geometric_try_new() {
+        assert!(Geometric::try_new(1.0).is_ok());
+        assert!(Geometric::try_new(1e-300).is_ok());
+        for &p in [0.0, -0.5, 1.5, ::std::f64::NAN].iter() {
+            assert_eq!(Geometric::try_new(p).err(), Some(OutOfRange("p")));
+        }
+    }
+}
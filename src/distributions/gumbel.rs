@@ -0,0 +1,257 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Gumbel distribution.
+
+use Rng;
+use distributions::{Distribution, Open01};
+use distributions::error::{DistributionError, unwrap_new, check_finite, check_positive};
+
+/// The Gumbel (type I extreme value) distribution `Gumbel(location, scale)`.
+///
+/// This distribution has a density function:
+/// `f(x) = exp(-(z + exp(-z))) / scale` where `z = (x - location) / scale`.
+///
+/// Its mean is `location + scale * γ`, where `γ ≈ 0.5772` is the
+/// Euler–Mascheroni constant.
+///
+/// Samples are generated by transforming a sample `u` from [`Open01`] with
+/// the inverse of the cumulative distribution function,
+/// `location - scale * ln(-ln(u))`. Since `u` is never 0 or 1, samples are
+/// never infinite or NaN; they lie within about
+/// `location + [-3.6, 36.8] * scale`.
+///
+/// `Gumbel` implements both `Distribution<f64>` and `Distribution<f32>`, so
+/// the type of the sample may need to be annotated. `f32` samples are
+/// computed in double precision and rounded.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Gumbel, Distribution};
+///
+/// let gumbel = Gumbel::new(2.0, 5.0);
+/// let v: f64 = gumbel.sample(&mut rand::thread_rng());
+/// println!("{} is from a Gumbel(2, 5) distribution", v);
+/// ```
+///
+/// [`Open01`]: struct.Open01.html
+#[derive(Clone, Copy, Debug)]
+pub struct Gumbel {
+    location: f64,
+    scale: f64,
+}
+
+impl Gumbel {
+    /// Construct a new `Gumbel` distribution with the given `location` and
+    /// `scale`. Panics if `scale <= 0` or if either parameter is not finite.
+    pub fn new(location: f64, scale: f64) -> Gumbel {
+        unwrap_new(Gumbel::try_new(location, scale), "Gumbel::new")
+    }
+
+    /// Construct a new `Gumbel` distribution with the given `location` and
+    /// `scale`, returning an error if `scale <= 0` or if either parameter is
+    /// not finite.
+    pub fn try_new(location: f64, scale: f64) -> Result<Gumbel, DistributionError> {
+        check_finite(location, "location")?;
+        check_positive(scale, "scale")?;
+        Ok(Gumbel {
+            location,
+            scale,
+        })
+    }
+
+    /// Sample an index from a slice of (unnormalized) log-weights, using the
+    /// Gumbel-max trick.
+    ///
+    /// Index `i` is selected with probability
+    /// `exp(log_weights[i]) / sum(exp(log_weights))`, i.e. according to the
+    /// softmax of `log_weights`. This works without computing any
+    /// exponentials, so it does not suffer from overflow for large
+    /// log-weights. A log-weight of negative infinity is never selected.
+    ///
+    /// Returns `None` if `log_weights` is empty or all log-weights are
+    /// negative infinity (or NaN).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::distributions::Gumbel;
+    ///
+    /// let log_weights = [1000.0, 1000.0 + 2.0f64.ln()];
+    /// // Picks index 1 twice as often as index 0.
+    /// let i = Gumbel::sample_argmax(&mut rand::thread_rng(), &log_weights);
+    /// assert!(i == Some(0) || i == Some(1));
+    /// ```
+    pub fn sample_argmax<R: Rng + ?Sized>(rng: &mut R, log_weights: &[f64])
+        -> Option<usize>
+    {
+        let standard = Gumbel::new(0.0, 1.0);
+        let mut best = None;
+        let mut best_value = ::core::f64::NEG_INFINITY;
+        for (i, &w) in log_weights.iter().enumerate() {
+            if w == ::core::f64::NEG_INFINITY {
+                continue;
+            }
+            let value: f64 = w + Distribution::<f64>::sample(&standard, rng);
+            if value > best_value {
+                best = Some(i);
+                best_value = value;
+            }
+        }
+        best
+    }
+}
+
+impl Distribution<f64> for Gumbel {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        // `u` is in the open interval (0, 1), so neither logarithm is
+        // infinite.
+        let u: f64 = rng.sample(Open01);
+        self.location - self.scale * (-u.ln()).ln()
+    }
+}
+
+impl Distribution<f32> for Gumbel {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+        let x: f64 = self.sample(rng);
+        x as f32
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::Distribution;
+    use rngs::mock::StepRng;
+    use super::Gumbel;
+    use distributions::DistributionError::*;
+
+    const EULER_GAMMA: f64 = 0.577_215_664_901_532_9;
+
+    #[test]
+    fn test_gumbel_mean() {
+        let mut rng = ::test::rng(311);
+        for &(location, scale) in [(0.0, 1.0), (10.0, 2.5), (-3.0, 0.1)].iter() {
+            let gumbel = Gumbel::new(location, scale);
+            const N: usize = 100_000;
+            let mut sum = 0.0;
+            for _ in 0..N {
+                let x: f64 = gumbel.sample(&mut rng);
+                assert!(x.is_finite());
+                sum += x;
+            }
+            let mean = sum / N as f64;
+            let expected = location + scale * EULER_GAMMA;
+            // the standard deviation is `scale * pi / sqrt(6)`
+            assert!((mean - expected).abs() < 0.02 * scale,
+                    "mean {} != {}", mean, expected);
+        }
+    }
+
+    #[test]
+    fn test_gumbel_max() {
+        let mut rng = ::test::rng(312);
+        let weights = [1.0f64, 2.0, 0.0, 5.0, 0.5];
+        let log_weights: Vec<f64> = weights.iter().map(|w| w.ln()).collect();
+        let total: f64 = weights.iter().sum();
+
+        const N: usize = 100_000;
+        let mut counts = [0usize; 5];
+        for _ in 0..N {
+            let i = Gumbel::sample_argmax(&mut rng, &log_weights).unwrap();
+            counts[i] += 1;
+        }
+        assert_eq!(counts[2], 0);
+        for (count, w) in counts.iter().zip(weights.iter()) {
+            let freq = *count as f64 / N as f64;
+            assert!((freq - w / total).abs() < 0.01,
+                    "frequency {} != {}", freq, w / total);
+        }
+    }
+
+    #[test]
+    fn test_gumbel_max_degenerate() {
+        let mut rng = ::test::rng(313);
+        assert_eq!(Gumbel::sample_argmax(&mut rng, &[]), None);
+        let neg_inf = ::std::f64::NEG_INFINITY;
+        assert_eq!(Gumbel::sample_argmax(&mut rng, &[neg_inf, neg_inf]), None);
+        assert_eq!(Gumbel::sample_argmax(&mut rng, &[neg_inf, 3.0]), Some(1));
+    }
+
+    #[test]
+    fn test_gumbel_quantiles() {
+        // The fraction of samples below each quantile `Q(p)` must be close
+        // to `p`.
+        let mut rng = ::test::rng(314);
+        let (location, scale) = (-1.5, 3.0);
+        let gumbel = Gumbel::new(location, scale);
+        const N: usize = 50_000;
+        let samples: Vec<f64> = (0..N).map(|_| gumbel.sample(&mut rng)).collect();
+        let samples_f32: Vec<f32> = (0..N).map(|_| gumbel.sample(&mut rng)).collect();
+        for &p in [0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99].iter() {
+            let q: f64 = location - scale * (-(p as f64).ln()).ln();
+            let below = samples.iter().filter(|&&x| x <= q).count();
+            let below_f32 = samples_f32.iter().filter(|&&x| x <= q as f32).count();
+            let err = 5.0 * (p * (1.0 - p) / N as f64).sqrt();
+            for &count in [below, below_f32].iter() {
+                let freq = count as f64 / N as f64;
+                assert!((freq - p).abs() < err,
+                        "P(X <= {}) = {} != {}", q, freq, p);
+            }
+        }
+    }
+
+    #[test]
+    fn test_gumbel_finite() {
+        let mut rng = ::test::rng(315);
+        let gumbel = Gumbel::new(0.0, 1.0);
+        for _ in 0..1_000_000 {
+            let x: f64 = gumbel.sample(&mut rng);
+            assert!(x.is_finite());
+        }
+        // The most extreme values of `u`
+        for &bits in [0, ::core::u64::MAX].iter() {
+            let x: f64 = gumbel.sample(&mut StepRng::new(bits, 0));
+            assert!(x.is_finite() && x > -3.7 && x < 36.8);
+            let x: f32 = gumbel.sample(&mut StepRng::new(bits, 0));
+            assert!(x.is_finite());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gumbel_invalid_scale() {
+        Gumbel::new(0.0, 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gumbel_infinite_scale() {
+        Gumbel::new(0.0, ::std::f64::INFINITY);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gumbel_nan_location() {
+        Gumbel::new(::std::f64::NAN, 1.0);
+    }
+
+
+    #[test]
+    fn test_gumbel_try_new() {
+        assert!(Gumbel::try_new(-1e300, 1e-300).is_ok());
+        assert_eq!(Gumbel::try_new(0.0, 0.0).err(), Some(NonPositive("scale")));
+        assert_eq!(Gumbel::try_new(0.0, -1.0).err(), Some(NonPositive("scale")));
+        assert_eq!(Gumbel::try_new(0.0, ::std::f64::INFINITY).err(), Some(NonFinite("scale")));
+        assert_eq!(Gumbel::try_new(0.0, ::std::f64::NAN).err(), Some(NonFinite("scale")));
+        assert_eq!(Gumbel::try_new(::std::f64::NAN, 1.0).err(), Some(NonFinite("location")));
+        assert_eq!(Gumbel::try_new(-::std::f64::INFINITY, 1.0).err(), Some(NonFinite("location")));
+    }
+}
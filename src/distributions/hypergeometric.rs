@@ -0,0 +1,493 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The hypergeometric distribution.
+
+use core::cmp;
+
+use Rng;
+use distributions::{Distribution, Uniform};
+use distributions::log_gamma::log_gamma;
+use distributions::error::{DistributionError, unwrap_new};
+
+/// The hypergeometric distribution `Hypergeometric(N, K, n)`.
+///
+/// This is the distribution of the number of successes in `n` draws, without
+/// replacement, from a population of size `N` that contains `K` successes.
+/// For example, the number of aces in a hand of 5 cards from a deck of 52 has
+/// the distribution `Hypergeometric(52, 4, 5)`.
+///
+/// The probability mass function is
+/// `f(k) = binomial(K, k) * binomial(N - K, n - k) / binomial(N, n)`,
+/// where `binomial(a, b) = a! / (b! * (a - b)!)`. The mean is `n * K / N` and
+/// the variance is `n * K / N * (N - K) / N * (N - n) / (N - 1)`.
+///
+/// If the result is fully determined by the parameters (if `n` is 0 or `N`,
+/// or `K` is 0 or `N`), sampling returns that value without using the RNG.
+/// Otherwise, the HIN inversion algorithm is used when the mode is close to
+/// the smallest possible value, and the H2PE rejection algorithm otherwise,
+/// which takes expected constant time regardless of the parameters [1].
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Hypergeometric, Distribution};
+///
+/// let aces = Hypergeometric::new(52, 4, 5);
+/// let v = aces.sample(&mut rand::thread_rng());
+/// println!("{} aces in the hand", v);
+/// ```
+///
+/// [1]: Voratas Kachitvichyanukul and Bruce W. Schmeiser. 1985. "Computer
+/// generation of hypergeometric random variates." *J. Statist. Comput.
+/// Simul.* 22, 2 (1985), 127-145.
+#[derive(Clone, Copy, Debug)]
+pub struct Hypergeometric {
+    // The parameters are transformed so that `n1 <= n2` and `k <= N / 2`,
+    // where `n1` is the number of successes, `n2` the number of failures and
+    // `k` the sample size. The result is `offset_x + sign_x * x`, where `x` is
+    // sampled with the transformed parameters.
+    n1: u64,
+    n2: u64,
+    k: u64,
+    offset_x: i64,
+    sign_x: i64,
+    sampling_method: SamplingMethod,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum SamplingMethod {
+    Constant,
+    InverseTransform {
+        initial_p: f64,
+        initial_x: i64,
+    },
+    RejectionAcceptance {
+        m: f64,
+        a: f64,
+        lambda_l: f64,
+        lambda_r: f64,
+        x_l: f64,
+        x_r: f64,
+        p1: f64,
+        p2: f64,
+        p3: f64,
+    },
+}
+
+// Computes `(numerator.0! * numerator.1!) / (denominator.0! * denominator.1!)`
+fn fraction_of_products_of_factorials(numerator: (u64, u64), denominator: (u64, u64)) -> f64 {
+    let min_top = cmp::min(numerator.0, numerator.1);
+    let min_bottom = cmp::min(denominator.0, denominator.1);
+    // the factorial of this will cancel out:
+    let min_all = cmp::min(min_top, min_bottom);
+
+    let max_top = cmp::max(numerator.0, numerator.1);
+    let max_bottom = cmp::max(denominator.0, denominator.1);
+    let max_all = cmp::max(max_top, max_bottom);
+
+    let mut result = 1.0;
+    for i in (min_all + 1)..(max_all + 1) {
+        if i <= min_top {
+            result *= i as f64;
+        }
+        if i <= min_bottom {
+            result /= i as f64;
+        }
+        if i <= max_top {
+            result *= i as f64;
+        }
+        if i <= max_bottom {
+            result /= i as f64;
+        }
+    }
+    result
+}
+
+// `ln(v!)`, extended to non-integer `v`
+fn ln_of_factorial(v: f64) -> f64 {
+    log_gamma(v + 1.0)
+}
+
+impl Hypergeometric {
+    /// Construct a new `Hypergeometric` for a population of size
+    /// `total_population` containing `successes` successes, from which
+    /// `sample_size` items are drawn.
+    ///
+    /// Panics if `successes > total_population` or
+    /// `sample_size > total_population`.
+    pub fn new(total_population: u64, successes: u64, sample_size: u64) -> Hypergeometric {
+        unwrap_new(Hypergeometric::try_new(total_population, successes, sample_size),
+                   "Hypergeometric::new")
+    }
+
+    /// Construct a new `Hypergeometric` for a population of size
+    /// `total_population` containing `successes` successes, from which
+    /// `sample_size` items are drawn, returning an error if
+    /// `successes > total_population` or `sample_size > total_population`.
+    pub fn try_new(total_population: u64, successes: u64, sample_size: u64)
+        -> Result<Hypergeometric, DistributionError>
+    {
+        if successes > total_population {
+            return Err(DistributionError::OutOfRange("successes"));
+        }
+        if sample_size > total_population {
+            return Err(DistributionError::OutOfRange("sample_size"));
+        }
+
+        let n = total_population;
+        let (mut sign_x, mut offset_x) = (1, 0);
+        let (n1, n2) = {
+            // switch around success and failure states if necessary to ensure
+            // n1 <= n2
+            let failures = n - successes;
+            if successes > failures {
+                sign_x = -1;
+                offset_x = sample_size as i64;
+                (failures, successes)
+            } else {
+                (successes, failures)
+            }
+        };
+        // When sampling more than half the total population, take the smaller
+        // group as sampled instead (we can then return n1 - x instead).
+        //
+        // Note: the boundary condition given in the paper is
+        // `sample_size < n / 2`; we deviate here, because when n is odd,
+        // `n / 2 < n - n / 2`, so switching when `k == n / 2` would actually
+        // take the _larger_ group as sampled.
+        let k = if sample_size <= n / 2 {
+            sample_size
+        } else {
+            offset_x += n1 as i64 * sign_x;
+            sign_x *= -1;
+            n - sample_size
+        };
+
+        // Algorithm H2PE has bounded runtime only if `M - max(0, k - n2) >= 10`,
+        // where `M` is the mode of the distribution. Use algorithm HIN for the
+        // remaining parameter space.
+        const HIN_THRESHOLD: f64 = 10.0;
+        let m = ((k + 1) as f64 * (n1 + 1) as f64 / (n + 2) as f64).floor();
+        let sampling_method = if k == 0 || n1 == 0 {
+            SamplingMethod::Constant
+        } else if m - f64::max(0.0, k as f64 - n2 as f64) < HIN_THRESHOLD {
+            let (initial_p, initial_x) = if k < n2 {
+                (fraction_of_products_of_factorials((n2, n - k), (n, n2 - k)), 0)
+            } else {
+                (fraction_of_products_of_factorials((n1, k), (n, k - n2)), (k - n2) as i64)
+            };
+            if !(initial_p > 0.0 && initial_p.is_finite()) {
+                // The parameters are too large to be sampled.
+                return Err(DistributionError::OutOfRange("total_population"));
+            }
+            SamplingMethod::InverseTransform { initial_p, initial_x }
+        } else {
+            let a = ln_of_factorial(m) +
+                ln_of_factorial(n1 as f64 - m) +
+                ln_of_factorial(k as f64 - m) +
+                ln_of_factorial((n2 - k) as f64 + m);
+
+            let numerator = (n - k) as f64 * k as f64 * n1 as f64 * n2 as f64;
+            let denominator = (n - 1) as f64 * n as f64 * n as f64;
+            let d = 1.5 * (numerator / denominator).sqrt() + 0.5;
+
+            let x_l = m - d + 0.5;
+            let x_r = m + d + 0.5;
+
+            let k_l = (a -
+                ln_of_factorial(x_l) -
+                ln_of_factorial(n1 as f64 - x_l) -
+                ln_of_factorial(k as f64 - x_l) -
+                ln_of_factorial((n2 - k) as f64 + x_l)).exp();
+            let k_r = (a -
+                ln_of_factorial(x_r - 1.0) -
+                ln_of_factorial(n1 as f64 - x_r + 1.0) -
+                ln_of_factorial(k as f64 - x_r + 1.0) -
+                ln_of_factorial((n2 - k) as f64 + x_r - 1.0)).exp();
+
+            let numerator = x_l * ((n2 - k) as f64 + x_l);
+            let denominator = (n1 as f64 - x_l + 1.0) * (k as f64 - x_l + 1.0);
+            let lambda_l = -((numerator / denominator).ln());
+
+            let numerator = (n1 as f64 - x_r + 1.0) * (k as f64 - x_r + 1.0);
+            let denominator = x_r * ((n2 - k) as f64 + x_r);
+            let lambda_r = -((numerator / denominator).ln());
+
+            // The paper gives `p2 <- p2 + kL / lambdaL`, which is a misprint
+            // for `p2 <- p1 + kL / lambdaL`.
+            let p1 = 2.0 * d;
+            let p2 = p1 + k_l / lambda_l;
+            let p3 = p2 + k_r / lambda_r;
+
+            SamplingMethod::RejectionAcceptance {
+                m, a, lambda_l, lambda_r, x_l, x_r, p1, p2, p3
+            }
+        };
+
+        Ok(Hypergeometric { n1, n2, k, offset_x, sign_x, sampling_method })
+    }
+}
+
+impl Distribution<u64> for Hypergeometric {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+        let Hypergeometric { n1, n2, k, sign_x, offset_x, sampling_method } = *self;
+        let x = match sampling_method {
+            SamplingMethod::Constant => 0,
+            SamplingMethod::InverseTransform { initial_p: mut p, initial_x: mut x } => {
+                let mut u = rng.gen::<f64>();
+                // The paper uses `until u < p`, which is a misprint.
+                while u > p && x < k as i64 {
+                    u -= p;
+                    p *= ((n1 as i64 - x) * (k as i64 - x)) as f64;
+                    p /= ((x + 1) * (n2 as i64 - k as i64 + 1 + x)) as f64;
+                    x += 1;
+                }
+                x
+            }
+            SamplingMethod::RejectionAcceptance {
+                m, a, lambda_l, lambda_r, x_l, x_r, p1, p2, p3
+            } => {
+                let distr_region_select = Uniform::new(0.0, p3);
+                loop {
+                    let (y, v) = loop {
+                        let u = distr_region_select.sample(rng);
+                        // for the accept/reject decision
+                        let v = rng.gen::<f64>();
+
+                        if u <= p1 {
+                            // Region 1, central bell
+                            let y = (x_l + u).floor();
+                            break (y, v);
+                        } else if u <= p2 {
+                            // Region 2, left exponential tail
+                            let y = (x_l + v.ln() / lambda_l).floor();
+                            if y as i64 >= cmp::max(0, k as i64 - n2 as i64) {
+                                let v = v * (u - p1) * lambda_l;
+                                break (y, v);
+                            }
+                        } else {
+                            // Region 3, right exponential tail
+                            let y = (x_r - v.ln() / lambda_r).floor();
+                            if y as u64 <= cmp::min(n1, k) {
+                                let v = v * (u - p2) * lambda_r;
+                                break (y, v);
+                            }
+                        }
+                    };
+
+                    // Step 4: Acceptance/Rejection Comparison
+                    if m < 100.0 || y <= 50.0 {
+                        // Step 4.1: evaluate f(y) via recursive relationship
+                        let mut f = 1.0;
+                        if m < y {
+                            for i in (m as u64 + 1)..(y as u64 + 1) {
+                                f *= (n1 - i + 1) as f64 * (k - i + 1) as f64;
+                                f /= i as f64 * (n2 - k + i) as f64;
+                            }
+                        } else {
+                            for i in (y as u64 + 1)..(m as u64 + 1) {
+                                f *= i as f64 * (n2 - k + i) as f64;
+                                f /= (n1 - i + 1) as f64 * (k - i + 1) as f64;
+                            }
+                        }
+
+                        if v <= f {
+                            break y as i64;
+                        }
+                    } else {
+                        // Step 4.2: Squeezing
+                        let y1 = y + 1.0;
+                        let ym = y - m;
+                        let yn = n1 as f64 - y + 1.0;
+                        let yk = k as f64 - y + 1.0;
+                        let nk = n2 as f64 - k as f64 + y1;
+                        let r = -ym / y1;
+                        let s = ym / yn;
+                        let t = ym / yk;
+                        let e = -ym / nk;
+                        let g = yn * yk / (y1 * nk) - 1.0;
+                        let dg = if g < 0.0 { 1.0 + g } else { 1.0 };
+                        let gu = g * (1.0 + g * (-0.5 + g / 3.0));
+                        let gl = gu - g.powi(4) / (4.0 * dg);
+                        let xm = m + 0.5;
+                        let xn = n1 as f64 - m + 0.5;
+                        let xk = k as f64 - m + 0.5;
+                        let nm = n2 as f64 - k as f64 + xm;
+                        let ub = xm * r * (1.0 + r * (-0.5 + r / 3.0)) +
+                            xn * s * (1.0 + s * (-0.5 + s / 3.0)) +
+                            xk * t * (1.0 + t * (-0.5 + t / 3.0)) +
+                            nm * e * (1.0 + e * (-0.5 + e / 3.0)) +
+                            y * gu - m * gl + 0.0034;
+                        let av = v.ln();
+                        if av > ub {
+                            continue;
+                        }
+                        let dr = if r < 0.0 { xm * r.powi(4) / (1.0 + r) } else { xm * r.powi(4) };
+                        let ds = if s < 0.0 { xn * s.powi(4) / (1.0 + s) } else { xn * s.powi(4) };
+                        let dt = if t < 0.0 { xk * t.powi(4) / (1.0 + t) } else { xk * t.powi(4) };
+                        let de = if e < 0.0 { nm * e.powi(4) / (1.0 + e) } else { nm * e.powi(4) };
+
+                        if av < ub - 0.25 * (dr + ds + dt + de) + (y + m) * (gl - gu) - 0.0078 {
+                            break y as i64;
+                        }
+
+                        // Step 4.3: Final Acceptance/Rejection Test
+                        let av_critical = a -
+                            ln_of_factorial(y) -
+                            ln_of_factorial(n1 as f64 - y) -
+                            ln_of_factorial(k as f64 - y) -
+                            ln_of_factorial((n2 - k) as f64 + y);
+                        if av <= av_critical {
+                            break y as i64;
+                        }
+                    }
+                }
+            }
+        };
+
+        (offset_x + sign_x * x) as u64
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::Distribution;
+    use rngs::mock::StepRng;
+    use seq::index;
+    use super::{Hypergeometric, ln_of_factorial};
+    use distributions::DistributionError::*;
+
+    fn test_hypergeometric_mean_and_variance(n: u64, k: u64, s: u64, seed: u64) {
+        let distr = Hypergeometric::new(n, k, s);
+        let mut rng = ::test::rng(seed);
+
+        let expected_mean = s as f64 * k as f64 / n as f64;
+        let expected_variance = {
+            let (n, k, s) = (n as f64, k as f64, s as f64);
+            s * k * (n - k) * (n - s) / (n * n * (n - 1.0))
+        };
+
+        const N: usize = 10_000;
+        let mut results = [0.0; N];
+        for i in results.iter_mut() {
+            let x = distr.sample(&mut rng);
+            assert!(x <= k && x <= s && s - x <= n - k);
+            *i = x as f64;
+        }
+
+        let mean = results.iter().sum::<f64>() / N as f64;
+        let variance = results.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>()
+            / N as f64;
+        // the standard error of the mean is `sqrt(variance / N)`
+        assert!((mean - expected_mean).abs() < 5.0 * (expected_variance / N as f64).sqrt(),
+                "mean {} != {} for ({}, {}, {})", mean, expected_mean, n, k, s);
+        assert!((variance / expected_variance - 1.0).abs() < 0.1,
+                "variance {} != {} for ({}, {}, {})", variance, expected_variance, n, k, s);
+    }
+
+    #[test]
+    fn test_hypergeometric() {
+        // HIN
+        test_hypergeometric_mean_and_variance(20, 10, 5, 431);
+        test_hypergeometric_mean_and_variance(52, 4, 5, 432);
+        test_hypergeometric_mean_and_variance(100, 94, 90, 433);
+        test_hypergeometric_mean_and_variance(1_000_000, 30, 10_000, 434);
+        // H2PE
+        test_hypergeometric_mean_and_variance(500, 400, 30, 435);
+        test_hypergeometric_mean_and_variance(10_000, 500, 8000, 436);
+        test_hypergeometric_mean_and_variance(100_000_000, 10_000_000, 40_000, 437);
+    }
+
+    #[test]
+    fn test_hypergeometric_explicit_draws() {
+        // Compare with explicitly drawing `s` items out of `n`, of which the
+        // first `k` are successes.
+        let mut rng = ::test::rng(438);
+        for &(n, k, s) in [(10, 3, 4), (12, 9, 5), (7, 5, 6), (40, 30, 30)].iter() {
+            let distr = Hypergeometric::new(n, k, s);
+            const N: usize = 20_000;
+            let mut counts = vec![0usize; s as usize + 1];
+            let mut explicit = vec![0usize; s as usize + 1];
+            for _ in 0..N {
+                counts[distr.sample(&mut rng) as usize] += 1;
+                let drawn = index::sample(&mut rng, n as usize, s as usize);
+                explicit[drawn.iter().filter(|&i| (i as u64) < k).count()] += 1;
+            }
+            for (c, e) in counts.iter().zip(explicit.iter()) {
+                let diff = (*c as f64 - *e as f64).abs() / N as f64;
+                assert!(diff < 0.015, "{:?} != {:?} for ({}, {}, {})",
+                        counts, explicit, n, k, s);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hypergeometric_pmf() {
+        // The mode is large enough for H2PE to use the squeeze.
+        let (n, k, s) = (1000u64, 600u64, 400u64);
+        let distr = Hypergeometric::new(n, k, s);
+        let mut rng = ::test::rng(439);
+        const N: usize = 200_000;
+        let mut counts = vec![0usize; s as usize + 1];
+        for _ in 0..N {
+            counts[distr.sample(&mut rng) as usize] += 1;
+        }
+        let ln_binomial = |a: u64, b: u64| {
+            ln_of_factorial(a as f64) - ln_of_factorial(b as f64)
+                - ln_of_factorial((a - b) as f64)
+        };
+        for x in 200..280 {
+            let p = (ln_binomial(k, x) + ln_binomial(n - k, s - x)
+                     - ln_binomial(n, s)).exp();
+            let freq = counts[x as usize] as f64 / N as f64;
+            // within 5 standard errors
+            assert!((freq - p).abs() < 5.0 * (p / N as f64).sqrt() + 1e-4,
+                    "frequency of {}: {} != {}", x, freq, p);
+        }
+    }
+
+    #[test]
+    fn test_hypergeometric_degenerate() {
+        // The RNG must not be used: `StepRng` counts its calls.
+        let mut rng = StepRng::new(0, 1);
+        let cases = [((10, 3, 0), 0), ((10, 3, 10), 3), ((10, 0, 4), 0),
+                     ((10, 10, 4), 4), ((0, 0, 0), 0), ((1, 1, 1), 1)];
+        for &((n, k, s), expected) in cases.iter() {
+            let distr = Hypergeometric::new(n, k, s);
+            for _ in 0..10 {
+                assert_eq!(distr.sample(&mut rng), expected);
+            }
+        }
+        use RngCore;
+        assert_eq!(rng.next_u64(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_hypergeometric_invalid_successes() {
+        Hypergeometric::new(10, 11, 5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_hypergeometric_invalid_sample_size() {
+        Hypergeometric::new(10, 5, 11);
+    }
+
+
+    #[test]
+    fn test_hypergeometric_try_new() {
+        assert!(Hypergeometric::try_new(0, 0, 0).is_ok());
+        assert!(Hypergeometric::try_new(10, 10, 10).is_ok());
+        assert_eq!(Hypergeometric::try_new(10, 11, 5).err(), Some(OutOfRange("successes")));
+        assert_eq!(Hypergeometric::try_new(10, 5, 11).err(), Some(OutOfRange("sample_size")));
+    }
+}
@@ -12,12 +12,20 @@
 
 use {Rng};
 use distributions::{Distribution, Standard};
+#[cfg(rustc_1_28)]
+use core::num::{NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU128,
+                NonZeroUsize};
 
 impl Distribution<u8> for Standard {
     #[inline]
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u8 {
         rng.next_u32() as u8
     }
+
+    #[inline]
+    fn sample_fill<R: Rng + ?Sized>(&self, rng: &mut R, out: &mut [u8]) {
+        rng.fill(out)
+    }
 }
 
 impl Distribution<u16> for Standard {
@@ -25,6 +33,11 @@ impl Distribution<u16> for Standard {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u16 {
         rng.next_u32() as u16
     }
+
+    #[inline]
+    fn sample_fill<R: Rng + ?Sized>(&self, rng: &mut R, out: &mut [u16]) {
+        rng.fill(out)
+    }
 }
 
 impl Distribution<u32> for Standard {
@@ -32,6 +45,11 @@ impl Distribution<u32> for Standard {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u32 {
         rng.next_u32()
     }
+
+    #[inline]
+    fn sample_fill<R: Rng + ?Sized>(&self, rng: &mut R, out: &mut [u32]) {
+        rng.fill(out)
+    }
 }
 
 impl Distribution<u64> for Standard {
@@ -39,17 +57,28 @@ impl Distribution<u64> for Standard {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
         rng.next_u64()
     }
+
+    #[inline]
+    fn sample_fill<R: Rng + ?Sized>(&self, rng: &mut R, out: &mut [u64]) {
+        rng.fill(out)
+    }
 }
 
-#[cfg(feature = "i128_support")]
+#[cfg(any(feature = "i128_support", rustc_1_26))]
 impl Distribution<u128> for Standard {
     #[inline]
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u128 {
-        // Use LE; we explicitly generate one value before the next.
+        // Use LE; we explicitly generate one value before the next, so that
+        // the low word is the first value of the RNG.
         let x = rng.next_u64() as u128;
         let y = rng.next_u64() as u128;
         (y << 64) | x
     }
+
+    #[inline]
+    fn sample_fill<R: Rng + ?Sized>(&self, rng: &mut R, out: &mut [u128]) {
+        rng.fill(out)
+    }
 }
 
 impl Distribution<usize> for Standard {
@@ -64,6 +93,11 @@ impl Distribution<usize> for Standard {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
         rng.next_u64() as usize
     }
+
+    #[inline]
+    fn sample_fill<R: Rng + ?Sized>(&self, rng: &mut R, out: &mut [usize]) {
+        rng.fill(out)
+    }
 }
 
 macro_rules! impl_int_from_uint {
@@ -73,6 +107,11 @@ macro_rules! impl_int_from_uint {
             fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
                 rng.gen::<$uty>() as $ty
             }
+
+            #[inline]
+            fn sample_fill<R: Rng + ?Sized>(&self, rng: &mut R, out: &mut [$ty]) {
+                rng.fill(out)
+            }
         }
     }
 }
@@ -81,9 +120,33 @@ impl_int_from_uint! { i8, u8 }
 impl_int_from_uint! { i16, u16 }
 impl_int_from_uint! { i32, u32 }
 impl_int_from_uint! { i64, u64 }
-#[cfg(feature = "i128_support")] impl_int_from_uint! { i128, u128 }
+#[cfg(any(feature = "i128_support", rustc_1_26))] impl_int_from_uint! { i128, u128 }
 impl_int_from_uint! { isize, usize }
 
+macro_rules! impl_nzint {
+    ($ty:ty, $new:path) => {
+        impl Distribution<$ty> for Standard {
+            #[inline]
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                // Rejecting zero keeps the distribution uniform over the
+                // non-zero values.
+                loop {
+                    if let Some(nz) = $new(rng.gen()) {
+                        break nz;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(rustc_1_28)] impl_nzint! { NonZeroU8, NonZeroU8::new }
+#[cfg(rustc_1_28)] impl_nzint! { NonZeroU16, NonZeroU16::new }
+#[cfg(rustc_1_28)] impl_nzint! { NonZeroU32, NonZeroU32::new }
+#[cfg(rustc_1_28)] impl_nzint! { NonZeroU64, NonZeroU64::new }
+#[cfg(rustc_1_28)] impl_nzint! { NonZeroU128, NonZeroU128::new }
+#[cfg(rustc_1_28)] impl_nzint! { NonZeroUsize, NonZeroUsize::new }
+
 
 #[cfg(test)]
 mod tests {
@@ -99,7 +162,7 @@ mod tests {
         rng.sample::<i16, _>(Standard);
         rng.sample::<i32, _>(Standard);
         rng.sample::<i64, _>(Standard);
-        #[cfg(feature = "i128_support")]
+        #[cfg(any(feature = "i128_support", rustc_1_26))]
         rng.sample::<i128, _>(Standard);
         
         rng.sample::<usize, _>(Standard);
@@ -107,7 +170,105 @@ mod tests {
         rng.sample::<u16, _>(Standard);
         rng.sample::<u32, _>(Standard);
         rng.sample::<u64, _>(Standard);
-        #[cfg(feature = "i128_support")]
+        #[cfg(any(feature = "i128_support", rustc_1_26))]
         rng.sample::<u128, _>(Standard);
     }
+
+    #[test]
+    fn test_sample_fill() {
+        use core::fmt::Debug;
+        use distributions::Distribution;
+
+        // With a block RNG, filling a slice gives the same values as sampling
+        // each element, and leaves the RNG in the same state.
+        fn check<T: Copy + Default + PartialEq + Debug>(seed: u64)
+            where Standard: Distribution<T>
+        {
+            for &len in [0, 1, 3, 16, 67].iter() {
+                let mut rng1 = ::test::rng(seed);
+                let mut rng2 = ::test::rng(seed);
+                let mut filled = [T::default(); 67];
+                Standard.sample_fill(&mut rng1, &mut filled[..len]);
+                let mut sampled = [T::default(); 67];
+                for x in sampled[..len].iter_mut() {
+                    *x = rng2.sample(Standard);
+                }
+                assert_eq!(&filled[..len], &sampled[..len]);
+                assert_eq!(rng1.gen::<u32>(), rng2.gen::<u32>());
+            }
+        }
+        check::<u32>(808);
+        check::<i32>(809);
+        check::<u64>(810);
+        check::<i64>(811);
+        check::<usize>(812);
+        check::<isize>(813);
+        #[cfg(any(feature = "i128_support", rustc_1_26))]
+        check::<u128>(814);
+        #[cfg(any(feature = "i128_support", rustc_1_26))]
+        check::<i128>(815);
+
+        // Small integers are filled with all bytes of the generated values.
+        let mut rng1 = ::test::rng(816);
+        let mut rng2 = ::test::rng(816);
+        let mut filled = [0u8; 20];
+        Standard.sample_fill(&mut rng1, &mut filled[..]);
+        let mut bytes = [0u8; 20];
+        rng2.fill(&mut bytes);
+        assert_eq!(filled, bytes);
+        let mut filled = [0i16; 10];
+        Standard.sample_fill(&mut rng1, &mut filled[..]);
+        let mut bytes = [0i16; 10];
+        rng2.fill(&mut bytes);
+        assert_eq!(filled, bytes);
+    }
+
+    #[test]
+    #[cfg(rustc_1_28)]
+    fn test_nonzero() {
+        use core::num::{NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64,
+                        NonZeroU128, NonZeroUsize};
+        use rngs::mock::StepRng;
+
+        // Zero is rejected, and the next value is used instead.
+        let mut rng = StepRng::new(0x100, 1);
+        assert_eq!(rng.gen::<NonZeroU8>().get(), 1);
+        let mut rng = StepRng::new(0x1_0000, 1);
+        assert_eq!(rng.gen::<NonZeroU16>().get(), 1);
+        let mut rng = StepRng::new(0, 1);
+        assert_eq!(rng.gen::<NonZeroU32>().get(), 1);
+        let mut rng = StepRng::new(0, 1);
+        assert_eq!(rng.gen::<NonZeroU64>().get(), 1);
+        let mut rng = StepRng::new(0, 1);
+        assert_eq!(rng.gen::<NonZeroUsize>().get(), 1);
+        let mut rng = ::test::rng(806);
+        rng.gen::<NonZeroU128>();
+
+        // All other values are equally likely.
+        let mut rng = ::test::rng(807);
+        let mut counts = [0u32; 256];
+        const N: usize = 256_000;
+        for _ in 0..N {
+            counts[rng.gen::<NonZeroU8>().get() as usize] += 1;
+        }
+        assert_eq!(counts[0], 0);
+        let p = 1.0 / 255.0;
+        let expected = N as f64 * p;
+        let err = 5.0 * (N as f64 * p * (1.0 - p)).sqrt();
+        for &count in counts[1..].iter() {
+            assert!((count as f64 - expected).abs() < err,
+                    "count {} != {}", count, expected);
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "i128_support", rustc_1_26))]
+    fn test_128bit_word_order() {
+        use rngs::mock::StepRng;
+        let mut rng = StepRng::new(1, 1);
+        assert_eq!(rng.gen::<u128>(), (2 << 64) | 1);
+        assert_eq!(rng.gen::<i128>(), (4 << 64) | 3);
+        let mut rng = StepRng::new(0, ::core::u64::MAX);
+        assert_eq!(rng.gen::<i128>(), -1 << 64);
+    }
 }
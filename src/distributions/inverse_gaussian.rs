@@ -0,0 +1,162 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The inverse Gaussian distribution.
+
+use Rng;
+use distributions::{Distribution, StandardNormal};
+use distributions::error::{DistributionError, unwrap_new, check_positive};
+
+/// The inverse Gaussian (Wald) distribution `IG(mean, shape)`.
+///
+/// This distribution has a density function:
+/// `f(x) = sqrt(shape / (2 * pi * x^3)) *
+/// exp(-shape * (x - mean)^2 / (2 * mean^2 * x))` for `x > 0`.
+///
+/// It describes the time a Brownian motion with positive drift takes to reach
+/// a fixed level. Its mean is `mean`, its variance is `mean^3 / shape` and its
+/// skewness is `3 * sqrt(mean / shape)`; for large `shape` it approaches a
+/// normal distribution.
+///
+/// Samples are generated with the transformation method of Michael, Schucany
+/// and Haas, which uses one normal and one uniform sample.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{InverseGaussian, Distribution};
+///
+/// // Mean 1, variance 1 / 3: a moderately skewed distribution
+/// let ig = InverseGaussian::new(1.0, 3.0);
+/// let v = ig.sample(&mut rand::thread_rng());
+/// println!("{} is from an IG(1, 3) distribution", v);
+///
+/// // First passage time to the level 10 of a Brownian motion with drift 2
+/// // and volatility 0.5: mean 10 / 2, shape (10 / 0.5)^2
+/// let passage = InverseGaussian::new(10.0 / 2.0, (10.0f64 / 0.5).powi(2));
+/// let t = passage.sample(&mut rand::thread_rng());
+/// println!("reached the level after {}", t);
+/// ```
+///
+/// # References
+///
+/// J. R. Michael, W. R. Schucany and R. W. Haas,
+/// *Generating Random Variates Using Transformations with Multiple Roots*,
+/// The American Statistician 30(2), 1976, 88-90.
+#[derive(Clone, Copy, Debug)]
+pub struct InverseGaussian {
+    mean: f64,
+    shape: f64,
+}
+
+impl InverseGaussian {
+    /// Construct a new `InverseGaussian` distribution with the given `mean`
+    /// and `shape`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mean <= 0`, `shape <= 0`, or either is not finite.
+    pub fn new(mean: f64, shape: f64) -> InverseGaussian {
+        unwrap_new(InverseGaussian::try_new(mean, shape), "InverseGaussian::new")
+    }
+
+    /// Construct a new `InverseGaussian` distribution with the given `mean`
+    /// and `shape`, returning an error if `mean <= 0`, `shape <= 0`, or
+    /// either is not finite.
+    pub fn try_new(mean: f64, shape: f64) -> Result<InverseGaussian, DistributionError> {
+        check_positive(mean, "mean")?;
+        check_positive(shape, "shape")?;
+        Ok(InverseGaussian {
+            mean,
+            shape,
+        })
+    }
+}
+
+impl Distribution<f64> for InverseGaussian {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let n: f64 = rng.sample(StandardNormal);
+        // The smaller root of the quadratic in the paper is
+        // `x = mean * (1 + w/2 - sqrt(w + w^2/4))` with `w = mean * n^2 / shape`,
+        // which we compute without cancellation as below.
+        let w = self.mean * n * n / self.shape;
+        let r = (1.0 + 0.25 * w).sqrt() + (0.25 * w).sqrt();
+        let x = self.mean / (r * r);
+        // Choose between the two roots `x` and `mean^2 / x`.
+        let u: f64 = rng.gen();
+        if u * (self.mean + x) <= self.mean {
+            x
+        } else {
+            self.mean * r * r
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::Distribution;
+    use super::InverseGaussian;
+    use distributions::DistributionError::*;
+
+    #[test]
+    fn test_inverse_gaussian_moments() {
+        let mut rng = ::test::rng(431);
+        for &(mean, shape) in [(1.0, 3.0), (1.0, 50.0), (20.0, 5.0),
+                               (0.01, 0.2)].iter() {
+            let ig = InverseGaussian::new(mean, shape);
+            const N: usize = 200_000;
+            let samples: Vec<f64> = (0..N).map(|_| ig.sample(&mut rng)).collect();
+            assert!(samples.iter().all(|&x| x > 0.0 && x.is_finite()));
+
+            let sample_mean = samples.iter().sum::<f64>() / N as f64;
+            let variance: f64 = mean * mean * mean / shape;
+            let err = 5.0 * (variance / N as f64).sqrt();
+            assert!((sample_mean - mean).abs() < err,
+                    "mean {} != {}", sample_mean, mean);
+
+            let m2 = samples.iter().map(|x| (x - sample_mean).powi(2))
+                .sum::<f64>() / N as f64;
+            let m3 = samples.iter().map(|x| (x - sample_mean).powi(3))
+                .sum::<f64>() / N as f64;
+            let skewness = m3 / m2.powf(1.5);
+            let expected: f64 = 3.0 * (mean / shape).sqrt();
+            assert!((skewness - expected).abs() < 0.1 * expected.max(1.0),
+                    "skewness {} != {}", skewness, expected);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_inverse_gaussian_invalid_mean() {
+        InverseGaussian::new(0.0, 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_inverse_gaussian_invalid_shape() {
+        InverseGaussian::new(1.0, -1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_inverse_gaussian_infinite_shape() {
+        InverseGaussian::new(1.0, ::std::f64::INFINITY);
+    }
+
+
+    #[test]
+    fn test_inverse_gaussian_try_new() {
+        assert!(InverseGaussian::try_new(0.01, 0.2).is_ok());
+        assert_eq!(InverseGaussian::try_new(0.0, 1.0).err(), Some(NonPositive("mean")));
+        assert_eq!(InverseGaussian::try_new(1.0, -1.0).err(), Some(NonPositive("shape")));
+        assert_eq!(InverseGaussian::try_new(::std::f64::NAN, 1.0).err(), Some(NonFinite("mean")));
+        assert_eq!(InverseGaussian::try_new(1.0, ::std::f64::INFINITY).err(), Some(NonFinite("shape")));
+    }
+}
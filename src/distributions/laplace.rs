@@ -0,0 +1,188 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Laplace distribution.
+
+use Rng;
+use distributions::{Distribution, Open01};
+use distributions::error::{DistributionError, unwrap_new, check_finite, check_positive};
+
+/// The Laplace (double exponential) distribution `Laplace(location, scale)`.
+///
+/// This distribution has a density function:
+/// `f(x) = exp(-|x - location| / scale) / (2 * scale)`.
+///
+/// Its mean and median are `location`, and its variance is `2 * scale^2`.
+/// Laplace noise is commonly used to achieve differential privacy.
+///
+/// Samples are generated as `location ± scale * E`, where `E = -ln(u)` is a
+/// standard exponential sample obtained from a sample `u` from [`Open01`] and
+/// the sign is chosen by a separate random bit. Both tails are therefore
+/// sampled exactly symmetrically, with the same resolution. Since `u` is never
+/// 0, samples are never infinite or NaN; they lie within about
+/// `location ± 36.8 * scale`.
+///
+/// `Laplace` implements both `Distribution<f64>` and `Distribution<f32>`, so
+/// the type of the sample may need to be annotated. `f32` samples are
+/// computed in double precision and rounded.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Laplace, Distribution};
+///
+/// let laplace = Laplace::new(2.0, 5.0);
+/// let v: f64 = laplace.sample(&mut rand::thread_rng());
+/// println!("{} is from a Laplace(2, 5) distribution", v);
+/// ```
+///
+/// [`Open01`]: struct.Open01.html
+#[derive(Clone, Copy, Debug)]
+pub struct Laplace {
+    location: f64,
+    scale: f64,
+}
+
+impl Laplace {
+    /// Construct a new `Laplace` distribution with the given `location` and
+    /// `scale`. Panics if `scale <= 0` or if either parameter is not finite.
+    pub fn new(location: f64, scale: f64) -> Laplace {
+        unwrap_new(Laplace::try_new(location, scale), "Laplace::new")
+    }
+
+    /// Construct a new `Laplace` distribution with the given `location` and
+    /// `scale`, returning an error if `scale <= 0` or if either parameter is
+    /// not finite.
+    pub fn try_new(location: f64, scale: f64) -> Result<Laplace, DistributionError> {
+        check_finite(location, "location")?;
+        check_positive(scale, "scale")?;
+        Ok(Laplace {
+            location,
+            scale,
+        })
+    }
+}
+
+impl Distribution<f64> for Laplace {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let u: f64 = rng.sample(Open01);
+        let e = -u.ln();
+        if rng.gen::<bool>() {
+            self.location + self.scale * e
+        } else {
+            self.location - self.scale * e
+        }
+    }
+}
+
+impl Distribution<f32> for Laplace {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+        let x: f64 = self.sample(rng);
+        x as f32
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::Distribution;
+    use rngs::mock::StepRng;
+    use super::Laplace;
+    use distributions::DistributionError::*;
+
+    #[test]
+    fn test_laplace_quantiles() {
+        // The fraction of samples below each quantile `Q(p)` must be close
+        // to `p`.
+        let mut rng = ::test::rng(421);
+        let (location, scale) = (10.0, 0.5);
+        let laplace = Laplace::new(location, scale);
+        const N: usize = 50_000;
+        let samples: Vec<f64> = (0..N).map(|_| laplace.sample(&mut rng)).collect();
+        let samples_f32: Vec<f32> = (0..N).map(|_| laplace.sample(&mut rng)).collect();
+        for &p in [0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99].iter() {
+            let q: f64 = if p < 0.5 {
+                location + scale * (2.0 * p as f64).ln()
+            } else {
+                location - scale * (2.0 - 2.0 * p as f64).ln()
+            };
+            let below = samples.iter().filter(|&&x| x <= q).count();
+            let below_f32 = samples_f32.iter().filter(|&&x| x <= q as f32).count();
+            let err = 5.0 * (p * (1.0 - p) / N as f64).sqrt();
+            for &count in [below, below_f32].iter() {
+                let freq = count as f64 / N as f64;
+                assert!((freq - p).abs() < err,
+                        "P(X <= {}) = {} != {}", q, freq, p);
+            }
+        }
+    }
+
+    #[test]
+    fn test_laplace_symmetric() {
+        // The same exponential sample is used for both tails. `StepRng`
+        // yields `bits` for `u`, and then the sign is taken from bit 31 of
+        // `bits + increment`.
+        let laplace = Laplace::new(0.0, 2.0);
+        for &bits in [1u64 << 40, 1 << 63, ::core::u64::MAX - (1 << 31)].iter() {
+            let a: f64 = laplace.sample(&mut StepRng::new(bits, 0));
+            let b: f64 = laplace.sample(&mut StepRng::new(bits, 1 << 31));
+            assert!(a != 0.0);
+            assert_eq!(a, -b);
+        }
+    }
+
+    #[test]
+    fn test_laplace_finite() {
+        let mut rng = ::test::rng(422);
+        let laplace = Laplace::new(0.0, 1.0);
+        for _ in 0..1_000_000 {
+            let x: f64 = laplace.sample(&mut rng);
+            assert!(x.is_finite());
+        }
+        // The extreme values of `u`, for both signs
+        for &(bits, increment) in [(0, 0), (0, 1 << 31),
+                                   (::core::u64::MAX, 0),
+                                   (::core::u64::MAX, 1 << 31)].iter() {
+            let x: f64 = laplace.sample(&mut StepRng::new(bits, increment));
+            assert!(x.is_finite() && x.abs() < 36.8);
+            let x: f32 = laplace.sample(&mut StepRng::new(bits, increment));
+            assert!(x.is_finite());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_laplace_invalid_scale() {
+        Laplace::new(0.0, -1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_laplace_infinite_location() {
+        Laplace::new(::std::f64::INFINITY, 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_laplace_nan_scale() {
+        Laplace::new(0.0, ::std::f64::NAN);
+    }
+
+
+    #[test]
+    fn test_laplace_try_new() {
+        assert!(Laplace::try_new(-1e300, 1e-300).is_ok());
+        assert_eq!(Laplace::try_new(0.0, 0.0).err(), Some(NonPositive("scale")));
+        assert_eq!(Laplace::try_new(0.0, -1.0).err(), Some(NonPositive("scale")));
+        assert_eq!(Laplace::try_new(0.0, ::std::f64::INFINITY).err(), Some(NonFinite("scale")));
+        assert_eq!(Laplace::try_new(0.0, ::std::f64::NAN).err(), Some(NonFinite("scale")));
+        assert_eq!(Laplace::try_new(::std::f64::NAN, 1.0).err(), Some(NonFinite("location")));
+        assert_eq!(Laplace::try_new(-::std::f64::INFINITY, 1.0).err(), Some(NonFinite("location")));
+    }
+}
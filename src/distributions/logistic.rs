@@ -0,0 +1,174 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The logistic distribution.
+
+use Rng;
+use distributions::{Distribution, Open01};
+use distributions::error::{DistributionError, unwrap_new, check_finite, check_positive};
+
+/// The logistic distribution `Logistic(location, scale)`.
+///
+/// This distribution has a cumulative distribution function:
+/// `F(x) = 1 / (1 + exp(-(x - location) / scale))`.
+///
+/// Its mean and median are `location`, and its variance is
+/// `scale^2 * pi^2 / 3`.
+///
+/// Samples are generated by transforming a sample `u` from [`Open01`] with
+/// the inverse of the cumulative distribution function,
+/// `location + scale * ln(u / (1 - u))`. The values of `u` are spaced
+/// symmetrically around 1/2 and `1 - u` is exact, so both tails are sampled
+/// symmetrically. Since `u` is never 0 or 1, samples are never infinite or
+/// NaN; they lie within about `location ± 36.8 * scale`.
+///
+/// `Logistic` implements both `Distribution<f64>` and `Distribution<f32>`, so
+/// the type of the sample may need to be annotated. `f32` samples are
+/// computed in double precision and rounded.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Logistic, Distribution};
+///
+/// let logistic = Logistic::new(2.0, 5.0);
+/// let v: f64 = logistic.sample(&mut rand::thread_rng());
+/// println!("{} is from a Logistic(2, 5) distribution", v);
+/// ```
+///
+/// [`Open01`]: struct.Open01.html
+#[derive(Clone, Copy, Debug)]
+pub struct Logistic {
+    location: f64,
+    scale: f64,
+}
+
+impl Logistic {
+    /// Construct a new `Logistic` distribution with the given `location` and
+    /// `scale`. Panics if `scale <= 0` or if either parameter is not finite.
+    pub fn new(location: f64, scale: f64) -> Logistic {
+        unwrap_new(Logistic::try_new(location, scale), "Logistic::new")
+    }
+
+    /// Construct a new `Logistic` distribution with the given `location` and
+    /// `scale`, returning an error if `scale <= 0` or if either parameter is
+    /// not finite.
+    pub fn try_new(location: f64, scale: f64) -> Result<Logistic, DistributionError> {
+        check_finite(location, "location")?;
+        check_positive(scale, "scale")?;
+        Ok(Logistic {
+            location,
+            scale,
+        })
+    }
+}
+
+impl Distribution<f64> for Logistic {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let u: f64 = rng.sample(Open01);
+        self.location + self.scale * (u / (1.0 - u)).ln()
+    }
+}
+
+impl Distribution<f32> for Logistic {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+        let x: f64 = self.sample(rng);
+        x as f32
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::Distribution;
+    use rngs::mock::StepRng;
+    use super::Logistic;
+    use distributions::DistributionError::*;
+
+    #[test]
+    fn test_logistic_quantiles() {
+        // The fraction of samples below each quantile `Q(p)` must be close
+        // to `p`.
+        let mut rng = ::test::rng(423);
+        let (location, scale) = (-4.0, 2.0);
+        let logistic = Logistic::new(location, scale);
+        const N: usize = 50_000;
+        let samples: Vec<f64> = (0..N).map(|_| logistic.sample(&mut rng)).collect();
+        let samples_f32: Vec<f32> = (0..N).map(|_| logistic.sample(&mut rng)).collect();
+        for &p in [0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99].iter() {
+            let q: f64 = location + scale * (p as f64 / (1.0 - p as f64)).ln();
+            let below = samples.iter().filter(|&&x| x <= q).count();
+            let below_f32 = samples_f32.iter().filter(|&&x| x <= q as f32).count();
+            let err = 5.0 * (p * (1.0 - p) / N as f64).sqrt();
+            for &count in [below, below_f32].iter() {
+                let freq = count as f64 / N as f64;
+                assert!((freq - p).abs() < err,
+                        "P(X <= {}) = {} != {}", q, freq, p);
+            }
+        }
+    }
+
+    #[test]
+    fn test_logistic_symmetric() {
+        // Mirrored values of `u` give mirrored samples.
+        let logistic = Logistic::new(0.0, 1.0);
+        for &bits in [0u64, 1 << 40, 3 << 62].iter() {
+            let a: f64 = logistic.sample(&mut StepRng::new(bits, 0));
+            let b: f64 = logistic.sample(&mut StepRng::new(!bits, 0));
+            assert_eq!(a, -b);
+        }
+    }
+
+    #[test]
+    fn test_logistic_finite() {
+        let mut rng = ::test::rng(424);
+        let logistic = Logistic::new(0.0, 1.0);
+        for _ in 0..1_000_000 {
+            let x: f64 = logistic.sample(&mut rng);
+            assert!(x.is_finite());
+        }
+        // The most extreme values of `u`
+        for &bits in [0, ::core::u64::MAX].iter() {
+            let x: f64 = logistic.sample(&mut StepRng::new(bits, 0));
+            assert!(x.is_finite() && x.abs() > 36.0 && x.abs() < 36.8);
+            let x: f32 = logistic.sample(&mut StepRng::new(bits, 0));
+            assert!(x.is_finite());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_logistic_invalid_scale() {
+        Logistic::new(0.0, 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_logistic_infinite_scale() {
+        Logistic::new(0.0, ::std::f64::INFINITY);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_logistic_nan_location() {
+        Logistic::new(::std::f64::NAN, 1.0);
+    }
+
+
+    #[test]
+    fn test_logistic_try_new() {
+        assert!(Logistic::try_new(-1e300, 1e-300).is_ok());
+        assert_eq!(Logistic::try_new(0.0, 0.0).err(), Some(NonPositive("scale")));
+        assert_eq!(Logistic::try_new(0.0, -1.0).err(), Some(NonPositive("scale")));
+        assert_eq!(Logistic::try_new(0.0, ::std::f64::INFINITY).err(), Some(NonFinite("scale")));
+        assert_eq!(Logistic::try_new(0.0, ::std::f64::NAN).err(), Some(NonFinite("scale")));
+        assert_eq!(Logistic::try_new(::std::f64::NAN, 1.0).err(), Some(NonFinite("location")));
+        assert_eq!(Logistic::try_new(-::std::f64::INFINITY, 1.0).err(), Some(NonFinite("location")));
+    }
+}
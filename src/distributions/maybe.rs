@@ -0,0 +1,115 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The `Maybe` combinator.
+
+use Rng;
+use distributions::{Distribution, Bernoulli, BernoulliError};
+
+/// A distribution which samples `Some` value from an inner distribution with a
+/// given probability, and `None` otherwise.
+///
+/// The inner distribution is only sampled when `Some` is returned. The choice
+/// between `Some` and `None` is made as with [`Bernoulli`], and has the same
+/// precision.
+///
+/// In contrast, [`Standard`] returns `None` with probability 0.5 when sampling
+/// an `Option<T>`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, Maybe, Uniform};
+///
+/// // A die roll which is missing 10% of the time
+/// let maybe = Maybe::new(Uniform::new_inclusive(1, 6), 0.9).unwrap();
+/// match maybe.sample(&mut rand::thread_rng()) {
+///     Some(roll) => println!("rolled a {}", roll),
+///     None => println!("the die fell off the table"),
+/// }
+/// ```
+///
+/// [`Bernoulli`]: struct.Bernoulli.html
+/// [`Standard`]: struct.Standard.html
+#[derive(Clone, Copy, Debug)]
+pub struct Maybe<D> {
+    inner: D,
+    some: Bernoulli,
+}
+
+impl<D> Maybe<D> {
+    /// Construct a new `Maybe` distribution, which samples `Some` value from
+    /// `inner` with probability `some_prob`.
+    ///
+    /// Returns an error if `some_prob < 0`, `some_prob > 1` or `some_prob` is
+    /// NaN.
+    #[inline]
+    pub fn new(inner: D, some_prob: f64) -> Result<Maybe<D>, BernoulliError> {
+        Ok(Maybe {
+            inner,
+            some: Bernoulli::new(some_prob)?,
+        })
+    }
+}
+
+impl<T, D: Distribution<T>> Distribution<Option<T>> for Maybe<D> {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<T> {
+        if self.some.sample(rng) {
+            Some(self.inner.sample(rng))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use Rng;
+    use core::cell::Cell;
+    use distributions::{Distribution, BernoulliError};
+    use super::Maybe;
+
+    // Counts how often it is sampled.
+    struct Counting {
+        count: Cell<usize>,
+    }
+
+    impl Distribution<u32> for Counting {
+        fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u32 {
+            self.count.set(self.count.get() + 1);
+            rng.gen()
+        }
+    }
+
+    #[test]
+    fn test_maybe_none_rate() {
+        let mut rng = ::test::rng(461);
+        for &p in [0.0, 0.1, 0.5, 0.99, 1.0].iter() {
+            let maybe = Maybe::new(Counting { count: Cell::new(0) }, p).unwrap();
+            const N: usize = 100_000;
+            let nones = (0..N).filter(|_| maybe.sample(&mut rng).is_none()).count();
+            // the inner distribution is only sampled for `Some`
+            assert_eq!(maybe.inner.count.get(), N - nones);
+            let freq = nones as f64 / N as f64;
+            let err = 5.0 * (p * (1.0 - p) / N as f64).sqrt();
+            assert!((freq - (1.0 - p)).abs() <= err,
+                    "None rate {} != {}", freq, 1.0 - p);
+        }
+    }
+
+    #[test]
+    fn test_maybe_invalid_probability() {
+        for &p in [-0.1, 1.1, ::core::f64::NAN].iter() {
+            assert_eq!(Maybe::new(Counting { count: Cell::new(0) }, p).err(),
+                       Some(BernoulliError::InvalidProbability));
+        }
+    }
+}
@@ -0,0 +1,198 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The mixture distribution.
+
+use Rng;
+use distributions::{Distribution, WeightedIndex, WeightedError};
+use distributions::uniform::SampleUniform;
+use core::cmp::PartialOrd;
+use core::fmt;
+use core::ops::AddAssign;
+
+#[cfg(not(feature="std"))] use alloc::Vec;
+
+/// A mixture of several component distributions.
+///
+/// To sample from a mixture, a component is chosen at random with probability
+/// proportional to its weight, and a value is then sampled from that
+/// component. The weights can use any type `X` supported by
+/// [`WeightedIndex`], which is used to choose the component.
+///
+/// All components have the same type `D`. If they are all the same kind of
+/// distribution, such as [`Normal`], this uses static dispatch. Components of
+/// different kinds can be mixed by boxing them, i.e. with
+/// `D = Box<DynDistribution<T>>` (see [`DynDistribution`]).
+///
+/// # Example
+///
+/// ```
+/// # #![allow(bare_trait_objects)]
+/// use rand::distributions::{Distribution, DynDistribution, Mixture, Normal, Uniform};
+///
+/// // A contamination model: usually N(0, 1), sometimes N(0, 10^2)
+/// let mixture = Mixture::new(vec![(0.9, Normal::new(0.0, 1.0)),
+///                                 (0.1, Normal::new(0.0, 10.0))]).unwrap();
+/// let v = mixture.sample(&mut rand::thread_rng());
+/// println!("{} is from a mixture of normal distributions", v);
+///
+/// // Components of different types
+/// let mixed: Mixture<Box<DynDistribution<f64>>, u32> =
+///     Mixture::new(vec![(3, Box::new(Normal::new(5.0, 1.0)) as Box<_>),
+///                       (1, Box::new(Uniform::new(0.0, 10.0)) as Box<_>)]).unwrap();
+/// let v = mixed.sample(&mut rand::thread_rng());
+/// println!("{} is from a mixture of a normal and a uniform distribution", v);
+/// ```
+///
+/// [`DynDistribution`]: trait.DynDistribution.html
+/// [`Normal`]: struct.Normal.html
+/// [`WeightedIndex`]: struct.WeightedIndex.html
+pub struct Mixture<D, X: SampleUniform + PartialOrd> {
+    components: Vec<D>,
+    index: WeightedIndex<X>,
+}
+
+impl<D, X: SampleUniform + PartialOrd> Mixture<D, X> {
+    /// Creates a new `Mixture` from `(weight, component)` pairs.
+    ///
+    /// Returns an error in the same cases as [`WeightedIndex::new`]: if there
+    /// are no components, if any weight is `< 0`, if the total weight is 0,
+    /// or if a floating point weight is NaN or the total is infinite.
+    ///
+    /// [`WeightedIndex::new`]: struct.WeightedIndex.html#method.new
+    pub fn new<I>(components: I) -> Result<Mixture<D, X>, WeightedError>
+        where I: IntoIterator<Item = (X, D)>,
+              X: for<'a> AddAssign<&'a X> + Clone + Default
+    {
+        let iter = components.into_iter();
+        let mut weights = Vec::with_capacity(iter.size_hint().0);
+        let mut distributions = Vec::with_capacity(iter.size_hint().0);
+        for (weight, component) in iter {
+            weights.push(weight);
+            distributions.push(component);
+        }
+        Ok(Mixture {
+            index: WeightedIndex::new(weights)?,
+            components: distributions,
+        })
+    }
+}
+
+impl<T, D, X> Distribution<T> for Mixture<D, X>
+    where D: Distribution<T>, X: SampleUniform + PartialOrd
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        let i = self.index.sample(rng);
+        self.components[i].sample(rng)
+    }
+}
+
+impl<D, X> Clone for Mixture<D, X>
+    where D: Clone, X: SampleUniform + PartialOrd, WeightedIndex<X>: Clone
+{
+    fn clone(&self) -> Self {
+        Mixture {
+            components: self.components.clone(),
+            index: self.index.clone(),
+        }
+    }
+}
+
+impl<D, X> fmt::Debug for Mixture<D, X>
+    where X: SampleUniform + PartialOrd, WeightedIndex<X>: fmt::Debug
+{
+    // The components are not required to implement `Debug`, as boxed
+    // distributions do not.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Mixture")
+            .field("components", &self.components.len())
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::{Distribution, DynDistribution, WeightedIndex,
+                        WeightedError, Normal, Uniform, Exp};
+    use super::Mixture;
+
+    #[test]
+    fn test_mixture_branching() {
+        // Sampling is equivalent to choosing the component manually.
+        let components = [Normal::new(0.0, 1.0), Normal::new(0.0, 10.0)];
+        let mixture = Mixture::new(vec![(0.9, components[0]),
+                                        (0.1, components[1])]).unwrap();
+        let index = WeightedIndex::new(&[0.9, 0.1]).unwrap();
+        let mut rng1 = ::test::rng(491);
+        let mut rng2 = ::test::rng(491);
+        for _ in 0..1000 {
+            let x: f64 = mixture.sample(&mut rng1);
+            let y = components[index.sample(&mut rng2)].sample(&mut rng2);
+            assert_eq!(x, y);
+        }
+    }
+
+    #[test]
+    fn test_mixture_boxed() {
+        let mixture: Mixture<Box<DynDistribution<f64>>, u32> =
+            Mixture::new(vec![(1, Box::new(Exp::new(1.0)) as Box<_>),
+                              (0, Box::new(Normal::new(0.0, 1.0)) as Box<_>),
+                              (3, Box::new(Uniform::new(10.0, 11.0)) as Box<_>)])
+            .unwrap();
+        let mut rng = ::test::rng(492);
+        const N: usize = 10_000;
+        let uniform = (0..N).filter(|_| mixture.sample(&mut rng) >= 10.0).count();
+        // the uniform component is chosen with probability 3/4
+        let p = 0.75;
+        let err = 5.0 * (N as f64 * p * (1.0 - p)).sqrt();
+        assert!((uniform as f64 - N as f64 * p).abs() < err);
+
+        let cloned = Mixture::new(vec![(1, Exp::new(2.0))]).unwrap().clone();
+        let x: f64 = cloned.sample(&mut rng);
+        assert!(x >= 0.0);
+        assert!(format!("{:?}", mixture).starts_with("Mixture { components: 3"));
+    }
+
+    #[test]
+    fn test_mixture_disjoint_uniforms() {
+        // 70% from [0, 1) and 30% from [10, 20)
+        let mixture = Mixture::new(vec![(0.7, Uniform::new(0.0, 1.0)),
+                                        (0.3, Uniform::new(10.0, 20.0))]).unwrap();
+        let mut rng = ::test::rng(493);
+        const N: usize = 10_000;
+        let mut low = 0;
+        for _ in 0..N {
+            let x: f64 = mixture.sample(&mut rng);
+            if x < 1.0 {
+                assert!(x >= 0.0);
+                low += 1;
+            } else {
+                assert!(x >= 10.0 && x < 20.0);
+            }
+        }
+        let p = 0.7;
+        let err = 5.0 * (N as f64 * p * (1.0 - p)).sqrt();
+        assert!((low as f64 - N as f64 * p).abs() < err, "{} of {} low", low, N);
+    }
+
+    #[test]
+    fn test_mixture_invalid_weights() {
+        let empty: Vec<(f64, Normal)> = vec![];
+        assert_eq!(Mixture::new(empty).err(), Some(WeightedError::NoItem));
+        assert_eq!(Mixture::new(vec![(0.5, Normal::new(0.0, 1.0)),
+                                     (-0.1, Normal::new(0.0, 1.0))]).err(),
+                   Some(WeightedError::NegativeWeight));
+        assert_eq!(Mixture::new(vec![(0, Normal::new(0.0, 1.0))]).err(),
+                   Some(WeightedError::AllWeightsZero));
+        assert_eq!(Mixture::new(vec![(::std::f64::NAN, Normal::new(0.0, 1.0))]).err(),
+                   Some(WeightedError::InvalidWeight));
+    }
+}
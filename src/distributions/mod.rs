@@ -66,12 +66,34 @@
 //! There are surprisingly many ways to uniformly generate random floats. A
 //! range between 0 and 1 is standard, but the exact bounds (open vs closed)
 //! and accuracy differ. In addition to the [`Standard`] distribution Rand offers
-//! [`Open01`] and [`OpenClosed01`]. See [Floating point implementation] for
-//! more details.
+//! [`Open01`] and [`OpenClosed01`], and [`HighPrecision01`] which can generate
+//! every representable value in `[0, 1)`. See [Floating point implementation]
+//! for more details.
+//!
+//! [`WeightedIndex`] samples an index from a list of weights, with each index
+//! chosen with probability proportional to its weight.
+//!
+//! [`Slice`] samples elements of a slice uniformly.
+//!
+//! [`UniformStep`] samples uniformly from the values `low + i * step` for
+//! `i` in `0..count`, such as the points of a grid.
+//!
+//! [`Empirical`] samples from the distribution of observed data, or from a
+//! given cumulative distribution function.
 //!
 //! [`Alphanumeric`] is a simple distribution to sample random letters and
-//! numbers of the `char` type; in contrast [`Standard`] may sample any valid
-//! `char`.
+//! numbers, as `char` or as ASCII `u8`; in contrast [`Standard`] may sample any
+//! valid `char`. [`Hex`] samples lowercase hexadecimal digits. Both implement
+//! [`DistString`] to generate random strings directly.
+//!
+//! [`Maybe`] wraps another distribution, and samples `Some` value from it with
+//! a given probability and `None` otherwise.
+//!
+//! [`Mixture`] combines several distributions, sampling from a randomly chosen
+//! one with given weights.
+//!
+//! The output of a distribution can be transformed with [`Distribution::map`],
+//! and two distributions can be sampled together as pairs with [`zip`].
 //!
 //!
 //! # Non-uniform probability distributions
@@ -80,22 +102,60 @@
 //!
 //! - Related to real-valued quantities that grow linearly
 //!   (e.g. errors, offsets):
-//!   - [`Normal`] distribution, and [`StandardNormal`] as a primitive
+//!   - [`Normal`] distribution, and [`StandardNormal`] and
+//!     [`StandardNormalF32`] as primitives
+//!   - [`SkewNormal`] distribution
+//!   - [`TruncatedNormal`] distribution, for normal values within bounds
 //!   - [`Cauchy`] distribution
+//!   - [`Laplace`] distribution
+//!   - [`Logistic`] distribution
+//! - Related to extreme values (e.g. maxima of many samples):
+//!   - [`Gumbel`] distribution
+//!   - [`Frechet`] distribution
+//!   - [`Weibull`] distribution
 //! - Related to Bernoulli trials (yes/no events, with a given probability):
 //!   - [`Binomial`] distribution
 //!   - [`Bernoulli`] distribution, similar to [`Rng::gen_bool`].
+//!   - [`Hypergeometric`] distribution, for draws without replacement
+//!   - [`Geometric`] and [`NegativeBinomial`] distributions, for the number
+//!     of failures before a number of successes
 //! - Related to positive real-valued quantities that grow exponentially
 //!   (e.g. prices, incomes, populations):
 //!   - [`LogNormal`] distribution
+//!   - [`InverseGaussian`] distribution
+//!   - [`Pareto`] distribution
 //! - Related to the occurrence of independent events at a given rate:
 //!   - [`Poisson`] distribution
-//!   - [`Exp`]onential distribution, and [`Exp1`] as a primitive
+//!   - [`Exp`]onential distribution, and [`Exp1`] and [`Exp1F32`] as
+//!     primitives
 //! - Gamma and derived distributions:
 //!   - [`Gamma`] distribution
 //!   - [`ChiSquared`] distribution
 //!   - [`StudentT`] distribution
 //!   - [`FisherF`] distribution
+//!   - [`Beta`] distribution
+//! - Related to three-point estimates (minimum, most likely, maximum):
+//!   - [`Triangular`] distribution
+//!   - [`Pert`] distribution
+//! - Related to the popularity of ranked items:
+//!   - [`Zipf`] distribution
+//!   - [`Zeta`] distribution
+//! - Related to angles and directions (circular data):
+//!   - [`VonMises`] distribution
+//! - Multivariate probability distributions
+//!   - [`Dirichlet`] distribution
+//!   - [`Multinomial`] distribution
+//!   - [`UnitCircle`] distribution
+//!   - [`UnitSphereSurface`] distribution
+//!   - [`UnitDisc`] distribution
+//!   - [`UnitBall`] distribution
+//!   - [`UnitQuaternion`] distribution, for random rotations
+//!
+//! The `new` constructors of these distributions panic on invalid parameters.
+//! Each of them has a `try_new` counterpart returning a [`DistributionError`]
+//! instead, for parameters which are not known to be valid in advance.
+//! (`Multinomial::try_new` returns a [`WeightedError`], like
+//! [`WeightedIndex::new`].)
 //!
 //!
 //! # Examples
@@ -129,9 +189,26 @@
 //! }
 //! ```
 //!
+//! With the `derive` feature, the same implementation can be derived; fields
+//! may be sampled from other distributions with the `rand(dist)` attribute:
+//!
+//! ```ignore
+//! use rand::distributions::{Distribution, Uniform};
+//!
+//! #[derive(Distribution)]
+//! struct Input {
+//!     x: f32,
+//!     #[rand(dist = "Uniform::new(0, 10)")]
+//!     len: usize,
+//! }
+//! ```
+//!
 //!
 //! [probability distribution]: https://en.wikipedia.org/wiki/Probability_distribution
 //! [`Distribution`]: trait.Distribution.html
+//! [`DistributionError`]: enum.DistributionError.html
+//! [`Distribution::map`]: trait.Distribution.html#method.map
+//! [`zip`]: fn.zip.html
 //! [`gen_range`]: ../trait.Rng.html#method.gen_range
 //! [`gen`]: ../trait.Rng.html#method.gen
 //! [`sample`]: ../trait.Rng.html#method.sample
@@ -146,44 +223,145 @@
 //! [Floating point implementation]: struct.Standard.html#floating-point-implementation
 // distributions
 //! [`Alphanumeric`]: struct.Alphanumeric.html
+//! [`DistString`]: trait.DistString.html
 //! [`Bernoulli`]: struct.Bernoulli.html
+//! [`Beta`]: struct.Beta.html
 //! [`Binomial`]: struct.Binomial.html
 //! [`Cauchy`]: struct.Cauchy.html
 //! [`ChiSquared`]: struct.ChiSquared.html
+//! [`Dirichlet`]: struct.Dirichlet.html
+//! [`Empirical`]: struct.Empirical.html
 //! [`Exp`]: struct.Exp.html
 //! [`Exp1`]: struct.Exp1.html
+//! [`Exp1F32`]: struct.Exp1F32.html
 //! [`FisherF`]: struct.FisherF.html
+//! [`Frechet`]: struct.Frechet.html
 //! [`Gamma`]: struct.Gamma.html
+//! [`Geometric`]: struct.Geometric.html
+//! [`Gumbel`]: struct.Gumbel.html
+//! [`Hex`]: struct.Hex.html
+//! [`HighPrecision01`]: struct.HighPrecision01.html
+//! [`Hypergeometric`]: struct.Hypergeometric.html
+//! [`InverseGaussian`]: struct.InverseGaussian.html
+//! [`Laplace`]: struct.Laplace.html
+//! [`Logistic`]: struct.Logistic.html
 //! [`LogNormal`]: struct.LogNormal.html
+//! [`Maybe`]: struct.Maybe.html
+//! [`Mixture`]: struct.Mixture.html
+//! [`Multinomial`]: struct.Multinomial.html
+//! [`NegativeBinomial`]: struct.NegativeBinomial.html
 //! [`Normal`]: struct.Normal.html
 //! [`Open01`]: struct.Open01.html
 //! [`OpenClosed01`]: struct.OpenClosed01.html
+//! [`Pareto`]: struct.Pareto.html
+//! [`Pert`]: struct.Pert.html
 //! [`Poisson`]: struct.Poisson.html
+//! [`SkewNormal`]: struct.SkewNormal.html
+//! [`Slice`]: struct.Slice.html
+//! [`UniformStep`]: struct.UniformStep.html
 //! [`Standard`]: struct.Standard.html
 //! [`StandardNormal`]: struct.StandardNormal.html
+//! [`StandardNormalF32`]: struct.StandardNormalF32.html
 //! [`StudentT`]: struct.StudentT.html
+//! [`Triangular`]: struct.Triangular.html
+//! [`TruncatedNormal`]: struct.TruncatedNormal.html
 //! [`Uniform`]: struct.Uniform.html
+//! [`UnitBall`]: struct.UnitBall.html
+//! [`UnitCircle`]: struct.UnitCircle.html
+//! [`UnitDisc`]: struct.UnitDisc.html
+//! [`UnitQuaternion`]: struct.UnitQuaternion.html
+//! [`UnitSphereSurface`]: struct.UnitSphereSurface.html
+//! [`VonMises`]: struct.VonMises.html
+//! [`Weibull`]: struct.Weibull.html
+//! [`WeightedIndex`]: struct.WeightedIndex.html
+//! [`WeightedIndex::new`]: struct.WeightedIndex.html#method.new
+//! [`WeightedError`]: enum.WeightedError.html
+//! [`Zeta`]: struct.Zeta.html
+//! [`Zipf`]: struct.Zipf.html
 
-use Rng;
+use {Rng, RngCore};
+use core::fmt;
 
-#[doc(inline)] pub use self::other::Alphanumeric;
-#[doc(inline)] pub use self::uniform::Uniform;
-#[doc(inline)] pub use self::float::{OpenClosed01, Open01};
+#[cfg(all(feature="alloc", not(feature="std")))] use alloc::boxed::Box;
+#[cfg(all(feature="alloc", not(feature="std")))] use alloc::String;
+
+#[doc(inline)] pub use self::other::{Alphanumeric, Hex};
+#[doc(inline)] pub use self::uniform::{Uniform, UniformStep};
+#[doc(inline)] pub use self::float::{OpenClosed01, Open01, HighPrecision01};
 #[deprecated(since="0.5.0", note="use Uniform instead")]
 pub use self::uniform::Uniform as Range;
 #[cfg(feature="std")]
-#[doc(inline)] pub use self::gamma::{Gamma, ChiSquared, FisherF, StudentT};
+#[doc(inline)] pub use self::gamma::{Gamma, ChiSquared, FisherF, StudentT, Beta};
+#[cfg(feature="std")]
+#[doc(inline)] pub use self::normal::{Normal, LogNormal, StandardNormal, StandardNormalF32};
+#[cfg(feature="std")]
+#[doc(inline)] pub use self::skew_normal::SkewNormal;
+#[cfg(feature="std")]
+#[doc(inline)] pub use self::truncated_normal::TruncatedNormal;
 #[cfg(feature="std")]
-#[doc(inline)] pub use self::normal::{Normal, LogNormal, StandardNormal};
+#[doc(inline)] pub use self::inverse_gaussian::InverseGaussian;
 #[cfg(feature="std")]
-#[doc(inline)] pub use self::exponential::{Exp, Exp1};
+#[doc(inline)] pub use self::exponential::{Exp, Exp1, Exp1F32};
 #[cfg(feature = "std")]
 #[doc(inline)] pub use self::poisson::Poisson;
 #[cfg(feature = "std")]
 #[doc(inline)] pub use self::binomial::Binomial;
-#[doc(inline)] pub use self::bernoulli::Bernoulli;
+#[cfg(feature = "std")]
+#[doc(inline)] pub use self::hypergeometric::Hypergeometric;
+#[cfg(feature = "std")]
+#[doc(inline)] pub use self::geometric::Geometric;
+#[cfg(feature = "std")]
+#[doc(inline)] pub use self::negative_binomial::NegativeBinomial;
+#[cfg(feature = "std")]
+#[doc(inline)] pub use self::zipf::{Zipf, Zeta};
+#[cfg(feature = "std")]
+#[doc(inline)] pub use self::dirichlet::Dirichlet;
+#[cfg(feature = "std")]
+#[doc(inline)] pub use self::multinomial::Multinomial;
+#[cfg(feature = "std")]
+#[doc(inline)] pub use self::von_mises::VonMises;
+#[cfg(feature = "std")]
+#[doc(inline)] pub use self::unit_circle::UnitCircle;
+#[cfg(feature = "std")]
+#[doc(inline)] pub use self::unit_sphere::UnitSphereSurface;
+#[cfg(feature = "std")]
+#[doc(hidden)] pub use self::unit_sphere::UnitSphereSurface as UnitSphere;
+#[cfg(feature = "std")]
+#[doc(inline)] pub use self::unit_disc::UnitDisc;
+#[cfg(feature = "std")]
+#[doc(inline)] pub use self::unit_ball::UnitBall;
+#[cfg(feature = "std")]
+#[doc(inline)] pub use self::unit_quaternion::UnitQuaternion;
+#[doc(inline)] pub use self::bernoulli::{Bernoulli, BernoulliError};
+#[doc(inline)] pub use self::error::DistributionError;
+#[doc(inline)] pub use self::maybe::Maybe;
+#[doc(inline)] pub use self::slice::{Slice, EmptySlice};
+#[cfg(feature = "alloc")]
+#[doc(inline)] pub use self::weighted::{WeightedIndex, WeightedError};
+#[cfg(feature = "alloc")]
+#[doc(inline)] pub use self::mixture::Mixture;
+#[cfg(feature = "alloc")]
+#[doc(inline)] pub use self::empirical::{Empirical, EmpiricalError};
 #[cfg(feature = "std")]
 #[doc(inline)] pub use self::cauchy::Cauchy;
+#[cfg(feature = "std")]
+#[doc(inline)] pub use self::gumbel::Gumbel;
+#[cfg(feature = "std")]
+#[doc(inline)] pub use self::laplace::Laplace;
+#[cfg(feature = "std")]
+#[doc(inline)] pub use self::logistic::Logistic;
+#[cfg(feature = "std")]
+#[doc(inline)] pub use self::frechet::Frechet;
+#[cfg(feature = "std")]
+#[doc(inline)] pub use self::pareto::Pareto;
+#[cfg(feature = "std")]
+#[doc(inline)] pub use self::weibull::Weibull;
+#[cfg(feature = "std")]
+#[doc(inline)] pub use self::triangular::Triangular;
+#[cfg(feature = "std")]
+#[doc(inline)] pub use self::pert::Pert;
+#[cfg(feature = "derive")]
+#[doc(inline)] pub use rand_derive::Distribution;
 
 pub mod uniform;
 #[cfg(feature="std")]
@@ -191,15 +369,70 @@ pub mod uniform;
 #[cfg(feature="std")]
 #[doc(hidden)] pub mod normal;
 #[cfg(feature="std")]
+#[doc(hidden)] pub mod skew_normal;
+#[cfg(feature="std")]
+#[doc(hidden)] pub mod truncated_normal;
+#[cfg(feature="std")]
+#[doc(hidden)] pub mod inverse_gaussian;
+#[cfg(feature="std")]
 #[doc(hidden)] pub mod exponential;
 #[cfg(feature = "std")]
 #[doc(hidden)] pub mod poisson;
 #[cfg(feature = "std")]
 #[doc(hidden)] pub mod binomial;
+#[cfg(feature = "std")]
+#[doc(hidden)] pub mod hypergeometric;
+#[cfg(feature = "std")]
+#[doc(hidden)] pub mod geometric;
+#[cfg(feature = "std")]
+#[doc(hidden)] pub mod negative_binomial;
+#[cfg(feature = "std")]
+#[doc(hidden)] pub mod zipf;
+#[cfg(feature = "std")]
+#[doc(hidden)] pub mod dirichlet;
+#[cfg(feature = "std")]
+#[doc(hidden)] pub mod multinomial;
+#[cfg(feature = "std")]
+#[doc(hidden)] pub mod von_mises;
+#[cfg(feature = "std")]
+#[doc(hidden)] pub mod unit_circle;
+#[cfg(feature = "std")]
+#[doc(hidden)] pub mod unit_sphere;
+#[cfg(feature = "std")]
+#[doc(hidden)] pub mod unit_disc;
+#[cfg(feature = "std")]
+#[doc(hidden)] pub mod unit_ball;
+#[cfg(feature = "std")]
+#[doc(hidden)] pub mod unit_quaternion;
 #[doc(hidden)] pub mod bernoulli;
+#[doc(hidden)] pub mod maybe;
+#[doc(hidden)] pub mod slice;
+#[cfg(feature = "alloc")]
+#[doc(hidden)] pub mod weighted;
+#[cfg(feature = "alloc")]
+#[doc(hidden)] pub mod mixture;
+#[cfg(feature = "alloc")]
+#[doc(hidden)] pub mod empirical;
 #[cfg(feature = "std")]
 #[doc(hidden)] pub mod cauchy;
+#[cfg(feature = "std")]
+#[doc(hidden)] pub mod gumbel;
+#[cfg(feature = "std")]
+#[doc(hidden)] pub mod laplace;
+#[cfg(feature = "std")]
+#[doc(hidden)] pub mod logistic;
+#[cfg(feature = "std")]
+#[doc(hidden)] pub mod frechet;
+#[cfg(feature = "std")]
+#[doc(hidden)] pub mod pareto;
+#[cfg(feature = "std")]
+#[doc(hidden)] pub mod weibull;
+#[cfg(feature = "std")]
+#[doc(hidden)] pub mod triangular;
+#[cfg(feature = "std")]
+#[doc(hidden)] pub mod pert;
 
+mod error;
 mod float;
 mod integer;
 #[cfg(feature="std")]
@@ -307,6 +540,38 @@ pub trait Distribution<T> {
     /// Generate a random value of `T`, using `rng` as the source of randomness.
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T;
 
+    /// Fill `out` with random values of `T`, using `rng` as the source of
+    /// randomness.
+    ///
+    /// The default implementation calls `sample` for each element in turn.
+    /// Distributions which can generate values in bulk override it; this is
+    /// the case for [`Standard`] over the integer and floating point types,
+    /// and for [`Uniform`] over the integer types. See their documentation
+    /// for whether the result equals that of the default implementation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use rand::distributions::{Distribution, Standard, Uniform};
+    ///
+    /// let mut rng = thread_rng();
+    ///
+    /// let mut noise = [0f64; 1000];
+    /// Standard.sample_fill(&mut rng, &mut noise);
+    ///
+    /// let mut rolls = vec![0u8; 100];
+    /// Uniform::new_inclusive(1, 6).sample_fill(&mut rng, &mut rolls);
+    /// ```
+    ///
+    /// [`Standard`]: struct.Standard.html
+    /// [`Uniform`]: uniform/struct.Uniform.html
+    fn sample_fill<R: Rng + ?Sized>(&self, rng: &mut R, out: &mut [T]) {
+        for x in out.iter_mut() {
+            *x = self.sample(rng);
+        }
+    }
+
     /// Create an iterator that generates random values of `T`, using `rng` as
     /// the source of randomness.
     ///
@@ -322,7 +587,8 @@ pub trait Distribution<T> {
     /// let v: Vec<f32> = Standard.sample_iter(&mut rng).take(16).collect();
     ///
     /// // String:
-    /// let s: String = Alphanumeric.sample_iter(&mut rng).take(7).collect();
+    /// let s: String = Distribution::<char>::sample_iter(&Alphanumeric, &mut rng)
+    ///     .take(7).collect();
     ///
     /// // Dice-rolling:
     /// let die_range = Uniform::new_inclusive(1, 6);
@@ -340,12 +606,129 @@ pub trait Distribution<T> {
             phantom: ::core::marker::PhantomData,
         }
     }
+
+    /// Create a distribution of values of `S` by mapping the output of
+    /// `Self` through the closure `F`.
+    ///
+    /// Note that the resulting distribution is in general not uniform, even
+    /// if `Self` is: for example squaring uniform values in `[0, 1)` makes
+    /// small values more likely.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use rand::distributions::{Distribution, Uniform};
+    ///
+    /// let mut rng = thread_rng();
+    ///
+    /// let die = Uniform::new_inclusive(1, 6);
+    /// let even_number = die.map(|num| num % 2 == 0);
+    /// while !even_number.sample(&mut rng) {
+    ///     println!("Still odd; rolling again!");
+    /// }
+    /// ```
+    fn map<F, S>(self, func: F) -> DistMap<Self, F, T, S>
+        where F: Fn(T) -> S, Self: Sized
+    {
+        DistMap {
+            distr: self,
+            func,
+            phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+/// Distributions of characters which can generate random strings.
+///
+/// # Example
+///
+/// ```
+/// use rand::thread_rng;
+/// use rand::distributions::{DistString, Alphanumeric, Hex};
+///
+/// let mut rng = thread_rng();
+/// let token = Alphanumeric.sample_string(&mut rng, 32);
+/// assert_eq!(token.len(), 32);
+///
+/// let mut id = String::from("0x");
+/// Hex.append_string(&mut rng, &mut id, 8);
+/// assert_eq!(id.len(), 10);
+/// ```
+#[cfg(feature="alloc")]
+pub trait DistString {
+    /// Append `len` random characters to `string`.
+    fn append_string<R: Rng + ?Sized>(&self, rng: &mut R, string: &mut String, len: usize);
+
+    /// Generate a `String` of `len` random characters.
+    fn sample_string<R: Rng + ?Sized>(&self, rng: &mut R, len: usize) -> String {
+        let mut s = String::new();
+        self.append_string(rng, &mut s, len);
+        s
+    }
 }
 
 impl<'a, T, D: Distribution<T>> Distribution<T> for &'a D {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
         (*self).sample(rng)
     }
+
+    fn sample_fill<R: Rng + ?Sized>(&self, rng: &mut R, out: &mut [T]) {
+        (*self).sample_fill(rng, out)
+    }
+}
+
+#[cfg(feature="alloc")]
+impl<T, D: Distribution<T> + ?Sized> Distribution<T> for Box<D> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        (**self).sample(rng)
+    }
+
+    fn sample_fill<R: Rng + ?Sized>(&self, rng: &mut R, out: &mut [T]) {
+        (**self).sample_fill(rng, out)
+    }
+}
+
+/// An object-safe version of [`Distribution`].
+///
+/// [`Distribution`] can not be used as a trait object, because its `sample`
+/// method is generic over the RNG. This trait is implemented for every
+/// distribution, and trait objects of it implement [`Distribution`] again.
+/// This allows distributions of different types to be stored together, e.g.
+/// as `Box<DynDistribution<T>>`. Each sample then takes a virtual call to the
+/// distribution and to the RNG.
+///
+/// # Example
+///
+/// ```
+/// # #![allow(bare_trait_objects)]
+/// use rand::distributions::{Distribution, DynDistribution, Exp, Uniform};
+///
+/// let distributions: Vec<Box<DynDistribution<f64>>> =
+///     vec![Box::new(Exp::new(2.0)), Box::new(Uniform::new(0.0, 1.0))];
+/// let mut rng = rand::thread_rng();
+/// for distr in distributions.iter() {
+///     println!("{}", distr.sample(&mut rng));
+/// }
+/// ```
+///
+/// [`Distribution`]: trait.Distribution.html
+pub trait DynDistribution<T> {
+    /// Generate a random value of `T`, using `rng` as the source of
+    /// randomness.
+    fn sample_dyn(&self, rng: &mut RngCore) -> T;
+}
+
+impl<T, D: Distribution<T>> DynDistribution<T> for D {
+    fn sample_dyn(&self, rng: &mut RngCore) -> T {
+        self.sample(rng)
+    }
+}
+
+impl<'a, T> Distribution<T> for DynDistribution<T> + 'a {
+    fn sample<R: Rng + ?Sized>(&self, mut rng: &mut R) -> T {
+        self.sample_dyn(&mut rng)
+    }
 }
 
 
@@ -380,6 +763,90 @@ impl<'a, D, R, T> Iterator for DistIter<'a, D, R, T>
 }
 
 
+/// A distribution of values of type `S` derived from the distribution `D`
+/// by mapping its output of type `T` through the closure `F`.
+///
+/// This `struct` is created by the [`map`] method on [`Distribution`].
+/// See its documentation for more.
+///
+/// [`Distribution`]: trait.Distribution.html
+/// [`map`]: trait.Distribution.html#method.map
+pub struct DistMap<D, F, T, S> {
+    distr: D,
+    func: F,
+    phantom: ::core::marker::PhantomData<fn(T) -> S>,
+}
+
+impl<D, F, T, S> Distribution<S> for DistMap<D, F, T, S>
+    where D: Distribution<T>, F: Fn(T) -> S
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> S {
+        (self.func)(self.distr.sample(rng))
+    }
+}
+
+impl<D: Clone, F: Clone, T, S> Clone for DistMap<D, F, T, S> {
+    fn clone(&self) -> Self {
+        DistMap {
+            distr: self.distr.clone(),
+            func: self.func.clone(),
+            phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<D: fmt::Debug, F, T, S> fmt::Debug for DistMap<D, F, T, S> {
+    // Closures do not implement `Debug`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DistMap")
+            .field("distr", &self.distr)
+            .finish()
+    }
+}
+
+/// Create a distribution of pairs `(A, B)`, sampling the two elements
+/// independently from `d1` and `d2`.
+///
+/// Each sample first samples `d1` and then `d2`.
+///
+/// # Example
+///
+/// ```
+/// use rand::thread_rng;
+/// use rand::distributions::{self, Distribution, Uniform, Alphanumeric};
+///
+/// let seat = distributions::zip(Uniform::new_inclusive(1, 30), Alphanumeric);
+/// let (row, letter): (i32, char) = seat.sample(&mut thread_rng());
+/// println!("seat {}{}", row, letter);
+/// ```
+pub fn zip<D1, D2>(d1: D1, d2: D2) -> DistZip<D1, D2> {
+    DistZip { d1, d2 }
+}
+
+/// A distribution of pairs, sampling each element independently from one of
+/// two distributions.
+///
+/// This `struct` is created by the [`zip`] function. See its documentation
+/// for more.
+///
+/// [`zip`]: fn.zip.html
+#[derive(Clone, Copy, Debug)]
+pub struct DistZip<D1, D2> {
+    d1: D1,
+    d2: D2,
+}
+
+impl<A, B, D1, D2> Distribution<(A, B)> for DistZip<D1, D2>
+    where D1: Distribution<A>, D2: Distribution<B>
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> (A, B) {
+        let a = self.d1.sample(rng);
+        let b = self.d2.sample(rng);
+        (a, b)
+    }
+}
+
+
 /// A generic random value distribution, implemented for many primitive types.
 /// Usually generates values with a numerically uniform distribution, and with a
 /// range appropriate to the type.
@@ -390,7 +857,11 @@ impl<'a, D, R, T> Iterator for DistIter<'a, D, R, T>
 /// generate values with the following ranges and distributions:
 ///
 /// * Integers (`i32`, `u32`, `isize`, `usize`, etc.): Uniformly distributed
-///   over all values of the type.
+///   over all values of the type. `u128` and `i128` (Rust 1.26 or later) are
+///   composed of two values of `next_u64`, the first being the low word.
+/// * Non-zero integers (`NonZeroU8`, `NonZeroU32`, `NonZeroUsize`, etc.,
+///   Rust 1.28 or later): Uniformly distributed over all non-zero values of
+///   the type. A zero is rejected and a new value is generated instead.
 /// * `char`: Uniformly distributed over all Unicode scalar values, i.e. all
 ///   code points in the range `0...0x10_FFFF`, except for the range
 ///   `0xD800...0xDFFF` (the surrogate code points). This includes
@@ -400,12 +871,18 @@ impl<'a, D, R, T> Iterator for DistIter<'a, D, R, T>
 ///   half-open range `[0, 1)`. See notes below.
 /// * Wrapping integers (`Wrapping<T>`), besides the type identical to their
 ///   normal integer variants.
+/// * IP addresses (`Ipv4Addr` and, with Rust 1.26 or later, `Ipv6Addr`;
+///   requires `std`): Uniformly distributed over all addresses, generated
+///   from a `u32` or `u128` value.
 ///
 /// The following aggregate types also implement the distribution `Standard` as
 /// long as their component types implement it:
 ///
-/// * Tuples and arrays: Each element of the tuple or array is generated
-///   independently, using the `Standard` distribution recursively.
+/// * Tuples (up to 12 elements) and arrays (up to 32 elements): Each element
+///   of the tuple or array is generated independently, using the `Standard`
+///   distribution recursively. Elements are generated in order, from left to
+///   right, so sampling a tuple is equivalent to sampling each of its
+///   elements in turn.
 /// * `Option<T>` where `Standard` is implemented for `T`: Returns `None` with
 ///   probability 0.5; otherwise generates a random `x: T` and returns `Some(x)`.
 ///
@@ -416,8 +893,22 @@ impl<'a, D, R, T> Iterator for DistIter<'a, D, R, T>
 ///
 /// let val: f32 = SmallRng::from_entropy().sample(Standard);
 /// println!("f32 from [0, 1): {}", val);
+///
+/// let (x, y, flag): (u32, f64, bool) = thread_rng().gen();
+/// println!("tuple: ({}, {}, {})", x, y, flag);
 /// ```
 ///
+/// # Filling slices
+/// [`Distribution::sample_fill`] fills slices of integers using
+/// [`Rng::fill`], and slices of `f32` and `f64` by converting integers
+/// generated the same way. The result is identical to sampling each element
+/// in turn if the RNG's `fill_bytes` produces the same bytes as successive
+/// calls to `next_u32` or `next_u64`, as is the case for the block RNGs in
+/// this crate (`ChaChaRng`, `Hc128Rng` and `StdRng`), but not for all RNGs.
+/// `u8`, `i8`, `u16` and `i16` are an exception: they are sampled from one
+/// `next_u32` each, but filled with all bytes of the generated values, so
+/// the results differ.
+///
 /// # Floating point implementation
 /// The floating point implementations for `Standard` generate a random value in
 /// the half-open interval `[0, 1)`, i.e. including 0 but not 1.
@@ -432,8 +923,12 @@ impl<'a, D, R, T> Iterator for DistIter<'a, D, R, T>
 /// `[0, 1)`. Note that `Open01` and `gen_range` (which uses [`Uniform`]) use
 /// transmute-based methods which yield 1 bit less precision but may perform
 /// faster on some architectures (on modern Intel CPUs all methods have
-/// approximately equal performance).
+/// approximately equal performance). [`HighPrecision01`] also samples from
+/// `[0, 1)`, but with the full precision of the floating point type.
 ///
+/// [`Distribution::sample_fill`]: trait.Distribution.html#method.sample_fill
+/// [`Rng::fill`]: ../trait.Rng.html#method.fill
+/// [`HighPrecision01`]: struct.HighPrecision01.html
 /// [`Open01`]: struct.Open01.html
 /// [`OpenClosed01`]: struct.OpenClosed01.html
 /// [`Uniform`]: uniform/struct.Uniform.html
@@ -630,6 +1125,50 @@ fn ziggurat<R: Rng + ?Sized, P, Z>(
     }
 }
 
+/// Sample a random `f32` using the Ziggurat method.
+///
+/// This is the single-precision counterpart of `ziggurat`, with the same
+/// arguments. It uses tables rounded to `f32` and consumes a single `u32` per
+/// iteration: the low 8 bits select the layer and the high 23 bits form the
+/// fraction of `u`.
+#[cfg(feature="std")]
+#[inline(always)]
+fn ziggurat_f32<R: Rng + ?Sized, P, Z>(
+            rng: &mut R,
+            symmetric: bool,
+            x_tab: ziggurat_tables::ZigTableF32,
+            f_tab: ziggurat_tables::ZigTableF32,
+            mut pdf: P,
+            mut zero_case: Z)
+            -> f32 where P: FnMut(f32) -> f32, Z: FnMut(&mut R, f32) -> f32 {
+    loop {
+        let bits = rng.next_u32();
+        let i = bits as usize & 0xff;
+
+        let u = if symmetric {
+            // Convert to a value in the range [2,4) and substract to get [-1,1)
+            (bits >> 9).into_float_with_exponent(1) - 3.0
+        } else {
+            // Convert to a value in the range [1,2) and substract to get (0,1)
+            (bits >> 9).into_float_with_exponent(0)
+            - (1.0 - ::core::f32::EPSILON / 2.0)
+        };
+        let x = u * x_tab[i];
+
+        let test_x = if symmetric { x.abs() } else {x};
+
+        if test_x < x_tab[i + 1] {
+            return x;
+        }
+        if i == 0 {
+            return zero_case(rng, u);
+        }
+        if f_tab[i + 1] + (f_tab[i] - f_tab[i + 1]) * rng.gen::<f32>() < pdf(x) {
+            return x;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use Rng;
@@ -763,6 +1302,46 @@ mod tests {
         sampler.ind_sample(&mut ::test::rng(235));
     }
 
+    #[cfg(feature="std")]
+    #[test]
+    fn test_distributions_map() {
+        use distributions::Uniform;
+        let distr = Uniform::new(0.0, 1.0).map(|x: f64| x * x);
+        let uniform = Uniform::new(0.0, 1.0);
+        let mut rng1 = ::test::rng(211);
+        let mut rng2 = ::test::rng(211);
+        for _ in 0..100 {
+            let x = uniform.sample(&mut rng2);
+            assert_eq!(distr.sample(&mut rng1), x * x);
+        }
+
+        // with `sample_iter`, and on a cloned distribution
+        let cloned = distr.clone();
+        let mapped: Vec<f64> = cloned.sample_iter(&mut rng1).take(10).collect();
+        let manual: Vec<f64> = uniform.sample_iter(&mut rng2).take(10)
+            .map(|x| x * x).collect();
+        assert_eq!(mapped, manual);
+        assert!(format!("{:?}", distr).starts_with("DistMap { distr: Uniform"));
+
+        // changing the type
+        let even = Uniform::new_inclusive(1, 6).map(|x| x % 2 == 0);
+        let _: bool = rng1.sample(&even);
+    }
+
+    #[test]
+    fn test_distributions_zip() {
+        use distributions::{zip, Uniform, Standard};
+        let distr = zip(Uniform::new(10u32, 20), Standard);
+        let mut rng1 = ::test::rng(212);
+        let mut rng2 = ::test::rng(212);
+        for (a, b) in distr.sample_iter(&mut rng1).take(100) {
+            let b: bool = b;
+            assert!(a >= 10 && a < 20);
+            assert_eq!(a, rng2.sample(Uniform::new(10u32, 20)));
+            assert_eq!(b, rng2.gen::<bool>());
+        }
+    }
+
     #[cfg(feature="std")]
     #[test]
     fn test_distributions_iter() {
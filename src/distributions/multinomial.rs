@@ -0,0 +1,217 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The multinomial distribution.
+
+use Rng;
+use distributions::{Distribution, Binomial, WeightedError};
+
+/// The multinomial distribution `Multinomial(n, p)`.
+///
+/// Samples are the numbers of times each of `k` categories is chosen in `n`
+/// independent trials, where category `i` is chosen with probability
+/// `p[i] = weights[i] / sum(weights)`. The count of category `i` has mean
+/// `n * p[i]` and variance `n * p[i] * (1 - p[i])`.
+///
+/// Samples are generated by the conditional binomial method: the count of
+/// each category is sampled from `Binomial(m, p[i] / (p[i] + ... + p[k]))`,
+/// where `m` is the number of trials not assigned to the previous
+/// categories. This takes `O(k)` time regardless of `n`. The last category
+/// takes all remaining trials, so the counts always sum to exactly `n`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Multinomial, Distribution};
+///
+/// // Roll a die 600 times.
+/// let die = Multinomial::new(600, &[1.0; 6]);
+/// let counts = die.sample(&mut rand::thread_rng());
+/// assert_eq!(counts.iter().sum::<u64>(), 600);
+/// println!("{:?} is from a multinomial distribution", counts);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Multinomial {
+    /// Number of trials.
+    n: u64,
+    /// Probability of each category but the last, conditional on not
+    /// choosing any of the previous categories.
+    conditional_p: Vec<f64>,
+}
+
+impl Multinomial {
+    /// Construct a new `Multinomial` with `n` trials and the probability of
+    /// each category proportional to its weight in `weights`.
+    ///
+    /// Panics if `weights` is empty, if any weight is negative or not finite,
+    /// or if all weights are zero.
+    pub fn new(n: u64, weights: &[f64]) -> Multinomial {
+        Multinomial::try_new(n, weights).unwrap_or_else(|err|
+            panic!("Multinomial::new called with invalid weights: {}", err))
+    }
+
+    /// Construct a new `Multinomial` with `n` trials and the probability of
+    /// each category proportional to its weight in `weights`.
+    ///
+    /// The weights are validated like those of [`WeightedIndex`]: an error
+    /// is returned if `weights` is empty, if any weight is negative, or if
+    /// all weights are zero. Weights which are NaN or infinite are rejected
+    /// with `WeightedError::InvalidWeight`.
+    ///
+    /// [`WeightedIndex`]: struct.WeightedIndex.html
+    pub fn try_new(n: u64, weights: &[f64]) -> Result<Multinomial, WeightedError> {
+        if weights.is_empty() {
+            return Err(WeightedError::NoItem);
+        }
+        for &w in weights {
+            if !w.is_finite() {
+                return Err(WeightedError::InvalidWeight);
+            }
+            if w < 0.0 {
+                return Err(WeightedError::NegativeWeight);
+            }
+        }
+
+        // The probabilities are computed from the sum of the remaining
+        // weights rather than by subtracting from the total, so they are not
+        // affected by cancellation and are at most 1.
+        let mut conditional_p = vec![0.0; weights.len() - 1];
+        let mut remaining = weights[weights.len() - 1];
+        for (p, &w) in conditional_p.iter_mut().zip(weights).rev() {
+            remaining += w;
+            *p = if remaining > 0.0 { w / remaining } else { 0.0 };
+        }
+        if remaining == 0.0 {
+            return Err(WeightedError::AllWeightsZero);
+        }
+        if remaining.is_infinite() {
+            return Err(WeightedError::InvalidWeight);
+        }
+        Ok(Multinomial { n, conditional_p })
+    }
+}
+
+impl Distribution<Vec<u64>> for Multinomial {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<u64> {
+        let mut counts = Vec::with_capacity(self.conditional_p.len() + 1);
+        let mut remaining = self.n;
+        for &p in self.conditional_p.iter() {
+            let count = if remaining > 0 {
+                Binomial::new(remaining, p).sample(rng)
+            } else {
+                0
+            };
+            counts.push(count);
+            remaining -= count;
+        }
+        counts.push(remaining);
+        counts
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use Rng;
+    use distributions::{Distribution, WeightedError};
+    use super::Multinomial;
+
+    #[test]
+    fn test_multinomial_sum() {
+        let mut rng = ::test::rng(350);
+        for _ in 0..200 {
+            let k = rng.gen_range(1, 12);
+            let weights: Vec<f64> = (0..k).map(|i| {
+                // Some zero weights, and weights of different magnitudes
+                if i % 3 == 1 {
+                    0.0
+                } else {
+                    rng.gen::<f64>() * 10f64.powi(rng.gen_range(-20, 20))
+                }
+            }).collect();
+            if weights.iter().all(|&w| w == 0.0) {
+                continue;
+            }
+            let n = match rng.gen_range(0, 3) {
+                0 => rng.gen_range(0, 10),
+                1 => rng.gen_range(0, 100_000),
+                _ => rng.gen::<u64>(),
+            };
+            let multinomial = Multinomial::new(n, &weights);
+            let counts = multinomial.sample(&mut rng);
+            assert_eq!(counts.len(), k);
+            assert_eq!(counts.iter().fold(0u64, |sum, &c| sum.checked_add(c).unwrap()), n);
+            for (&count, &w) in counts.iter().zip(weights.iter()) {
+                if w == 0.0 {
+                    assert_eq!(count, 0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_multinomial_mean() {
+        let mut rng = ::test::rng(351);
+        let weights = [1.0, 2.0, 0.5, 4.0, 2.5];
+        let total: f64 = weights.iter().sum();
+        for &n in [20u64, 1000, 1_000_000].iter() {
+            let multinomial = Multinomial::new(n, &weights);
+            const N: usize = 10_000;
+            let mut sums = [0u64; 5];
+            for _ in 0..N {
+                let counts = multinomial.sample(&mut rng);
+                for (sum, count) in sums.iter_mut().zip(counts) {
+                    *sum += count;
+                }
+            }
+            for (&sum, &w) in sums.iter().zip(weights.iter()) {
+                let p = w / total;
+                let mean = sum as f64 / N as f64;
+                let expected = n as f64 * p;
+                let err = 5.0 * (n as f64 * p * (1.0 - p) / N as f64).sqrt();
+                assert!((mean - expected).abs() < err,
+                        "mean {} != {}", mean, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_multinomial_edge_cases() {
+        let mut rng = ::test::rng(352);
+        let one = Multinomial::new(1234, &[0.3]);
+        let none = Multinomial::new(0, &[1.0, 2.0, 3.0]);
+        for _ in 0..100 {
+            assert_eq!(one.sample(&mut rng), vec![1234]);
+            assert_eq!(none.sample(&mut rng), vec![0, 0, 0]);
+        }
+        let last = Multinomial::new(50, &[0.0, 0.0, 1e-300]);
+        assert_eq!(last.sample(&mut rng), vec![0, 0, 50]);
+    }
+
+    #[test]
+    fn test_multinomial_invalid() {
+        assert_eq!(Multinomial::try_new(1, &[]).unwrap_err(), WeightedError::NoItem);
+        assert_eq!(Multinomial::try_new(1, &[0.0, 0.0]).unwrap_err(),
+                   WeightedError::AllWeightsZero);
+        assert_eq!(Multinomial::try_new(1, &[1.0, -1.0]).unwrap_err(),
+                   WeightedError::NegativeWeight);
+        assert_eq!(Multinomial::try_new(1, &[1.0, ::std::f64::NAN]).unwrap_err(),
+                   WeightedError::InvalidWeight);
+        assert_eq!(Multinomial::try_new(1, &[::std::f64::INFINITY]).unwrap_err(),
+                   WeightedError::InvalidWeight);
+        assert_eq!(Multinomial::try_new(1, &[1e308, 1e308]).unwrap_err(),
+                   WeightedError::InvalidWeight);
+    }
+
+    #[test]
+    #[should_panic(expected = "Multinomial::new called with invalid weights")]
+    fn test_multinomial_new_invalid() {
+        Multinomial::new(10, &[1.0, -2.0]);
+    }
+}
@@ -0,0 +1,164 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The negative binomial distribution.
+
+use Rng;
+use distributions::{Distribution, Gamma, Poisson};
+use distributions::error::{DistributionError, unwrap_new, check_positive};
+
+/// The negative binomial distribution `NegativeBinomial(r, p)`.
+///
+/// For integer `r`, this is the distribution of the number of failures before
+/// the `r`-th success in a series of Bernoulli trials, each with probability
+/// of success `p`; for `r == 1` it is the [`Geometric`] distribution. Its mean
+/// is `r (1 - p) / p` and its variance is `r (1 - p) / p^2`.
+///
+/// Samples are generated as a Gamma-Poisson mixture: `λ` is drawn from
+/// `Gamma(r, (1 - p) / p)`, and the sample is drawn from `Poisson(λ)`. This
+/// also supports non-integer `r`. Samples saturate at `u64::MAX` if `λ` is
+/// too large for the result to fit in a `u64`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{NegativeBinomial, Distribution};
+///
+/// let nb = NegativeBinomial::new(3.0, 0.25);
+/// let v = nb.sample(&mut rand::thread_rng());
+/// println!("{} failures before the third success", v);
+/// ```
+///
+/// [`Geometric`]: struct.Geometric.html
+#[derive(Clone, Copy, Debug)]
+pub struct NegativeBinomial {
+    // `None` if `p == 1`
+    gamma: Option<Gamma>,
+}
+
+impl NegativeBinomial {
+    /// Construct a new `NegativeBinomial` with the given number of successes
+    /// `r` and probability of success `p`.
+    ///
+    /// Panics if `r` is not finite and positive, or if `p <= 0` or `p > 1`.
+    pub fn new(r: f64, p: f64) -> NegativeBinomial {
+        unwrap_new(NegativeBinomial::try_new(r, p), "NegativeBinomial::new")
+    }
+
+    /// Construct a new `NegativeBinomial` with the given number of successes
+    /// `r` and probability of success `p`, returning an error if `r` is not
+    /// finite and positive, or if `p <= 0` or `p > 1`.
+    pub fn try_new(r: f64, p: f64) -> Result<NegativeBinomial, DistributionError> {
+        check_positive(r, "r")?;
+        if !(p > 0.0 && p <= 1.0) {
+            return Err(DistributionError::OutOfRange("p"));
+        }
+        let gamma = if p == 1.0 {
+            None
+        } else {
+            // The scale overflows for subnormal `p`.
+            Some(Gamma::try_new(r, (1.0 - p) / p)
+                 .map_err(|_| DistributionError::OutOfRange("p"))?)
+        };
+        Ok(NegativeBinomial { gamma })
+    }
+}
+
+impl Distribution<u64> for NegativeBinomial {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+        // Poisson samples stay within a few `sqrt(λ)` of `λ`, so this leaves
+        // plenty of margin below `u64::MAX`.
+        const MAX_LAMBDA: f64 = 1.8e19;
+
+        let gamma = match self.gamma {
+            Some(ref gamma) => gamma,
+            None => return 0,
+        };
+        let lambda = gamma.sample(rng);
+        if lambda <= 0.0 {
+            // `λ` may underflow to 0 for small `r`
+            0
+        } else if lambda >= MAX_LAMBDA {
+            ::core::u64::MAX
+        } else {
+            Poisson::new(lambda).sample(rng)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::Distribution;
+    use super::NegativeBinomial;
+    use distributions::DistributionError::*;
+
+    fn check_mean_variance(r: f64, p: f64, seed: u64) {
+        let nb = NegativeBinomial::new(r, p);
+        let mut rng = ::test::rng(seed);
+        const N: usize = 100_000;
+        let mut sum = 0.0;
+        let mut sum_sq = 0.0;
+        for _ in 0..N {
+            let x = nb.sample(&mut rng) as f64;
+            sum += x;
+            sum_sq += x * x;
+        }
+        let mean = sum / N as f64;
+        let variance = sum_sq / N as f64 - mean * mean;
+        let expected_mean = r * (1.0 - p) / p;
+        let expected_variance = r * (1.0 - p) / (p * p);
+        assert!((mean - expected_mean).abs()
+                < 5.0 * (expected_variance / N as f64).sqrt(),
+                "mean {} != {}", mean, expected_mean);
+        assert!((variance / expected_variance - 1.0).abs() < 0.05,
+                "variance {} != {}", variance, expected_variance);
+    }
+
+    #[test]
+    fn test_negative_binomial() {
+        check_mean_variance(1.0, 0.3, 461);
+        check_mean_variance(2.5, 0.3, 462);
+        check_mean_variance(10.0, 0.8, 463);
+        check_mean_variance(0.5, 0.5, 464);
+        check_mean_variance(3.0, 1e-9, 465);
+    }
+
+    #[test]
+    fn test_negative_binomial_one() {
+        let mut rng = ::test::rng(466);
+        let nb = NegativeBinomial::new(4.5, 1.0);
+        for _ in 0..100 {
+            assert_eq!(nb.sample(&mut rng), 0);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_negative_binomial_invalid_r() {
+        NegativeBinomial::new(0.0, 0.5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_negative_binomial_invalid_p() {
+        NegativeBinomial::new(1.0, 0.0);
+    }
+
+
+    #[test]
+    fn test_negative_binomial_try_new() {
+        assert!(NegativeBinomial::try_new(0.5, 1.0).is_ok());
+        assert_eq!(NegativeBinomial::try_new(0.0, 0.5).err(), Some(NonPositive("r")));
+        assert_eq!(NegativeBinomial::try_new(::std::f64::INFINITY, 0.5).err(), Some(NonFinite("r")));
+        for &p in [0.0, -0.5, 1.5, 1e-320, ::std::f64::NAN].iter() {
+            assert_eq!(NegativeBinomial::try_new(1.0, p).err(), Some(OutOfRange("p")));
+        }
+    }
+}
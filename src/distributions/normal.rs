@@ -11,7 +11,9 @@
 //! The normal and derived distributions.
 
 use Rng;
-use distributions::{ziggurat, ziggurat_tables, Distribution, Open01};
+use distributions::{ziggurat, ziggurat_f32, ziggurat_tables, Distribution, Open01};
+use distributions::error::{DistributionError, unwrap_new, check_finite, check_positive,
+                           check_non_negative};
 
 /// Samples floating-point numbers according to the normal distribution
 /// `N(0, 1)` (a.k.a.  a standard normal, or Gaussian). This is equivalent to
@@ -26,6 +28,8 @@ use distributions::{ziggurat, ziggurat_tables, Distribution, Open01};
 /// Samples*](https://www.doornik.com/research/ziggurat.pdf). Nuffield
 /// College, Oxford
 ///
+/// See [`StandardNormalF32`] to sample `f32` values.
+///
 /// # Example
 /// ```
 /// use rand::prelude::*;
@@ -34,6 +38,8 @@ use distributions::{ziggurat, ziggurat_tables, Distribution, Open01};
 /// let val: f64 = SmallRng::from_entropy().sample(StandardNormal);
 /// println!("{}", val);
 /// ```
+///
+/// [`StandardNormalF32`]: struct.StandardNormalF32.html
 #[derive(Clone, Copy, Debug)]
 pub struct StandardNormal;
 
@@ -72,6 +78,60 @@ impl Distribution<f64> for StandardNormal {
     }
 }
 
+/// Samples `f32` values according to the standard normal distribution
+/// `N(0, 1)`.
+///
+/// This is the single-precision counterpart of [`StandardNormal`]. It uses
+/// its own Ziggurat tables rounded to `f32` and consumes one `u32` per
+/// iteration, which is faster than sampling an `f64` and converting it.
+///
+/// # Example
+/// ```
+/// use rand::prelude::*;
+/// use rand::distributions::StandardNormalF32;
+///
+/// let val: f32 = SmallRng::from_entropy().sample(StandardNormalF32);
+/// println!("{}", val);
+/// ```
+///
+/// [`StandardNormal`]: struct.StandardNormal.html
+#[derive(Clone, Copy, Debug)]
+pub struct StandardNormalF32;
+
+impl Distribution<f32> for StandardNormalF32 {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+        #[inline]
+        fn pdf(x: f32) -> f32 {
+            (-x*x/2.0).exp()
+        }
+        #[inline]
+        fn zero_case<R: Rng + ?Sized>(rng: &mut R, u: f32) -> f32 {
+            // compute a random number in the tail by hand, as for `f64`
+            let mut x = 1.0f32;
+            let mut y = 0.0f32;
+
+            while -2.0 * y < x * x {
+                let x_: f32 = rng.sample(Open01);
+                let y_: f32 = rng.sample(Open01);
+
+                x = x_.ln() / ziggurat_tables::ZIG_NORM_R_F32;
+                y = y_.ln();
+            }
+
+            if u < 0.0 {
+                x - ziggurat_tables::ZIG_NORM_R_F32
+            } else {
+                ziggurat_tables::ZIG_NORM_R_F32 - x
+            }
+        }
+
+        ziggurat_f32(rng, true, // this is symmetric
+                     &ziggurat_tables::ZIG_NORM_X_F32,
+                     &ziggurat_tables::ZIG_NORM_F_F32,
+                     pdf, zero_case)
+    }
+}
+
 /// The normal distribution `N(mean, std_dev**2)`.
 ///
 /// This uses the ZIGNOR variant of the Ziggurat method, see
@@ -99,14 +159,23 @@ impl Normal {
     ///
     /// # Panics
     ///
-    /// Panics if `std_dev < 0`.
+    /// Panics if `std_dev < 0` or if either parameter is not finite.
     #[inline]
     pub fn new(mean: f64, std_dev: f64) -> Normal {
-        assert!(std_dev >= 0.0, "Normal::new called with `std_dev` < 0");
-        Normal {
+        unwrap_new(Normal::try_new(mean, std_dev), "Normal::new")
+    }
+
+    /// Construct a new `Normal` distribution with the given mean and
+    /// standard deviation, returning an error if `std_dev < 0` or if either
+    /// parameter is not finite.
+    #[inline]
+    pub fn try_new(mean: f64, std_dev: f64) -> Result<Normal, DistributionError> {
+        check_finite(mean, "mean")?;
+        check_non_negative(std_dev, "std_dev")?;
+        Ok(Normal {
             mean,
             std_dev
-        }
+        })
     }
 }
 impl Distribution<f64> for Normal {
@@ -122,6 +191,10 @@ impl Distribution<f64> for Normal {
 /// If `X` is log-normal distributed, then `ln(X)` is `N(mean,
 /// std_dev**2)` distributed.
 ///
+/// Note that the parameters of `new` are those of `ln(X)`, not the mean and
+/// standard deviation of `X` itself. Use [`from_mean_variance`] or
+/// [`from_mean_cv`] to construct the distribution from the latter.
+///
 /// # Example
 ///
 /// ```
@@ -130,8 +203,16 @@ impl Distribution<f64> for Normal {
 /// // mean 2, standard deviation 3
 /// let log_normal = LogNormal::new(2.0, 3.0);
 /// let v = log_normal.sample(&mut rand::thread_rng());
-/// println!("{} is from an ln N(2, 9) distribution", v)
+/// println!("{} is from an ln N(2, 9) distribution", v);
+///
+/// // A log-normal variable with mean 100 and standard deviation 20
+/// let price = LogNormal::from_mean_variance(100.0, 20.0 * 20.0);
+/// let v = price.sample(&mut rand::thread_rng());
+/// println!("the price is {}", v);
 /// ```
+///
+/// [`from_mean_variance`]: #method.from_mean_variance
+/// [`from_mean_cv`]: #method.from_mean_cv
 #[derive(Clone, Copy, Debug)]
 pub struct LogNormal {
     norm: Normal
@@ -143,11 +224,75 @@ impl LogNormal {
     ///
     /// # Panics
     ///
-    /// Panics if `std_dev < 0`.
+    /// Panics if `std_dev < 0` or if either parameter is not finite.
     #[inline]
     pub fn new(mean: f64, std_dev: f64) -> LogNormal {
-        assert!(std_dev >= 0.0, "LogNormal::new called with `std_dev` < 0");
-        LogNormal { norm: Normal::new(mean, std_dev) }
+        unwrap_new(LogNormal::try_new(mean, std_dev), "LogNormal::new")
+    }
+
+    /// Construct a new `LogNormal` distribution with the given mean and
+    /// standard deviation, returning an error if `std_dev < 0` or if either
+    /// parameter is not finite.
+    #[inline]
+    pub fn try_new(mean: f64, std_dev: f64) -> Result<LogNormal, DistributionError> {
+        Normal::try_new(mean, std_dev).map(|norm| LogNormal { norm })
+    }
+
+    /// Construct a new `LogNormal` distribution whose samples have the given
+    /// `mean` and `variance`.
+    ///
+    /// A `variance` of 0 gives a constant distribution, always returning
+    /// `mean` (up to rounding).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mean <= 0` or `variance < 0`, or if either is not finite.
+    pub fn from_mean_variance(mean: f64, variance: f64) -> LogNormal {
+        unwrap_new(LogNormal::try_from_mean_variance(mean, variance),
+                   "LogNormal::from_mean_variance")
+    }
+
+    /// Construct a new `LogNormal` distribution whose samples have the given
+    /// `mean` and `variance`, returning an error if `mean <= 0` or
+    /// `variance < 0`, or if either is not finite.
+    pub fn try_from_mean_variance(mean: f64, variance: f64)
+        -> Result<LogNormal, DistributionError>
+    {
+        check_positive(mean, "mean")?;
+        check_non_negative(variance, "variance")?;
+        let cv = variance.sqrt() / mean;
+        if !cv.is_finite() {
+            // The coefficient of variation overflows for tiny `mean`.
+            return Err(DistributionError::OutOfRange("variance"));
+        }
+        LogNormal::try_from_mean_cv(mean, cv)
+    }
+
+    /// Construct a new `LogNormal` distribution whose samples have the given
+    /// `mean` and coefficient of variation `cv`, i.e. a standard deviation of
+    /// `cv * mean`.
+    ///
+    /// A `cv` of 0 gives a constant distribution, always returning `mean`
+    /// (up to rounding).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mean <= 0` or `cv < 0`, or if either is not finite.
+    pub fn from_mean_cv(mean: f64, cv: f64) -> LogNormal {
+        unwrap_new(LogNormal::try_from_mean_cv(mean, cv), "LogNormal::from_mean_cv")
+    }
+
+    /// Construct a new `LogNormal` distribution whose samples have the given
+    /// `mean` and coefficient of variation `cv`, returning an error if
+    /// `mean <= 0` or `cv < 0`, or if either is not finite.
+    pub fn try_from_mean_cv(mean: f64, cv: f64) -> Result<LogNormal, DistributionError> {
+        check_positive(mean, "mean")?;
+        check_non_negative(cv, "cv")?;
+        // With `sigma^2 = ln(1 + cv^2)` and `mu = ln(mean) - sigma^2 / 2`,
+        // `exp(N(mu, sigma^2))` has the requested mean and variance. For
+        // large `cv` the square would overflow, but then the 1 is negligible.
+        let sigma2 = if cv < 1e100 { (cv * cv).ln_1p() } else { 2.0 * cv.ln() };
+        LogNormal::try_new(mean.ln() - 0.5 * sigma2, sigma2.sqrt())
     }
 }
 impl Distribution<f64> for LogNormal {
@@ -158,8 +303,32 @@ impl Distribution<f64> for LogNormal {
 
 #[cfg(test)]
 mod tests {
+    use Rng;
     use distributions::Distribution;
-    use super::{Normal, LogNormal};
+    use super::{StandardNormalF32, Normal, LogNormal};
+
+    #[test]
+    fn test_standard_normal_f32() {
+        let mut rng = ::test::rng(212);
+        const N: usize = 1_000_000;
+        let samples: Vec<f32> = (0..N).map(|_| rng.sample(StandardNormalF32)).collect();
+        assert!(samples.iter().all(|x| x.is_finite()));
+
+        let mean = samples.iter().map(|&x| x as f64).sum::<f64>() / N as f64;
+        assert!(mean.abs() < 5.0 / (N as f64).sqrt(), "mean {} != 0", mean);
+        let var = samples.iter().map(|&x| (x as f64 - mean).powi(2))
+            .sum::<f64>() / N as f64;
+        assert!((var - 1.0).abs() < 5.0 * (2.0 / N as f64).sqrt(),
+                "variance {} != 1", var);
+
+        // The tail beyond the base layer is sampled by `zero_case`;
+        // `P(|X| > 3.6541529) = 2.58e-4`.
+        let p = 2.5803e-4;
+        let tail = samples.iter().filter(|x| x.abs() > 3.6541529).count();
+        let freq = tail as f64 / N as f64;
+        assert!((freq - p).abs() < 5.0 * (p / N as f64).sqrt(),
+                "P(|X| > R) = {} != {}", freq, p);
+    }
 
     #[test]
     fn test_normal() {
@@ -175,6 +344,18 @@ mod tests {
         Normal::new(10.0, -1.0);
     }
 
+    #[test]
+    fn test_normal_try_new() {
+        use distributions::DistributionError::*;
+        use std::f64::{INFINITY, NAN};
+        assert!(Normal::try_new(10.0, 0.0).is_ok());
+        assert_eq!(Normal::try_new(10.0, -1.0).err(), Some(Negative("std_dev")));
+        assert_eq!(Normal::try_new(10.0, NAN).err(), Some(NonFinite("std_dev")));
+        assert_eq!(Normal::try_new(10.0, INFINITY).err(), Some(NonFinite("std_dev")));
+        assert_eq!(Normal::try_new(NAN, 1.0).err(), Some(NonFinite("mean")));
+        assert_eq!(Normal::try_new(-INFINITY, 1.0).err(), Some(NonFinite("mean")));
+    }
+
 
     #[test]
     fn test_log_normal() {
@@ -189,4 +370,94 @@ mod tests {
     fn test_log_normal_invalid_sd() {
         LogNormal::new(10.0, -1.0);
     }
+
+    #[test]
+    fn test_log_normal_try_new() {
+        use distributions::DistributionError::*;
+        assert!(LogNormal::try_new(10.0, 0.0).is_ok());
+        assert_eq!(LogNormal::try_new(10.0, -1.0).err(), Some(Negative("std_dev")));
+        assert_eq!(LogNormal::try_new(::std::f64::NAN, 1.0).err(), Some(NonFinite("mean")));
+    }
+
+    #[test]
+    fn test_log_normal_from_mean_variance() {
+        let mut rng = ::test::rng(215);
+        for &(mean, variance) in [(1.0, 0.25), (100.0, 400.0), (0.01, 1e-6),
+                                  (3.0, 9.0)].iter() {
+            let lnorm = LogNormal::from_mean_variance(mean, variance);
+            const N: usize = 100_000;
+            let samples: Vec<f64> = (0..N).map(|_| lnorm.sample(&mut rng)).collect();
+            let sample_mean = samples.iter().sum::<f64>() / N as f64;
+            let sample_variance = samples.iter().map(|x| (x - sample_mean).powi(2))
+                .sum::<f64>() / (N - 1) as f64;
+
+            // The standard error of the variance depends on the kurtosis,
+            // `exp(4s) + 2 exp(3s) + 3 exp(2s) - 3` with `s = sigma^2`.
+            let s: f64 = (variance / (mean * mean) as f64).ln_1p();
+            let kurtosis = (4.0 * s).exp() + 2.0 * (3.0 * s).exp()
+                + 3.0 * (2.0 * s).exp() - 3.0;
+            let err_mean = 5.0 * (variance / N as f64).sqrt();
+            let err_variance = 5.0 * variance * ((kurtosis - 1.0) / N as f64).sqrt();
+            assert!((sample_mean - mean).abs() < err_mean,
+                    "mean {} != {}", sample_mean, mean);
+            assert!((sample_variance - variance).abs() < err_variance,
+                    "variance {} != {}", sample_variance, variance);
+        }
+    }
+
+    #[test]
+    fn test_log_normal_from_mean_cv() {
+        let mut rng = ::test::rng(216);
+        let lnorm = LogNormal::from_mean_cv(5.0, 0.1);
+        const N: usize = 100_000;
+        let samples: Vec<f64> = (0..N).map(|_| lnorm.sample(&mut rng)).collect();
+        let sample_mean = samples.iter().sum::<f64>() / N as f64;
+        let sample_sd = (samples.iter().map(|x| (x - sample_mean).powi(2))
+            .sum::<f64>() / (N - 1) as f64).sqrt();
+        assert!((sample_mean - 5.0).abs() < 5.0 * 0.5 / (N as f64).sqrt());
+        assert!((sample_sd / sample_mean - 0.1).abs() < 0.002);
+
+        // A huge coefficient of variation does not overflow.
+        assert!(LogNormal::try_from_mean_cv(1.0, 1e200).is_ok());
+    }
+
+    #[test]
+    fn test_log_normal_degenerate() {
+        let mut rng = ::test::rng(217);
+        for &mean in [1.0, 2.5, 1e-100, 1e100].iter() {
+            let a = LogNormal::from_mean_variance(mean, 0.0);
+            let b = LogNormal::from_mean_cv(mean, 0.0);
+            let first: f64 = a.sample(&mut rng);
+            assert!((first - mean).abs() <= mean * 1e-13);
+            for _ in 0..100 {
+                let (x, y): (f64, f64) = (a.sample(&mut rng), b.sample(&mut rng));
+                assert_eq!(x, first);
+                assert_eq!(y, first);
+            }
+        }
+    }
+
+    #[test]
+    fn test_log_normal_from_mean_variance_invalid() {
+        use distributions::DistributionError::*;
+        assert_eq!(LogNormal::try_from_mean_variance(0.0, 1.0).err(),
+                   Some(NonPositive("mean")));
+        assert_eq!(LogNormal::try_from_mean_variance(1.0, -1.0).err(),
+                   Some(Negative("variance")));
+        assert_eq!(LogNormal::try_from_mean_variance(::std::f64::NAN, 1.0).err(),
+                   Some(NonFinite("mean")));
+        assert_eq!(LogNormal::try_from_mean_variance(1e-300, 1e300).err(),
+                   Some(OutOfRange("variance")));
+        assert_eq!(LogNormal::try_from_mean_cv(-1.0, 1.0).err(),
+                   Some(NonPositive("mean")));
+        assert_eq!(LogNormal::try_from_mean_cv(1.0, -0.5).err(), Some(Negative("cv")));
+        assert_eq!(LogNormal::try_from_mean_cv(1.0, ::std::f64::INFINITY).err(),
+                   Some(NonFinite("cv")));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_log_normal_from_mean_variance_panics() {
+        LogNormal::from_mean_variance(-1.0, 1.0);
+    }
 }
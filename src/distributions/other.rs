@@ -12,32 +12,68 @@
 
 use core::char;
 use core::num::Wrapping;
+#[cfg(feature="std")]
+use std::net::Ipv4Addr;
+#[cfg(all(feature="std", rustc_1_26))]
+use std::net::Ipv6Addr;
 
 use {Rng};
 use distributions::{Distribution, Standard, Uniform};
+#[cfg(feature="alloc")] use distributions::DistString;
+#[cfg(all(feature="alloc", not(feature="std")))] use alloc::String;
 
 // ----- Sampling distributions -----
 
-/// Sample a `char`, uniformly distributed over ASCII letters and numbers:
-/// a-z, A-Z and 0-9.
+/// Sample a `char` or an ASCII `u8`, uniformly distributed over ASCII letters
+/// and numbers: a-z, A-Z and 0-9.
+///
+/// Each of the 62 symbols is equally likely: values are taken from the 6 most
+/// significant bits of a `u32`, rejecting the 2 values out of 64 which do not
+/// map to a symbol.
 /// 
 /// # Example
 ///
 /// ```
 /// use std::iter;
 /// use rand::{Rng, thread_rng};
-/// use rand::distributions::Alphanumeric;
+/// use rand::distributions::{Alphanumeric, DistString};
 /// 
 /// let mut rng = thread_rng();
 /// let chars: String = iter::repeat(())
-///         .map(|()| rng.sample(Alphanumeric))
+///         .map(|()| rng.sample::<char, _>(Alphanumeric))
 ///         .take(7)
 ///         .collect();
 /// println!("Random chars: {}", chars);
+///
+/// // The same, using `DistString`
+/// let chars = Alphanumeric.sample_string(&mut rng, 7);
+///
+/// // ASCII bytes
+/// let mut key = [0u8; 16];
+/// for byte in key.iter_mut() {
+///     *byte = rng.sample(Alphanumeric);
+/// }
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct Alphanumeric;
 
+/// Sample a `char` or an ASCII `u8`, uniformly distributed over the lowercase
+/// hexadecimal digits 0-9 and a-f.
+///
+/// Each value is taken from the 4 most significant bits of a `u32`.
+///
+/// # Example
+///
+/// ```
+/// use rand::thread_rng;
+/// use rand::distributions::{Hex, DistString};
+///
+/// let color = format!("#{}", Hex.sample_string(&mut thread_rng(), 6));
+/// println!("Random color: {}", color);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Hex;
+
 
 // ----- Implementations of distributions -----
 
@@ -56,8 +92,8 @@ impl Distribution<char> for Standard {
     }
 }
 
-impl Distribution<char> for Alphanumeric {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> char {
+impl Distribution<u8> for Alphanumeric {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u8 {
         const RANGE: u32 = 26 + 26 + 10;
         const GEN_ASCII_STR_CHARSET: &[u8] =
             b"ABCDEFGHIJKLMNOPQRSTUVWXYZ\
@@ -70,12 +106,55 @@ impl Distribution<char> for Alphanumeric {
         loop {
             let var = rng.next_u32() >> (32 - 6);
             if var < RANGE {
-                return GEN_ASCII_STR_CHARSET[var as usize] as char
+                return GEN_ASCII_STR_CHARSET[var as usize]
             }
         }
     }
 }
 
+impl Distribution<char> for Alphanumeric {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> char {
+        let byte: u8 = self.sample(rng);
+        byte as char
+    }
+}
+
+#[cfg(feature="alloc")]
+impl DistString for Alphanumeric {
+    fn append_string<R: Rng + ?Sized>(&self, rng: &mut R, string: &mut String, len: usize) {
+        string.reserve(len);
+        for _ in 0..len {
+            let c: char = self.sample(rng);
+            string.push(c);
+        }
+    }
+}
+
+impl Distribution<u8> for Hex {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u8 {
+        const HEX_CHARSET: &[u8] = b"0123456789abcdef";
+        HEX_CHARSET[(rng.next_u32() >> (32 - 4)) as usize]
+    }
+}
+
+impl Distribution<char> for Hex {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> char {
+        let byte: u8 = self.sample(rng);
+        byte as char
+    }
+}
+
+#[cfg(feature="alloc")]
+impl DistString for Hex {
+    fn append_string<R: Rng + ?Sized>(&self, rng: &mut R, string: &mut String, len: usize) {
+        string.reserve(len);
+        for _ in 0..len {
+            let c: char = self.sample(rng);
+            string.push(c);
+        }
+    }
+}
+
 impl Distribution<bool> for Standard {
     #[inline]
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> bool {
@@ -85,6 +164,16 @@ impl Distribution<bool> for Standard {
         // easiest done using a sign test.
         (rng.next_u32() as i32) < 0
     }
+
+    fn sample_fill<R: Rng + ?Sized>(&self, rng: &mut R, out: &mut [bool]) {
+        // Use all bits of each `u64`, most significant bits first.
+        for chunk in out.chunks_mut(64) {
+            let bits = rng.next_u64();
+            for (i, b) in chunk.iter_mut().enumerate() {
+                *b = (bits << i as u32) >> 63 == 1;
+            }
+        }
+    }
 }
 
 macro_rules! tuple_impl {
@@ -169,11 +258,27 @@ impl<T> Distribution<Wrapping<T>> for Standard where Standard: Distribution<T> {
     }
 }
 
+#[cfg(feature="std")]
+impl Distribution<Ipv4Addr> for Standard {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Ipv4Addr {
+        Ipv4Addr::from(rng.gen::<u32>())
+    }
+}
+
+#[cfg(all(feature="std", rustc_1_26))]
+impl Distribution<Ipv6Addr> for Standard {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Ipv6Addr {
+        Ipv6Addr::from(rng.gen::<u128>())
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
     use {Rng, RngCore, Standard};
-    use distributions::Alphanumeric;
+    use distributions::{Distribution, Alphanumeric, Hex};
     #[cfg(all(not(feature="std"), feature="alloc"))] use alloc::String;
 
     #[test]
@@ -197,6 +302,32 @@ mod tests {
         assert!(word.len() != 0);
     }
 
+    #[test]
+    fn test_tuples() {
+        // Sampling a tuple must be equivalent to sampling its elements from
+        // left to right.
+        let mut rng1 = ::test::rng(807);
+        let mut rng2 = ::test::rng(807);
+        for _ in 0..10 {
+            let t: (u32, f64, bool, (u8, i64), [u16; 3]) = rng1.gen();
+            assert_eq!(t.0, rng2.gen::<u32>());
+            assert_eq!(t.1, rng2.gen::<f64>());
+            assert_eq!(t.2, rng2.gen::<bool>());
+            assert_eq!((t.3).0, rng2.gen::<u8>());
+            assert_eq!((t.3).1, rng2.gen::<i64>());
+            assert_eq!(t.4, rng2.gen::<[u16; 3]>());
+        }
+
+        let t: (u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u32) = rng1.gen();
+        let mut bytes = [0u8; 11];
+        for b in bytes.iter_mut() {
+            *b = rng2.gen();
+        }
+        assert_eq!([t.0, t.1, t.2, t.3, t.4, t.5, t.6, t.7, t.8, t.9, t.10],
+                   bytes);
+        assert_eq!(t.11, rng2.gen::<u32>());
+    }
+
     #[test]
     fn test_alphanumeric() {
         let mut rng = ::test::rng(806);
@@ -205,11 +336,78 @@ mod tests {
         // take the rejection sampling path.
         let mut incorrect = false;
         for _ in 0..100 {
-            let c = rng.sample(Alphanumeric);
+            let c: char = rng.sample(Alphanumeric);
             incorrect |= !((c >= '0' && c <= '9') ||
                            (c >= 'A' && c <= 'Z') ||
                            (c >= 'a' && c <= 'z') );
         }
         assert!(incorrect == false);
     }
+
+    // Check that `distr` samples each of the bytes in `alphabet` with equal
+    // frequency, and chars equal to the bytes.
+    fn check_ascii_distr<D>(distr: D, alphabet: &[u8], seed: u64)
+        where D: Distribution<u8> + Distribution<char>
+    {
+        let mut rng = ::test::rng(seed);
+        let mut counts = [0usize; 128];
+        const N: usize = 200_000;
+        for _ in 0..N {
+            let byte: u8 = distr.sample(&mut rng);
+            counts[byte as usize] += 1;
+        }
+        let p = 1.0 / alphabet.len() as f64;
+        let err = 5.0 * (N as f64 * p * (1.0 - p)).sqrt();
+        for (byte, &count) in counts.iter().enumerate() {
+            if alphabet.contains(&(byte as u8)) {
+                assert!((count as f64 - N as f64 * p).abs() < err,
+                        "count of {:?}: {}", byte as u8 as char, count);
+            } else {
+                assert_eq!(count, 0);
+            }
+        }
+
+        let mut rng1 = ::test::rng(seed);
+        let mut rng2 = ::test::rng(seed);
+        for _ in 0..100 {
+            let c: char = distr.sample(&mut rng1);
+            let byte: u8 = distr.sample(&mut rng2);
+            assert_eq!(c, byte as char);
+        }
+    }
+
+    #[test]
+    fn test_alphanumeric_bytes() {
+        check_ascii_distr(Alphanumeric,
+                          b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+                          808);
+    }
+
+    #[test]
+    fn test_hex() {
+        check_ascii_distr(Hex, b"0123456789abcdef", 809);
+    }
+
+    #[cfg(feature="alloc")]
+    #[test]
+    fn test_dist_string() {
+        use distributions::DistString;
+        let mut rng = ::test::rng(810);
+        for &len in [0, 1, 7, 64].iter() {
+            let s = Alphanumeric.sample_string(&mut rng, len);
+            assert_eq!(s.len(), len);
+            assert!(s.chars().all(|c| c.is_ascii_alphanumeric()));
+
+            let mut s = String::from("0x");
+            Hex.append_string(&mut rng, &mut s, len);
+            assert_eq!(s.len(), len + 2);
+            assert!(s[2..].chars().all(|c| c.is_digit(16) && !c.is_uppercase()));
+        }
+
+        let mut rng1 = ::test::rng(811);
+        let mut rng2 = ::test::rng(811);
+        let s = Alphanumeric.sample_string(&mut rng1, 16);
+        let expected: String = rng2.sample_iter::<char, _>(&Alphanumeric).take(16).collect();
+        assert_eq!(s, expected);
+    }
 }
@@ -0,0 +1,120 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Pareto distribution.
+
+use Rng;
+use distributions::{Distribution, OpenClosed01};
+use distributions::error::{DistributionError, unwrap_new, check_positive};
+
+/// Samples floating-point numbers according to the Pareto distribution
+/// `Pareto(scale, shape)`.
+///
+/// The cumulative distribution function is `F(x) = 1 - (scale / x)^shape`
+/// for `x >= scale`. This distribution is heavy-tailed: the mean only exists
+/// for `shape > 1` and the variance only for `shape > 2`.
+///
+/// # Example
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand::distributions::Pareto;
+///
+/// let val: f64 = SmallRng::from_entropy().sample(Pareto::new(1., 2.));
+/// println!("{}", val);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Pareto {
+    scale: f64,
+    inv_neg_shape: f64,
+}
+
+impl Pareto {
+    /// Construct a new Pareto distribution with given `scale` and `shape`.
+    ///
+    /// In the literature, `scale` is commonly written as x<sub>m</sub> or k and
+    /// `shape` is often written as α.
+    ///
+    /// # Panics
+    ///
+    /// `scale` and `shape` have to be non-zero, positive and finite.
+    pub fn new(scale: f64, shape: f64) -> Pareto {
+        unwrap_new(Pareto::try_new(scale, shape), "Pareto::new")
+    }
+
+    /// Construct a new Pareto distribution with given `scale` and `shape`,
+    /// returning an error if either is not positive and finite.
+    pub fn try_new(scale: f64, shape: f64) -> Result<Pareto, DistributionError> {
+        check_positive(scale, "scale")?;
+        check_positive(shape, "shape")?;
+        Ok(Pareto { scale, inv_neg_shape: -1.0 / shape })
+    }
+}
+
+impl Distribution<f64> for Pareto {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        // `u` is in `(0, 1]`, so the result is never infinite.
+        let u: f64 = rng.sample(OpenClosed01);
+        self.scale * u.powf(self.inv_neg_shape)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use distributions::Distribution;
+    use super::Pareto;
+    use distributions::DistributionError::*;
+
+    #[test]
+    #[should_panic]
+    fn invalid() {
+        Pareto::new(0., 0.);
+    }
+
+    #[test]
+    fn sample() {
+        let scale = 1.0;
+        let shape = 2.0;
+        let d = Pareto::new(scale, shape);
+        let mut r = ::test::rng(1);
+        for _ in 0..1000 {
+            let x = d.sample(&mut r);
+            assert!(x >= scale && x.is_finite());
+        }
+    }
+
+    #[test]
+    fn quantiles() {
+        let mut r = ::test::rng(2);
+        for &(scale, shape) in [(1.0, 0.5), (2.0, 1.5), (0.5, 3.0)].iter() {
+            let d = Pareto::new(scale, shape);
+            const N: usize = 100_000;
+            let mut samples: Vec<f64> = (0..N).map(|_| d.sample(&mut r)).collect();
+            samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            for &p in [0.1, 0.5, 0.9].iter() {
+                let x = samples[(p * N as f64) as usize];
+                // the analytic CDF at the empirical quantile
+                let cdf = 1.0 - (scale / x).powf(shape);
+                assert!((cdf - p).abs() < 0.005,
+                        "quantile {}: CDF({}) = {}", p, x, cdf);
+            }
+        }
+    }
+
+
+    #[test]
+    fn try_new() {
+        assert!(Pareto::try_new(1e-300, 1e300).is_ok());
+        assert_eq!(Pareto::try_new(0., 1.).err(), Some(NonPositive("scale")));
+        assert_eq!(Pareto::try_new(1., -1.).err(), Some(NonPositive("shape")));
+        assert_eq!(Pareto::try_new(::std::f64::INFINITY, 1.).err(), Some(NonFinite("scale")));
+        assert_eq!(Pareto::try_new(1., ::std::f64::NAN).err(), Some(NonFinite("shape")));
+    }
+}
@@ -0,0 +1,192 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The PERT distribution.
+
+use Rng;
+use distributions::{Distribution, Beta};
+use distributions::error::{DistributionError, unwrap_new, check_finite, check_positive};
+
+/// The PERT distribution `Pert(min, mode, max)`.
+///
+/// Like the [`Triangular`] distribution, this is parameterized by a minimum,
+/// most likely and maximum value, but it is smooth and puts less weight on
+/// the extremes. It is a [`Beta`] distribution scaled to `[min, max]`, with
+/// shape parameters
+///
+/// ```text
+/// α = 1 + shape * (mode - min) / (max - min)
+/// β = 1 + shape * (max - mode) / (max - min)
+/// ```
+///
+/// where `shape` is 4 for the standard PERT distribution; the mean is then
+/// `(min + 4 * mode + max) / 6`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Pert, Distribution};
+///
+/// let d = Pert::new(0., 2.5, 5.);
+/// let v = d.sample(&mut rand::thread_rng());
+/// println!("{} is from a PERT distribution", v);
+/// ```
+///
+/// [`Triangular`]: struct.Triangular.html
+/// [`Beta`]: struct.Beta.html
+#[derive(Clone, Copy, Debug)]
+pub struct Pert {
+    min: f64,
+    range: f64,
+    // `None` if `min == max`
+    beta: Option<Beta>,
+}
+
+impl Pert {
+    /// Construct a new standard `Pert` distribution with the given `min`,
+    /// `mode` and `max`.
+    ///
+    /// If `min == max`, the distribution always returns `min`.
+    ///
+    /// # Panics
+    ///
+    /// If `min <= mode <= max` does not hold, or if `min` or `max` is not
+    /// finite.
+    pub fn new(min: f64, mode: f64, max: f64) -> Pert {
+        unwrap_new(Pert::try_new(min, mode, max), "Pert::new")
+    }
+
+    /// Construct a new standard `Pert` distribution with the given `min`,
+    /// `mode` and `max`, returning an error if `min <= mode <= max` does not
+    /// hold, or if `min` or `max` is not finite.
+    pub fn try_new(min: f64, mode: f64, max: f64) -> Result<Pert, DistributionError> {
+        Pert::try_new_with_shape(min, mode, max, 4.0)
+    }
+
+    /// Construct a new `Pert` distribution with the given `min`, `mode`,
+    /// `max` and `shape`. Larger values of `shape` concentrate the samples
+    /// more closely around `mode`.
+    ///
+    /// # Panics
+    ///
+    /// If `min <= mode <= max` does not hold, if `min` or `max` is not
+    /// finite, or if `shape` is not finite and positive.
+    pub fn new_with_shape(min: f64, mode: f64, max: f64, shape: f64) -> Pert {
+        unwrap_new(Pert::try_new_with_shape(min, mode, max, shape),
+                   "Pert::new_with_shape")
+    }
+
+    /// Construct a new `Pert` distribution with the given `min`, `mode`,
+    /// `max` and `shape`, returning an error if `min <= mode <= max` does not
+    /// hold, if `min` or `max` is not finite, or if `shape` is not finite and
+    /// positive.
+    pub fn try_new_with_shape(min: f64, mode: f64, max: f64, shape: f64)
+        -> Result<Pert, DistributionError>
+    {
+        check_finite(min, "min")?;
+        check_finite(max, "max")?;
+        if min > max {
+            return Err(DistributionError::EmptyRange);
+        }
+        if !(min <= mode && mode <= max) {
+            return Err(DistributionError::OutOfRange("mode"));
+        }
+        check_positive(shape, "shape")?;
+        let range = max - min;
+        let beta = if range > 0.0 {
+            let alpha = 1.0 + shape * (mode - min) / range;
+            let beta = 1.0 + shape * (max - mode) / range;
+            Some(Beta::try_new(alpha, beta)?)
+        } else {
+            None
+        };
+        Ok(Pert { min, range, beta })
+    }
+}
+
+impl Distribution<f64> for Pert {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        match self.beta {
+            Some(ref beta) => self.min + self.range * beta.sample(rng),
+            None => self.min,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::Distribution;
+    use super::Pert;
+    use distributions::DistributionError::*;
+
+    #[test]
+    fn test_pert() {
+        let mut rng = ::test::rng(411);
+        for &(min, mode, max) in [(0.0, 0.25, 1.0), (-10.0, -10.0, 5.0),
+                                  (1.0, 3.0, 3.0), (2.0, 7.0, 100.0)].iter() {
+            let d = Pert::new(min, mode, max);
+            const N: usize = 100_000;
+            const BINS: usize = 20;
+            let mut hist = [0usize; BINS];
+            let mut sum = 0.0;
+            for _ in 0..N {
+                let x = d.sample(&mut rng);
+                assert!(min <= x && x <= max);
+                sum += x;
+                let bin = ((x - min) / (max - min) * BINS as f64) as usize;
+                hist[if bin < BINS { bin } else { BINS - 1 }] += 1;
+            }
+            let mean = sum / N as f64;
+            let expected = (min + 4.0 * mode + max) / 6.0;
+            assert!((mean - expected).abs() < 0.01 * (max - min));
+
+            // The most populated bin must be the one containing the mode, or
+            // a neighbouring one.
+            let mode_bin = (mode - min) / (max - min) * BINS as f64;
+            let max_bin = (0..BINS).max_by_key(|&i| hist[i]).unwrap();
+            assert!((max_bin as f64 + 0.5 - mode_bin).abs() <= 1.5,
+                    "mode bin {} != {}", max_bin, mode_bin);
+        }
+    }
+
+    #[test]
+    fn test_pert_degenerate() {
+        let mut rng = ::test::rng(412);
+        let d = Pert::new_with_shape(-1.5, -1.5, -1.5, 2.0);
+        for _ in 0..100 {
+            assert_eq!(d.sample(&mut rng), -1.5);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_pert_invalid() {
+        Pert::new(5.0, 6.0, 5.5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_pert_invalid_shape() {
+        Pert::new_with_shape(0.0, 0.5, 1.0, 0.0);
+    }
+
+
+    #[test]
+    fn test_pert_try_new() {
+        assert!(Pert::try_new(5.0, 5.0, 5.0).is_ok());
+        assert_eq!(Pert::try_new(5.0, 6.0, 5.5).err(), Some(OutOfRange("mode")));
+        assert_eq!(Pert::try_new(5.0, 5.0, 4.0).err(), Some(EmptyRange));
+        assert_eq!(Pert::try_new(::std::f64::NAN, 0.0, 1.0).err(), Some(NonFinite("min")));
+        assert_eq!(Pert::try_new_with_shape(0.0, 0.5, 1.0, 0.0).err(),
+                   Some(NonPositive("shape")));
+        assert_eq!(Pert::try_new_with_shape(0.0, 0.5, 1.0, ::std::f64::INFINITY).err(),
+                   Some(NonFinite("shape")));
+    }
+}
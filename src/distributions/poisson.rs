@@ -13,12 +13,19 @@
 use Rng;
 use distributions::{Distribution, Cauchy};
 use distributions::log_gamma::log_gamma;
+use distributions::error::{DistributionError, unwrap_new, check_positive};
 
 /// The Poisson distribution `Poisson(lambda)`.
 ///
 /// This distribution has a density function:
 /// `f(k) = lambda^k * exp(-lambda) / k!` for `k >= 0`.
 ///
+/// For `lambda < 12`, samples are generated by multiplying uniform values
+/// until the product drops below `exp(-lambda)` (Knuth's method), which takes
+/// `O(lambda)` time. For larger `lambda`, a rejection method with a Cauchy
+/// comparison distribution is used, which takes expected constant time and
+/// never evaluates `exp(-lambda)`, so it also works for very large `lambda`.
+///
 /// # Example
 ///
 /// ```
@@ -40,17 +47,23 @@ pub struct Poisson {
 
 impl Poisson {
     /// Construct a new `Poisson` with the given shape parameter
-    /// `lambda`. Panics if `lambda <= 0`.
+    /// `lambda`. Panics if `lambda <= 0` or `lambda` is not finite.
     pub fn new(lambda: f64) -> Poisson {
-        assert!(lambda > 0.0, "Poisson::new called with lambda <= 0");
+        unwrap_new(Poisson::try_new(lambda), "Poisson::new")
+    }
+
+    /// Construct a new `Poisson` with the given shape parameter `lambda`,
+    /// returning an error if `lambda <= 0` or `lambda` is not finite.
+    pub fn try_new(lambda: f64) -> Result<Poisson, DistributionError> {
+        check_positive(lambda, "lambda")?;
         let log_lambda = lambda.ln();
-        Poisson {
+        Ok(Poisson {
             lambda,
             exp_lambda: (-lambda).exp(),
             log_lambda,
             sqrt_2lambda: (2.0 * lambda).sqrt(),
             magic_val: lambda * log_lambda - log_gamma(1.0 + lambda),
-        }
+        })
     }
 }
 
@@ -78,7 +91,7 @@ impl Distribution<u64> for Poisson {
 
             loop {
                 let mut result;
-                let mut comp_dev;
+                let mut comp_dev: f64;
 
                 loop {
                     // draw from the Cauchy distribution
@@ -116,6 +129,7 @@ impl Distribution<u64> for Poisson {
 mod test {
     use distributions::Distribution;
     use super::Poisson;
+    use distributions::DistributionError::*;
 
     #[test]
     fn test_poisson_10() {
@@ -144,6 +158,47 @@ mod test {
         assert!((avg - 15.0).abs() < 0.5); // not 100% certain, but probable enough
     }
 
+    fn check_mean_variance(lambda: f64, n: usize, seed: u64) {
+        let poisson = Poisson::new(lambda);
+        let mut rng = ::test::rng(seed);
+        let mut sum = 0.0;
+        let mut sum_sq = 0.0;
+        for _ in 0..n {
+            let x = poisson.sample(&mut rng) as f64;
+            sum += x;
+            sum_sq += x * x;
+        }
+        let mean = sum / n as f64;
+        let variance = sum_sq / n as f64 - mean * mean;
+        // the standard error of the mean is `sqrt(lambda / n)`
+        let mean_tolerance = 5.0 * (lambda / n as f64).sqrt();
+        assert!((mean - lambda).abs() < mean_tolerance,
+                "mean {} != {}", mean, lambda);
+        assert!((variance / lambda - 1.0).abs() < 0.05,
+                "variance {} != {}", variance, lambda);
+    }
+
+    #[test]
+    fn test_poisson_moments() {
+        check_mean_variance(1.0, 20_000, 124);
+        check_mean_variance(11.5, 20_000, 125);
+        check_mean_variance(1e3, 20_000, 126);
+        check_mean_variance(1e6, 20_000, 127);
+    }
+
+    #[test]
+    fn test_poisson_exp_underflow() {
+        // `exp(-lambda)` underflows to 0 for `lambda > ~745`; sampling must
+        // not depend on it.
+        check_mean_variance(800.0, 10_000, 128);
+        let poisson = Poisson::new(1e15);
+        let mut rng = ::test::rng(129);
+        for _ in 0..100 {
+            let x = poisson.sample(&mut rng) as f64;
+            assert!((x - 1e15).abs() < 1e9);
+        }
+    }
+
     #[test]
     #[should_panic]
     fn test_poisson_invalid_lambda_zero() {
@@ -155,4 +210,20 @@ mod test {
     fn test_poisson_invalid_lambda_neg() {
         Poisson::new(-10.0);
     }
+
+    #[test]
+    #[should_panic]
+    fn test_poisson_invalid_lambda_infinite() {
+        Poisson::new(::std::f64::INFINITY);
+    }
+
+
+    #[test]
+    fn test_poisson_try_new() {
+        assert!(Poisson::try_new(1e-300).is_ok());
+        assert_eq!(Poisson::try_new(0.0).err(), Some(NonPositive("lambda")));
+        assert_eq!(Poisson::try_new(-10.0).err(), Some(NonPositive("lambda")));
+        assert_eq!(Poisson::try_new(::std::f64::INFINITY).err(), Some(NonFinite("lambda")));
+        assert_eq!(Poisson::try_new(::std::f64::NAN).err(), Some(NonFinite("lambda")));
+    }
 }
@@ -0,0 +1,190 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The skew-normal distribution.
+
+use Rng;
+use distributions::{Distribution, StandardNormal};
+use distributions::error::{DistributionError, unwrap_new, check_finite, check_positive};
+
+/// The skew-normal distribution `SN(location, scale, shape)`.
+///
+/// This distribution has a density function:
+/// `f(x) = 2 / scale * phi(z) * Phi(shape * z)` with
+/// `z = (x - location) / scale`, where `phi` and `Phi` are the density and the
+/// cumulative distribution function of the standard normal distribution.
+///
+/// A positive `shape` skews the distribution to the right, and a negative
+/// `shape` to the left. With `delta = shape / sqrt(1 + shape^2)`, the mean is
+/// `location + scale * delta * sqrt(2 / pi)` and the variance is
+/// `scale^2 * (1 - 2 * delta^2 / pi)`. With `shape == 0` this is the
+/// [`Normal`] distribution `N(location, scale^2)`, and samples are then
+/// exactly those of `Normal::new(location, scale)`.
+///
+/// Samples are generated from two independent standard normal samples `u`
+/// and `v`, as `location + scale * sign(u) * (delta * u + sqrt(1 - delta^2) * v)`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{SkewNormal, Distribution};
+///
+/// // Location 0 and scale 1, skewed to the right
+/// let skew_normal = SkewNormal::new(0.0, 1.0, 4.0);
+/// let v = skew_normal.sample(&mut rand::thread_rng());
+/// println!("{} is from an SN(0, 1, 4) distribution", v);
+///
+/// // A mirror image, skewed to the left
+/// let mirrored = SkewNormal::new(0.0, 1.0, -4.0);
+/// let v = mirrored.sample(&mut rand::thread_rng());
+/// println!("{} is from an SN(0, 1, -4) distribution", v);
+/// ```
+///
+/// # References
+///
+/// A. Azzalini and A. Dalla Valle, *The multivariate skew-normal
+/// distribution*, Biometrika 83(4), 1996, 715-726.
+///
+/// [`Normal`]: struct.Normal.html
+#[derive(Clone, Copy, Debug)]
+pub struct SkewNormal {
+    location: f64,
+    scale: f64,
+    shape: f64,
+    delta: f64,
+    delta_comp: f64,
+}
+
+impl SkewNormal {
+    /// Construct a new `SkewNormal` distribution with the given `location`,
+    /// `scale` and `shape`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale <= 0` or if any parameter is not finite.
+    pub fn new(location: f64, scale: f64, shape: f64) -> SkewNormal {
+        unwrap_new(SkewNormal::try_new(location, scale, shape), "SkewNormal::new")
+    }
+
+    /// Construct a new `SkewNormal` distribution with the given `location`,
+    /// `scale` and `shape`, returning an error if `scale <= 0` or if any
+    /// parameter is not finite.
+    pub fn try_new(location: f64, scale: f64, shape: f64)
+        -> Result<SkewNormal, DistributionError>
+    {
+        check_finite(location, "location")?;
+        check_positive(scale, "scale")?;
+        check_finite(shape, "shape")?;
+        let delta = shape / (1.0 + shape * shape).sqrt();
+        Ok(SkewNormal {
+            location,
+            scale,
+            shape,
+            delta,
+            delta_comp: (1.0 - delta * delta).sqrt(),
+        })
+    }
+}
+
+impl Distribution<f64> for SkewNormal {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        if self.shape == 0.0 {
+            let n = rng.sample(StandardNormal);
+            return self.location + self.scale * n;
+        }
+        let u: f64 = rng.sample(StandardNormal);
+        let v: f64 = rng.sample(StandardNormal);
+        let z = self.delta * u + self.delta_comp * v;
+        if u >= 0.0 {
+            self.location + self.scale * z
+        } else {
+            self.location - self.scale * z
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::{Distribution, Normal};
+    use super::SkewNormal;
+    use distributions::DistributionError::*;
+
+    #[test]
+    fn test_skew_normal_moments() {
+        let mut rng = ::test::rng(432);
+        for &(location, scale, shape) in [(0.0, 1.0, 4.0), (0.0, 1.0, -4.0),
+                                          (10.0, 2.0, 1.0), (-3.0, 0.5, 100.0),
+                                          (1.0, 3.0, 0.0)].iter() {
+            let skew_normal = SkewNormal::new(location, scale, shape);
+            const N: usize = 200_000;
+            let samples: Vec<f64> = (0..N).map(|_| skew_normal.sample(&mut rng)).collect();
+
+            let pi = ::std::f64::consts::PI;
+            let delta: f64 = shape / (1.0 + shape * shape as f64).sqrt();
+            let m = delta * (2.0 / pi).sqrt();
+            let mean = location + scale * m;
+            let variance = scale * scale * (1.0 - m * m);
+            let skewness = (4.0 - pi) / 2.0 * m.powi(3) / (1.0 - m * m).powf(1.5);
+
+            let sample_mean = samples.iter().sum::<f64>() / N as f64;
+            let err = 5.0 * (variance / N as f64).sqrt();
+            assert!((sample_mean - mean).abs() < err,
+                    "mean {} != {}", sample_mean, mean);
+
+            let m2 = samples.iter().map(|x| (x - sample_mean).powi(2))
+                .sum::<f64>() / N as f64;
+            let m3 = samples.iter().map(|x| (x - sample_mean).powi(3))
+                .sum::<f64>() / N as f64;
+            let sample_skewness = m3 / m2.powf(1.5);
+            // the standard error of the skewness is about `sqrt(6 / N)`
+            assert!((sample_skewness - skewness).abs() < 5.0 * (6.0 / N as f64).sqrt(),
+                    "skewness {} != {}", sample_skewness, skewness);
+        }
+    }
+
+    #[test]
+    fn test_skew_normal_zero_shape() {
+        let skew_normal = SkewNormal::new(2.0, 3.0, 0.0);
+        let normal = Normal::new(2.0, 3.0);
+        let mut rng1 = ::test::rng(433);
+        let mut rng2 = ::test::rng(433);
+        for _ in 0..100 {
+            assert_eq!(skew_normal.sample(&mut rng1), normal.sample(&mut rng2));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_skew_normal_invalid_scale() {
+        SkewNormal::new(0.0, 0.0, 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_skew_normal_infinite_shape() {
+        SkewNormal::new(0.0, 1.0, ::std::f64::INFINITY);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_skew_normal_nan_location() {
+        SkewNormal::new(::std::f64::NAN, 1.0, 1.0);
+    }
+
+
+    #[test]
+    fn test_skew_normal_try_new() {
+        assert!(SkewNormal::try_new(-1.0, 1e-300, -1e300).is_ok());
+        assert_eq!(SkewNormal::try_new(0.0, 0.0, 1.0).err(), Some(NonPositive("scale")));
+        assert_eq!(SkewNormal::try_new(0.0, ::std::f64::INFINITY, 1.0).err(), Some(NonFinite("scale")));
+        assert_eq!(SkewNormal::try_new(0.0, 1.0, ::std::f64::INFINITY).err(), Some(NonFinite("shape")));
+        assert_eq!(SkewNormal::try_new(::std::f64::NAN, 1.0, 1.0).err(), Some(NonFinite("location")));
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Sampling elements of a slice.
+
+use Rng;
+use distributions::{Distribution, Uniform};
+use core::fmt;
+
+/// A distribution to sample items uniformly from a slice.
+///
+/// Sampling returns a reference to an element of the slice. Every element
+/// is equally likely to be chosen; elements are compared by position, not by
+/// value, so repeated values are proportionally more likely.
+///
+/// This is convenient to sample repeatedly from the same slice, e.g. with
+/// [`sample_iter`]. To sample a single element, see [`Rng::choose`].
+///
+/// # Example
+///
+/// ```
+/// use rand::Rng;
+/// use rand::distributions::Slice;
+///
+/// let vowels = ['a', 'e', 'i', 'o', 'u'];
+/// let vowels_dist = Slice::new(&vowels).unwrap();
+/// let mut rng = rand::thread_rng();
+///
+/// // build a string of 10 vowels
+/// let vowel_string: String = rng.sample_iter(&vowels_dist)
+///     .take(10)
+///     .collect();
+/// println!("{}", vowel_string);
+/// assert_eq!(vowel_string.len(), 10);
+/// assert!(vowel_string.chars().all(|c| vowels.contains(&c)));
+/// ```
+///
+/// [`sample_iter`]: trait.Distribution.html#method.sample_iter
+/// [`Rng::choose`]: ../trait.Rng.html#method.choose
+#[derive(Debug, Clone, Copy)]
+pub struct Slice<'a, T: 'a> {
+    slice: &'a [T],
+    range: Uniform<usize>,
+}
+
+impl<'a, T> Slice<'a, T> {
+    /// Create a new `Slice` instance which samples uniformly from the slice.
+    ///
+    /// Returns an error if the slice is empty.
+    pub fn new(slice: &'a [T]) -> Result<Slice<'a, T>, EmptySlice> {
+        if slice.is_empty() {
+            return Err(EmptySlice);
+        }
+        Ok(Slice {
+            slice,
+            range: Uniform::new(0, slice.len()),
+        })
+    }
+}
+
+impl<'a, T> Distribution<&'a T> for Slice<'a, T> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> &'a T {
+        let index = self.range.sample(rng);
+        &self.slice[index]
+    }
+}
+
+/// Error type returned from `Slice::new`: the slice is empty.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EmptySlice;
+
+impl fmt::Display for EmptySlice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Tried to create a `distributions::Slice` with an empty slice")
+    }
+}
+
+#[cfg(feature="std")]
+impl ::std::error::Error for EmptySlice {
+    fn description(&self) -> &str {
+        "Tried to create a `distributions::Slice` with an empty slice"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use Rng;
+    use super::{Slice, EmptySlice};
+
+    #[test]
+    fn test_slice_uniform() {
+        let items = ["a", "b", "c", "d", "e", "f", "g"];
+        let distr = Slice::new(&items).unwrap();
+        let mut rng = ::test::rng(501);
+        const N: usize = 70_000;
+        let mut counts = [0usize; 7];
+        for &item in rng.sample_iter(&distr).take(N) {
+            let index = items.iter().position(|&x| x == item).unwrap();
+            counts[index] += 1;
+        }
+        let p = 1.0 / 7.0;
+        let err = 5.0 * (N as f64 * p * (1.0 - p)).sqrt();
+        for &count in counts.iter() {
+            assert!((count as f64 - N as f64 * p).abs() < err,
+                    "count {} != {}", count, N as f64 * p);
+        }
+    }
+
+    #[test]
+    fn test_slice_single() {
+        let distr = Slice::new(&[42]).unwrap();
+        let mut rng = ::test::rng(502);
+        for _ in 0..10 {
+            assert_eq!(*rng.sample(&distr), 42);
+        }
+    }
+
+    #[test]
+    fn test_slice_empty() {
+        let empty: &[u8] = &[];
+        assert_eq!(Slice::new(empty).err(), Some(EmptySlice));
+    }
+}
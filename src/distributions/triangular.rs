@@ -0,0 +1,164 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The triangular distribution.
+
+use Rng;
+use distributions::{Distribution, Standard};
+use distributions::error::{DistributionError, unwrap_new, check_finite};
+
+/// The triangular distribution `Triangular(min, mode, max)`.
+///
+/// The density rises linearly from zero at `min` to its peak at `mode`, then
+/// falls linearly back to zero at `max`. This is a simple model for
+/// three-point estimates; see also [`Pert`].
+///
+/// Samples are generated from a single uniform value using the inverse of
+/// the cumulative distribution function.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Triangular, Distribution};
+///
+/// let d = Triangular::new(0., 2.5, 5.);
+/// let v = d.sample(&mut rand::thread_rng());
+/// println!("{} is from a triangular distribution", v);
+/// ```
+///
+/// [`Pert`]: struct.Pert.html
+#[derive(Clone, Copy, Debug)]
+pub struct Triangular {
+    min: f64,
+    max: f64,
+    // the CDF at `mode`, i.e. `(mode - min) / (max - min)`
+    mode_cdf: f64,
+    // `(max - min) * (mode - min)`
+    left: f64,
+    // `(max - min) * (max - mode)`
+    right: f64,
+}
+
+impl Triangular {
+    /// Construct a new `Triangular` with the given `min`, `mode` and `max`.
+    ///
+    /// If `min == max`, the distribution always returns `min`.
+    ///
+    /// # Panics
+    ///
+    /// If `min <= mode <= max` does not hold, or if `min` or `max` is not
+    /// finite.
+    pub fn new(min: f64, mode: f64, max: f64) -> Triangular {
+        unwrap_new(Triangular::try_new(min, mode, max), "Triangular::new")
+    }
+
+    /// Construct a new `Triangular` with the given `min`, `mode` and `max`,
+    /// returning an error if `min <= mode <= max` does not hold, or if `min`
+    /// or `max` is not finite.
+    pub fn try_new(min: f64, mode: f64, max: f64) -> Result<Triangular, DistributionError> {
+        check_finite(min, "min")?;
+        check_finite(max, "max")?;
+        if min > max {
+            return Err(DistributionError::EmptyRange);
+        }
+        if !(min <= mode && mode <= max) {
+            return Err(DistributionError::OutOfRange("mode"));
+        }
+        let range = max - min;
+        let mode_cdf = if range > 0.0 { (mode - min) / range } else { 1.0 };
+        Ok(Triangular {
+            min,
+            max,
+            mode_cdf,
+            left: range * (mode - min),
+            right: range * (max - mode),
+        })
+    }
+}
+
+impl Distribution<f64> for Triangular {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let u: f64 = rng.sample(Standard);
+        if u < self.mode_cdf {
+            self.min + (u * self.left).sqrt()
+        } else {
+            self.max - ((1.0 - u) * self.right).sqrt()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::Distribution;
+    use super::Triangular;
+    use distributions::DistributionError::*;
+
+    #[test]
+    fn test_triangular() {
+        let mut rng = ::test::rng(401);
+        for &(min, mode, max) in [(0.0, 0.25, 1.0), (-10.0, -10.0, 5.0),
+                                  (1.0, 3.0, 3.0), (2.0, 7.0, 100.0)].iter() {
+            let d = Triangular::new(min, mode, max);
+            const N: usize = 100_000;
+            const BINS: usize = 20;
+            let mut hist = [0usize; BINS];
+            let mut sum = 0.0;
+            for _ in 0..N {
+                let x = d.sample(&mut rng);
+                assert!(min <= x && x <= max);
+                sum += x;
+                let bin = ((x - min) / (max - min) * BINS as f64) as usize;
+                hist[if bin < BINS { bin } else { BINS - 1 }] += 1;
+            }
+            let mean = sum / N as f64;
+            let expected = (min + mode + max) / 3.0;
+            assert!((mean - expected).abs() < 0.01 * (max - min));
+
+            // The most populated bin must be the one containing the mode, or
+            // a neighbouring one.
+            let mode_bin = (mode - min) / (max - min) * BINS as f64;
+            let max_bin = (0..BINS).max_by_key(|&i| hist[i]).unwrap();
+            assert!((max_bin as f64 + 0.5 - mode_bin).abs() <= 1.5,
+                    "mode bin {} != {}", max_bin, mode_bin);
+        }
+    }
+
+    #[test]
+    fn test_triangular_degenerate() {
+        let mut rng = ::test::rng(402);
+        let d = Triangular::new(3.5, 3.5, 3.5);
+        for _ in 0..100 {
+            assert_eq!(d.sample(&mut rng), 3.5);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_triangular_invalid() {
+        Triangular::new(0.0, 2.0, 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_triangular_nan() {
+        Triangular::new(0.0, ::std::f64::NAN, 1.0);
+    }
+
+
+    #[test]
+    fn test_triangular_try_new() {
+        assert!(Triangular::try_new(1.0, 1.0, 1.0).is_ok());
+        assert_eq!(Triangular::try_new(0.0, 2.0, 1.0).err(), Some(OutOfRange("mode")));
+        assert_eq!(Triangular::try_new(0.0, ::std::f64::NAN, 1.0).err(), Some(OutOfRange("mode")));
+        assert_eq!(Triangular::try_new(1.0, 1.0, 0.0).err(), Some(EmptyRange));
+        assert_eq!(Triangular::try_new(-::std::f64::INFINITY, 0.0, 1.0).err(), Some(NonFinite("min")));
+        assert_eq!(Triangular::try_new(0.0, 0.0, ::std::f64::NAN).err(), Some(NonFinite("max")));
+    }
+}
@@ -0,0 +1,367 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The truncated normal distribution.
+
+use Rng;
+use distributions::{Distribution, StandardNormal, Exp1};
+use distributions::error::{DistributionError, unwrap_new, check_finite, check_positive};
+
+/// The normal distribution `N(mean, std_dev**2)` truncated to the interval
+/// `[low, high]`.
+///
+/// The bounds may be infinite, so `TruncatedNormal::new(0.0, 1.0, 2.0,
+/// f64::INFINITY)` is a standard normal distribution conditioned on being at
+/// least 2.
+///
+/// Samples are generated with one of several rejection methods after Robert,
+/// depending on the position of the interval relative to the mean. Intervals
+/// containing the mean use rejection from the normal distribution, or from
+/// the uniform distribution if they are narrow. Intervals in the tail use
+/// rejection from an exponential distribution shifted to the near bound, or
+/// from the uniform distribution if they are narrow. The expected number of
+/// iterations is bounded by a small constant for all intervals, including
+/// those far in the tails.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{TruncatedNormal, Distribution};
+///
+/// // mean 2, standard deviation 3, restricted to [0, 10]
+/// let truncated = TruncatedNormal::new(2.0, 3.0, 0.0, 10.0);
+/// let v = truncated.sample(&mut rand::thread_rng());
+/// println!("{} is from a truncated N(2, 9) distribution", v);
+///
+/// // far in the upper tail
+/// let tail = TruncatedNormal::new(0.0, 1.0, 5.0, 6.0);
+/// let v = tail.sample(&mut rand::thread_rng());
+/// assert!(v >= 5.0 && v <= 6.0);
+/// ```
+///
+/// # References
+///
+/// C. P. Robert, *Simulation of truncated normal variables*, Statistics and
+/// Computing 5(2), 1995, 121-125.
+#[derive(Clone, Copy, Debug)]
+pub struct TruncatedNormal {
+    mean: f64,
+    std_dev: f64,
+    low: f64,
+    high: f64,
+    /// Whether the standardized interval was mirrored to lie in the upper
+    /// tail.
+    flip: bool,
+    repr: TruncatedNormalRepr,
+}
+
+/// The method used to sample `z` from the standard normal distribution
+/// truncated to `[a, b]`.
+#[derive(Clone, Copy, Debug)]
+enum TruncatedNormalRepr {
+    /// Sample from the standard normal distribution until the sample is
+    /// within the interval.
+    Normal { a: f64, b: f64 },
+    /// Sample `z` uniformly from the interval, and accept it with probability
+    /// `exp((center^2 - z^2) / 2)`, where `center` is the point of the
+    /// interval closest to 0.
+    Uniform { a: f64, b: f64, center: f64 },
+    /// Sample `z = a + e / lambda` for a standard exponential sample `e`, and
+    /// accept it with probability `exp(-(z - lambda)^2 / 2)`. `shift` is
+    /// `lambda - a`.
+    Exponential { a: f64, b: f64, lambda: f64, shift: f64 },
+    /// The interval is so far in the tail that all mass is at its near
+    /// bound, in floating point arithmetic.
+    Bound,
+}
+
+impl TruncatedNormal {
+    /// Construct a new `TruncatedNormal` distribution with the given `mean`
+    /// and `std_dev`, truncated to `[low, high]`. The bounds may be
+    /// infinite.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `std_dev <= 0`, `low >= high` (or either is NaN), or if
+    /// `mean` or `std_dev` is not finite.
+    pub fn new(mean: f64, std_dev: f64, low: f64, high: f64) -> TruncatedNormal {
+        unwrap_new(TruncatedNormal::try_new(mean, std_dev, low, high),
+                   "TruncatedNormal::new")
+    }
+
+    /// Construct a new `TruncatedNormal` distribution with the given `mean`
+    /// and `std_dev`, truncated to `[low, high]`, returning an error if
+    /// `std_dev <= 0`, `low >= high` (or either is NaN), or if `mean` or
+    /// `std_dev` is not finite.
+    pub fn try_new(mean: f64, std_dev: f64, low: f64, high: f64)
+        -> Result<TruncatedNormal, DistributionError>
+    {
+        check_finite(mean, "mean")?;
+        check_positive(std_dev, "std_dev")?;
+        if low.is_nan() || high.is_nan() || low >= high {
+            return Err(DistributionError::EmptyRange);
+        }
+
+        let alpha = (low - mean) / std_dev;
+        let beta = (high - mean) / std_dev;
+        let (flip, repr) = if alpha > 0.0 {
+            (false, TruncatedNormalRepr::tail(alpha, beta))
+        } else if beta < 0.0 {
+            (true, TruncatedNormalRepr::tail(-beta, -alpha))
+        } else if beta - alpha < (2.0 * ::std::f64::consts::PI).sqrt() {
+            (false, TruncatedNormalRepr::Uniform { a: alpha, b: beta, center: 0.0 })
+        } else {
+            (false, TruncatedNormalRepr::Normal { a: alpha, b: beta })
+        };
+        Ok(TruncatedNormal { mean, std_dev, low, high, flip, repr })
+    }
+}
+
+impl TruncatedNormalRepr {
+    /// Choose the method for an interval `[a, b]` with `0 < a < b`.
+    fn tail(a: f64, b: f64) -> TruncatedNormalRepr {
+        if a.is_infinite() {
+            return TruncatedNormalRepr::Bound;
+        }
+        // `hypot` avoids overflow of `a^2 + 4` for large `a`.
+        let h = a.hypot(2.0);
+        // The optimal rate of the exponential distribution, and the
+        // difference `lambda - a` computed without cancellation.
+        let lambda = (a + h) / 2.0;
+        let shift = 2.0 / (a + h);
+        // Uniform rejection is more efficient for intervals narrower than
+        // `2 * sqrt(e) / (a + h) * exp((a^2 - a * h) / 4)`.
+        let threshold = 2.0 * 0.5f64.exp() / (a + h) * (-a / (a + h)).exp();
+        if b - a < threshold {
+            TruncatedNormalRepr::Uniform { a, b, center: a }
+        } else {
+            TruncatedNormalRepr::Exponential { a, b, lambda, shift }
+        }
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        match *self {
+            TruncatedNormalRepr::Normal { a, b } => {
+                loop {
+                    let z: f64 = rng.sample(StandardNormal);
+                    if z >= a && z <= b {
+                        return z;
+                    }
+                }
+            }
+            TruncatedNormalRepr::Uniform { a, b, center } => {
+                loop {
+                    let u: f64 = rng.gen();
+                    let z = a + (b - a) * u;
+                    let v: f64 = rng.gen();
+                    if v < (-(z - center) * (z + center) / 2.0).exp() {
+                        return z;
+                    }
+                }
+            }
+            TruncatedNormalRepr::Exponential { a, b, lambda, shift } => {
+                loop {
+                    let e: f64 = rng.sample(Exp1);
+                    let t = e / lambda;
+                    if t > b - a {
+                        continue;
+                    }
+                    let d = t - shift;
+                    let v: f64 = rng.gen();
+                    if v < (-d * d / 2.0).exp() {
+                        return a + t;
+                    }
+                }
+            }
+            TruncatedNormalRepr::Bound => unreachable!(),
+        }
+    }
+}
+
+impl Distribution<f64> for TruncatedNormal {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        if let TruncatedNormalRepr::Bound = self.repr {
+            return if self.flip { self.high } else { self.low };
+        }
+        let z = self.repr.sample(rng);
+        let x = if self.flip {
+            self.mean - self.std_dev * z
+        } else {
+            self.mean + self.std_dev * z
+        };
+        // Rounding may put `x` just outside the interval.
+        if x < self.low {
+            self.low
+        } else if x > self.high {
+            self.high
+        } else {
+            x
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {Rng, RngCore, Error};
+    use distributions::Distribution;
+    use std::f64::INFINITY;
+    use super::TruncatedNormal;
+    use distributions::DistributionError::*;
+
+    // The mean of the truncated normal distribution, by integrating the
+    // density numerically.
+    fn truncated_mean(mean: f64, std_dev: f64, low: f64, high: f64) -> f64 {
+        let (mut a, mut b) = ((low - mean) / std_dev, (high - mean) / std_dev);
+        // the density is negligible beyond `40 / |c|` from the point `c`
+        // closest to 0
+        let c = if a > 0.0 { a } else if b < 0.0 { b } else { 0.0 };
+        let width = 40.0 / c.abs().max(1.0);
+        a = a.max(c - width);
+        b = b.min(c + width);
+        // Simpson's rule, with the density scaled to 1 at `c`
+        let n = 100_000;
+        let h = (b - a) / n as f64;
+        let (mut mass, mut moment) = (0.0, 0.0);
+        for i in 0..(n + 1) {
+            let z = a + h * i as f64;
+            let w = if i == 0 || i == n { 1.0 } else if i % 2 == 1 { 4.0 } else { 2.0 };
+            let density = (-(z - c) * (z + c) / 2.0).exp();
+            mass += w * density;
+            moment += w * density * z;
+        }
+        mean + std_dev * moment / mass
+    }
+
+    #[test]
+    fn test_truncated_normal_mean() {
+        let mut rng = ::test::rng(481);
+        for &(mean, std_dev, low, high) in
+            [(0.0, 1.0, -1.0, 1.0),         // narrow central interval
+             (2.0, 3.0, 0.0, 10.0),         // wide central interval
+             (0.0, 1.0, -INFINITY, 0.5),    // one-sided
+             (0.0, 1.0, 5.0, 6.0),          // deep tail
+             (1.0, 2.0, -INFINITY, -9.0),   // deep lower tail
+             (0.0, 1.0, 10.0, 10.01),       // narrow deep tail
+             (-5.0, 0.1, 0.0, INFINITY),    // very deep tail
+             (0.0, 1.0, 0.5, 1.5)].iter()   // moderate tail
+        {
+            let truncated = TruncatedNormal::new(mean, std_dev, low, high);
+            const N: usize = 100_000;
+            let samples: Vec<f64> = (0..N).map(|_| truncated.sample(&mut rng)).collect();
+            assert!(samples.iter().all(|&x| x >= low && x <= high));
+
+            let expected = truncated_mean(mean, std_dev, low, high);
+            let sample_mean = samples.iter().sum::<f64>() / N as f64;
+            let var = samples.iter().map(|x| (x - sample_mean).powi(2))
+                .sum::<f64>() / N as f64;
+            let err = 5.0 * (var / N as f64).sqrt() + 1e-12 * expected.abs();
+            assert!((sample_mean - expected).abs() < err,
+                    "N({}, {}) on [{}, {}]: mean {} != {}",
+                    mean, std_dev, low, high, sample_mean, expected);
+        }
+    }
+
+    // Counts the number of random values drawn.
+    struct CountingRng<R> {
+        rng: R,
+        count: usize,
+    }
+
+    impl<R: RngCore> RngCore for CountingRng<R> {
+        fn next_u32(&mut self) -> u32 {
+            self.count += 1;
+            self.rng.next_u32()
+        }
+        fn next_u64(&mut self) -> u64 {
+            self.count += 1;
+            self.rng.next_u64()
+        }
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            self.count += 1;
+            self.rng.fill_bytes(dest)
+        }
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+            self.count += 1;
+            self.rng.try_fill_bytes(dest)
+        }
+    }
+
+    #[test]
+    fn test_truncated_normal_iterations() {
+        // Sampling from intervals far in the tail needs only a few random
+        // values on average, unlike naive rejection.
+        let mut rng = CountingRng { rng: ::test::rng(482), count: 0 };
+        for &(low, high) in [(5.0, 6.0), (-6.0, -5.0), (20.0, INFINITY),
+                             (40.0, 40.001), (-1e6, -1e6 + 1.0), (1e200, 1e201),
+                             (-1e-3, 1e-3), (-0.1, INFINITY)].iter() {
+            let truncated = TruncatedNormal::new(0.0, 1.0, low, high);
+            rng.count = 0;
+            const N: usize = 10_000;
+            for _ in 0..N {
+                let x = truncated.sample(&mut rng);
+                assert!(x >= low && x <= high);
+            }
+            assert!(rng.count < 8 * N, "[{}, {}]: {} draws per sample",
+                    low, high, rng.count as f64 / N as f64);
+        }
+    }
+
+    #[test]
+    fn test_truncated_normal_bound() {
+        // The interval is `1e310` standard deviations away.
+        let truncated = TruncatedNormal::new(0.0, 1e-10, 1e300, 2e300);
+        assert_eq!(truncated.sample(&mut ::test::rng(483)), 1e300);
+        let truncated = TruncatedNormal::new(0.0, 1e-10, -INFINITY, -1e300);
+        assert_eq!(truncated.sample(&mut ::test::rng(483)), -1e300);
+        let x = ::test::rng(484).sample(TruncatedNormal::new(0.0, 1.0, 0.0, 1.0));
+        assert!(x >= 0.0 && x <= 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_truncated_normal_invalid_std_dev() {
+        TruncatedNormal::new(0.0, 0.0, -1.0, 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_truncated_normal_invalid_bounds() {
+        TruncatedNormal::new(0.0, 1.0, 1.0, 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_truncated_normal_nan_bound() {
+        TruncatedNormal::new(0.0, 1.0, ::std::f64::NAN, 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_truncated_normal_infinite_mean() {
+        TruncatedNormal::new(INFINITY, 1.0, 0.0, 1.0);
+    }
+
+
+    #[test]
+    fn test_truncated_normal_try_new() {
+        assert!(TruncatedNormal::try_new(0.0, 1.0, -INFINITY, INFINITY).is_ok());
+        assert_eq!(TruncatedNormal::try_new(0.0, 0.0, -1.0, 1.0).err(),
+                   Some(NonPositive("std_dev")));
+        assert_eq!(TruncatedNormal::try_new(0.0, INFINITY, -1.0, 1.0).err(),
+                   Some(NonFinite("std_dev")));
+        assert_eq!(TruncatedNormal::try_new(INFINITY, 1.0, 0.0, 1.0).err(),
+                   Some(NonFinite("mean")));
+        for &(low, high) in [(1.0, 1.0), (1.0, 0.0), (::std::f64::NAN, 1.0),
+                             (0.0, ::std::f64::NAN), (INFINITY, INFINITY)].iter() {
+            assert_eq!(TruncatedNormal::try_new(0.0, 1.0, low, high).err(),
+                       Some(EmptyRange));
+        }
+    }
+}
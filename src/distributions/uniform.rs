@@ -16,14 +16,19 @@
 //! [`Uniform`].
 //!
 //! This distribution is provided with support for several primitive types
-//! (all integer and floating-point types) as well as `std::time::Duration`,
-//! and supports extension to user-defined types via a type-specific *back-end*
-//! implementation.
+//! (all integer and floating-point types, `char` and `bool`) as well as
+//! wrapping integers (`Wrapping<T>`), `std::time::Duration` and the IP
+//! address types `std::net::Ipv4Addr` and `Ipv6Addr`, and supports extension
+//! to user-defined types via a type-specific *back-end* implementation.
 //!
-//! The types [`UniformInt`], [`UniformFloat`] and [`UniformDuration`] are the
-//! back-ends supporting sampling from primitive integer and floating-point
-//! ranges as well as from `std::time::Duration`; these types do not normally
-//! need to be used directly (unless implementing a derived back-end).
+//! The types [`UniformInt`], [`UniformFloat`], [`UniformChar`],
+//! [`UniformBool`] and [`UniformDuration`] are the back-ends supporting
+//! sampling from primitive integer, floating-point, `char` and `bool` ranges
+//! as well as from `std::time::Duration`; these types do not normally need to
+//! be used directly (unless implementing a derived back-end).
+//!
+//! [`UniformStep`] samples from a finite arithmetic sequence instead of a
+//! range, such as the points of a grid.
 //!
 //! # Example usage
 //!
@@ -89,19 +94,81 @@
 //! let x = uniform.sample(&mut thread_rng());
 //! ```
 //!
+//! A newtype around an integer can delegate to [`UniformInt`] in the same
+//! way, which keeps sampling unbiased. Implementing `sample_single` as well
+//! lets `Rng::gen_range` use the faster single-sample algorithm, and deriving
+//! `PartialOrd` is needed for `gen_range`:
+//!
+//! ```
+//! use rand::prelude::*;
+//! use rand::distributions::uniform::{Uniform, SampleUniform,
+//!         UniformSampler, UniformInt};
+//!
+//! /// An amount in cents.
+//! #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+//! struct Money(i64);
+//!
+//! #[derive(Clone, Copy, Debug)]
+//! struct UniformMoney(UniformInt<i64>);
+//!
+//! impl UniformSampler for UniformMoney {
+//!     type X = Money;
+//!     fn new(low: Money, high: Money) -> Self {
+//!         UniformMoney(UniformInt::<i64>::new(low.0, high.0))
+//!     }
+//!     fn new_inclusive(low: Money, high: Money) -> Self {
+//!         UniformMoney(UniformInt::<i64>::new_inclusive(low.0, high.0))
+//!     }
+//!     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Money {
+//!         Money(self.0.sample(rng))
+//!     }
+//!     fn sample_single<R: Rng + ?Sized>(low: Money, high: Money, rng: &mut R)
+//!         -> Money
+//!     {
+//!         Money(UniformInt::<i64>::sample_single(low.0, high.0, rng))
+//!     }
+//! }
+//!
+//! impl SampleUniform for Money {
+//!     type Sampler = UniformMoney;
+//! }
+//!
+//! let mut rng = thread_rng();
+//! let price = rng.gen_range(Money(100), Money(1000));
+//! assert!(Money(100) <= price && price < Money(1000));
+//! let discount = rng.sample(Uniform::new_inclusive(Money(0), Money(50)));
+//! assert!(discount <= Money(50));
+//! ```
+//!
+//! With the `derive` feature, this back-end can be derived instead for
+//! structs with a single field, with `#[derive(SampleUniform)]`; the
+//! generated back-end is named `UniformMoney` here.
+//!
 //! [`Uniform`]: struct.Uniform.html
 //! [`Rng::gen_range`]: ../../trait.Rng.html#method.gen_range
 //! [`SampleUniform`]: trait.SampleUniform.html
 //! [`UniformSampler`]: trait.UniformSampler.html
 //! [`UniformInt`]: struct.UniformInt.html
 //! [`UniformFloat`]: struct.UniformFloat.html
+//! [`UniformChar`]: struct.UniformChar.html
+//! [`UniformBool`]: struct.UniformBool.html
 //! [`UniformDuration`]: struct.UniformDuration.html
+//! [`UniformStep`]: struct.UniformStep.html
 
+use core::num::Wrapping;
 #[cfg(feature = "std")]
 use std::time::Duration;
+#[cfg(feature = "std")]
+use std::net::Ipv4Addr;
+#[cfg(all(feature = "std", rustc_1_26))]
+use std::net::Ipv6Addr;
+
+#[cfg(feature = "derive")]
+#[doc(inline)] pub use rand_derive::SampleUniform;
 
 use Rng;
-use distributions::Distribution;
+use distributions::{Distribution, DistributionError, Standard};
+use distributions::error::unwrap_new;
 use distributions::float::IntoFloat;
 
 /// Sample values uniformly between two bounds.
@@ -154,22 +221,64 @@ pub struct Uniform<X: SampleUniform> {
 
 impl<X: SampleUniform> Uniform<X> {
     /// Create a new `Uniform` instance which samples uniformly from the half
-    /// open range `[low, high)` (excluding `high`). Panics if `low >= high`.
+    /// open range `[low, high)` (excluding `high`). Panics if `low >= high`,
+    /// except for floating-point types, where `low == high` always samples
+    /// `low`.
     pub fn new(low: X, high: X) -> Uniform<X> {
         Uniform { inner: X::Sampler::new(low, high) }
     }
 
     /// Create a new `Uniform` instance which samples uniformly from the closed
     /// range `[low, high]` (inclusive). Panics if `low > high`.
+    ///
+    /// For floating-point types `high` can be sampled, with the same
+    /// probability as any other value the sampler produces; see
+    /// [`UniformFloat`] for how the values are constructed.
+    ///
+    /// [`UniformFloat`]: struct.UniformFloat.html
     pub fn new_inclusive(low: X, high: X) -> Uniform<X> {
         Uniform { inner: X::Sampler::new_inclusive(low, high) }
     }
+
+    /// Create a new `Uniform` instance which samples uniformly from the half
+    /// open range `[low, high)` (excluding `high`), returning an error if
+    /// `low >= high`.
+    pub fn try_new(low: X, high: X) -> Result<Uniform<X>, DistributionError>
+        where X: PartialOrd
+    {
+        X::Sampler::try_new(low, high).map(|inner| Uniform { inner })
+    }
+
+    /// Create a new `Uniform` instance which samples uniformly from the closed
+    /// range `[low, high]` (inclusive), returning an error if `low > high`.
+    pub fn try_new_inclusive(low: X, high: X) -> Result<Uniform<X>, DistributionError>
+        where X: PartialOrd
+    {
+        X::Sampler::try_new_inclusive(low, high).map(|inner| Uniform { inner })
+    }
+
+    /// Sample a single value uniformly from the half open range `[low, high)`
+    /// (excluding `high`). Panics if `low >= high`, except for floating-point
+    /// types, where `low == high` returns `low`.
+    ///
+    /// This skips the set-up work of `Uniform::new`, which only pays off when
+    /// sampling multiple values from the same range. [`Rng::gen_range`] uses
+    /// this method.
+    ///
+    /// [`Rng::gen_range`]: ../../trait.Rng.html#method.gen_range
+    pub fn sample_single<R: Rng + ?Sized>(low: X, high: X, rng: &mut R) -> X {
+        X::Sampler::sample_single(low, high, rng)
+    }
 }
 
 impl<X: SampleUniform> Distribution<X> for Uniform<X> {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> X {
         self.inner.sample(rng)
     }
+
+    fn sample_fill<R: Rng + ?Sized>(&self, rng: &mut R, out: &mut [X]) {
+        self.inner.sample_fill(rng, out)
+    }
 }
 
 /// Helper trait for creating objects using the correct implementation of
@@ -215,9 +324,50 @@ pub trait UniformSampler: Sized {
     /// calling this.
     fn new_inclusive(low: Self::X, high: Self::X) -> Self;
 
+    /// Construct self, with inclusive lower bound and exclusive upper bound
+    /// `[low, high)`, returning an error if the range is invalid.
+    ///
+    /// The default implementation returns `DistributionError::EmptyRange` if
+    /// `low >= high`, and calls `UniformSampler::new` otherwise.
+    fn try_new(low: Self::X, high: Self::X) -> Result<Self, DistributionError>
+        where Self::X: PartialOrd
+    {
+        if low < high {
+            Ok(UniformSampler::new(low, high))
+        } else {
+            Err(DistributionError::EmptyRange)
+        }
+    }
+
+    /// Construct self, with inclusive bounds `[low, high]`, returning an
+    /// error if the range is invalid.
+    ///
+    /// The default implementation returns `DistributionError::EmptyRange` if
+    /// `low > high`, and calls `UniformSampler::new_inclusive` otherwise.
+    fn try_new_inclusive(low: Self::X, high: Self::X) -> Result<Self, DistributionError>
+        where Self::X: PartialOrd
+    {
+        if low <= high {
+            Ok(UniformSampler::new_inclusive(low, high))
+        } else {
+            Err(DistributionError::EmptyRange)
+        }
+    }
+
     /// Sample a value.
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X;
 
+    /// Fill `out` with sampled values.
+    ///
+    /// Usually users should not call this directly but instead use
+    /// `Distribution::sample_fill` on a `Uniform`. The default implementation
+    /// calls `sample` for each element in turn.
+    fn sample_fill<R: Rng + ?Sized>(&self, rng: &mut R, out: &mut [Self::X]) {
+        for x in out.iter_mut() {
+            *x = self.sample(rng);
+        }
+    }
+
     /// Sample a single value uniformly from a range with inclusive lower bound
     /// and exclusive upper bound `[low, high)`.
     ///
@@ -235,6 +385,30 @@ pub trait UniformSampler: Sized {
         let uniform: Self = UniformSampler::new(low, high);
         uniform.sample(rng)
     }
+
+    /// Sample a single value uniformly from a range with inclusive lower bound
+    /// and exclusive upper bound `[low, high)`, except for the value
+    /// `exclude`.
+    ///
+    /// Usually users should not call this directly but instead use
+    /// `Rng::gen_range_excluding`. If `exclude` is not in the range, this
+    /// samples from the whole range.
+    ///
+    /// The default implementation calls `sample_single` until a value other
+    /// than `exclude` is found. It never returns if `exclude` is the only
+    /// value in the range, so implementations for discrete types should
+    /// override it to panic in that case instead.
+    fn sample_single_excluding<R: Rng + ?Sized>(low: Self::X, high: Self::X,
+                                                exclude: Self::X, rng: &mut R)
+        -> Self::X where Self::X: PartialOrd + Clone
+    {
+        loop {
+            let x = Self::sample_single(low.clone(), high.clone(), rng);
+            if x != exclude {
+                return x;
+            }
+        }
+    }
 }
 
 impl<X: SampleUniform> From<::core::ops::Range<X>> for Uniform<X> {
@@ -243,6 +417,26 @@ impl<X: SampleUniform> From<::core::ops::Range<X>> for Uniform<X> {
     }
 }
 
+/// Helper trait similar to [`Borrow`] but implemented
+/// only for SampleUniform and references to SampleUniform in
+/// order to resolve ambiguity issues.
+///
+/// [`Borrow`]: https://doc.rust-lang.org/std/borrow/trait.Borrow.html
+pub trait SampleBorrow<Borrowed> {
+    /// Immutably borrows from an owned value. See [`Borrow::borrow`]
+    ///
+    /// [`Borrow::borrow`]: https://doc.rust-lang.org/std/borrow/trait.Borrow.html#tymethod.borrow
+    fn borrow(&self) -> &Borrowed;
+}
+impl<Borrowed> SampleBorrow<Borrowed> for Borrowed where Borrowed: SampleUniform {
+    #[inline(always)]
+    fn borrow(&self) -> &Borrowed { self }
+}
+impl<'a, Borrowed> SampleBorrow<Borrowed> for &'a Borrowed where Borrowed: SampleUniform {
+    #[inline(always)]
+    fn borrow(&self) -> &Borrowed { self }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 
 // What follows are all back-ends.
@@ -276,27 +470,42 @@ impl<X: SampleUniform> From<::core::ops::Range<X>> for Uniform<X> {
 /// The smallest integer PRNGs generate is `u32`. That is why for small integer
 /// sizes (`i8`/`u8` and `i16`/`u16`) there is an optimization: don't pick the
 /// largest zone that can fit in the small type, but pick the largest zone that
-/// can fit in an `u32`. `ints_to_reject` is always less than half the size of
-/// the small integer. This means the first bit of `zone` is always 1, and so
-/// are all the other preceding bits of a larger integer. The easiest way to
-/// grow the `zone` for the larger type is to simply sign extend it.
+/// can fit in an `u32`. `ints_to_reject` is then calculated with `u32`
+/// arithmetic; it is less than `range`, so it always fits in the small type.
+/// We therefore store `ints_to_reject` rather than `zone`, and calculate
+/// `zone = u32::MAX - ints_to_reject` when sampling.
 ///
 /// An alternative to using a modulus is widening multiply: After a widening
 /// multiply by `range`, the result is in the high word. Then comparing the low
 /// word against `zone` makes sure our distribution is uniform.
 ///
+/// `usize` and `isize` use `u64` arithmetic and random values on all
+/// platforms, so that their results are the same on 32-bit and 64-bit
+/// platforms. The exception is a range covering all values of the type,
+/// which is sampled with `Standard`.
+///
+/// For `u128` and `i128` each random integer is composed of two `u64` values,
+/// the first one being the low word, and the widening multiply is emulated
+/// with four 64-bit multiplications.
+///
+/// `sample_fill` generates the random integers in chunks with `Rng::fill`.
+/// With the block RNGs of this crate, which produce the same values with
+/// `fill_bytes` as with `next_u32` and `next_u64`, the result is identical to
+/// calling `sample` for each element. For a range covering all values of the
+/// type the slice is filled with `Standard`, whose results differ for the
+/// 8 and 16-bit types.
+///
 /// [`UniformSampler`]: trait.UniformSampler.html
 /// [`Uniform`]: struct.Uniform.html
 #[derive(Clone, Copy, Debug)]
 pub struct UniformInt<X> {
     low: X,
     range: X,
-    zone: X,
+    ints_to_reject: X,
 }
 
 macro_rules! uniform_int_impl {
-    ($ty:ty, $signed:ty, $unsigned:ident,
-     $i_large:ident, $u_large:ident) => {
+    ($ty:ty, $unsigned:ident, $u_large:ident) => {
         impl SampleUniform for $ty {
             type Sampler = UniformInt<$ty>;
         }
@@ -321,34 +530,35 @@ macro_rules! uniform_int_impl {
             fn new_inclusive(low: Self::X, high: Self::X) -> Self {
                 assert!(low <= high,
                         "Uniform::new_inclusive called with `low > high`");
-                let unsigned_max = ::core::$unsigned::MAX;
+                let unsigned_max = ::core::$u_large::MAX;
 
-                let range = high.wrapping_sub(low).wrapping_add(1) as $unsigned;
+                // The difference is taken in the unsigned type, where it is
+                // the size of the range even if `high - low` overflows a
+                // signed `$ty`. The full range of the type wraps around to 0.
+                let range = (high as $unsigned).wrapping_sub(low as $unsigned)
+                    .wrapping_add(1);
                 let ints_to_reject =
                     if range > 0 {
+                        let range = range as $u_large;
                         (unsigned_max - range + 1) % range
                     } else {
                         0
                     };
-                let zone = unsigned_max - ints_to_reject;
 
                 UniformInt {
                     low: low,
                     // These are really $unsigned values, but store as $ty:
                     range: range as $ty,
-                    zone: zone as $ty
+                    ints_to_reject: ints_to_reject as $unsigned as $ty
                 }
             }
 
             fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
                 let range = self.range as $unsigned as $u_large;
                 if range > 0 {
-                    // Grow `zone` to fit a type of at least 32 bits, by
-                    // sign-extending it (the first bit is always 1, so are all
-                    // the preceding bits of the larger type).
-                    // For types that already have the right size, all the
-                    // casting is a no-op.
-                    let zone = self.zone as $signed as $i_large as $u_large;
+                    let unsigned_max = ::core::$u_large::MAX;
+                    let zone = unsigned_max -
+                        (self.ints_to_reject as $unsigned as $u_large);
                     loop {
                         let v: $u_large = rng.gen();
                         let (hi, lo) = v.wmul(range);
@@ -357,57 +567,112 @@ macro_rules! uniform_int_impl {
                         }
                     }
                 } else {
-                    // Sample from the entire integer range.
+                    // Sample from the entire integer range; every value of
+                    // the underlying random integer maps to one result, so
+                    // no rejection is needed.
                     rng.gen()
                 }
             }
 
+            fn sample_fill<R: Rng + ?Sized>(&self, rng: &mut R, out: &mut [Self::X]) {
+                let range = self.range as $unsigned as $u_large;
+                if range == 0 {
+                    return Standard.sample_fill(rng, out);
+                }
+                let unsigned_max = ::core::$u_large::MAX;
+                let zone = unsigned_max -
+                    (self.ints_to_reject as $unsigned as $u_large);
+                // Generate the random integers in chunks with `Rng::fill`.
+                // A chunk is never larger than the number of values still
+                // needed, so exactly as many integers are used as by calling
+                // `sample` for each element.
+                let mut buf = [0 as $u_large; 32];
+                let (mut pos, mut len) = (0, 0);
+                let out_len = out.len();
+                for (i, x) in out.iter_mut().enumerate() {
+                    loop {
+                        if pos == len {
+                            len = ::core::cmp::min(buf.len(), out_len - i);
+                            rng.fill(&mut buf[..len]);
+                            pos = 0;
+                        }
+                        let (hi, lo) = buf[pos].wmul(range);
+                        pos += 1;
+                        if lo <= zone {
+                            *x = self.low.wrapping_add(hi as $ty);
+                            break;
+                        }
+                    }
+                }
+            }
+
             fn sample_single<R: Rng + ?Sized>(low: Self::X,
                                               high: Self::X,
                                               rng: &mut R) -> Self::X
             {
                 assert!(low < high,
                         "Uniform::sample_single called with low >= high");
-                let range = high.wrapping_sub(low) as $unsigned as $u_large;
-                let zone =
-                    if ::core::$unsigned::MAX <= ::core::u16::MAX as $unsigned {
-                        // Using a modulus is faster than the approximation for
-                        // i8 and i16. I suppose we trade the cost of one
-                        // modulus for near-perfect branch prediction.
-                        let unsigned_max: $u_large = ::core::$u_large::MAX;
-                        let ints_to_reject = (unsigned_max - range + 1) % range;
-                        unsigned_max - ints_to_reject
-                    } else {
-                        // conservative but fast approximation
-                       range << range.leading_zeros()
-                    };
-
-                loop {
-                    let v: $u_large = rng.gen();
-                    let (hi, lo) = v.wmul(range);
-                    if lo <= zone {
-                        return low.wrapping_add(hi as $ty);
+                // As in `new_inclusive`, subtract in the unsigned type.
+                let range = (high as $unsigned).wrapping_sub(low as $unsigned)
+                    as $u_large;
+                // Lemire's method: reject the `2^N % range` smallest values of
+                // the low word of the widening multiply. This number is less
+                // than `range`, and less than `2^N - (range << lz)` with `lz`
+                // the leading zeros of `range`. The modulus is only
+                // calculated for the rare values below both bounds.
+                let shifted = range << range.leading_zeros();
+                let bound = ::core::cmp::min(range, shifted.wrapping_neg());
+                let v: $u_large = rng.gen();
+                let (mut hi, mut lo) = v.wmul(range);
+                if lo < bound {
+                    let ints_to_reject = range.wrapping_neg() % range;
+                    while lo < ints_to_reject {
+                        let v: $u_large = rng.gen();
+                        let (new_hi, new_lo) = v.wmul(range);
+                        hi = new_hi;
+                        lo = new_lo;
                     }
                 }
+                low.wrapping_add(hi as $ty)
+            }
+
+            fn sample_single_excluding<R: Rng + ?Sized>(low: Self::X,
+                                                        high: Self::X,
+                                                        exclude: Self::X,
+                                                        rng: &mut R)
+                -> Self::X
+            {
+                assert!(low < high,
+                        "Rng::gen_range_excluding called with low >= high");
+                if exclude < low || exclude >= high {
+                    return Self::sample_single(low, high, rng);
+                }
+                assert!(low < high - 1,
+                        "Rng::gen_range_excluding called with `exclude` the \
+                         only value in the range");
+                // Sample from a range with one value less, and skip over
+                // `exclude`.
+                let x = Self::sample_single(low, high - 1, rng);
+                if x >= exclude { x + 1 } else { x }
             }
         }
     }
 }
 
-uniform_int_impl! { i8, i8, u8, i32, u32 }
-uniform_int_impl! { i16, i16, u16, i32, u32 }
-uniform_int_impl! { i32, i32, u32, i32, u32 }
-uniform_int_impl! { i64, i64, u64, i64, u64 }
-#[cfg(feature = "i128_support")]
-uniform_int_impl! { i128, i128, u128, u128, u128 }
-uniform_int_impl! { isize, isize, usize, isize, usize }
-uniform_int_impl! { u8, i8, u8, i32, u32 }
-uniform_int_impl! { u16, i16, u16, i32, u32 }
-uniform_int_impl! { u32, i32, u32, i32, u32 }
-uniform_int_impl! { u64, i64, u64, i64, u64 }
-uniform_int_impl! { usize, isize, usize, isize, usize }
-#[cfg(feature = "i128_support")]
-uniform_int_impl! { u128, u128, u128, i128, u128 }
+uniform_int_impl! { i8, u8, u32 }
+uniform_int_impl! { i16, u16, u32 }
+uniform_int_impl! { i32, u32, u32 }
+uniform_int_impl! { i64, u64, u64 }
+#[cfg(any(feature = "i128_support", rustc_1_26))]
+uniform_int_impl! { i128, u128, u128 }
+uniform_int_impl! { isize, usize, u64 }
+uniform_int_impl! { u8, u8, u32 }
+uniform_int_impl! { u16, u16, u32 }
+uniform_int_impl! { u32, u32, u32 }
+uniform_int_impl! { u64, u64, u64 }
+uniform_int_impl! { usize, usize, u64 }
+#[cfg(any(feature = "i128_support", rustc_1_26))]
+uniform_int_impl! { u128, u128, u128 }
 
 
 trait WideningMultiply<RHS = Self> {
@@ -432,7 +697,7 @@ macro_rules! wmul_impl {
 wmul_impl! { u8, u16, 8 }
 wmul_impl! { u16, u32, 16 }
 wmul_impl! { u32, u64, 32 }
-#[cfg(feature = "i128_support")]
+#[cfg(any(feature = "i128_support", rustc_1_26))]
 wmul_impl! { u64, u128, 64 }
 
 // This code is a translation of the __mulddi3 function in LLVM's
@@ -467,9 +732,9 @@ macro_rules! wmul_impl_large {
         }
     }
 }
-#[cfg(not(feature = "i128_support"))]
+#[cfg(not(any(feature = "i128_support", rustc_1_26)))]
 wmul_impl_large! { u64, 32 }
-#[cfg(feature = "i128_support")]
+#[cfg(any(feature = "i128_support", rustc_1_26))]
 wmul_impl_large! { u128, 64 }
 
 macro_rules! wmul_impl_usize {
@@ -490,6 +755,46 @@ wmul_impl_usize! { u32 }
 #[cfg(target_pointer_width = "64")]
 wmul_impl_usize! { u64 }
 
+// Sample an integer in `[0, bound)` for `Rng::gen_index`.
+//
+// This computes `floor(v * bound / 2^(2n))` for a random `2n`-bit value `v`,
+// drawing the second half of `v` only when it can change the result. The
+// result `hi` of the first multiplication can only be incremented by a carry
+// from the second if `lo > MAX - bound`, which happens with probability
+// `bound / 2^n`. The result is not exactly uniform, but each value has a
+// probability within `2^-(2n)` of `1 / bound`.
+macro_rules! sample_below_impl {
+    ($fnn:ident, $ty:ident) => {
+        #[inline]
+        pub(crate) fn $fnn<R: Rng + ?Sized>(rng: &mut R, bound: $ty) -> $ty {
+            let (hi, lo) = rng.gen::<$ty>().wmul(bound);
+            if lo > ::core::$ty::MAX - bound {
+                let (hi2, _) = rng.gen::<$ty>().wmul(bound);
+                let (_, carry) = lo.overflowing_add(hi2);
+                return hi + carry as $ty;
+            }
+            hi
+        }
+    }
+}
+sample_below_impl! { sample_below_u32, u32 }
+sample_below_impl! { sample_below_u64, u64 }
+
+// Sample an integer in `[0, bound)` as `gen_range(0, bound)` did in Rand
+// 0.5.0, for the deprecated slice methods of `Rng`. This rejects the low words
+// above a conservative approximation of the zone, and uses a random `usize`.
+pub(crate) fn sample_below_usize_0_5<R: Rng + ?Sized>(rng: &mut R, bound: usize)
+    -> usize
+{
+    let zone = bound << bound.leading_zeros();
+    loop {
+        let (hi, lo) = rng.gen::<usize>().wmul(bound);
+        if lo <= zone {
+            return hi;
+        }
+    }
+}
+
 
 
 /// The back-end implementing [`UniformSampler`] for floating-point types.
@@ -504,13 +809,36 @@ wmul_impl_usize! { u64 }
 /// way one or two steps can be optimized out.
 ///
 /// The floats are first converted to a value in the `[1, 2)` interval using a
-/// transmute-based method, and then mapped to the expected range with a
-/// multiply and addition. Values produced this way have what equals 22 bits of
-/// random digits for an `f32`, and 52 for an `f64`.
+/// transmute-based method, shifted to `[0, 1)`, and then mapped to the
+/// expected range with a multiply and addition. Values produced this way have
+/// what equals 23 bits of random digits for an `f32`, and 52 for an `f64`.
+///
+/// Rounding could make the result of the multiply and addition equal to
+/// `high`, or larger. To prevent this the scale is adjusted when the sampler
+/// is constructed, so that the largest random value maps to a value below
+/// `high` for [`new`] and not above `high` for [`new_inclusive`]. Since
+/// rounding is monotonic, all results are then within the range. With
+/// [`new_inclusive`], `high` itself is the result for the largest random value
+/// if the width of the range can be represented exactly.
+///
+/// Before rounding, the `2^23` (for `f32`) or `2^52` (for `f64`) possible
+/// results of [`new_inclusive`] are evenly spaced from `low` to `high`, both
+/// included, so `high` is exactly as likely as any other point of the grid.
+/// For example `Uniform::new_inclusive(0.0, 1023.0)` with `f32` samples each
+/// multiple of `1023 / (2^23 - 1)` with probability `2^-23`. The results of
+/// [`new`] are spaced by `(high - low) / 2^23` (or `2^-52`) instead, and stop
+/// one step before `high`.
 ///
-/// Currently there is no difference between [`new`] and [`new_inclusive`],
-/// because the boundaries of a floats range are a bit of a fuzzy concept due to
-/// rounding errors.
+/// If the width of the range does not fit in the float type, e.g. for
+/// `Uniform::new(::std::f64::MIN, ::std::f64::MAX)`, the range is halved
+/// before sampling and the result doubled afterwards; both steps are exact.
+///
+/// `sample_single` skips the set-up and instead rejects the few largest
+/// random values for which the result is rounded up to `high`.
+///
+/// Both bounds must be finite. An empty range `[low, low)` is allowed, and
+/// always samples `low`; this avoids a special case for callers computing
+/// ranges of possibly zero width.
 ///
 /// [`UniformSampler`]: trait.UniformSampler.html
 /// [`new`]: trait.UniformSampler.html#tymethod.new
@@ -519,72 +847,214 @@ wmul_impl_usize! { u64 }
 /// [`Standard`]: ../struct.Standard.html
 #[derive(Clone, Copy, Debug)]
 pub struct UniformFloat<X> {
+    low: X,
     scale: X,
-    offset: X,
+    factor: X,
 }
 
 macro_rules! uniform_float_impl {
-    ($ty:ty, $bits_to_discard:expr, $next_u:ident) => {
+    ($ty:ident, $uty:ident, $bits_to_discard:expr, $next_u:ident) => {
         impl SampleUniform for $ty {
             type Sampler = UniformFloat<$ty>;
         }
 
+        impl UniformFloat<$ty> {
+            /// The largest value in `[0, 1)` produced from random bits.
+            #[inline]
+            fn max_rand() -> $ty {
+                (::core::$uty::MAX >> $bits_to_discard)
+                    .into_float_with_exponent(0) - 1.0
+            }
+
+            /// Halve the range if its width overflows. Returns the new bounds,
+            /// the width and the factor to multiply results with.
+            #[inline]
+            fn split_range(low: $ty, high: $ty) -> ($ty, $ty, $ty, $ty) {
+                let width = high - low;
+                if width.is_finite() {
+                    (low, high, width, 1.0)
+                } else {
+                    // The width can only overflow if both bounds are far
+                    // from the subnormal range, so halving them is exact.
+                    let (low, high) = (low * 0.5, high * 0.5);
+                    (low, high, high - low, 2.0)
+                }
+            }
+
+            /// Find the largest scale, not larger than `scale`, for which the
+            /// largest random value maps to a value not above (or, if
+            /// `inclusive` is false, below) `high`.
+            fn adjust_scale(low: $ty, high: $ty, scale: $ty, inclusive: bool)
+                -> $ty
+            {
+                let max_rand = Self::max_rand();
+                let fits = |bits: $uty| {
+                    let max = max_rand * <$ty>::from_bits(bits) + low;
+                    max < high || (inclusive && max == high)
+                };
+                // For positive floats the order of the bit patterns is the
+                // order of the values. A scale of 0 always fits.
+                let mut too_large = scale.to_bits();
+                if fits(too_large) {
+                    return scale;
+                }
+                // Usually a few steps down are enough, but if `low` is much
+                // larger than the width it may take many: search with growing
+                // steps, then bisect.
+                let mut step = 1;
+                let mut fitting = 0;
+                while step < too_large {
+                    if fits(too_large - step) {
+                        fitting = too_large - step;
+                        break;
+                    }
+                    too_large -= step;
+                    step *= 2;
+                }
+                while too_large - fitting > 1 {
+                    let mid = fitting + (too_large - fitting) / 2;
+                    if fits(mid) {
+                        fitting = mid;
+                    } else {
+                        too_large = mid;
+                    }
+                }
+                <$ty>::from_bits(fitting)
+            }
+        }
+
         impl UniformSampler for UniformFloat<$ty> {
             type X = $ty;
 
             fn new(low: Self::X, high: Self::X) -> Self {
-                assert!(low < high, "Uniform::new called with `low >= high`");
-                let scale = high - low;
-                let offset = low - scale;
+                assert!(low.is_finite() && high.is_finite(),
+                        "Uniform::new called with non-finite boundaries");
+                assert!(low <= high, "Uniform::new called with `low > high`");
+                if low == high {
+                    return Self::new_inclusive(low, high);
+                }
+                let (low, high, width, factor) = Self::split_range(low, high);
                 UniformFloat {
-                    scale: scale,
-                    offset: offset,
+                    low,
+                    scale: Self::adjust_scale(low, high, width, false),
+                    factor,
                 }
             }
 
             fn new_inclusive(low: Self::X, high: Self::X) -> Self {
+                assert!(low.is_finite() && high.is_finite(),
+                        "Uniform::new_inclusive called with non-finite boundaries");
                 assert!(low <= high,
                         "Uniform::new_inclusive called with `low > high`");
-                let scale = high - low;
-                let offset = low - scale;
+                let (low, high, width, factor) = Self::split_range(low, high);
+                // Scale up so the largest random value maps to `high`.
+                let mut scale = width / Self::max_rand();
+                if !scale.is_finite() {
+                    scale = ::core::$ty::MAX;
+                }
                 UniformFloat {
-                    scale: scale,
-                    offset: offset,
+                    low,
+                    scale: Self::adjust_scale(low, high, scale, true),
+                    factor,
                 }
             }
 
+            fn try_new(low: Self::X, high: Self::X) -> Result<Self, DistributionError> {
+                if !low.is_finite() {
+                    return Err(DistributionError::NonFinite("low"));
+                }
+                if !high.is_finite() {
+                    return Err(DistributionError::NonFinite("high"));
+                }
+                if low > high {
+                    return Err(DistributionError::EmptyRange);
+                }
+                Ok(UniformSampler::new(low, high))
+            }
+
+            fn try_new_inclusive(low: Self::X, high: Self::X)
+                -> Result<Self, DistributionError>
+            {
+                if !low.is_finite() {
+                    return Err(DistributionError::NonFinite("low"));
+                }
+                if !high.is_finite() {
+                    return Err(DistributionError::NonFinite("high"));
+                }
+                if low > high {
+                    return Err(DistributionError::EmptyRange);
+                }
+                Ok(UniformSampler::new_inclusive(low, high))
+            }
+
             fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
                 // Generate a value in the range [1, 2)
                 let value1_2 = (rng.$next_u() >> $bits_to_discard)
                                .into_float_with_exponent(0);
+                // Shift to [0, 1), so the multiplication can not overflow.
+                let value0_1 = value1_2 - 1.0;
                 // We don't use `f64::mul_add`, because it is not available with
                 // `no_std`. Furthermore, it is slower for some targets (but
                 // faster for others). However, the order of multiplication and
                 // addition is important, because on some platforms (e.g. ARM)
                 // it will be optimized to a single (non-FMA) instruction.
-                value1_2 * self.scale + self.offset
+                (value0_1 * self.scale + self.low) * self.factor
             }
 
             fn sample_single<R: Rng + ?Sized>(low: Self::X,
                                               high: Self::X,
                                               rng: &mut R) -> Self::X {
-                assert!(low < high,
-                        "Uniform::sample_single called with low >= high");
+                assert!(low.is_finite() && high.is_finite(),
+                        "Uniform::sample_single called with non-finite boundaries");
+                assert!(low <= high,
+                        "Uniform::sample_single called with low > high");
+                if low == high {
+                    return low;
+                }
                 let scale = high - low;
-                let offset = low - scale;
-                // Generate a value in the range [1, 2)
-                let value1_2 = (rng.$next_u() >> $bits_to_discard)
-                               .into_float_with_exponent(0);
-                // Doing multiply before addition allows some architectures to
-                // use a single instruction.
-                value1_2 * scale + offset
+                if !scale.is_finite() {
+                    return Self::new(low, high).sample(rng);
+                }
+                loop {
+                    // Generate a value in the range [1, 2), and shift to
+                    // [0, 1)
+                    let value1_2 = (rng.$next_u() >> $bits_to_discard)
+                                   .into_float_with_exponent(0);
+                    let value0_1 = value1_2 - 1.0;
+                    // Doing multiply before addition allows some
+                    // architectures to use a single instruction.
+                    let res = value0_1 * scale + low;
+                    // Rounding can only make the result equal to `high` for
+                    // the largest few random values; rejecting those is
+                    // cheaper than adjusting the scale up front.
+                    if res < high {
+                        return res;
+                    }
+                }
+            }
+
+            fn sample_single_excluding<R: Rng + ?Sized>(low: Self::X,
+                                                        high: Self::X,
+                                                        exclude: Self::X,
+                                                        rng: &mut R)
+                -> Self::X
+            {
+                assert!(low != high || low != exclude,
+                        "Rng::gen_range_excluding called with `exclude` the \
+                         only value in the range");
+                loop {
+                    let x = Self::sample_single(low, high, rng);
+                    if x != exclude {
+                        return x;
+                    }
+                }
             }
         }
     }
 }
 
-uniform_float_impl! { f32, 32 - 23, next_u32 }
-uniform_float_impl! { f64, 64 - 52, next_u64 }
+uniform_float_impl! { f32, u32, 32 - 23, next_u32 }
+uniform_float_impl! { f64, u64, 64 - 52, next_u64 }
 
 
 
@@ -593,6 +1063,13 @@ uniform_float_impl! { f64, 64 - 52, next_u64 }
 /// Unless you are implementing [`UniformSampler`] for your own types, this type
 /// should not be used directly, use [`Uniform`] instead.
 ///
+/// # Implementation notes
+///
+/// If the size of the range fits in a `u64` when counted in nanoseconds
+/// (about 584 years), a single nanosecond count is sampled and converted back
+/// to seconds and sub-second nanoseconds. Larger ranges sample the seconds and
+/// nanoseconds separately and reject results beyond the end of the range.
+///
 /// [`UniformSampler`]: trait.UniformSampler.html
 /// [`Uniform`]: struct.Uniform.html
 #[cfg(feature = "std")]
@@ -681,58 +1158,566 @@ impl UniformSampler for UniformDuration {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use Rng;
-    use distributions::uniform::{Uniform, UniformSampler, UniformFloat, SampleUniform};
+#[cfg(feature = "std")]
+macro_rules! uniform_ip_impl {
+    ($(#[$meta:meta])* $name:ident, $ty:ty, $int:ty) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug)]
+        pub struct $name {
+            sampler: UniformInt<$int>,
+        }
 
-    #[should_panic]
-    #[test]
-    fn test_uniform_bad_limits_equal_int() {
-        Uniform::new(10, 10);
-    }
+        impl SampleUniform for $ty {
+            type Sampler = $name;
+        }
 
-    #[should_panic]
-    #[test]
-    fn test_uniform_bad_limits_equal_float() {
-        Uniform::new(10., 10.);
+        impl UniformSampler for $name {
+            type X = $ty;
+
+            #[inline]
+            fn new(low: $ty, high: $ty) -> $name {
+                $name { sampler: UniformInt::<$int>::new(low.into(), high.into()) }
+            }
+
+            #[inline]
+            fn new_inclusive(low: $ty, high: $ty) -> $name {
+                $name {
+                    sampler: UniformInt::<$int>::new_inclusive(low.into(), high.into()),
+                }
+            }
+
+            #[inline]
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                self.sampler.sample(rng).into()
+            }
+
+            #[inline]
+            fn sample_single<R: Rng + ?Sized>(low: $ty, high: $ty, rng: &mut R)
+                -> $ty
+            {
+                UniformInt::<$int>::sample_single(low.into(), high.into(), rng).into()
+            }
+
+            #[inline]
+            fn sample_single_excluding<R: Rng + ?Sized>(low: $ty, high: $ty,
+                                                        exclude: $ty, rng: &mut R)
+                -> $ty
+            {
+                UniformInt::<$int>::sample_single_excluding(
+                    low.into(), high.into(), exclude.into(), rng).into()
+            }
+        }
     }
+}
 
-    #[test]
-    fn test_uniform_good_limits_equal_int() {
-        let mut rng = ::test::rng(804);
-        let dist = Uniform::new_inclusive(10, 10);
-        for _ in 0..20 {
-            assert_eq!(rng.sample(dist), 10);
+#[cfg(feature = "std")]
+uniform_ip_impl! {
+    /// The back-end implementing [`UniformSampler`] for `Ipv4Addr`.
+    ///
+    /// Unless you are implementing [`UniformSampler`] for your own type, this
+    /// type should not be used directly, use [`Uniform`] instead.
+    ///
+    /// # Implementation notes
+    ///
+    /// Addresses are mapped to their `u32` representation, in which the
+    /// first octet is the most significant byte, so ranges are ordered like
+    /// the addresses themselves. The integer range is sampled with
+    /// [`UniformInt`]. A subnet such as `10.0.0.0/16` is covered by the
+    /// inclusive range from `10.0.0.0` to `10.0.255.255`.
+    ///
+    /// [`UniformSampler`]: trait.UniformSampler.html
+    /// [`Uniform`]: struct.Uniform.html
+    /// [`UniformInt`]: struct.UniformInt.html
+    UniformIpv4, Ipv4Addr, u32
+}
+
+#[cfg(all(feature = "std", rustc_1_26))]
+uniform_ip_impl! {
+    /// The back-end implementing [`UniformSampler`] for `Ipv6Addr`.
+    ///
+    /// Unless you are implementing [`UniformSampler`] for your own type, this
+    /// type should not be used directly, use [`Uniform`] instead.
+    ///
+    /// # Implementation notes
+    ///
+    /// Addresses are mapped to their `u128` representation, in which the
+    /// first segment is the most significant, so ranges are ordered like the
+    /// addresses themselves. The integer range is sampled with
+    /// [`UniformInt`]. Requires Rust 1.26 or later.
+    ///
+    /// [`UniformSampler`]: trait.UniformSampler.html
+    /// [`Uniform`]: struct.Uniform.html
+    /// [`UniformInt`]: struct.UniformInt.html
+    UniformIpv6, Ipv6Addr, u128
+}
+
+/// The back-end implementing [`UniformSampler`] for `char`.
+///
+/// Unless you are implementing [`UniformSampler`] for your own type, this type
+/// should not be used directly, use [`Uniform`] instead.
+///
+/// # Implementation notes
+///
+/// A `char` is mapped to its `u32` scalar value, with the values above the
+/// surrogate range `0xD800..0xE000` moved down to close the gap. The resulting
+/// integer range is sampled with [`UniformInt`] and mapped back, so every
+/// `char` in the range is equally likely and surrogates never occur.
+///
+/// [`UniformSampler`]: trait.UniformSampler.html
+/// [`Uniform`]: struct.Uniform.html
+/// [`UniformInt`]: struct.UniformInt.html
+#[derive(Clone, Copy, Debug)]
+pub struct UniformChar {
+    sampler: UniformInt<u32>,
+}
+
+/// Start and size of the surrogate range, which contains no `char` values.
+const CHAR_SURROGATE_START: u32 = 0xD800;
+const CHAR_SURROGATE_LEN: u32 = 0xE000 - CHAR_SURROGATE_START;
+
+/// Map a `char` to a `u32` without the gap of the surrogate range.
+#[inline]
+fn char_to_compact_u32(c: char) -> u32 {
+    let x = c as u32;
+    if x >= CHAR_SURROGATE_START { x - CHAR_SURROGATE_LEN } else { x }
+}
+
+/// Inverse of `char_to_compact_u32`.
+#[inline]
+fn compact_u32_to_char(x: u32) -> char {
+    let x = if x >= CHAR_SURROGATE_START { x + CHAR_SURROGATE_LEN } else { x };
+    // The range only contains values mapped from valid `char`s.
+    ::core::char::from_u32(x).unwrap()
+}
+
+impl SampleUniform for char {
+    type Sampler = UniformChar;
+}
+
+impl UniformSampler for UniformChar {
+    type X = char;
+
+    #[inline]
+    fn new(low: char, high: char) -> UniformChar {
+        assert!(low < high, "Uniform::new called with `low >= high`");
+        UniformChar {
+            sampler: UniformInt::<u32>::new(char_to_compact_u32(low),
+                                            char_to_compact_u32(high)),
         }
     }
 
-    #[test]
-    fn test_uniform_good_limits_equal_float() {
-        let mut rng = ::test::rng(805);
-        let dist = Uniform::new_inclusive(10., 10.);
-        for _ in 0..20 {
-            assert_eq!(rng.sample(dist), 10.);
+    #[inline]
+    fn new_inclusive(low: char, high: char) -> UniformChar {
+        assert!(low <= high, "Uniform::new_inclusive called with `low > high`");
+        UniformChar {
+            sampler: UniformInt::<u32>::new_inclusive(char_to_compact_u32(low),
+                                                      char_to_compact_u32(high)),
         }
     }
 
-    #[should_panic]
-    #[test]
-    fn test_uniform_bad_limits_flipped_int() {
-        Uniform::new(10, 5);
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> char {
+        compact_u32_to_char(self.sampler.sample(rng))
     }
 
-    #[should_panic]
-    #[test]
-    fn test_uniform_bad_limits_flipped_float() {
-        Uniform::new(10., 5.);
+    #[inline]
+    fn sample_single<R: Rng + ?Sized>(low: char, high: char, rng: &mut R)
+        -> char
+    {
+        assert!(low < high, "Uniform::sample_single called with low >= high");
+        let x = UniformInt::<u32>::sample_single(char_to_compact_u32(low),
+                                                 char_to_compact_u32(high),
+                                                 rng);
+        compact_u32_to_char(x)
     }
 
-    #[test]
-    fn test_integers() {
-        let mut rng = ::test::rng(251);
-        macro_rules! t {
-            ($($ty:ident),*) => {{
+    #[inline]
+    fn sample_single_excluding<R: Rng + ?Sized>(low: char, high: char,
+                                                exclude: char, rng: &mut R)
+        -> char
+    {
+        let x = UniformInt::<u32>::sample_single_excluding(
+            char_to_compact_u32(low), char_to_compact_u32(high),
+            char_to_compact_u32(exclude), rng);
+        compact_u32_to_char(x)
+    }
+}
+
+/// The back-end implementing [`UniformSampler`] for `bool`.
+///
+/// Unless you are implementing [`UniformSampler`] for your own type, this type
+/// should not be used directly, use [`Uniform`] instead.
+///
+/// # Implementation notes
+///
+/// With `false < true`, the only non-empty exclusive range is
+/// `[false, true)`, which contains only `false`; sampling from it always
+/// returns `false` without using the RNG. The inclusive range
+/// `[false, true]` returns `false` or `true` with probability 0.5 each, like
+/// `Rng::gen::<bool>()`, and the inclusive ranges `[false, false]` and
+/// `[true, true]` always return their single value.
+///
+/// This is mostly useful for generic code over [`SampleUniform`] types; to
+/// generate a random `bool` use `Rng::gen` or `Rng::gen_bool` instead.
+///
+/// [`UniformSampler`]: trait.UniformSampler.html
+/// [`Uniform`]: struct.Uniform.html
+/// [`SampleUniform`]: trait.SampleUniform.html
+#[derive(Clone, Copy, Debug)]
+pub struct UniformBool {
+    low: bool,
+    // Whether the range is `[false, true]`
+    random: bool,
+}
+
+impl SampleUniform for bool {
+    type Sampler = UniformBool;
+}
+
+impl UniformSampler for UniformBool {
+    type X = bool;
+
+    #[inline]
+    fn new(low: bool, high: bool) -> UniformBool {
+        assert!(!low && high, "Uniform::new called with `low >= high`");
+        UniformBool { low, random: false }
+    }
+
+    #[inline]
+    fn new_inclusive(low: bool, high: bool) -> UniformBool {
+        assert!(low <= high, "Uniform::new_inclusive called with `low > high`");
+        UniformBool { low, random: low != high }
+    }
+
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> bool {
+        if self.random { rng.gen() } else { self.low }
+    }
+
+    #[inline]
+    fn sample_single_excluding<R: Rng + ?Sized>(low: bool, high: bool,
+                                                exclude: bool, _rng: &mut R)
+        -> bool
+    {
+        assert!(!low && high, "Rng::gen_range_excluding called with low >= high");
+        assert!(low != exclude,
+                "Rng::gen_range_excluding called with `exclude` the only value \
+                 in the range");
+        low
+    }
+}
+
+/// The back-end implementing [`UniformSampler`] for `Wrapping<T>`.
+///
+/// Wraps the back-end of the integer type `T`, so `Wrapping<T>` ranges are
+/// sampled exactly like ranges of `T`; the bounds are compared as ordinary
+/// integers, not modulo the size of the type.
+///
+/// Unless you are implementing [`UniformSampler`] for your own type, this type
+/// should not be used directly, use [`Uniform`] instead.
+///
+/// [`UniformSampler`]: trait.UniformSampler.html
+/// [`Uniform`]: struct.Uniform.html
+#[derive(Clone, Copy, Debug)]
+pub struct UniformWrapping<S>(S);
+
+impl<T: SampleUniform + PartialOrd + Clone> SampleUniform for Wrapping<T> {
+    type Sampler = UniformWrapping<T::Sampler>;
+}
+
+impl<S> UniformSampler for UniformWrapping<S>
+    where S: UniformSampler, S::X: PartialOrd + Clone
+{
+    type X = Wrapping<S::X>;
+
+    #[inline]
+    fn new(low: Wrapping<S::X>, high: Wrapping<S::X>) -> Self {
+        UniformWrapping(S::new(low.0, high.0))
+    }
+
+    #[inline]
+    fn new_inclusive(low: Wrapping<S::X>, high: Wrapping<S::X>) -> Self {
+        UniformWrapping(S::new_inclusive(low.0, high.0))
+    }
+
+    #[inline]
+    fn try_new(low: Wrapping<S::X>, high: Wrapping<S::X>)
+        -> Result<Self, DistributionError>
+    {
+        S::try_new(low.0, high.0).map(UniformWrapping)
+    }
+
+    #[inline]
+    fn try_new_inclusive(low: Wrapping<S::X>, high: Wrapping<S::X>)
+        -> Result<Self, DistributionError>
+    {
+        S::try_new_inclusive(low.0, high.0).map(UniformWrapping)
+    }
+
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Wrapping<S::X> {
+        Wrapping(self.0.sample(rng))
+    }
+
+    #[inline]
+    fn sample_single<R: Rng + ?Sized>(low: Wrapping<S::X>, high: Wrapping<S::X>,
+                                      rng: &mut R) -> Wrapping<S::X>
+    {
+        Wrapping(S::sample_single(low.0, high.0, rng))
+    }
+
+    #[inline]
+    fn sample_single_excluding<R: Rng + ?Sized>(low: Wrapping<S::X>,
+                                                high: Wrapping<S::X>,
+                                                exclude: Wrapping<S::X>,
+                                                rng: &mut R)
+        -> Wrapping<S::X>
+    {
+        Wrapping(S::sample_single_excluding(low.0, high.0, exclude.0, rng))
+    }
+}
+
+/// A distribution sampling uniformly from a finite arithmetic sequence.
+///
+/// `UniformStep::new(low, step, count)` samples one of the `count` lattice
+/// points `low, low + step, ..., low + (count - 1) * step`, each with
+/// probability `1 / count`. The index of the point is sampled with
+/// [`UniformInt`], and the point is computed as `low + index * step`, so for
+/// floating-point types every sample is exactly on the lattice (up to the
+/// rounding of that expression) instead of being rounded from a continuous
+/// value. `step` may be negative for signed and floating-point types.
+///
+/// This is supported for all primitive integer and floating-point types.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, UniformStep};
+///
+/// // One of 0.0, 0.25, 0.5 and 0.75
+/// let grid = UniformStep::new(0.0, 0.25, 4);
+/// let x = grid.sample(&mut rand::thread_rng());
+/// assert!(x == 0.0 || x == 0.25 || x == 0.5 || x == 0.75);
+///
+/// // One of 10, 7, 4 and 1
+/// let countdown = UniformStep::new(10, -3, 4);
+/// let n = countdown.sample(&mut rand::thread_rng());
+/// assert!(n == 10 || n == 7 || n == 4 || n == 1);
+/// ```
+///
+/// [`UniformInt`]: struct.UniformInt.html
+#[derive(Clone, Copy, Debug)]
+pub struct UniformStep<X> {
+    low: X,
+    step: X,
+    index: UniformInt<usize>,
+}
+
+impl<X: SampleStep> UniformStep<X> {
+    /// Create a new `UniformStep` sampling one of the `count` values
+    /// `low + i * step` for `i` in `0..count`.
+    ///
+    /// # Panics
+    ///
+    /// If `step` is zero, if `count` is zero, or if the last value is not
+    /// representable (it overflows an integer type, or is not finite).
+    /// For floating-point types, also if `low` or `step` is not finite.
+    pub fn new(low: X, step: X, count: usize) -> UniformStep<X> {
+        unwrap_new(UniformStep::try_new(low, step, count), "UniformStep::new")
+    }
+
+    /// Create a new `UniformStep` sampling one of the `count` values
+    /// `low + i * step` for `i` in `0..count`, returning an error if the
+    /// parameters are invalid as described for [`new`].
+    ///
+    /// [`new`]: #method.new
+    pub fn try_new(low: X, step: X, count: usize)
+        -> Result<UniformStep<X>, DistributionError>
+    {
+        X::check_lattice(low, step, count)?;
+        Ok(UniformStep {
+            low,
+            step,
+            index: UniformInt::<usize>::new(0, count),
+        })
+    }
+}
+
+impl<X: SampleStep> Distribution<X> for UniformStep<X> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> X {
+        X::lattice_point(self.low, self.step, self.index.sample(rng))
+    }
+}
+
+/// Helper trait for the types supported by [`UniformStep`].
+///
+/// This is implemented for all primitive integer and floating-point types.
+///
+/// [`UniformStep`]: struct.UniformStep.html
+pub trait SampleStep: Copy {
+    /// Check that `step` is not zero, that `count` is not zero, and that the
+    /// values `low + i * step` for `i` in `0..count` are all representable.
+    fn check_lattice(low: Self, step: Self, count: usize)
+        -> Result<(), DistributionError>;
+
+    /// Compute `low + index * step`, where the parameters have been checked
+    /// with `check_lattice` and `index` is less than its `count`.
+    fn lattice_point(low: Self, step: Self, index: usize) -> Self;
+}
+
+macro_rules! sample_step_int_impl {
+    ($ty:ident, $unsigned:ident) => {
+        impl SampleStep for $ty {
+            fn check_lattice(low: $ty, step: $ty, count: usize)
+                -> Result<(), DistributionError>
+            {
+                if step == 0 {
+                    return Err(DistributionError::OutOfRange("step"));
+                }
+                if count == 0 {
+                    return Err(DistributionError::EmptyRange);
+                }
+                // The distance to the last value is computed in the unsigned
+                // type, and compared with the room left above (or below)
+                // `low`.
+                let last_index = (count - 1) as $unsigned;
+                if last_index as usize != count - 1 {
+                    return Err(DistributionError::OutOfRange("count"));
+                }
+                let zero: $ty = 0;
+                let (magnitude, room) = if step < zero {
+                    ((step as $unsigned).wrapping_neg(),
+                     low.wrapping_sub(::core::$ty::MIN) as $unsigned)
+                } else {
+                    (step as $unsigned,
+                     ::core::$ty::MAX.wrapping_sub(low) as $unsigned)
+                };
+                match last_index.checked_mul(magnitude) {
+                    Some(distance) if distance <= room => Ok(()),
+                    _ => Err(DistributionError::OutOfRange("count")),
+                }
+            }
+
+            #[inline]
+            fn lattice_point(low: $ty, step: $ty, index: usize) -> $ty {
+                // This does not overflow, as checked by `check_lattice`; the
+                // wrapping operations only take care of a negative `step`.
+                let offset = (index as $unsigned).wrapping_mul(step as $unsigned);
+                low.wrapping_add(offset as $ty)
+            }
+        }
+    }
+}
+
+sample_step_int_impl! { i8, u8 }
+sample_step_int_impl! { i16, u16 }
+sample_step_int_impl! { i32, u32 }
+sample_step_int_impl! { i64, u64 }
+#[cfg(any(feature = "i128_support", rustc_1_26))]
+sample_step_int_impl! { i128, u128 }
+sample_step_int_impl! { isize, usize }
+sample_step_int_impl! { u8, u8 }
+sample_step_int_impl! { u16, u16 }
+sample_step_int_impl! { u32, u32 }
+sample_step_int_impl! { u64, u64 }
+sample_step_int_impl! { usize, usize }
+#[cfg(any(feature = "i128_support", rustc_1_26))]
+sample_step_int_impl! { u128, u128 }
+
+macro_rules! sample_step_float_impl {
+    ($ty:ident) => {
+        impl SampleStep for $ty {
+            fn check_lattice(low: $ty, step: $ty, count: usize)
+                -> Result<(), DistributionError>
+            {
+                if !low.is_finite() {
+                    return Err(DistributionError::NonFinite("low"));
+                }
+                if !step.is_finite() {
+                    return Err(DistributionError::NonFinite("step"));
+                }
+                if step == 0.0 {
+                    return Err(DistributionError::OutOfRange("step"));
+                }
+                if count == 0 {
+                    return Err(DistributionError::EmptyRange);
+                }
+                if !$ty::lattice_point(low, step, count - 1).is_finite() {
+                    return Err(DistributionError::OutOfRange("count"));
+                }
+                Ok(())
+            }
+
+            #[inline]
+            fn lattice_point(low: $ty, step: $ty, index: usize) -> $ty {
+                low + index as $ty * step
+            }
+        }
+    }
+}
+
+sample_step_float_impl! { f32 }
+sample_step_float_impl! { f64 }
+
+#[cfg(test)]
+mod tests {
+    use Rng;
+    use distributions::uniform::{Uniform, UniformSampler, UniformFloat, SampleUniform};
+
+    #[should_panic]
+    #[test]
+    fn test_uniform_bad_limits_equal_int() {
+        Uniform::new(10, 10);
+    }
+
+    #[test]
+    fn test_uniform_limits_equal_float() {
+        let mut rng = ::test::rng(803);
+        let dist = Uniform::new(10., 10.);
+        for _ in 0..20 {
+            assert_eq!(rng.sample(dist), 10.);
+            assert_eq!(rng.gen_range(-0.5f32, -0.5), -0.5);
+        }
+        let mut max_rng = ::rngs::mock::StepRng::new(!0, 0);
+        assert_eq!(max_rng.gen_range(1e-300, 1e-300), 1e-300);
+        assert_eq!(max_rng.sample(Uniform::new(0.0f32, 0.0)), 0.0);
+    }
+
+    #[test]
+    fn test_uniform_good_limits_equal_int() {
+        let mut rng = ::test::rng(804);
+        let dist = Uniform::new_inclusive(10, 10);
+        for _ in 0..20 {
+            assert_eq!(rng.sample(dist), 10);
+        }
+    }
+
+    #[test]
+    fn test_uniform_good_limits_equal_float() {
+        let mut rng = ::test::rng(805);
+        let dist = Uniform::new_inclusive(10., 10.);
+        for _ in 0..20 {
+            assert_eq!(rng.sample(dist), 10.);
+        }
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_uniform_bad_limits_flipped_int() {
+        Uniform::new(10, 5);
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_uniform_bad_limits_flipped_float() {
+        Uniform::new(10., 5.);
+    }
+
+    #[test]
+    fn test_integers() {
+        let mut rng = ::test::rng(251);
+        macro_rules! t {
+            ($($ty:ident),*) => {{
                 $(
                    let v: &[($ty, $ty)] = &[(0, 10),
                                             (10, 127),
@@ -760,10 +1745,413 @@ mod tests {
         }
         t!(i8, i16, i32, i64, isize,
            u8, u16, u32, u64, usize);
-        #[cfg(feature = "i128_support")]
+        #[cfg(any(feature = "i128_support", rustc_1_26))]
         t!(i128, u128)
     }
 
+    #[test]
+    fn test_integers_signed_extremes() {
+        // Ranges touching the extremes of signed types and crossing zero,
+        // where `high - low` overflows the type. The test profile has
+        // overflow checks enabled.
+        let mut rng = ::test::rng(258);
+        macro_rules! t {
+            ($($ty:ident),*) => {{
+                $(
+                    let min = ::core::$ty::MIN;
+                    let max = ::core::$ty::MAX;
+                    let v: &[($ty, $ty)] = &[(min, max), (min, min + 1),
+                                             (max - 1, max), (min, 0), (-1, max),
+                                             (min + 1, max), (-1, 1), (-100, 27)];
+                    for &(low, high) in v.iter() {
+                        for _ in 0..100 {
+                            let x: $ty = rng.gen_range(low, high);
+                            assert!(low <= x && x < high);
+                            let x = rng.sample(Uniform::new(low, high));
+                            assert!(low <= x && x < high);
+                            let x = rng.sample(Uniform::new_inclusive(low, high));
+                            assert!(low <= x && x <= high);
+                        }
+                    }
+                    assert_eq!(rng.gen_range(min, min + 1), min);
+                    assert_eq!(rng.gen_range(max - 1, max), max - 1);
+                    assert_eq!(rng.sample(Uniform::new_inclusive(max, max)), max);
+
+                    // Both signs are sampled from ranges crossing zero.
+                    let (mut negative, mut positive) = (0, 0);
+                    for _ in 0..1000 {
+                        let x: $ty = rng.gen_range(min, max);
+                        if x < 0 { negative += 1 } else { positive += 1 }
+                    }
+                    assert!(negative > 400 && positive > 400,
+                            "{} negative and {} positive", negative, positive);
+                )*
+            }}
+        }
+        t!(i8, i16, i32, i64, isize);
+        #[cfg(any(feature = "i128_support", rustc_1_26))]
+        t!(i128);
+
+        // Every value of a small range crossing zero is equally likely.
+        let mut counts = [0usize; 7];
+        const N: usize = 70_000;
+        for _ in 0..N {
+            counts[(rng.gen_range(-3i8, 4) + 3) as usize] += 1;
+        }
+        let p = 1.0 / 7.0;
+        let err = 5.0 * (p * (1.0 - p) * N as f64).sqrt();
+        for &count in counts.iter() {
+            assert!((count as f64 - p * N as f64).abs() < err, "counts {:?}", counts);
+        }
+    }
+
+    #[test]
+    fn test_integers_sample_single_bias() {
+        // `2^32 % 3 == 1`, so exactly one `u32` must be rejected for the
+        // range `[0, 3)`: the low word of `v * 3` is below 1 only for `v == 0`.
+        use rngs::mock::StepRng;
+        let mut rng = StepRng::new(0, 1);
+        assert_eq!(Uniform::sample_single(0u32, 3, &mut rng), 0);
+
+        // Every result of a small range is equally likely.
+        let mut rng = ::test::rng(257);
+        for &high in [3u8, 7, 100, 255].iter() {
+            let mut counts = [0usize; 256];
+            const N: usize = 100_000;
+            for _ in 0..N {
+                counts[Uniform::sample_single(0u8, high, &mut rng) as usize] += 1;
+            }
+            let p = 1.0 / high as f64;
+            let err = 5.0 * (p * (1.0 - p) * N as f64).sqrt();
+            for (i, &count) in counts.iter().enumerate() {
+                if i < high as usize {
+                    assert!((count as f64 - p * N as f64).abs() < err,
+                            "{} sampled {} times from [0, {})", i, count, high);
+                } else {
+                    assert_eq!(count, 0);
+                }
+            }
+        }
+
+        // A range just above a power of two, which needs many rejections
+        // with a conservative zone.
+        let high = (1u64 << 63) + 1;
+        let mut below_half = 0;
+        const N: usize = 100_000;
+        for _ in 0..N {
+            let x = Uniform::sample_single(0u64, high, &mut rng);
+            assert!(x < high);
+            if x < high / 2 {
+                below_half += 1;
+            }
+        }
+        let freq = below_half as f64 / N as f64;
+        assert!((freq - 0.5).abs() < 5.0 * (0.25 / N as f64).sqrt());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_uniform_sample_single_bad_limits() {
+        let mut rng = ::test::rng(258);
+        Uniform::sample_single(5u32, 5, &mut rng);
+    }
+
+    #[test]
+    fn test_uniform_sample_fill() {
+        use core::fmt::Debug;
+        use distributions::Distribution;
+
+        // With a block RNG, filling a slice gives the same values as sampling
+        // each element, and leaves the RNG in the same state.
+        fn check<X>(seed: u64, distr: Uniform<X>)
+            where X: SampleUniform + Copy + Default + PartialEq + Debug
+        {
+            for &len in [0, 1, 31, 32, 33, 100].iter() {
+                let mut rng1 = ::test::rng(seed);
+                let mut rng2 = ::test::rng(seed);
+                let mut filled = [X::default(); 100];
+                (&distr).sample_fill(&mut rng1, &mut filled[..len]);
+                let mut sampled = [X::default(); 100];
+                for x in sampled[..len].iter_mut() {
+                    *x = distr.sample(&mut rng2);
+                }
+                assert_eq!(&filled[..len], &sampled[..len]);
+                assert_eq!(rng1.gen::<u32>(), rng2.gen::<u32>());
+            }
+        }
+        check(260, Uniform::new_inclusive(1u8, 6));
+        check(261, Uniform::new(-1000i16, 1000));
+        check(262, Uniform::new_inclusive(::core::i32::MIN, ::core::i32::MAX));
+        // About half of the values are rejected.
+        check(263, Uniform::new_inclusive(0u64, 1 << 63));
+        check(264, Uniform::new(10usize, 20));
+        #[cfg(any(feature = "i128_support", rustc_1_26))]
+        check(265, Uniform::new_inclusive(0u128, (1 << 127) + 1));
+        // Types without an optimized implementation
+        check(266, Uniform::new(-2.5f64, 1.5));
+        check(267, Uniform::new('a', 'z'));
+    }
+
+    #[test]
+    fn test_gen_range_excluding() {
+        let mut rng = ::test::rng(259);
+        // All values but `exclude` are equally likely.
+        const N: usize = 90_000;
+        let mut counts = [0usize; 10];
+        for _ in 0..N {
+            counts[rng.gen_range_excluding(0usize, 10, 3)] += 1;
+        }
+        assert_eq!(counts[3], 0);
+        let p = 1.0 / 9.0;
+        let err = 5.0 * (N as f64 * p * (1.0 - p)).sqrt();
+        for (i, &count) in counts.iter().enumerate().filter(|&(i, _)| i != 3) {
+            assert!((count as f64 - N as f64 * p).abs() < err,
+                    "count of {}: {}", i, count);
+        }
+
+        // Excluding the bounds of the type
+        for _ in 0..1000 {
+            let x = rng.gen_range_excluding(::core::i8::MIN, ::core::i8::MAX,
+                                            ::core::i8::MIN);
+            assert!(x > ::core::i8::MIN && x < ::core::i8::MAX);
+            let x = rng.gen_range_excluding(::core::u64::MAX - 2, ::core::u64::MAX,
+                                            ::core::u64::MAX - 1);
+            assert_eq!(x, ::core::u64::MAX - 2);
+            let x = rng.gen_range_excluding(-1i32, 1, 0);
+            assert_eq!(x, -1);
+        }
+
+        // `exclude` outside of the range
+        for _ in 0..1000 {
+            let x = rng.gen_range_excluding(5u32, 7, 10);
+            assert!(x == 5 || x == 6);
+        }
+
+        for _ in 0..1000 {
+            let c = rng.gen_range_excluding('\u{D7FE}', '\u{E001}', '\u{E000}');
+            assert!(c == '\u{D7FE}' || c == '\u{D7FF}');
+            let x = rng.gen_range_excluding(0.0f64, 1.0, 0.0);
+            assert!(0.0 < x && x < 1.0);
+            assert_eq!(rng.gen_range_excluding(2.0f32, 2.0, 3.0), 2.0);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "only value in the range")]
+    fn test_gen_range_excluding_only_value() {
+        let mut rng = ::test::rng(260);
+        rng.gen_range_excluding(5u32, 6, 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "only value in the range")]
+    fn test_gen_range_excluding_only_value_float() {
+        let mut rng = ::test::rng(261);
+        rng.gen_range_excluding(5.0, 5.0, 5.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_range_excluding_bad_limits() {
+        let mut rng = ::test::rng(262);
+        rng.gen_range_excluding(5i16, 5, 0);
+    }
+
+    #[test]
+    fn test_uniform_try_new() {
+        use distributions::DistributionError::*;
+        assert!(Uniform::try_new(0u8, 1).is_ok());
+        assert!(Uniform::try_new_inclusive(5i32, 5).is_ok());
+        assert_eq!(Uniform::try_new(5u32, 5).err(), Some(EmptyRange));
+        assert_eq!(Uniform::try_new(5i64, -5).err(), Some(EmptyRange));
+        assert_eq!(Uniform::try_new_inclusive(6u16, 5).err(), Some(EmptyRange));
+        assert_eq!(Uniform::try_new('z', 'a').err(), Some(EmptyRange));
+
+        assert!(Uniform::try_new(-1e300, 1e300f64).is_ok());
+        assert!(Uniform::try_new_inclusive(1.0f32, 1.0).is_ok());
+        assert!(Uniform::try_new(1.0f64, 1.0).is_ok());
+        assert_eq!(Uniform::try_new(1.0f64, 0.5).err(), Some(EmptyRange));
+        assert_eq!(Uniform::try_new_inclusive(1.0f32, 0.0).err(), Some(EmptyRange));
+        assert_eq!(Uniform::try_new(::core::f64::NAN, 1.0).err(), Some(NonFinite("low")));
+        assert_eq!(Uniform::try_new(0.0, ::core::f32::INFINITY).err(),
+                   Some(NonFinite("high")));
+        assert_eq!(Uniform::try_new_inclusive(::core::f64::NEG_INFINITY, 0.0).err(),
+                   Some(NonFinite("low")));
+    }
+
+    #[test]
+    fn test_integers_zone() {
+        // `zone + 1` must be a multiple of `range` in the type sampled from
+        // the RNG, otherwise the results are biased.
+        for high in 0..256u32 {
+            let sampler = <u8 as SampleUniform>::Sampler::new_inclusive(0, high as u8);
+            let range = high + 1;
+            let zone = ::core::u32::MAX - sampler.ints_to_reject as u32;
+            assert_eq!((zone as u64 + 1) % range as u64, 0);
+        }
+        for &high in [1i16, 200, 1000, 12345, 30000, ::core::i16::MAX].iter() {
+            let sampler = <i16 as SampleUniform>::Sampler::new_inclusive(::core::i16::MIN, high);
+            let range = (high as i32 - ::core::i16::MIN as i32 + 1) as u64;
+            let zone = ::core::u32::MAX - sampler.ints_to_reject as u16 as u32;
+            assert_eq!((zone as u64 + 1) % range, 0);
+        }
+    }
+
+    #[test]
+    fn test_integers_full_range() {
+        use rngs::mock::StepRng;
+        let mut rng = ::test::rng(253);
+
+        // The full range of the type takes the raw output of the RNG
+        let full = Uniform::new_inclusive(::core::i32::MIN, ::core::i32::MAX);
+        let mut step = StepRng::new(0xffff_fffe, 1);
+        assert_eq!(step.sample(full), -2);
+        assert_eq!(step.sample(full), -1);
+        assert_eq!(step.sample(full), 0);
+
+        // Both ends of the (almost) full range are reached equally often
+        const N: usize = 100_000;
+        let mut low_count = 0;
+        let mut high_count = 0;
+        for _ in 0..N {
+            let v = rng.gen_range(::core::i32::MIN, ::core::i32::MAX);
+            if v < ::core::i32::MIN + (1 << 28) { low_count += 1; }
+            if v >= ::core::i32::MAX - (1 << 28) { high_count += 1; }
+        }
+        // the expected frequency is 1/16, with standard error below 1/1000
+        for &count in [low_count, high_count].iter() {
+            let freq = count as f64 / N as f64;
+            assert!((freq - 1.0 / 16.0).abs() < 0.005, "frequency {}", freq);
+        }
+
+        // Every value of `u8` is reached
+        let mut seen = [false; 256];
+        let full = Uniform::new_inclusive(0u8, 255);
+        for _ in 0..10_000 {
+            seen[rng.sample(full) as usize] = true;
+        }
+        assert!(seen.iter().all(|&x| x));
+        let mut seen = [false; 256];
+        for _ in 0..10_000 {
+            seen[rng.gen_range(0u8, 255) as usize] = true;
+        }
+        assert!(seen[..255].iter().all(|&x| x) && !seen[255]);
+    }
+
+    #[test]
+    #[cfg(any(feature = "i128_support", rustc_1_26))]
+    fn test_integers_128bit() {
+        use rngs::mock::StepRng;
+        use super::WideningMultiply;
+
+        // The widening multiply emulating a 256-bit intermediate
+        let max = ::core::u128::MAX;
+        assert_eq!(max.wmul(max), (max - 1, 1));
+        assert_eq!((1u128 << 127).wmul(6), (3, 0));
+        assert_eq!(0x1234_5678_9abc_def0_u128.wmul(1 << 100),
+                   (0x1_2345_6789, 0xabc_def0 << 100));
+
+        // The full range takes the raw output of the RNG, low word first
+        let full = Uniform::new_inclusive(::core::i128::MIN, ::core::i128::MAX);
+        let mut step = StepRng::new(::core::u64::MAX - 1, 1);
+        assert_eq!(step.sample(full), -2);
+        assert_eq!(step.sample(full), 1 << 64);
+        let full = Uniform::new_inclusive(0u128, ::core::u128::MAX);
+        assert_eq!(step.sample(full), 3 << 64 | 2);
+
+        // A single value
+        let mut rng = ::test::rng(254);
+        let single = Uniform::new_inclusive(::core::i128::MIN, ::core::i128::MIN);
+        assert_eq!(rng.sample(single), ::core::i128::MIN);
+        let single = Uniform::new_inclusive(::core::u128::MAX, ::core::u128::MAX);
+        assert_eq!(rng.sample(single), ::core::u128::MAX);
+        assert_eq!(rng.gen_range(-7i128, -6), -7);
+
+        // A counting RNG whose high words are `1, 3, 5, 7` times `2^61`
+        // yields the top two bits in turn, so each value of a range of 4 is
+        // returned exactly once.
+        let mut step = StepRng::new(0, 1 << 61);
+        let range = Uniform::new(-2i128, 2);
+        for &expected in [-2, -1, 0, 1, -2, -1, 0, 1].iter() {
+            assert_eq!(step.sample(range), expected);
+        }
+        for &expected in [10u128, 11, 12, 13].iter() {
+            assert_eq!(step.gen_range(10, 14), expected);
+        }
+
+        // `2^128 % 3 == 1`, so the single value with a low word of `v * 3`
+        // above the zone is rejected.
+        let third = Uniform::new(0u128, 3);
+        assert_eq!(third.inner.ints_to_reject, 1);
+        assert_eq!((max / 3).wmul(3), (0, max));
+
+        // Unbiased over a small modulus
+        for &(low, high) in [(0u128, 3u128), (0, 7), (1 << 100, (1 << 100) + 10)].iter() {
+            let distr = Uniform::new(low, high);
+            let n = (high - low) as usize;
+            let mut counts = [0usize; 10];
+            let mut single_counts = [0usize; 10];
+            const N: usize = 50_000;
+            for _ in 0..N {
+                counts[(rng.sample(distr) - low) as usize] += 1;
+                single_counts[(rng.gen_range(low, high) - low) as usize] += 1;
+            }
+            let p = 1.0 / n as f64;
+            let err = 5.0 * (p * (1.0 - p) * N as f64).sqrt();
+            for i in 0..n {
+                for &count in [counts[i], single_counts[i]].iter() {
+                    assert!((count as f64 - p * N as f64).abs() < err,
+                            "{} sampled {} times from [{}, {})", i, count, low, high);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "i128_support", rustc_1_26))]
+    fn test_integers_128bit_wide() {
+        use rngs::mock::StepRng;
+        let mut rng = ::test::rng(266);
+        let max = ::core::u128::MAX;
+
+        // A range of `2^128 - 1` values rejects a single random value.
+        let distr = Uniform::new(0, max);
+        assert_eq!(distr.inner.ints_to_reject, 1);
+        assert_eq!(StepRng::new(0, 0).sample(distr), 0);
+        assert_eq!(StepRng::new(!0, 0).sample(distr), max - 1);
+
+        // The top 4 bits of `gen_range(0, u128::MAX)` are uniform, and the
+        // low bits are all used.
+        const N: usize = 16_000;
+        let mut buckets = [0usize; 16];
+        let mut or = 0u128;
+        let mut and = max;
+        for _ in 0..N {
+            let x = rng.gen_range(0, max);
+            assert!(x < max);
+            buckets[(x >> 124) as usize] += 1;
+            or |= x;
+            and &= x;
+        }
+        assert_eq!((or, and), (max, 0));
+        let p = 1.0 / 16.0;
+        let err = 5.0 * (p * (1.0 - p) * N as f64).sqrt();
+        for &count in buckets.iter() {
+            assert!((count as f64 - p * N as f64).abs() < err, "count {}", count);
+        }
+
+        // Signed ranges wider than `i128::MAX`, which overflow `high - low`
+        let mut negative = 0;
+        for _ in 0..1000 {
+            let x = rng.gen_range(::core::i128::MIN, ::core::i128::MAX);
+            assert!(x < ::core::i128::MAX);
+            let y = rng.gen_range(-(1i128 << 126), 1 << 126);
+            assert!(y >= -(1 << 126) && y < 1 << 126);
+            if x < 0 { negative += 1; }
+        }
+        assert!(negative > 400 && negative < 600, "{} negative", negative);
+    }
+
     #[test]
     fn test_floats() {
         let mut rng = ::test::rng(252);
@@ -788,6 +2176,166 @@ mod tests {
         t!(f32, f64)
     }
 
+    #[test]
+    fn test_floats_edges() {
+        use rngs::mock::StepRng;
+        macro_rules! t {
+            ($ty:ident, $uty:ident, $bits_to_discard:expr) => {{
+                // The smallest and largest random values.
+                let mut zero_rng = StepRng::new(0, 0);
+                let mut max_rng = StepRng::new(!0, 0);
+                let step = 1u64 << $bits_to_discard;
+
+                // With small mantissas the width is exact, and `high` is
+                // reached by `new_inclusive`. Check all ranges on a grid,
+                // with the largest random values.
+                for a in -32..32 {
+                    for b in a..33 {
+                        let (low, high) = (a as $ty / 8.0, b as $ty / 8.0);
+                        let inclusive = Uniform::new_inclusive(low, high);
+                        assert_eq!(zero_rng.sample(inclusive), low);
+                        assert_eq!(max_rng.sample(inclusive), high);
+                        for i in 0..16 {
+                            let mut rng = StepRng::new(!0 - i * step, 0);
+                            let x: $ty = rng.sample(inclusive);
+                            assert!(low <= x && x <= high);
+                        }
+                        if a == b {
+                            continue;
+                        }
+
+                        let exclusive = Uniform::new(low, high);
+                        assert_eq!(zero_rng.sample(exclusive), low);
+                        for i in 0..16 {
+                            let mut rng = StepRng::new(!0 - i * step, 0);
+                            let x: $ty = rng.sample(exclusive);
+                            assert!(low <= x && x < high);
+                        }
+                    }
+                }
+
+                // Ranges where rounding would give `high`.
+                let v: &[($ty, $ty)] = &[(-1000.0, 0.001),
+                                         (1.0, 1.0 + ::core::$ty::EPSILON),
+                                         (-(1.0 + ::core::$ty::EPSILON), -1.0),
+                                         (-1e-10, 1e30),
+                                         (-1e20, -3.0),
+                                         (3.0, 1e20)];
+                for &(low, high) in v.iter() {
+                    let x: $ty = max_rng.sample(Uniform::new(low, high));
+                    assert!(low <= x && x < high);
+                    let x: $ty = max_rng.sample(Uniform::new_inclusive(low, high));
+                    assert!(low <= x && x <= high);
+                    // `gen_range` rejects the largest random values if they
+                    // round to `high`; start with those and let the counter
+                    // wrap around.
+                    let mut rng = StepRng::new(!0 - 3 * step, step);
+                    for _ in 0..8 {
+                        let x: $ty = rng.gen_range(low, high);
+                        assert!(low <= x && x < high);
+                    }
+                }
+
+                // The full range does not overflow.
+                let (min, max) = (::core::$ty::MIN, ::core::$ty::MAX);
+                let full = Uniform::new(min, max);
+                let full_inclusive = Uniform::new_inclusive(min, max);
+                assert_eq!(zero_rng.sample(full), min);
+                assert_eq!(zero_rng.sample(full_inclusive), min);
+                let x: $ty = max_rng.sample(full);
+                assert!(x.is_finite() && x < max && x > max / 2.0);
+                let x: $ty = max_rng.sample(full_inclusive);
+                assert!(x.is_finite() && x > max / 2.0);
+                let mut rng = ::test::rng(255);
+                let mut positive = 0;
+                for _ in 0..1000 {
+                    let x: $ty = rng.sample(full);
+                    assert!(x.is_finite());
+                    if x > 0.0 {
+                        positive += 1;
+                    }
+                }
+                assert!(positive > 400 && positive < 600);
+                for _ in 0..1000 {
+                    let x: $ty = rng.gen_range(min, max);
+                    assert!(x.is_finite());
+                    let x: $ty = rng.gen_range(-1000.0, 0.001);
+                    assert!(-1000.0 <= x && x < 0.001);
+                }
+
+                // Ranges with a subnormal width produce all values in the
+                // range.
+                let tiny = <$ty>::from_bits(1);
+                for &low in [0.0, -2.0 * tiny, ::core::$ty::MIN_POSITIVE].iter() {
+                    let high = low + 4.0 * tiny;
+                    let exclusive = Uniform::new(low, high);
+                    let inclusive = Uniform::new_inclusive(low, high);
+                    let mut seen = [false; 5];
+                    let mut seen_inclusive = [false; 5];
+                    for _ in 0..1000 {
+                        let x: $ty = rng.sample(exclusive);
+                        assert!(low <= x && x < high);
+                        seen[((x - low) / tiny) as usize] = true;
+                        let x: $ty = rng.sample(inclusive);
+                        assert!(low <= x && x <= high);
+                        seen_inclusive[((x - low) / tiny) as usize] = true;
+                    }
+                    assert_eq!(seen, [true, true, true, true, false]);
+                    assert_eq!(seen_inclusive, [true; 5]);
+                }
+            }}
+        }
+
+        t!(f32, u32, 32 - 23);
+        t!(f64, u64, 64 - 52);
+    }
+
+    #[test]
+    fn test_floats_inclusive_grid() {
+        use rngs::mock::StepRng;
+        // The results of `new_inclusive` are an evenly spaced grid including
+        // both bounds, with one point for each random value. With a width of
+        // `2^23 - 1` (or `2^52 - 1`) the grid consists of the integers, and
+        // each random value maps to its own integer.
+        macro_rules! t {
+            ($ty:ident, $bits_to_discard:expr, $mantissa_bits:expr) => {{
+                let max = ((1u64 << $mantissa_bits) - 1) as $ty;
+                let inclusive = Uniform::new_inclusive(0.0, max);
+                let exclusive = Uniform::new(0.0, max);
+                let step = 1u64 << $bits_to_discard;
+                let stride = (1u64 << $mantissa_bits) / 1000 + 1;
+                for i in (0..1000).map(|k| k * stride).chain(Some((1u64 << $mantissa_bits) - 1)) {
+                    let mut rng = StepRng::new(i * step, 0);
+                    assert_eq!(rng.sample(inclusive), i as $ty);
+                    let x: $ty = rng.sample(exclusive);
+                    assert!(x < max);
+                }
+            }}
+        }
+
+        t!(f32, 32 - 23, 23);
+        t!(f64, 64 - 52, 52);
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_float_nan_limit() {
+        Uniform::new(::core::f64::NAN, 10.0);
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_float_infinite_limit() {
+        Uniform::new_inclusive(0.0, ::core::f32::INFINITY);
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_float_sample_single_nan_limit() {
+        let mut rng = ::test::rng(256);
+        rng.gen_range(0.0, ::core::f64::NAN);
+    }
+
     #[test]
     #[cfg(feature = "std")]
     fn test_durations() {
@@ -797,6 +2345,7 @@ mod tests {
 
         let v = &[(Duration::new(10, 50000), Duration::new(100, 1234)),
                   (Duration::new(0, 100), Duration::new(1, 50)),
+                  (Duration::from_millis(100), Duration::from_millis(500)),
                   (Duration::new(0, 0), Duration::new(u64::max_value(), 999_999_999))];
         for &(low, high) in v.iter() {
             let my_uniform = Uniform::new(low, high);
@@ -804,7 +2353,372 @@ mod tests {
                 let v = rng.sample(my_uniform);
                 assert!(low <= v && v < high);
             }
+
+            let my_uniform = Uniform::new_inclusive(low, high);
+            for _ in 0..1000 {
+                let v = rng.sample(my_uniform);
+                assert!(low <= v && v <= high);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_durations_sub_millisecond() {
+        use std::time::Duration;
+
+        let mut rng = ::test::rng(254);
+
+        // Ranges smaller than a millisecond, including ones which straddle a
+        // whole second, must still hit every nanosecond in the range.
+        let v = &[(Duration::new(0, 250), Duration::new(0, 260)),
+                  (Duration::new(7, 999_999_995), Duration::new(8, 5))];
+        for &(low, high) in v.iter() {
+            let my_uniform = Uniform::new(low, high);
+            let mut seen = [false; 10];
+            for _ in 0..1000 {
+                let v = rng.sample(my_uniform);
+                assert!(low <= v && v < high);
+                seen[(v - low).subsec_nanos() as usize] = true;
+            }
+            assert!(seen.iter().all(|x| *x));
+        }
+    }
+
+    #[should_panic]
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_durations_bad_limits_equal() {
+        use std::time::Duration;
+        Uniform::new(Duration::from_millis(100), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_char() {
+        use core::char;
+        let mut rng = ::test::rng(891);
+
+        // A range spanning the surrogates never yields a surrogate, and
+        // excludes `high`.
+        let high = char::from_u32(0xE000).unwrap();
+        let my_uniform = Uniform::new('a', high);
+        for _ in 0..10_000 {
+            let c = rng.sample(my_uniform);
+            assert!('a' <= c && c < high);
+        }
+        let (low, high) = (char::from_u32(0xD7FF).unwrap(),
+                           char::from_u32(0xE001).unwrap());
+        let my_uniform = Uniform::new(low, high);
+        let mut seen = [false; 2];
+        for _ in 0..100 {
+            let c = rng.sample(my_uniform);
+            assert!(c == low || c == char::from_u32(0xE000).unwrap());
+            seen[(c != low) as usize] = true;
+            let c = rng.gen_range(low, high);
+            assert!(c == low || c == char::from_u32(0xE000).unwrap());
+        }
+        assert!(seen.iter().all(|x| *x));
+
+        // `new_inclusive` includes `high`.
+        let my_uniform = Uniform::new_inclusive('a', 'c');
+        let mut seen = [false; 3];
+        for _ in 0..100 {
+            let c = rng.sample(my_uniform);
+            seen[(c as u32 - 'a' as u32) as usize] = true;
+        }
+        assert!(seen.iter().all(|x| *x));
+        let my_uniform = Uniform::new_inclusive('x', 'x');
+        assert_eq!(rng.sample(my_uniform), 'x');
+
+        let my_uniform = Uniform::new_inclusive('\0', char::MAX);
+        for _ in 0..1000 {
+            let c = rng.sample(my_uniform);
+            assert!((c as u32) < 0xD800 || (c as u32) > 0xDFFF);
+        }
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_char_bad_limits_equal() {
+        Uniform::new('a', 'a');
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_char_bad_limits_flipped() {
+        Uniform::new_inclusive('z', 'a');
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_ipv4() {
+        use std::net::Ipv4Addr;
+        use rngs::mock::StepRng;
+
+        let mut rng = ::test::rng(814);
+        let (low, high): (Ipv4Addr, Ipv4Addr) =
+            ("10.0.0.0".parse().unwrap(), "10.0.255.255".parse().unwrap());
+        let subnet = Uniform::new_inclusive(low, high);
+        let (mut third, mut fourth) = (0u8, 0u8);
+        for _ in 0..1000 {
+            let addr = rng.gen_range(low, high);
+            assert!(low <= addr && addr < high);
+            let octets = rng.sample(subnet).octets();
+            assert_eq!(&octets[..2], &[10, 0]);
+            third |= octets[2];
+            fourth |= octets[3];
+        }
+        assert_eq!((third, fourth), (0xff, 0xff));
+
+        // The subnet boundaries are reached by the extreme random values.
+        let low = Ipv4Addr::new(192, 168, 1, 0);
+        let high = Ipv4Addr::new(192, 168, 1, 255);
+        let subnet = Uniform::new_inclusive(low, high);
+        assert_eq!(StepRng::new(0, 0).sample(subnet), low);
+        assert_eq!(StepRng::new(!0, 0).sample(subnet), high);
+        assert_eq!(Uniform::sample_single(low, high, &mut StepRng::new(!0, 0)),
+                   Ipv4Addr::new(192, 168, 1, 254));
+
+        // A range crossing an octet boundary
+        let range = Uniform::new(Ipv4Addr::new(10, 0, 0, 255), Ipv4Addr::new(10, 0, 1, 1));
+        for _ in 0..100 {
+            let addr = rng.sample(range);
+            assert!(addr == Ipv4Addr::new(10, 0, 0, 255) || addr == Ipv4Addr::new(10, 0, 1, 0));
+        }
+
+        let full = Uniform::new_inclusive(Ipv4Addr::new(0, 0, 0, 0),
+                                          Ipv4Addr::new(255, 255, 255, 255));
+        assert_eq!(StepRng::new(0x0A00_0001, 0).sample(full), Ipv4Addr::new(10, 0, 0, 1));
+        assert_eq!(StepRng::new(0x0A00_0001, 0).gen::<Ipv4Addr>(), Ipv4Addr::new(10, 0, 0, 1));
+        assert_eq!(StepRng::new(0, 0).sample(full), Ipv4Addr::new(0, 0, 0, 0));
+        assert_eq!(StepRng::new(!0, 0).sample(full), Ipv4Addr::new(255, 255, 255, 255));
+        let mut octets = [0u8; 4];
+        for _ in 0..1000 {
+            for (acc, octet) in octets.iter_mut().zip(rng.sample(full).octets().iter()) {
+                *acc |= *octet;
+            }
+        }
+        assert_eq!(octets, [0xff; 4]);
+    }
+
+    #[should_panic(expected = "`low >= high`")]
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_ipv4_bad_limits_equal() {
+        use std::net::Ipv4Addr;
+        Uniform::new(Ipv4Addr::new(10, 0, 0, 1), Ipv4Addr::new(10, 0, 0, 1));
+    }
+
+    #[should_panic(expected = "`low > high`")]
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_ipv4_bad_limits_flipped() {
+        use std::net::Ipv4Addr;
+        // Ordered numerically: 10.0.1.0 is above 10.0.0.255
+        Uniform::new_inclusive(Ipv4Addr::new(10, 0, 1, 0), Ipv4Addr::new(10, 0, 0, 255));
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", rustc_1_26))]
+    fn test_ipv6() {
+        use std::net::Ipv6Addr;
+        use rngs::mock::StepRng;
+
+        let mut rng = ::test::rng(815);
+        let low: Ipv6Addr = "2001:db8::".parse().unwrap();
+        let high: Ipv6Addr = "2001:db8::ffff:ffff".parse().unwrap();
+        let subnet = Uniform::new_inclusive(low, high);
+        for _ in 0..1000 {
+            let addr = rng.gen_range(low, high);
+            assert!(low <= addr && addr < high);
+            let segments = rng.sample(subnet).segments();
+            assert_eq!(&segments[..6], &[0x2001, 0xdb8, 0, 0, 0, 0]);
+        }
+        assert_eq!(StepRng::new(0, 0).sample(subnet), low);
+        assert_eq!(StepRng::new(!0, 0).sample(subnet), high);
+
+        // `u128` values are composed with the first `u64` as the low word.
+        let addr: Ipv6Addr = StepRng::new(1, 0x2000_0000_0000_0000 - 1).gen();
+        assert_eq!(addr, Ipv6Addr::new(0x2000, 0, 0, 0, 0, 0, 0, 1));
+
+        let full = Uniform::new_inclusive(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0),
+                                          Ipv6Addr::new(!0, !0, !0, !0, !0, !0, !0, !0));
+        assert_eq!(StepRng::new(0, 0).sample(full), Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0));
+        assert_eq!(StepRng::new(!0, 0).sample(full),
+                   Ipv6Addr::new(!0, !0, !0, !0, !0, !0, !0, !0));
+        let mut segments = [0u16; 8];
+        for _ in 0..1000 {
+            for (acc, segment) in segments.iter_mut().zip(rng.sample(full).segments().iter()) {
+                *acc |= *segment;
+            }
         }
+        assert_eq!(segments, [0xffff; 8]);
+    }
+
+    #[should_panic(expected = "`low > high`")]
+    #[test]
+    #[cfg(all(feature = "std", rustc_1_26))]
+    fn test_ipv6_bad_limits_flipped() {
+        use std::net::Ipv6Addr;
+        Uniform::new_inclusive(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1),
+                               Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0));
+    }
+
+    #[test]
+    fn test_bool() {
+        use rngs::mock::StepRng;
+
+        // Generic code over `SampleUniform` types
+        fn sample_inclusive<T: SampleUniform, R: Rng>(low: T, high: T, rng: &mut R)
+            -> T
+        {
+            rng.sample(Uniform::new_inclusive(low, high))
+        }
+
+        let mut rng = ::test::rng(813);
+        const N: usize = 100_000;
+        let count = (0..N).filter(|_| sample_inclusive(false, true, &mut rng)).count();
+        let p = count as f64 / N as f64;
+        assert!((p - 0.5).abs() < 5.0 * (0.25 / N as f64).sqrt(),
+                "frequency of true: {}", p);
+
+        // Ranges with a single value do not use the RNG.
+        let mut rng = StepRng::new(0, 0);
+        for _ in 0..10 {
+            assert_eq!(sample_inclusive(false, false, &mut rng), false);
+            assert_eq!(sample_inclusive(true, true, &mut rng), true);
+            assert_eq!(rng.gen_range(false, true), false);
+            assert_eq!(rng.sample(Uniform::new(false, true)), false);
+            assert_eq!(rng.gen_range_excluding(false, true, true), false);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bool_bad_limits_equal() {
+        Uniform::new(true, true);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bool_bad_limits_flipped() {
+        Uniform::new_inclusive(true, false);
+    }
+
+    #[test]
+    fn test_wrapping() {
+        use core::num::Wrapping;
+        let mut rng1 = ::test::rng(453);
+        let mut rng2 = ::test::rng(453);
+        for _ in 0..100 {
+            let x: Wrapping<u32> = rng1.gen();
+            assert_eq!(x, Wrapping(rng2.gen::<u32>()));
+
+            let x = rng1.gen_range(Wrapping(-10i64), Wrapping(10));
+            assert_eq!(x, Wrapping(rng2.gen_range(-10i64, 10)));
+
+            let x = rng1.sample(Uniform::new_inclusive(Wrapping(3u8), Wrapping(200)));
+            assert_eq!(x, Wrapping(rng2.sample(Uniform::new_inclusive(3u8, 200))));
+
+            let x = rng1.gen_range_excluding(Wrapping(0usize), Wrapping(3), Wrapping(1));
+            assert_eq!(x, Wrapping(rng2.gen_range_excluding(0usize, 3, 1)));
+        }
+        // The bounds are ordered as integers, not modulo 2^32.
+        assert!(Uniform::try_new(Wrapping(!0u32), Wrapping(1)).is_err());
+        let x = rng1.gen_range(Wrapping(1u32), Wrapping(3)) * Wrapping(!0);
+        assert!(x == Wrapping(!0) || x == Wrapping(!1));
+    }
+
+    #[test]
+    fn test_uniform_step() {
+        use distributions::{Distribution, UniformStep};
+        let mut rng = ::test::rng(465);
+
+        // Every lattice point, including both endpoints, is sampled with
+        // probability 1/5.
+        const N: usize = 50_000;
+        let lattice = UniformStep::new(-7i32, 3, 5);
+        let mut counts = [0usize; 5];
+        for _ in 0..N {
+            let x = lattice.sample(&mut rng);
+            assert!((x + 7) % 3 == 0 && -7 <= x && x <= 5, "{} not on lattice", x);
+            counts[((x + 7) / 3) as usize] += 1;
+        }
+        let p = 1.0 / 5.0;
+        let err = 5.0 * (N as f64 * p * (1.0 - p)).sqrt();
+        for &count in counts.iter() {
+            assert!((count as f64 - N as f64 * p).abs() < err, "count {}", count);
+        }
+
+        let grid = UniformStep::new(1.5f64, -0.25, 4);
+        let mut counts = [0usize; 4];
+        for _ in 0..N {
+            let x = grid.sample(&mut rng);
+            let i = ((1.5 - x) / 0.25) as usize;
+            assert_eq!(x, 1.5 - i as f64 * 0.25);
+            counts[i] += 1;
+        }
+        let p = 1.0 / 4.0;
+        let err = 5.0 * (N as f64 * p * (1.0 - p)).sqrt();
+        for &count in counts.iter() {
+            assert!((count as f64 - N as f64 * p).abs() < err, "count {}", count);
+        }
+
+        // A single point, and lattices ending exactly at the limits of the type
+        let one = UniformStep::new(3.0f32, 1.0, 1);
+        let full = UniformStep::new(-128i8, 1, 256);
+        let down = UniformStep::new(127i8, -1, 256);
+        let wide = UniformStep::new(0u8, 85, 4);
+        for _ in 0..100 {
+            assert_eq!(one.sample(&mut rng), 3.0);
+            full.sample(&mut rng);
+            down.sample(&mut rng);
+            assert_eq!(wide.sample(&mut rng) % 85, 0);
+        }
+        let (mut min, mut max) = (0i8, 0i8);
+        for _ in 0..10_000 {
+            let x = down.sample(&mut rng);
+            min = min.min(x);
+            max = max.max(x);
+        }
+        assert_eq!((min, max), (-128, 127));
+    }
+
+    #[test]
+    fn test_uniform_step_invalid() {
+        use distributions::{DistributionError, UniformStep};
+        assert_eq!(UniformStep::try_new(0u32, 0, 5).unwrap_err(),
+                   DistributionError::OutOfRange("step"));
+        assert_eq!(UniformStep::try_new(0u32, 1, 0).unwrap_err(),
+                   DistributionError::EmptyRange);
+        assert_eq!(UniformStep::try_new(0u8, 1, 257).unwrap_err(),
+                   DistributionError::OutOfRange("count"));
+        assert_eq!(UniformStep::try_new(1i8, 1, 128).unwrap_err(),
+                   DistributionError::OutOfRange("count"));
+        assert_eq!(UniformStep::try_new(-1i8, -1, 129).unwrap_err(),
+                   DistributionError::OutOfRange("count"));
+        assert_eq!(UniformStep::try_new(0u8, 86, 4).unwrap_err(),
+                   DistributionError::OutOfRange("count"));
+        assert!(UniformStep::try_new(1i8, 1, 127).is_ok());
+        assert!(UniformStep::try_new(-1i8, -1, 128).is_ok());
+
+        assert_eq!(UniformStep::try_new(0.0, 0.0, 5).unwrap_err(),
+                   DistributionError::OutOfRange("step"));
+        assert_eq!(UniformStep::try_new(0.0, 1.0, 0).unwrap_err(),
+                   DistributionError::EmptyRange);
+        assert_eq!(UniformStep::try_new(::core::f64::NAN, 1.0, 5).unwrap_err(),
+                   DistributionError::NonFinite("low"));
+        assert_eq!(UniformStep::try_new(0.0, ::core::f64::INFINITY, 5).unwrap_err(),
+                   DistributionError::NonFinite("step"));
+        assert_eq!(UniformStep::try_new(0.0, 1e308, 5).unwrap_err(),
+                   DistributionError::OutOfRange("count"));
+    }
+
+    #[test]
+    #[should_panic(expected = "UniformStep::new called with invalid parameters")]
+    fn test_uniform_step_new_invalid() {
+        use distributions::UniformStep;
+        UniformStep::new(1u32, 0, 5);
     }
 
     #[test]
@@ -850,7 +2764,7 @@ mod tests {
         assert_eq!(r.inner.low, 2);
         assert_eq!(r.inner.range, 5);
         let r = Uniform::from(2.0f64..7.0);
-        assert_eq!(r.inner.offset, -3.0);
+        assert_eq!(r.inner.low, 2.0);
         assert_eq!(r.inner.scale, 5.0);
     }
 }
@@ -0,0 +1,84 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The unit ball distribution.
+
+use Rng;
+use distributions::{Distribution, Uniform};
+
+/// Samples uniformly from the inside of the unit ball in three dimensions.
+///
+/// Implemented by rejection sampling: points are sampled uniformly from the
+/// cube `[-1, 1)^3` until one lies inside the ball, which happens for about
+/// 52% of the points.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{UnitBall, Distribution};
+///
+/// let v = UnitBall.sample(&mut rand::thread_rng());
+/// println!("{:?} is from the unit ball.", v)
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct UnitBall;
+
+impl Distribution<[f64; 3]> for UnitBall {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> [f64; 3] {
+        let uniform = Uniform::new(-1., 1.);
+        loop {
+            let v = [uniform.sample(rng), uniform.sample(rng), uniform.sample(rng)];
+            if v[0]*v[0] + v[1]*v[1] + v[2]*v[2] <= 1. {
+                return v;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use distributions::Distribution;
+    use super::UnitBall;
+
+    #[test]
+    fn test_unit_ball() {
+        let mut rng = ::test::rng(445);
+        const N: usize = 100_000;
+        let mut sum = [0.; 3];
+        let mut sum_cross = [0.; 3];
+        let mut sum_dot = 0.;
+        let mut inner = 0;
+        let mut prev = UnitBall.sample(&mut rng);
+        for _ in 0..N {
+            let v = UnitBall.sample(&mut rng);
+            let norm2 = v[0]*v[0] + v[1]*v[1] + v[2]*v[2];
+            assert!(norm2 <= 1.);
+            for i in 0..3 {
+                sum[i] += v[i];
+                sum_cross[i] += v[i] * v[(i + 1) % 3];
+            }
+            sum_dot += v[0] * prev[0] + v[1] * prev[1] + v[2] * prev[2];
+            // The ball of radius 1/2 has 1/8 of the volume
+            if norm2 < 0.25 { inner += 1; }
+            prev = v;
+        }
+        // Each coordinate has mean 0 and variance 1/5, the dot product of
+        // independent samples variance 3/25, and the products of two
+        // coordinates variance 1/35.
+        for i in 0..3 {
+            assert!((sum[i] / N as f64).abs() < 5. * (0.2 / N as f64).sqrt());
+            assert!((sum_cross[i] / N as f64).abs() < 5. * (1. / 35. / N as f64).sqrt());
+        }
+        assert!((sum_dot / N as f64).abs() < 5. * (0.12 / N as f64).sqrt());
+        let freq = inner as f64 / N as f64;
+        assert!((freq - 0.125).abs() < 5. * (0.125 * 0.875 / N as f64).sqrt());
+    }
+}
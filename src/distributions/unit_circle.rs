@@ -0,0 +1,100 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The unit circle distribution.
+
+use Rng;
+use distributions::{Distribution, Uniform};
+
+/// Samples uniformly from the edge of the unit circle in two dimensions,
+/// i.e. a uniformly distributed direction in the plane.
+///
+/// Implemented via a method by von Neumann: a point is sampled uniformly from
+/// the unit disc by rejection, and its angle is doubled. This needs no
+/// trigonometric functions, and the returned vector has norm 1 up to
+/// rounding errors of a few ulp.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{UnitCircle, Distribution};
+///
+/// let v = UnitCircle.sample(&mut rand::thread_rng());
+/// println!("{:?} is from the unit circle.", v)
+/// ```
+///
+/// # References
+///
+/// J. von Neumann, *Various Techniques Used in Connection with Random
+/// Digits*, NBS Applied Mathematics Series 12, 1951, 36-38.
+#[derive(Clone, Copy, Debug)]
+pub struct UnitCircle;
+
+impl Distribution<[f64; 2]> for UnitCircle {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> [f64; 2] {
+        let uniform = Uniform::new(-1., 1.);
+        let mut x1;
+        let mut x2;
+        let mut sum;
+        loop {
+            x1 = uniform.sample(rng);
+            x2 = uniform.sample(rng);
+            sum = x1*x1 + x2*x2;
+            if sum < 1. && sum != 0. {
+                break;
+            }
+        }
+        let diff = x1*x1 - x2*x2;
+        [diff / sum, 2.*x1*x2 / sum]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use distributions::Distribution;
+    use super::UnitCircle;
+
+    #[test]
+    fn test_unit_circle_norm() {
+        let mut rng = ::test::rng(441);
+        for _ in 0..1000 {
+            let v = UnitCircle.sample(&mut rng);
+            assert!((v[0]*v[0] + v[1]*v[1] - 1.).abs() < 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_unit_circle_uniform() {
+        let mut rng = ::test::rng(442);
+        const N: usize = 100_000;
+        let (mut sum_x, mut sum_y, mut sum_xy, mut sum_dot) = (0., 0., 0., 0.);
+        let mut first_quadrant = 0;
+        let mut prev = UnitCircle.sample(&mut rng);
+        for _ in 0..N {
+            let v = UnitCircle.sample(&mut rng);
+            sum_x += v[0];
+            sum_y += v[1];
+            sum_xy += v[0] * v[1];
+            sum_dot += v[0] * prev[0] + v[1] * prev[1];
+            if v[0] > 0. && v[1] > 0. { first_quadrant += 1; }
+            prev = v;
+        }
+        // Each coordinate and the dot product of independent samples has mean
+        // 0 and variance 1/2; `x * y` has variance 1/8.
+        let err = 5. * (0.5 / N as f64).sqrt();
+        assert!((sum_x / N as f64).abs() < err);
+        assert!((sum_y / N as f64).abs() < err);
+        assert!((sum_dot / N as f64).abs() < err);
+        assert!((sum_xy / N as f64).abs() < 5. * (0.125 / N as f64).sqrt());
+        let freq = first_quadrant as f64 / N as f64;
+        assert!((freq - 0.25).abs() < 5. * (0.25 * 0.75 / N as f64).sqrt());
+    }
+}
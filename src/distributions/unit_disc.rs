@@ -0,0 +1,77 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The unit disc distribution.
+
+use Rng;
+use distributions::{Distribution, Uniform};
+
+/// Samples uniformly from the inside of the unit disc in two dimensions.
+///
+/// Implemented by rejection sampling: points are sampled uniformly from the
+/// square `[-1, 1)^2` until one lies inside the disc, which happens for about
+/// 79% of the points.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{UnitDisc, Distribution};
+///
+/// let v = UnitDisc.sample(&mut rand::thread_rng());
+/// println!("{:?} is from the unit disc.", v)
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct UnitDisc;
+
+impl Distribution<[f64; 2]> for UnitDisc {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> [f64; 2] {
+        let uniform = Uniform::new(-1., 1.);
+        loop {
+            let v = [uniform.sample(rng), uniform.sample(rng)];
+            if v[0]*v[0] + v[1]*v[1] <= 1. {
+                return v;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use distributions::Distribution;
+    use super::UnitDisc;
+
+    #[test]
+    fn test_unit_disc() {
+        let mut rng = ::test::rng(449);
+        const N: usize = 100_000;
+        let mut sum = [0.; 2];
+        let mut sum_cross = 0.;
+        let mut inner = 0;
+        for _ in 0..N {
+            let v = UnitDisc.sample(&mut rng);
+            let norm2 = v[0]*v[0] + v[1]*v[1];
+            assert!(norm2 <= 1.);
+            sum[0] += v[0];
+            sum[1] += v[1];
+            sum_cross += v[0] * v[1];
+            // The disc of radius 1/2 has 1/4 of the area
+            if norm2 < 0.25 { inner += 1; }
+        }
+        // Each coordinate has mean 0 and variance 1/4, and the product of
+        // the coordinates variance 1/24.
+        for i in 0..2 {
+            assert!((sum[i] / N as f64).abs() < 5. * (0.25 / N as f64).sqrt());
+        }
+        assert!((sum_cross / N as f64).abs() < 5. * (1. / 24. / N as f64).sqrt());
+        let freq = inner as f64 / N as f64;
+        assert!((freq - 0.25).abs() < 5. * (0.25 * 0.75 / N as f64).sqrt());
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The unit quaternion distribution.
+
+use Rng;
+use distributions::{Distribution, Uniform};
+
+/// Samples a unit quaternion representing a uniformly random rotation in
+/// three dimensions.
+///
+/// The quaternion is returned as `[w, x, y, z]`, with the scalar part `w`
+/// first. Since `q` and `-q` represent the same rotation, the sign is chosen
+/// such that `w >= 0`. The resulting rotations are uniformly distributed
+/// (according to the Haar measure), unlike rotations composed of independent
+/// uniformly sampled Euler angles. The returned quaternion has norm 1 up to
+/// rounding errors of a few ulp.
+///
+/// Implemented via a method by Marsaglia: two points are sampled uniformly
+/// from the unit disc by rejection, and combined into a uniformly distributed
+/// point on the unit sphere in four dimensions.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{UnitQuaternion, Distribution};
+///
+/// let q = UnitQuaternion.sample(&mut rand::thread_rng());
+/// println!("{:?} is a random rotation.", q)
+/// ```
+///
+/// # References
+///
+/// G. Marsaglia, *Choosing a Point from the Surface of a Sphere*, Annals of
+/// Mathematical Statistics 43(2), 1972, 645-646.
+#[derive(Clone, Copy, Debug)]
+pub struct UnitQuaternion;
+
+impl Distribution<[f64; 4]> for UnitQuaternion {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> [f64; 4] {
+        let uniform = Uniform::new(-1f64, 1.);
+        let (x1, y1, s1) = loop {
+            let (x, y) = (uniform.sample(rng), uniform.sample(rng));
+            let s = x*x + y*y;
+            if s < 1. {
+                break (x, y, s);
+            }
+        };
+        let (x2, y2, s2) = loop {
+            let (x, y) = (uniform.sample(rng), uniform.sample(rng));
+            let s = x*x + y*y;
+            if s < 1. && s != 0. {
+                break (x, y, s);
+            }
+        };
+        let factor = ((1. - s1) / s2).sqrt();
+        let q = [x1, y1, x2 * factor, y2 * factor];
+        if q[0] < 0. {
+            [-q[0], -q[1], -q[2], -q[3]]
+        } else {
+            q
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use distributions::Distribution;
+    use super::UnitQuaternion;
+
+    // Rotate `v` by the unit quaternion `q`.
+    fn rotate(q: [f64; 4], v: [f64; 3]) -> [f64; 3] {
+        let (w, x, y, z) = (q[0], q[1], q[2], q[3]);
+        [(1. - 2.*(y*y + z*z)) * v[0] + 2.*(x*y - w*z) * v[1] + 2.*(x*z + w*y) * v[2],
+         2.*(x*y + w*z) * v[0] + (1. - 2.*(x*x + z*z)) * v[1] + 2.*(y*z - w*x) * v[2],
+         2.*(x*z - w*y) * v[0] + 2.*(y*z + w*x) * v[1] + (1. - 2.*(x*x + y*y)) * v[2]]
+    }
+
+    #[test]
+    fn test_unit_quaternion_norm() {
+        let mut rng = ::test::rng(451);
+        for _ in 0..10_000 {
+            let q = UnitQuaternion.sample(&mut rng);
+            let norm2 = q[0]*q[0] + q[1]*q[1] + q[2]*q[2] + q[3]*q[3];
+            assert!((norm2 - 1.).abs() < 1e-12);
+            assert!(q[0] >= 0.);
+        }
+    }
+
+    #[test]
+    fn test_unit_quaternion_rotations() {
+        // A fixed vector rotated by uniform rotations is uniformly
+        // distributed on the sphere: the mean is 0 and the covariance I/3.
+        let mut rng = ::test::rng(452);
+        const N: usize = 100_000;
+        let mut sum = [0.; 3];
+        let mut sum_sq = [[0.; 3]; 3];
+        for _ in 0..N {
+            let q = UnitQuaternion.sample(&mut rng);
+            let v = rotate(q, [0., 0.6, 0.8]);
+            for i in 0..3 {
+                sum[i] += v[i];
+                for j in 0..3 {
+                    sum_sq[i][j] += v[i] * v[j];
+                }
+            }
+        }
+        for i in 0..3 {
+            // the coordinates have variance 1/3
+            assert!((sum[i] / N as f64).abs() < 5. * (1. / 3. / N as f64).sqrt());
+            for j in 0..3 {
+                let cov = sum_sq[i][j] / N as f64;
+                // `x^2` has variance 4/45, and `x * y` variance 1/15
+                if i == j {
+                    assert!((cov - 1. / 3.).abs() < 5. * (4. / 45. / N as f64).sqrt());
+                } else {
+                    assert!(cov.abs() < 5. * (1. / 15. / N as f64).sqrt());
+                }
+            }
+        }
+    }
+}
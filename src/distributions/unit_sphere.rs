@@ -0,0 +1,118 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The unit sphere surface distribution.
+
+use Rng;
+use distributions::{Distribution, Uniform};
+
+/// Samples uniformly from the surface of the unit sphere in three dimensions,
+/// i.e. a uniformly distributed direction in space.
+///
+/// Implemented via a method by Marsaglia: a point is sampled uniformly from
+/// the unit disc by rejection, and mapped onto the sphere. Unlike sampling
+/// two angles uniformly, this does not cluster points at the poles. The
+/// returned vector has norm 1 up to rounding errors of a few ulp.
+///
+/// This distribution is also available under the name `UnitSphere`, for
+/// symmetry with [`UnitCircle`].
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{UnitSphereSurface, Distribution};
+///
+/// let v = UnitSphereSurface.sample(&mut rand::thread_rng());
+/// println!("{:?} is from the unit sphere surface.", v)
+/// ```
+///
+/// # References
+///
+/// G. Marsaglia, *Choosing a Point from the Surface of a Sphere*, Annals of
+/// Mathematical Statistics 43(2), 1972, 645-646.
+///
+/// [`UnitCircle`]: struct.UnitCircle.html
+#[derive(Clone, Copy, Debug)]
+pub struct UnitSphereSurface;
+
+impl Distribution<[f64; 3]> for UnitSphereSurface {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> [f64; 3] {
+        let uniform = Uniform::new(-1., 1.);
+        loop {
+            let (x1, x2) = (uniform.sample(rng), uniform.sample(rng));
+            let sum = x1*x1 + x2*x2;
+            if sum >= 1. {
+                continue;
+            }
+            let factor = 2. * (1.0_f64 - sum).sqrt();
+            return [x1 * factor, x2 * factor, 1. - 2.*sum];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use distributions::Distribution;
+    use super::UnitSphereSurface;
+
+    #[test]
+    fn test_unit_sphere_norm() {
+        let mut rng = ::test::rng(443);
+        for _ in 0..1000 {
+            let v = UnitSphereSurface.sample(&mut rng);
+            assert!((v[0]*v[0] + v[1]*v[1] + v[2]*v[2] - 1.).abs() < 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_unit_sphere_uniform() {
+        let mut rng = ::test::rng(444);
+        const N: usize = 100_000;
+        let mut sum = [0.; 3];
+        let mut sum_cross = [0.; 3];
+        let mut sum_dot = 0.;
+        let mut near_pole = 0;
+        let mut prev = UnitSphereSurface.sample(&mut rng);
+        for _ in 0..N {
+            let v = UnitSphereSurface.sample(&mut rng);
+            for i in 0..3 {
+                sum[i] += v[i];
+                sum_cross[i] += v[i] * v[(i + 1) % 3];
+            }
+            sum_dot += v[0] * prev[0] + v[1] * prev[1] + v[2] * prev[2];
+            // The height of a uniform point on the sphere is uniform
+            if v[2] > 0.5 { near_pole += 1; }
+            prev = v;
+        }
+        // Each coordinate and the dot product of independent samples has mean
+        // 0 and variance 1/3; the products of two coordinates have variance
+        // 1/15.
+        let err = 5. * (1. / 3. / N as f64).sqrt();
+        for i in 0..3 {
+            assert!((sum[i] / N as f64).abs() < err);
+            assert!((sum_cross[i] / N as f64).abs() < 5. * (1. / 15. / N as f64).sqrt());
+        }
+        assert!((sum_dot / N as f64).abs() < err);
+        let freq = near_pole as f64 / N as f64;
+        assert!((freq - 0.25).abs() < 5. * (0.25 * 0.75 / N as f64).sqrt());
+    }
+
+    #[test]
+    fn test_unit_sphere_name() {
+        use distributions::UnitSphere;
+        let mut rng1 = ::test::rng(445);
+        let mut rng2 = ::test::rng(445);
+        for _ in 0..10 {
+            assert_eq!(UnitSphere.sample(&mut rng1),
+                       UnitSphereSurface.sample(&mut rng2));
+        }
+    }
+}
@@ -0,0 +1,237 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The von Mises distribution.
+
+use Rng;
+use distributions::{Distribution, StandardNormal};
+use distributions::error::{DistributionError, unwrap_new, check_finite, check_non_negative};
+use std::f64::consts::PI;
+
+/// Below this concentration samples are taken from the uniform distribution
+/// on the circle; the difference is of order `kappa`.
+const KAPPA_UNIFORM: f64 = 1e-8;
+/// Below this concentration the parameter `r` is computed from its Taylor
+/// series, as the exact formula suffers from cancellation.
+const KAPPA_SMALL: f64 = 1e-5;
+/// Above this concentration samples are taken from a wrapped normal
+/// distribution.
+const KAPPA_LARGE: f64 = 1e6;
+
+/// The von Mises distribution `VonMises(mu, kappa)` on the circle.
+///
+/// This is the circular analogue of the normal distribution, and is used to
+/// model angles such as directions or phases. It has a density function:
+/// `f(x) = exp(kappa * cos(x - mu)) / (2 * pi * I0(kappa))` for angles `x`,
+/// where `I0` is the modified Bessel function of order 0.
+///
+/// Samples are angles in the range `(-pi, pi]`. The mean direction is `mu`
+/// and the concentration `kappa` plays the role of the inverse of the
+/// variance; the mean resultant length `E[cos(x - mu)]` is
+/// `I1(kappa) / I0(kappa)`. With `kappa == 0` this is the uniform
+/// distribution on the circle.
+///
+/// Samples are generated with the rejection algorithm of Best and Fisher.
+/// For `kappa < 1e-8` the uniform distribution on the circle is used instead,
+/// and for `kappa > 1e6` the wrapped normal distribution with variance
+/// `1 / kappa`. The error of these approximations is of order `kappa` and
+/// `1 / kappa` respectively, which is far below what can be detected by
+/// statistical tests in practice.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{VonMises, Distribution};
+///
+/// // Wind from the north-east, with some variation
+/// let von_mises = VonMises::new(::std::f64::consts::FRAC_PI_4, 4.0);
+/// let v = von_mises.sample(&mut rand::thread_rng());
+/// println!("{} is from a VonMises(pi/4, 4) distribution", v);
+/// ```
+///
+/// # References
+///
+/// D. J. Best and N. I. Fisher, *Efficient Simulation of the von Mises
+/// Distribution*, Journal of the Royal Statistical Society, Series C 28(2),
+/// 1979, 152-157.
+#[derive(Clone, Copy, Debug)]
+pub struct VonMises {
+    mu: f64,
+    kappa: f64,
+    r: f64,
+}
+
+impl VonMises {
+    /// Construct a new `VonMises` distribution with the given mean direction
+    /// `mu` and concentration `kappa`. `mu` is wrapped into the range
+    /// `(-pi, pi]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `kappa < 0` or if either parameter is not finite.
+    pub fn new(mu: f64, kappa: f64) -> VonMises {
+        unwrap_new(VonMises::try_new(mu, kappa), "VonMises::new")
+    }
+
+    /// Construct a new `VonMises` distribution with the given mean direction
+    /// `mu` and concentration `kappa`, returning an error if `kappa < 0` or if
+    /// either parameter is not finite.
+    pub fn try_new(mu: f64, kappa: f64) -> Result<VonMises, DistributionError> {
+        check_finite(mu, "mu")?;
+        check_non_negative(kappa, "kappa")?;
+        let r = if kappa < KAPPA_SMALL {
+            1.0 / kappa + kappa
+        } else {
+            let tau = 1.0 + (1.0 + 4.0 * kappa * kappa).sqrt();
+            let rho = (tau - (2.0 * tau).sqrt()) / (2.0 * kappa);
+            (1.0 + rho * rho) / (2.0 * rho)
+        };
+        Ok(VonMises {
+            mu: wrap(mu),
+            kappa,
+            r,
+        })
+    }
+}
+
+/// Wrap an angle into the range `(-pi, pi]`. Angles within the range are
+/// returned unchanged.
+fn wrap(x: f64) -> f64 {
+    x - 2.0 * PI * ((x - PI) / (2.0 * PI)).ceil()
+}
+
+impl Distribution<f64> for VonMises {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        if self.kappa < KAPPA_UNIFORM {
+            let u: f64 = rng.gen();
+            return PI - 2.0 * PI * u;
+        }
+        if self.kappa > KAPPA_LARGE {
+            let n: f64 = rng.sample(StandardNormal);
+            return wrap(self.mu + n / self.kappa.sqrt());
+        }
+
+        let f = loop {
+            let u1: f64 = rng.gen();
+            let z = (PI * u1).cos();
+            let f = (1.0 + self.r * z) / (self.r + z);
+            let c = self.kappa * (self.r - f);
+            let u2: f64 = rng.gen();
+            if c * (2.0 - c) > u2 || (c / u2).ln() + 1.0 - c >= 0.0 {
+                break f;
+            }
+        };
+        // Rounding may put `f` just outside `[-1, 1]`.
+        let angle = if f >= 1.0 {
+            0.0
+        } else if f <= -1.0 {
+            PI
+        } else {
+            f.acos()
+        };
+        if rng.gen::<bool>() {
+            wrap(self.mu + angle)
+        } else {
+            wrap(self.mu - angle)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::Distribution;
+    use std::f64::consts::PI;
+    use super::{VonMises, wrap};
+    use distributions::DistributionError::*;
+
+    // `I1(x) / I0(x)` from the series of the modified Bessel functions,
+    // accurate for moderate `x`.
+    fn bessel_ratio(x: f64) -> f64 {
+        let (mut i0, mut i1) = (0.0, 0.0);
+        let mut term = 1.0; // (x/2)^(2k) / (k!)^2
+        for k in 0..100 {
+            let k = k as f64;
+            i0 += term;
+            i1 += term * x / 2.0 / (k + 1.0);
+            term *= (x / 2.0) * (x / 2.0) / ((k + 1.0) * (k + 1.0));
+        }
+        i1 / i0
+    }
+
+    #[test]
+    fn test_von_mises_moments() {
+        let mut rng = ::test::rng(471);
+        for &(mu, kappa) in [(0.0, 0.0), (1.0, 1e-6), (-2.0, 0.5), (3.0, 2.0),
+                             (PI, 10.0), (0.5, 1e6), (-3.1, 1e8)].iter() {
+            let von_mises = VonMises::new(mu, kappa);
+            const N: usize = 100_000;
+            let samples: Vec<f64> = (0..N).map(|_| von_mises.sample(&mut rng)).collect();
+            assert!(samples.iter().all(|&x| x > -PI && x <= PI));
+
+            // The mean resultant length in the direction of `mu`, and the
+            // component orthogonal to it.
+            let expected = if kappa > 100.0 {
+                1.0 - 0.5 / kappa
+            } else {
+                bessel_ratio(kappa)
+            };
+            let cos: Vec<f64> = samples.iter().map(|&x| (x - mu).cos()).collect();
+            let sin: Vec<f64> = samples.iter().map(|&x| (x - mu).sin()).collect();
+            for &(values, expected) in [(&cos, expected), (&sin, 0.0)].iter() {
+                let mean = values.iter().sum::<f64>() / N as f64;
+                let var = values.iter().map(|x| (x - mean).powi(2))
+                    .sum::<f64>() / N as f64;
+                let err = 5.0 * (var / N as f64).sqrt() + 1e-12;
+                assert!((mean - expected).abs() < err,
+                        "kappa {}: mean {} != {}", kappa, mean, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_von_mises_wrap() {
+        assert_eq!(wrap(PI), PI);
+        assert_eq!(wrap(-PI), PI);
+        assert_eq!(wrap(1.0), 1.0);
+        assert_eq!(wrap(-1.0), -1.0);
+        assert!((wrap(3.0 * PI) - PI).abs() < 1e-12);
+        assert!((wrap(7.0) - (7.0 - 2.0 * PI)).abs() < 1e-12);
+        assert!((wrap(-7.0) - (2.0 * PI - 7.0)).abs() < 1e-12);
+        assert_eq!(VonMises::new(2.0 * PI + 1.0, 1.0).mu, wrap(2.0 * PI + 1.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_von_mises_invalid_kappa() {
+        VonMises::new(0.0, -1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_von_mises_infinite_mu() {
+        VonMises::new(::std::f64::INFINITY, 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_von_mises_nan_kappa() {
+        VonMises::new(0.0, ::std::f64::NAN);
+    }
+
+
+    #[test]
+    fn test_von_mises_try_new() {
+        assert!(VonMises::try_new(10.0, 0.0).is_ok());
+        assert_eq!(VonMises::try_new(0.0, -1.0).err(), Some(Negative("kappa")));
+        assert_eq!(VonMises::try_new(0.0, ::std::f64::INFINITY).err(), Some(NonFinite("kappa")));
+        assert_eq!(VonMises::try_new(0.0, ::std::f64::NAN).err(), Some(NonFinite("kappa")));
+        assert_eq!(VonMises::try_new(::std::f64::INFINITY, 1.0).err(), Some(NonFinite("mu")));
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Weibull distribution.
+
+use Rng;
+use distributions::{Distribution, OpenClosed01};
+use distributions::error::{DistributionError, unwrap_new, check_positive};
+
+/// Samples floating-point numbers according to the Weibull distribution
+/// `Weibull(scale, shape)`.
+///
+/// The cumulative distribution function is
+/// `F(x) = 1 - exp(-(x / scale)^shape)` for `x >= 0`. All moments of this
+/// distribution exist; for `shape < 1` it is nevertheless heavy-tailed.
+///
+/// # Example
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand::distributions::Weibull;
+///
+/// let val: f64 = SmallRng::from_entropy().sample(Weibull::new(1., 10.));
+/// println!("{}", val);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Weibull {
+    inv_shape: f64,
+    scale: f64,
+}
+
+impl Weibull {
+    /// Construct a new `Weibull` distribution with given `scale` and `shape`.
+    ///
+    /// # Panics
+    ///
+    /// `scale` and `shape` have to be non-zero, positive and finite.
+    pub fn new(scale: f64, shape: f64) -> Weibull {
+        unwrap_new(Weibull::try_new(scale, shape), "Weibull::new")
+    }
+
+    /// Construct a new `Weibull` distribution with given `scale` and `shape`,
+    /// returning an error if either is not positive and finite.
+    pub fn try_new(scale: f64, shape: f64) -> Result<Weibull, DistributionError> {
+        check_positive(scale, "scale")?;
+        check_positive(shape, "shape")?;
+        Ok(Weibull { inv_shape: 1./shape, scale })
+    }
+}
+
+impl Distribution<f64> for Weibull {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        // `x` is in `(0, 1]`, so the logarithm is finite.
+        let x: f64 = rng.sample(OpenClosed01);
+        self.scale * (-x.ln()).powf(self.inv_shape)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use distributions::Distribution;
+    use super::Weibull;
+    use distributions::DistributionError::*;
+
+    #[test]
+    #[should_panic]
+    fn invalid() {
+        Weibull::new(0., 0.);
+    }
+
+    #[test]
+    fn sample() {
+        let scale = 1.0;
+        let shape = 2.0;
+        let d = Weibull::new(scale, shape);
+        let mut r = ::test::rng(1);
+        for _ in 0..1000 {
+            let x = d.sample(&mut r);
+            assert!(x >= 0. && x.is_finite());
+        }
+    }
+
+    #[test]
+    fn quantiles() {
+        let mut r = ::test::rng(2);
+        for &(scale, shape) in [(1.0, 0.5), (2.0, 1.5), (0.5, 5.0)].iter() {
+            let d = Weibull::new(scale, shape);
+            const N: usize = 100_000;
+            let mut samples: Vec<f64> = (0..N).map(|_| d.sample(&mut r)).collect();
+            samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            for &p in [0.1, 0.5, 0.9].iter() {
+                let x = samples[(p * N as f64) as usize];
+                // the analytic CDF at the empirical quantile
+                let cdf = 1.0 - (-(x / scale).powf(shape)).exp();
+                assert!((cdf - p).abs() < 0.005,
+                        "quantile {}: CDF({}) = {}", p, x, cdf);
+            }
+        }
+    }
+
+
+    #[test]
+    fn try_new() {
+        assert!(Weibull::try_new(1e-300, 1e300).is_ok());
+        assert_eq!(Weibull::try_new(0., 1.).err(), Some(NonPositive("scale")));
+        assert_eq!(Weibull::try_new(1., -1.).err(), Some(NonPositive("shape")));
+        assert_eq!(Weibull::try_new(::std::f64::NAN, 1.).err(), Some(NonFinite("scale")));
+        assert_eq!(Weibull::try_new(1., ::std::f64::INFINITY).err(), Some(NonFinite("shape")));
+    }
+}
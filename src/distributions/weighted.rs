@@ -0,0 +1,410 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Weighted index sampling.
+
+use Rng;
+use distributions::Distribution;
+use distributions::uniform::{UniformSampler, SampleUniform, SampleBorrow};
+use core::cmp::PartialOrd;
+use core::fmt;
+use core::ops::AddAssign;
+
+#[cfg(not(feature="std"))] use alloc::Vec;
+
+/// A distribution using weighted sampling to pick a discretely selected item.
+///
+/// Sampling a `WeightedIndex` distribution returns the index of a randomly
+/// selected element from the iterator used when the `WeightedIndex` was
+/// created. The chance of a given element being picked is proportional to the
+/// value of the element. The weights can use any type `X` for which an
+/// implementation of [`Uniform<X>`] exists.
+///
+/// The cumulative weights are kept alongside the original weights, so that
+/// [`update_weights`] can change a few weights without rebuilding the whole
+/// table.
+///
+/// # Example
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand::distributions::WeightedIndex;
+///
+/// let choices = ['a', 'b', 'c'];
+/// let weights = [2,   1,   1];
+/// let dist = WeightedIndex::new(&weights).unwrap();
+/// let mut rng = thread_rng();
+/// for _ in 0..100 {
+///     // 50% chance to print 'a', 25% chance to print 'b', 25% chance to print 'c'
+///     println!("{}", choices[dist.sample(&mut rng)]);
+/// }
+/// ```
+///
+/// [`Uniform<X>`]: struct.Uniform.html
+/// [`update_weights`]: struct.WeightedIndex.html#method.update_weights
+#[derive(Debug)]
+pub struct WeightedIndex<X: SampleUniform + PartialOrd> {
+    weights: Vec<X>,
+    cumulative_weights: Vec<X>,
+    weight_distribution: X::Sampler,
+}
+
+impl<X: SampleUniform + PartialOrd> WeightedIndex<X> {
+    /// Creates a new a `WeightedIndex` [`Distribution`] using the values
+    /// in `weights`. The weights can use any type `X` for which an
+    /// implementation of [`Uniform<X>`] exists.
+    ///
+    /// Returns an error if the iterator is empty, if any weight is `< 0`, if
+    /// its total value is 0, or if [`Uniform<X>`] rejects the total value (for
+    /// floating point weights, if a weight is NaN or the total is infinite).
+    ///
+    /// [`Distribution`]: trait.Distribution.html
+    /// [`Uniform<X>`]: struct.Uniform.html
+    pub fn new<I>(weights: I) -> Result<WeightedIndex<X>, WeightedError>
+        where I: IntoIterator,
+              I::Item: SampleBorrow<X>,
+              X: for<'a> AddAssign<&'a X> + Clone + Default
+    {
+        let iter = weights.into_iter();
+        let mut weights = Vec::<X>::with_capacity(iter.size_hint().0);
+        let mut cumulative_weights = Vec::<X>::with_capacity(iter.size_hint().0);
+
+        let zero = <X as Default>::default();
+        let mut total_weight = zero.clone();
+        for w in iter {
+            let w = w.borrow();
+            if *w < zero {
+                return Err(WeightedError::NegativeWeight);
+            }
+            total_weight += w;
+            weights.push(w.clone());
+            cumulative_weights.push(total_weight.clone());
+        }
+
+        if weights.is_empty() {
+            return Err(WeightedError::NoItem);
+        }
+        if total_weight == zero {
+            return Err(WeightedError::AllWeightsZero);
+        }
+        let distr = X::Sampler::try_new(zero, total_weight)
+            .map_err(|_| WeightedError::InvalidWeight)?;
+
+        Ok(WeightedIndex {
+            weights,
+            cumulative_weights,
+            weight_distribution: distr,
+        })
+    }
+
+    /// Update a subset of weights, without changing the number of weights.
+    ///
+    /// `new_weights` is a list of `(index, weight)` pairs; if an index occurs
+    /// more than once the last weight given for it is used. Only the
+    /// cumulative weights from the smallest updated index onwards are
+    /// recomputed, so this is much cheaper than creating a new
+    /// `WeightedIndex` when the updated weights are near the end. The
+    /// resulting distribution samples exactly like one created with
+    /// [`new`] from the updated weights.
+    ///
+    /// Returns an error if an index is out of range, if any new weight is
+    /// `< 0`, or if the new total weight is 0 or not accepted by
+    /// [`Uniform<X>`]. On error `self` is left unchanged.
+    ///
+    /// [`new`]: struct.WeightedIndex.html#method.new
+    /// [`Uniform<X>`]: struct.Uniform.html
+    pub fn update_weights(&mut self, new_weights: &[(usize, &X)])
+        -> Result<(), WeightedError>
+        where X: for<'a> AddAssign<&'a X> + Clone + Default
+    {
+        let zero = <X as Default>::default();
+
+        let mut first = self.weights.len();
+        for &(i, w) in new_weights {
+            if i >= self.weights.len() {
+                return Err(WeightedError::IndexOutOfRange);
+            }
+            if *w < zero {
+                return Err(WeightedError::NegativeWeight);
+            }
+            if i < first {
+                first = i;
+            }
+        }
+        if first == self.weights.len() {
+            // nothing to update
+            return Ok(());
+        }
+
+        let mut old_weights = Vec::with_capacity(new_weights.len());
+        for &(i, w) in new_weights {
+            old_weights.push((i, ::core::mem::replace(&mut self.weights[i],
+                                                      w.clone())));
+        }
+
+        let mut total_weight = if first == 0 {
+            zero.clone()
+        } else {
+            self.cumulative_weights[first - 1].clone()
+        };
+        for w in &self.weights[first..] {
+            total_weight += w;
+        }
+        let distr = if total_weight == zero {
+            Err(WeightedError::AllWeightsZero)
+        } else {
+            X::Sampler::try_new(zero.clone(), total_weight)
+                .map_err(|_| WeightedError::InvalidWeight)
+        };
+        let distr = match distr {
+            Ok(distr) => distr,
+            Err(err) => {
+                // Restore in reverse order, in case an index was given twice.
+                for (i, w) in old_weights.into_iter().rev() {
+                    self.weights[i] = w;
+                }
+                return Err(err);
+            }
+        };
+
+        let mut running_total = if first == 0 {
+            zero.clone()
+        } else {
+            self.cumulative_weights[first - 1].clone()
+        };
+        for (w, cw) in self.weights[first..].iter()
+            .zip(self.cumulative_weights[first..].iter_mut())
+        {
+            running_total += w;
+            *cw = running_total.clone();
+        }
+        self.weight_distribution = distr;
+        Ok(())
+    }
+}
+
+impl<X> Clone for WeightedIndex<X>
+    where X: SampleUniform + PartialOrd + Clone, X::Sampler: Clone
+{
+    fn clone(&self) -> Self {
+        WeightedIndex {
+            weights: self.weights.clone(),
+            cumulative_weights: self.cumulative_weights.clone(),
+            weight_distribution: self.weight_distribution.clone(),
+        }
+    }
+}
+
+impl<X> Distribution<usize> for WeightedIndex<X>
+    where X: SampleUniform + PartialOrd
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        use core::cmp::Ordering;
+        let chosen_weight = self.weight_distribution.sample(rng);
+        // Find the first item which has a weight *higher* than the chosen
+        // weight. The last cumulative weight (the total) is left out of the
+        // search, so rounding in float samplers cannot select a non-existent
+        // item past the end.
+        let len = self.cumulative_weights.len();
+        self.cumulative_weights[..len - 1].binary_search_by(
+            |w| if *w <= chosen_weight { Ordering::Less } else { Ordering::Greater })
+            .unwrap_err()
+    }
+}
+
+/// Error type returned from `WeightedIndex::new`,
+/// `WeightedIndex::update_weights`, `Multinomial::try_new` and the weighted
+/// sampling functions of the `seq` module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeightedError {
+    /// The provided iterator contained no items.
+    NoItem,
+
+    /// A weight lower than zero was used.
+    NegativeWeight,
+
+    /// All items in the provided iterator had a weight of zero.
+    AllWeightsZero,
+
+    /// An index passed to `update_weights` was out of range.
+    IndexOutOfRange,
+
+    /// A weight, or the sum of the weights, was NaN or infinite.
+    InvalidWeight,
+
+    /// Fewer items than requested had a positive weight, when sampling
+    /// without replacement.
+    InsufficientNonZero,
+}
+
+impl WeightedError {
+    fn msg(&self) -> &str {
+        match *self {
+            WeightedError::NoItem => "No items found",
+            WeightedError::NegativeWeight => "Item has negative weight",
+            WeightedError::AllWeightsZero => "All items had weight zero",
+            WeightedError::IndexOutOfRange => "Weight index out of range",
+            WeightedError::InvalidWeight => "Weight is not finite",
+            WeightedError::InsufficientNonZero => "Too few items had a positive weight",
+        }
+    }
+}
+
+#[cfg(feature="std")]
+impl ::std::error::Error for WeightedError {
+    fn description(&self) -> &str {
+        self.msg()
+    }
+}
+
+impl fmt::Display for WeightedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use distributions::Distribution;
+
+    #[test]
+    fn test_weightedindex() {
+        let mut r = ::test::rng(700);
+        const N_REPS: u32 = 5000;
+        let weights = [1u32, 2, 3, 0, 5, 6, 7, 1, 2, 3, 4, 5, 6, 7];
+        let total_weight = weights.iter().sum::<u32>() as f32;
+
+        let verify = |result: [i32; 14]| {
+            for (i, count) in result.iter().enumerate() {
+                let exp = (weights[i] * N_REPS) as f32 / total_weight;
+                let mut err = (*count as f32 - exp).abs();
+                if err != 0.0 {
+                    err /= exp;
+                }
+                assert!(err <= 0.25);
+            }
+        };
+
+        // WeightedIndex from vec
+        let mut chosen = [0i32; 14];
+        let distr = WeightedIndex::new(weights.to_vec()).unwrap();
+        for _ in 0..N_REPS {
+            chosen[distr.sample(&mut r)] += 1;
+        }
+        verify(chosen);
+
+        // WeightedIndex from slice
+        chosen = [0i32; 14];
+        let distr = WeightedIndex::new(&weights[..]).unwrap();
+        for _ in 0..N_REPS {
+            chosen[distr.sample(&mut r)] += 1;
+        }
+        verify(chosen);
+
+        for _ in 0..5 {
+            assert_eq!(WeightedIndex::new(&[0, 1]).unwrap().sample(&mut r), 1);
+            assert_eq!(WeightedIndex::new(&[1, 0]).unwrap().sample(&mut r), 0);
+            assert_eq!(WeightedIndex::new(&[0, 0, 0, 0, 10, 0]).unwrap().sample(&mut r), 4);
+        }
+
+        assert_eq!(WeightedIndex::new(&[10][0..0]).unwrap_err(), WeightedError::NoItem);
+        assert_eq!(WeightedIndex::new(&[0]).unwrap_err(), WeightedError::AllWeightsZero);
+        assert_eq!(WeightedIndex::new(&[10, 20, -1, 30]).unwrap_err(), WeightedError::NegativeWeight);
+        assert_eq!(WeightedIndex::new(&[-10, 20, 1, 30]).unwrap_err(), WeightedError::NegativeWeight);
+        assert_eq!(WeightedIndex::new(&[-10]).unwrap_err(), WeightedError::NegativeWeight);
+        assert_eq!(WeightedIndex::new(&[1.0, ::core::f64::NAN]).unwrap_err(),
+                   WeightedError::InvalidWeight);
+        assert_eq!(WeightedIndex::new(&[::core::f64::NAN, 0.0]).unwrap_err(),
+                   WeightedError::InvalidWeight);
+        assert_eq!(WeightedIndex::new(&[1.0, ::core::f64::INFINITY]).unwrap_err(),
+                   WeightedError::InvalidWeight);
+        assert_eq!(WeightedIndex::new(&[1e308, 1e308]).unwrap_err(),
+                   WeightedError::InvalidWeight);
+    }
+
+    #[test]
+    fn test_update_weights() {
+        let data = [
+            (&[10u32, 2, 3, 4][..],
+             &[(1, &100), (2, &4)][..], // positive change
+             &[10, 100, 4, 4][..]),
+            (&[1u32, 2, 3, 0, 5, 6, 7, 1, 2, 3, 4, 5, 6, 7][..],
+             &[(2, &1), (5, &1), (13, &100)][..], // negative and positive change
+             &[1u32, 2, 1, 0, 5, 1, 7, 1, 2, 3, 4, 5, 6, 100][..]),
+            (&[5u32, 5, 5][..],
+             &[(0, &0), (0, &7)][..], // repeated index, last one wins
+             &[7u32, 5, 5][..]),
+        ];
+
+        for (i, &(weights, update, expected_weights)) in data.iter().enumerate() {
+            let mut distr = WeightedIndex::new(weights.to_vec()).unwrap();
+            distr.update_weights(update).unwrap();
+            let expected = WeightedIndex::new(expected_weights.to_vec()).unwrap();
+            assert_eq!(distr.weights, expected.weights);
+            assert_eq!(distr.cumulative_weights, expected.cumulative_weights);
+
+            // Sampling must be identical to a freshly constructed index.
+            let mut rng1 = ::test::rng(701 + i as u64);
+            let mut rng2 = ::test::rng(701 + i as u64);
+            for _ in 0..1000 {
+                assert_eq!(distr.sample(&mut rng1), expected.sample(&mut rng2));
+            }
+        }
+    }
+
+    #[test]
+    fn test_update_weights_float() {
+        let weights = [0.1f64, 0.25, 1.5, 0.125, 3.0];
+        let new_weights = [0.1f64, 0.25, 1.5, 2.75, 0.0];
+        let mut distr = WeightedIndex::new(&weights[..]).unwrap();
+        distr.update_weights(&[(3, &2.75), (4, &0.0)]).unwrap();
+        let expected = WeightedIndex::new(&new_weights[..]).unwrap();
+        assert_eq!(distr.cumulative_weights, expected.cumulative_weights);
+
+        let mut rng1 = ::test::rng(704);
+        let mut rng2 = ::test::rng(704);
+        for _ in 0..1000 {
+            let i = distr.sample(&mut rng1);
+            assert_eq!(i, expected.sample(&mut rng2));
+            assert!(i != 4);
+        }
+    }
+
+    #[test]
+    fn test_update_weights_errors() {
+        let weights = [1i32, 2, 3];
+        let mut distr = WeightedIndex::new(&weights[..]).unwrap();
+        let expected = WeightedIndex::new(&weights[..]).unwrap();
+
+        assert_eq!(distr.update_weights(&[(3, &1)]),
+                   Err(WeightedError::IndexOutOfRange));
+        assert_eq!(distr.update_weights(&[(0, &4), (1, &-1)]),
+                   Err(WeightedError::NegativeWeight));
+        assert_eq!(distr.update_weights(&[(0, &0), (1, &0), (2, &0)]),
+                   Err(WeightedError::AllWeightsZero));
+
+        // failed updates leave the distribution unchanged
+        assert_eq!(distr.weights, expected.weights);
+        assert_eq!(distr.cumulative_weights, expected.cumulative_weights);
+
+        let weights = [1.0f64, 2.0, 3.0];
+        let mut float_distr = WeightedIndex::new(&weights[..]).unwrap();
+        assert_eq!(float_distr.update_weights(&[(2, &::core::f64::NAN)]),
+                   Err(WeightedError::InvalidWeight));
+        assert_eq!(float_distr.update_weights(&[(0, &::core::f64::INFINITY)]),
+                   Err(WeightedError::InvalidWeight));
+        assert_eq!(float_distr.weights, weights);
+
+        // an empty update is a no-op
+        distr.update_weights(&[]).unwrap();
+        assert_eq!(distr.cumulative_weights, expected.cumulative_weights);
+    }
+}
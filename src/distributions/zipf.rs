@@ -0,0 +1,294 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Zipf and Zeta distributions.
+
+use Rng;
+use distributions::{Distribution, OpenClosed01, Standard};
+use distributions::error::{DistributionError, unwrap_new, check_finite, check_positive};
+
+/// The Zipf distribution `Zipf(n, s)`.
+///
+/// Samples are integers in `1..=n`, where `k` is sampled with probability
+/// proportional to `1 / k^s`. This is a common model for the popularity of
+/// items ranked by popularity, e.g. words in a text or keys in a cache.
+///
+/// Samples are generated using the rejection-inversion method of Hörmann and
+/// Derflinger [1], which takes constant time and memory, regardless of `n`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Zipf, Distribution};
+///
+/// let zipf = Zipf::new(1_000_000_000, 1.1);
+/// let key = zipf.sample(&mut rand::thread_rng());
+/// println!("{} is from a Zipf(1e9, 1.1) distribution", key);
+/// ```
+///
+/// [1]: Wolfgang Hörmann and Gerhard Derflinger. 1996. "Rejection-inversion
+/// to generate variates from monotone discrete distributions." *ACM Trans.
+/// Model. Comput. Simul.* 6, 3 (July 1996), 169-184.
+#[derive(Clone, Copy, Debug)]
+pub struct Zipf {
+    n: f64,
+    s: f64,
+    // normalization of the proposal distribution
+    t: f64,
+    // `1 / (1 - s)`, or 0 if `s == 1`
+    q: f64,
+}
+
+impl Zipf {
+    /// Construct a new `Zipf` distribution for `n` elements with exponent
+    /// `s`.
+    ///
+    /// Panics if `n == 0`, or if `s` is not finite and positive.
+    pub fn new(n: u64, s: f64) -> Zipf {
+        unwrap_new(Zipf::try_new(n, s), "Zipf::new")
+    }
+
+    /// Construct a new `Zipf` distribution for `n` elements with exponent
+    /// `s`, returning an error if `n == 0`, or if `s` is not finite and
+    /// positive.
+    pub fn try_new(n: u64, s: f64) -> Result<Zipf, DistributionError> {
+        if n == 0 {
+            return Err(DistributionError::OutOfRange("n"));
+        }
+        check_positive(s, "s")?;
+        let n = n as f64;
+        let q = if s != 1.0 { 1.0 / (1.0 - s) } else { 0.0 };
+        let t = if s != 1.0 {
+            (n.powf(1.0 - s) - s) * q
+        } else {
+            1.0 + n.ln()
+        };
+        debug_assert!(t > 0.0);
+        Ok(Zipf { n, s, t, q })
+    }
+
+    /// Inverse cumulative distribution function of the proposal distribution,
+    /// which has density `1` on `[0, 1]` and `x^-s` on `[1, n]`.
+    #[inline]
+    fn inv_cdf(&self, p: f64) -> f64 {
+        let pt = p * self.t;
+        if pt <= 1.0 {
+            pt
+        } else if self.s != 1.0 {
+            (pt * (1.0 - self.s) + self.s).powf(self.q)
+        } else {
+            (pt - 1.0).exp()
+        }
+    }
+}
+
+impl Distribution<u64> for Zipf {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+        loop {
+            let inv_b = self.inv_cdf(rng.sample(Standard));
+            let x = (inv_b + 1.0).floor();
+            // `inv_b < n` mathematically, but rounding could break this
+            if x > self.n {
+                continue;
+            }
+            // the ratio of the target to the proposal density
+            let mut ratio = x.powf(-self.s);
+            if x > 1.0 {
+                ratio *= inv_b.powf(self.s);
+            }
+            let y: f64 = rng.sample(Standard);
+            if y < ratio {
+                return x as u64;
+            }
+        }
+    }
+}
+
+/// The Zeta distribution `Zeta(s)`.
+///
+/// This is the limit of the [`Zipf`] distribution as `n` goes to infinity:
+/// samples are integers `k >= 1`, sampled with probability `1 / (ζ(s) k^s)`,
+/// where `ζ` is the Riemann zeta function.
+///
+/// The tail of this distribution is very heavy for `s` close to 1. Samples
+/// which do not fit in a `u64` saturate at `u64::MAX`.
+///
+/// Samples are generated using the rejection method of Devroye [1].
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Zeta, Distribution};
+///
+/// let zeta = Zeta::new(2.0);
+/// let v = zeta.sample(&mut rand::thread_rng());
+/// println!("{} is from a Zeta(2) distribution", v);
+/// ```
+///
+/// [1]: Luc Devroye. 1986. *Non-Uniform Random Variate Generation.*
+/// Springer-Verlag, New York. Chapter X.6, page 551.
+///
+/// [`Zipf`]: struct.Zipf.html
+#[derive(Clone, Copy, Debug)]
+pub struct Zeta {
+    a_minus_1: f64,
+    // `2^(s - 1)`
+    b: f64,
+}
+
+impl Zeta {
+    /// Construct a new `Zeta` distribution with exponent `s`.
+    ///
+    /// Panics if `s <= 1` or `s` is not finite.
+    pub fn new(s: f64) -> Zeta {
+        unwrap_new(Zeta::try_new(s), "Zeta::new")
+    }
+
+    /// Construct a new `Zeta` distribution with exponent `s`, returning an
+    /// error if `s <= 1` or `s` is not finite.
+    pub fn try_new(s: f64) -> Result<Zeta, DistributionError> {
+        check_finite(s, "s")?;
+        if s <= 1.0 {
+            return Err(DistributionError::OutOfRange("s"));
+        }
+        let a_minus_1 = s - 1.0;
+        Ok(Zeta { a_minus_1, b: 2f64.powf(a_minus_1) })
+    }
+}
+
+impl Distribution<u64> for Zeta {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+        loop {
+            let u: f64 = rng.sample(OpenClosed01);
+            let x = u.powf(-1.0 / self.a_minus_1).floor();
+            debug_assert!(x >= 1.0);
+            if x >= ::core::u64::MAX as f64 {
+                // This includes infinite `x`, which would always be rejected
+                // below; for small `s` this would loop forever.
+                return ::core::u64::MAX;
+            }
+            let t = (1.0 + 1.0 / x).powf(self.a_minus_1);
+            let v: f64 = rng.sample(Standard);
+            if v * x * (t - 1.0) * self.b <= t * (self.b - 1.0) {
+                return x as u64;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use distributions::Distribution;
+    use super::{Zipf, Zeta};
+    use distributions::DistributionError::*;
+
+    // Compare the frequencies of the first `probs.len()` values with `probs`.
+    fn check_frequencies<D: Distribution<u64>>(distr: D, probs: &[f64], seed: u64) {
+        let mut rng = ::test::rng(seed);
+        const N: usize = 100_000;
+        let mut counts = vec![0usize; probs.len()];
+        for _ in 0..N {
+            let x = distr.sample(&mut rng);
+            assert!(x >= 1);
+            if x as usize <= probs.len() {
+                counts[x as usize - 1] += 1;
+            }
+        }
+        for (k, (&count, &p)) in counts.iter().zip(probs.iter()).enumerate() {
+            let freq = count as f64 / N as f64;
+            // within 5 standard errors
+            assert!((freq - p).abs() < 5.0 * (p / N as f64).sqrt(),
+                    "frequency of {}: {} != {}", k + 1, freq, p);
+        }
+    }
+
+    fn zipf_probs(n: u64, s: f64, top: usize) -> Vec<f64> {
+        let h: f64 = (1..n + 1).map(|k| (k as f64).powf(-s)).sum();
+        (1..top + 1).map(|k| (k as f64).powf(-s) / h).collect()
+    }
+
+    #[test]
+    fn test_zipf() {
+        check_frequencies(Zipf::new(10, 2.0), &zipf_probs(10, 2.0, 10), 471);
+        check_frequencies(Zipf::new(1000, 1.0), &zipf_probs(1000, 1.0, 5), 472);
+        check_frequencies(Zipf::new(100_000, 0.7), &zipf_probs(100_000, 0.7, 5), 473);
+        check_frequencies(Zipf::new(1, 1.5), &[1.0], 474);
+    }
+
+    #[test]
+    fn test_zipf_range() {
+        let mut rng = ::test::rng(475);
+        for &(n, s) in [(1, 0.5), (2, 1.0), (3, 3.0), (100, 0.01), (50, 1e-8),
+                        (5_000_000_000, 1.2), (5_000_000_000, 0.1)].iter() {
+            let zipf = Zipf::new(n, s);
+            for _ in 0..10_000 {
+                let x = zipf.sample(&mut rng);
+                assert!(x >= 1 && x <= n, "{} not in 1..={}", x, n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_zeta() {
+        let zeta_2 = ::std::f64::consts::PI * ::std::f64::consts::PI / 6.0;
+        let probs: Vec<f64> = (1..6).map(|k| 1.0 / (zeta_2 * (k * k) as f64)).collect();
+        check_frequencies(Zeta::new(2.0), &probs, 476);
+
+        let zeta_3 = 1.202_056_903_159_594_2;
+        let probs: Vec<f64> = (1..6).map(|k| 1.0 / (zeta_3 * (k * k * k) as f64)).collect();
+        check_frequencies(Zeta::new(3.0), &probs, 477);
+    }
+
+    #[test]
+    fn test_zeta_heavy_tail() {
+        // Most samples saturate for `s` this close to 1.
+        let mut rng = ::test::rng(478);
+        let zeta = Zeta::new(1.0 + 1e-3);
+        for _ in 0..1000 {
+            assert!(zeta.sample(&mut rng) >= 1);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zipf_invalid_n() {
+        Zipf::new(0, 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zipf_invalid_s() {
+        Zipf::new(10, 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zeta_invalid_s() {
+        Zeta::new(1.0);
+    }
+
+
+    #[test]
+    fn test_zipf_try_new() {
+        assert!(Zipf::try_new(1, 1e-300).is_ok());
+        assert_eq!(Zipf::try_new(0, 1.0).err(), Some(OutOfRange("n")));
+        assert_eq!(Zipf::try_new(10, 0.0).err(), Some(NonPositive("s")));
+        assert_eq!(Zipf::try_new(10, ::std::f64::NAN).err(), Some(NonFinite("s")));
+    }
+
+    #[test]
+    fn test_zeta_try_new() {
+        assert!(Zeta::try_new(1.0 + 1e-10).is_ok());
+        assert_eq!(Zeta::try_new(1.0).err(), Some(OutOfRange("s")));
+        assert_eq!(Zeta::try_new(-2.0).err(), Some(OutOfRange("s")));
+        assert_eq!(Zeta::try_new(::std::f64::INFINITY).err(), Some(NonFinite("s")));
+        assert_eq!(Zeta::try_new(::std::f64::NAN).err(), Some(NonFinite("s")));
+    }
+}
@@ -129,10 +129,10 @@
 //! - [`Rng::gen_bool`] generates boolean "events" with a given probability.
 //! - [`Rng::fill`] and [`Rng::try_fill`] are fast alternatives to fill a slice
 //!   of integers.
-//! - [`Rng::shuffle`] randomly shuffles elements in a slice.
-//! - [`Rng::choose`] picks one element at random from a slice.
 //!
-//! For more slice/sequence related functionality, look in the [`seq` module].
+//! Slices can be shuffled and sampled with the methods of [`SliceRandom`], and
+//! iterators with those of [`IteratorRandom`]. For more slice/sequence related
+//! functionality, look in the [`seq` module].
 //!
 //! There is also [`distributions::WeightedChoice`], which can be used to pick
 //! elements at random with some probability. But it does not work well at the
@@ -197,12 +197,12 @@
 //! [`rand_core`]: https://crates.io/crates/rand_core
 //! [`random()`]: fn.random.html
 //! [`ReadRng`]: rngs/adapter/struct.ReadRng.html
-//! [`Rng::choose`]: trait.Rng.html#method.choose
 //! [`Rng::fill`]: trait.Rng.html#method.fill
 //! [`Rng::gen_bool`]: trait.Rng.html#method.gen_bool
 //! [`Rng::gen`]: trait.Rng.html#method.gen
 //! [`Rng::sample_iter`]: trait.Rng.html#method.sample_iter
-//! [`Rng::shuffle`]: trait.Rng.html#method.shuffle
+//! [`SliceRandom`]: seq/trait.SliceRandom.html
+//! [`IteratorRandom`]: seq/trait.IteratorRandom.html
 //! [`RngCore`]: trait.RngCore.html
 //! [`RngCore::try_fill_bytes`]: trait.RngCore.html#method.try_fill_bytes
 //! [`rngs` module]: rngs/index.html
@@ -241,6 +241,7 @@
 #[cfg(test)] #[cfg(feature="serde1")] extern crate bincode;
 #[cfg(feature="serde1")] extern crate serde;
 #[cfg(feature="serde1")] #[macro_use] extern crate serde_derive;
+#[cfg(feature="derive")] extern crate rand_derive;
 
 #[cfg(all(target_arch="wasm32", not(target_os="emscripten"), feature="stdweb"))]
 #[macro_use]
@@ -258,7 +259,7 @@ extern crate rand_core;
 
 // Re-exports from rand_core
 pub use rand_core::{RngCore, CryptoRng, SeedableRng};
-pub use rand_core::{ErrorKind, Error};
+pub use rand_core::{ErrorKind, ErrorSeverity, Error};
 
 // Public exports
 #[cfg(feature="std")] pub use rngs::thread::thread_rng;
@@ -268,7 +269,7 @@ pub mod distributions;
 pub mod prelude;
 pub mod prng;
 pub mod rngs;
-#[cfg(feature = "alloc")] pub mod seq;
+pub mod seq;
 
 ////////////////////////////////////////////////////////////////////////////////
 // Compatibility re-exports. Documentation is hidden; will be removed eventually.
@@ -300,8 +301,9 @@ pub mod isaac {
 
 
 use core::{marker, mem, slice};
+#[cfg(all(feature="alloc", not(feature="std")))] use alloc::Vec;
 use distributions::{Distribution, Standard};
-use distributions::uniform::{SampleUniform, UniformSampler};
+use distributions::uniform::{SampleUniform, Uniform, UniformSampler};
 
 
 /// A type that can be randomly generated using an [`Rng`].
@@ -386,7 +388,8 @@ pub trait Rng: RngCore {
     ///
     /// # Panics
     ///
-    /// Panics if `low >= high`.
+    /// Panics if `low >= high`. As an exception, floating-point ranges with
+    /// `low == high` are allowed and always return `low`.
     ///
     /// # Example
     ///
@@ -402,7 +405,121 @@ pub trait Rng: RngCore {
     ///
     /// [`Uniform`]: distributions/uniform/struct.Uniform.html
     fn gen_range<T: PartialOrd + SampleUniform>(&mut self, low: T, high: T) -> T {
-        T::Sampler::sample_single(low, high, self)
+        Uniform::sample_single(low, high, self)
+    }
+
+    /// Generate a random value in the range [`low`, `high`), returning `low`
+    /// instead of panicking if the range is empty or invalid.
+    ///
+    /// This is meant for ranges with bounds from user input or other
+    /// parameters that may be out of order. If `low >= high`, or if `low`
+    /// and `high` can not be compared (a floating point NaN), `low` is
+    /// returned without using the RNG. Floating point ranges which
+    /// [`Uniform::try_new`] rejects, such as ranges with an infinite bound,
+    /// also return `low`.
+    ///
+    /// Note that this trades a panic for potentially surprising behaviour:
+    /// an inverted range is not swapped, and a caller passing bounds in the
+    /// wrong order gets a constant instead of an error. Use [`gen_range`]
+    /// when an invalid range is a bug.
+    ///
+    /// Valid ranges are sampled like `Uniform::new(low, high)`, so results
+    /// can differ from those of `gen_range`, which is optimised for a single
+    /// sample.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let n: u32 = rng.gen_range_clamped(0, 10);
+    /// assert!(n < 10);
+    /// // An empty range returns `low`
+    /// assert_eq!(rng.gen_range_clamped(10, 10), 10);
+    /// assert_eq!(rng.gen_range_clamped(10, 3), 10);
+    /// ```
+    ///
+    /// [`Uniform::try_new`]: distributions/uniform/struct.Uniform.html#method.try_new
+    /// [`gen_range`]: #method.gen_range
+    fn gen_range_clamped<T>(&mut self, low: T, high: T) -> T
+        where T: PartialOrd + Clone + SampleUniform
+    {
+        if low.partial_cmp(&high) != Some(::core::cmp::Ordering::Less) {
+            return low;
+        }
+        match Uniform::try_new(low.clone(), high) {
+            Ok(range) => range.sample(self),
+            Err(_) => low,
+        }
+    }
+
+    /// Generate a random value in the range [`low`, `high`), except for the
+    /// value `exclude`.
+    ///
+    /// All other values in the range are equally likely. For integers, a
+    /// value is sampled from a range with one value less, and values from
+    /// `exclude` upwards are shifted up by one; this never needs to resample.
+    /// If `exclude` is not in the range, this is the same as `gen_range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `low >= high`, or if `exclude` is the only value in the
+    /// range. As with `gen_range`, floating-point ranges with `low == high`
+    /// are allowed if `exclude` is a different value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let current = 3;
+    /// // Move to a different one of 8 tiles.
+    /// let next: usize = rng.gen_range_excluding(0, 8, current);
+    /// assert!(next != current);
+    /// ```
+    fn gen_range_excluding<T>(&mut self, low: T, high: T, exclude: T) -> T
+        where T: PartialOrd + Clone + SampleUniform
+    {
+        <T::Sampler as UniformSampler>::sample_single_excluding(
+            low, high, exclude, self)
+    }
+
+    /// Generate a random index in the range `[0, bound)`.
+    ///
+    /// This is a faster alternative to `gen_range(0, bound)`, intended for hot
+    /// paths such as shuffling and choosing elements. It uses Lemire's
+    /// multiply-shift technique, but instead of rejecting some samples to
+    /// remove the bias it draws at most one extra random value, and usually
+    /// none. The remaining bias is tiny: for `bound <= u32::MAX` each index
+    /// has a probability within 2<sup>-64</sup> of `1 / bound`, and for larger
+    /// bounds within 2<sup>-128</sup>.
+    ///
+    /// Results for bounds up to `u32::MAX` only consume `u32` values, and are
+    /// the same on 32-bit and 64-bit platforms.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bound == 0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let items = ["apple", "banana", "cherry"];
+    /// let i = thread_rng().gen_index(items.len());
+    /// println!("{}", items[i]);
+    /// ```
+    #[inline]
+    fn gen_index(&mut self, bound: usize) -> usize {
+        assert!(bound > 0, "Rng::gen_index called with bound == 0");
+        if bound <= ::core::u32::MAX as usize {
+            distributions::uniform::sample_below_u32(self, bound as u32) as usize
+        } else {
+            distributions::uniform::sample_below_u64(self, bound as u64) as usize
+        }
     }
 
     /// Sample a new value, using the given distribution.
@@ -437,7 +554,7 @@ pub trait Rng: RngCore {
     /// let v: Vec<f32> = thread_rng().sample_iter(&Standard).take(16).collect();
     ///
     /// // String:
-    /// let s: String = rng.sample_iter(&Alphanumeric).take(7).collect();
+    /// let s: String = rng.sample_iter::<char, _>(&Alphanumeric).take(7).collect();
     ///
     /// // Combined values
     /// println!("{:?}", thread_rng().sample_iter(&Standard).take(5)
@@ -456,6 +573,66 @@ pub trait Rng: RngCore {
         distr.sample_iter(self)
     }
 
+    /// Repeatedly sample from the given distribution until `pred` accepts a
+    /// value, then return that value.
+    ///
+    /// This is a convenient way to write a rejection loop. Note that this
+    /// never returns if `pred` rejects every value the distribution can
+    /// produce; see [`sample_until_n`] for a bounded variant.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    /// use rand::distributions::Uniform;
+    ///
+    /// let mut rng = thread_rng();
+    /// let die = Uniform::new_inclusive(1, 6);
+    /// // Roll until we get a 6.
+    /// let roll = rng.sample_until(&die, |&x| x == 6);
+    /// assert_eq!(roll, 6);
+    /// ```
+    ///
+    /// [`sample_until_n`]: trait.Rng.html#method.sample_until_n
+    fn sample_until<T, D, F>(&mut self, distr: &D, mut pred: F) -> T
+        where D: Distribution<T>, F: FnMut(&T) -> bool
+    {
+        loop {
+            let x = distr.sample(self);
+            if pred(&x) {
+                return x;
+            }
+        }
+    }
+
+    /// Sample from the given distribution at most `n` times, returning the
+    /// first value accepted by `pred`, or `None` if all `n` samples are
+    /// rejected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    /// use rand::distributions::Uniform;
+    ///
+    /// let mut rng = thread_rng();
+    /// let die = Uniform::new_inclusive(1, 6);
+    /// // A 7 never comes up, so this gives up after 10 rolls.
+    /// assert_eq!(rng.sample_until_n(&die, 10, |&x| x == 7), None);
+    /// ```
+    fn sample_until_n<T, D, F>(&mut self, distr: &D, n: usize, mut pred: F)
+        -> Option<T>
+        where D: Distribution<T>, F: FnMut(&T) -> bool
+    {
+        for _ in 0..n {
+            let x = distr.sample(self);
+            if pred(&x) {
+                return Some(x);
+            }
+        }
+        None
+    }
+
     /// Fill `dest` entirely with random bytes (uniform value distribution),
     /// where `dest` is any type supporting [`AsByteSliceMut`], namely slices
     /// and arrays over primitive integer types (`i8`, `i16`, `u32`, etc.).
@@ -496,6 +673,10 @@ pub trait Rng: RngCore {
     /// documentation for the RNG in use. If you do not plan to handle these
     /// errors you may prefer to use [`fill`].
     ///
+    /// If an error is returned, `dest` may have been partially overwritten:
+    /// its contents are then unspecified (but still valid values of the
+    /// element type), and should not be relied upon to be random or unchanged.
+    ///
     /// # Example
     ///
     /// ```
@@ -521,6 +702,61 @@ pub trait Rng: RngCore {
         Ok(())
     }
 
+    /// Return a newly allocated vector of `len` random bytes.
+    ///
+    /// This uses [`fill_bytes`] internally, which may handle some RNG errors
+    /// implicitly but panics on other errors. If `len` is 0 the RNG is not
+    /// used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let token = thread_rng().gen_bytes(32);
+    /// assert_eq!(token.len(), 32);
+    /// ```
+    ///
+    /// [`fill_bytes`]: trait.RngCore.html#method.fill_bytes
+    #[cfg(feature = "alloc")]
+    fn gen_bytes(&mut self, len: usize) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(len);
+        if len > 0 {
+            bytes.resize(len, 0);
+            self.fill_bytes(&mut bytes);
+        }
+        bytes
+    }
+
+    /// Generate a seed for another RNG, of type `S`.
+    ///
+    /// This creates a default value of `S` and fills its bytes using
+    /// [`fill_bytes`]. It is intended for the `Seed` types of
+    /// [`SeedableRng`], which are byte arrays; unlike [`SeedableRng::from_rng`]
+    /// this makes the seed available, e.g. to store it and recreate the RNG
+    /// later.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng, SeedableRng};
+    /// use rand::prng::ChaChaRng;
+    ///
+    /// let seed = thread_rng().gen_seed::<<ChaChaRng as SeedableRng>::Seed>();
+    /// let mut rng1 = ChaChaRng::from_seed(seed);
+    /// let mut rng2 = ChaChaRng::from_seed(seed);
+    /// assert_eq!(rng1.gen::<u64>(), rng2.gen::<u64>());
+    /// ```
+    ///
+    /// [`fill_bytes`]: trait.RngCore.html#method.fill_bytes
+    /// [`SeedableRng`]: trait.SeedableRng.html
+    /// [`SeedableRng::from_rng`]: trait.SeedableRng.html#method.from_rng
+    fn gen_seed<S: Default + AsMut<[u8]>>(&mut self) -> S {
+        let mut seed = S::default();
+        self.fill_bytes(seed.as_mut());
+        seed
+    }
+
     /// Return a bool with a probability `p` of being true.
     ///
     /// This is a wrapper around [`distributions::Bernoulli`].
@@ -541,17 +777,48 @@ pub trait Rng: RngCore {
     /// [`distributions::Bernoulli`]: distributions/bernoulli/struct.Bernoulli.html
     #[inline]
     fn gen_bool(&mut self, p: f64) -> bool {
-        let d = distributions::Bernoulli::new(p);
+        let d = distributions::Bernoulli::new(p).unwrap_or_else(|_|
+            panic!("Rng::gen_bool called with p not in the range [0, 1]"));
         self.sample(d)
     }
 
+    /// Fill `dest` with fair random bools.
+    ///
+    /// Each `next_u64` call provides the values of 64 bools, one bit each,
+    /// starting with the most significant bit, so this is much faster than
+    /// generating the bools one by one. This is the same as
+    /// `Standard.sample_fill(rng, dest)`. Use [`gen_bool`] or
+    /// [`distributions::Bernoulli`] for other probabilities.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut coins = [false; 100];
+    /// thread_rng().gen_bools(&mut coins);
+    /// println!("{} heads", coins.iter().filter(|&&c| c).count());
+    /// ```
+    ///
+    /// [`gen_bool`]: #method.gen_bool
+    /// [`distributions::Bernoulli`]: distributions/bernoulli/struct.Bernoulli.html
+    #[inline]
+    fn gen_bools(&mut self, dest: &mut [bool]) {
+        Standard.sample_fill(self, dest)
+    }
+
     /// Return a random element from `values`.
     ///
     /// Return `None` if `values` is empty.
     ///
+    /// Deprecated: use [`SliceRandom::choose`] instead. This method still
+    /// gives the same results as in Rand 0.5.0, which differ from those of
+    /// `SliceRandom::choose`.
+    ///
     /// # Example
     ///
     /// ```
+    /// # #![allow(deprecated)]
     /// use rand::{thread_rng, Rng};
     ///
     /// let choices = [1, 2, 4, 8, 16, 32];
@@ -559,23 +826,35 @@ pub trait Rng: RngCore {
     /// println!("{:?}", rng.choose(&choices));
     /// assert_eq!(rng.choose(&choices[..0]), None);
     /// ```
+    ///
+    /// [`SliceRandom::choose`]: seq/trait.SliceRandom.html#tymethod.choose
+    #[deprecated(since="0.5.1", note="use SliceRandom::choose instead")]
     fn choose<'a, T>(&mut self, values: &'a [T]) -> Option<&'a T> {
         if values.is_empty() {
             None
         } else {
-            Some(&values[self.gen_range(0, values.len())])
+            let i = distributions::uniform::sample_below_usize_0_5(self, values.len());
+            Some(&values[i])
         }
     }
 
     /// Return a mutable pointer to a random element from `values`.
     ///
     /// Return `None` if `values` is empty.
+    ///
+    /// Deprecated: use [`SliceRandom::choose_mut`] instead. This method still
+    /// gives the same results as in Rand 0.5.0, which differ from those of
+    /// `SliceRandom::choose_mut`.
+    ///
+    /// [`SliceRandom::choose_mut`]: seq/trait.SliceRandom.html#tymethod.choose_mut
+    #[deprecated(since="0.5.1", note="use SliceRandom::choose_mut instead")]
     fn choose_mut<'a, T>(&mut self, values: &'a mut [T]) -> Option<&'a mut T> {
         if values.is_empty() {
             None
         } else {
             let len = values.len();
-            Some(&mut values[self.gen_range(0, len)])
+            let i = distributions::uniform::sample_below_usize_0_5(self, len);
+            Some(&mut values[i])
         }
     }
 
@@ -585,9 +864,14 @@ pub trait Rng: RngCore {
     /// https://en.wikipedia.org/wiki/Fisher%E2%80%93Yates_shuffle#The_modern_algorithm)
     /// which produces an unbiased permutation.
     ///
+    /// Deprecated: use [`SliceRandom::shuffle`] instead. This method still
+    /// gives the same permutation as in Rand 0.5.0, which differs from that
+    /// of `SliceRandom::shuffle`.
+    ///
     /// # Example
     ///
     /// ```
+    /// # #![allow(deprecated)]
     /// use rand::{thread_rng, Rng};
     ///
     /// let mut rng = thread_rng();
@@ -597,13 +881,17 @@ pub trait Rng: RngCore {
     /// rng.shuffle(&mut y);
     /// println!("{:?}", y);
     /// ```
+    ///
+    /// [`SliceRandom::shuffle`]: seq/trait.SliceRandom.html#tymethod.shuffle
+    #[deprecated(since="0.5.1", note="use SliceRandom::shuffle instead")]
     fn shuffle<T>(&mut self, values: &mut [T]) {
         let mut i = values.len();
         while i >= 2 {
             // invariant: elements with index >= i have been locked in place.
             i -= 1;
             // lock element i in place.
-            values.swap(i, self.gen_range(0, i + 1));
+            let j = distributions::uniform::sample_below_usize_0_5(self, i + 1);
+            values.swap(i, j);
         }
     }
 
@@ -663,7 +951,7 @@ pub trait Rng: RngCore {
     /// println!("{}", s);
     /// ```
     #[allow(deprecated)]
-    #[deprecated(since="0.5.0", note="use sample_iter(&Alphanumeric) instead")]
+    #[deprecated(since="0.5.0", note="use sample_iter::<char, _>(&Alphanumeric) instead")]
     fn gen_ascii_chars(&mut self) -> AsciiGenerator<&mut Self> {
         AsciiGenerator { rng: self }
     }
@@ -725,13 +1013,13 @@ macro_rules! impl_as_byte_slice {
 impl_as_byte_slice!(u16);
 impl_as_byte_slice!(u32);
 impl_as_byte_slice!(u64);
-#[cfg(feature="i128_support")] impl_as_byte_slice!(u128);
+#[cfg(any(feature="i128_support", rustc_1_26))] impl_as_byte_slice!(u128);
 impl_as_byte_slice!(usize);
 impl_as_byte_slice!(i8);
 impl_as_byte_slice!(i16);
 impl_as_byte_slice!(i32);
 impl_as_byte_slice!(i64);
-#[cfg(feature="i128_support")] impl_as_byte_slice!(i128);
+#[cfg(any(feature="i128_support", rustc_1_26))] impl_as_byte_slice!(i128);
 impl_as_byte_slice!(isize);
 
 macro_rules! impl_as_byte_slice_arrays {
@@ -988,6 +1276,7 @@ pub fn sample<T, I, R>(rng: &mut R, iterable: I, amount: usize) -> Vec<T>
 mod test {
     use rngs::mock::StepRng;
     use super::*;
+    use seq::SliceRandom;
     #[cfg(all(not(feature="std"), feature="alloc"))] use alloc::boxed::Box;
 
     pub struct TestRng<R> { inner: R }
@@ -1064,6 +1353,100 @@ mod test {
         assert_eq!(rng.next_u32(), x as u32);
     }
     
+    #[test]
+    fn test_gen_seed() {
+        use prng::{ChaChaRng, XorShiftRng};
+        let mut rng = rng(112);
+        let seed = rng.gen_seed::<<ChaChaRng as SeedableRng>::Seed>();
+        let mut expected = [0u8; 32];
+        ::test::rng(112).fill_bytes(&mut expected);
+        assert_eq!(seed, expected);
+
+        // The seed reproduces the same stream.
+        let mut chacha1 = ChaChaRng::from_seed(seed);
+        let mut chacha2 = ChaChaRng::from_seed(seed);
+        for _ in 0..16 {
+            assert_eq!(chacha1.next_u64(), chacha2.next_u64());
+        }
+
+        let seed: [u8; 16] = rng.gen_seed();
+        let mut xorshift1 = XorShiftRng::from_seed(seed);
+        let mut xorshift2 = XorShiftRng::from_seed(seed);
+        for _ in 0..16 {
+            assert_eq!(xorshift1.next_u32(), xorshift2.next_u32());
+        }
+    }
+
+    #[test]
+    #[cfg(feature="alloc")]
+    fn test_gen_bytes() {
+        let mut rng = rng(111);
+        for &len in [1, 7, 32, 1000].iter() {
+            let a = rng.gen_bytes(len);
+            let b = rng.gen_bytes(len);
+            assert_eq!(a.len(), len);
+            assert_eq!(b.len(), len);
+            if len >= 7 {
+                assert!(a != b);
+            }
+        }
+
+        // A length of 0 does not use the RNG.
+        struct PanickingRng;
+        impl RngCore for PanickingRng {
+            fn next_u32(&mut self) -> u32 { panic!() }
+            fn next_u64(&mut self) -> u64 { panic!() }
+            fn fill_bytes(&mut self, _dest: &mut [u8]) { panic!() }
+            fn try_fill_bytes(&mut self, _dest: &mut [u8]) -> Result<(), Error> {
+                panic!()
+            }
+        }
+        assert!(PanickingRng.gen_bytes(0).is_empty());
+    }
+
+    #[test]
+    fn test_try_fill_error() {
+        // Yields random bytes until the given number of bytes have been
+        // requested, then fails.
+        struct FailingRng { remaining: usize }
+
+        impl RngCore for FailingRng {
+            fn next_u32(&mut self) -> u32 {
+                panic!("not used by try_fill")
+            }
+            fn next_u64(&mut self) -> u64 {
+                panic!("not used by try_fill")
+            }
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                self.try_fill_bytes(dest).unwrap()
+            }
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+                if dest.len() > self.remaining {
+                    self.remaining = 0;
+                    return Err(Error::new(ErrorKind::Unavailable, "out of bytes"));
+                }
+                self.remaining -= dest.len();
+                for b in dest.iter_mut() {
+                    *b = 0xAA;
+                }
+                Ok(())
+            }
+        }
+
+        let mut rng = FailingRng { remaining: 16 };
+        let mut array = [0u32; 4];
+        assert!(rng.try_fill(&mut array[..]).is_ok());
+        assert_eq!(array, [0xAAAA_AAAA; 4]);
+
+        let mut array = [0u64; 2];
+        let err = rng.try_fill(&mut array[..]).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Unavailable);
+
+        let mut rng = FailingRng { remaining: 7 };
+        let mut array = [0u16; 4];
+        assert!(rng.try_fill(&mut array).is_err());
+    }
+
     #[test]
     fn test_fill_empty() {
         let mut array = [0u32; 0];
@@ -1091,6 +1474,111 @@ mod test {
 
     }
 
+    #[test]
+    fn test_gen_range_clamped() {
+        let mut r = rng(109);
+        for _ in 0..1000 {
+            let a = r.gen_range_clamped(-3, 42);
+            assert!(a >= -3 && a < 42);
+            let b = r.gen_range_clamped(10u8, 255);
+            assert!(b >= 10 && b < 255);
+            let c = r.gen_range_clamped(-1.5f64, 2.0);
+            assert!(c >= -1.5 && c < 2.0);
+        }
+
+        // Empty and inverted ranges return `low` without using the RNG
+        let mut r = StepRng::new(0, 1);
+        assert_eq!(r.gen_range_clamped(5, 5), 5);
+        assert_eq!(r.gen_range_clamped(5, -2), 5);
+        assert_eq!(r.gen_range_clamped(::core::u64::MAX, 0), ::core::u64::MAX);
+        assert_eq!(r.gen_range_clamped(2.5f32, 2.5), 2.5);
+        assert_eq!(r.gen_range_clamped(1.0f64, -1.0), 1.0);
+        assert_eq!(r.next_u64(), 0);
+
+        // Invalid floating point bounds return `low`
+        assert_eq!(r.gen_range_clamped(0.0, ::core::f64::INFINITY), 0.0);
+        assert_eq!(r.gen_range_clamped(::core::f64::NEG_INFINITY, 0.0),
+                   ::core::f64::NEG_INFINITY);
+        assert_eq!(r.gen_range_clamped(0.0, ::core::f64::NAN), 0.0);
+        assert!(r.gen_range_clamped(::core::f64::NAN, 0.0).is_nan());
+    }
+
+    #[cfg(feature="std")]
+    #[test]
+    fn test_gen_index() {
+        let mut r = rng(108);
+        // every index is produced about equally often
+        for bound in (1..21).chain([100, 1000].iter().cloned()) {
+            let n = 2000 * bound;
+            let mut counts = vec![0usize; bound];
+            for _ in 0..n {
+                counts[r.gen_index(bound)] += 1;
+            }
+            let p = 1.0 / bound as f64;
+            let err = 5.0 * (n as f64 * p * (1.0 - p)).sqrt();
+            for &count in counts.iter() {
+                assert!((count as f64 - n as f64 * p).abs() <= err,
+                        "bound {}: count {} != {}", bound, count, n as f64 * p);
+            }
+        }
+
+        // extreme random values, with and without an extra draw
+        for &bound in [1, 2, 3, 1000, ::core::u32::MAX as usize].iter() {
+            assert_eq!(StepRng::new(0, 0).gen_index(bound), 0);
+            assert_eq!(StepRng::new(!0, 0).gen_index(bound), bound - 1);
+        }
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn test_gen_index_large() {
+        let mut r = rng(110);
+        let bound = (1usize << 40) + 12345;
+        let mut high = 0;
+        for _ in 0..1000 {
+            let i = r.gen_index(bound);
+            assert!(i < bound);
+            high |= i >> 32;
+        }
+        // the upper bits are used
+        assert_eq!(high, 0xff);
+        assert_eq!(StepRng::new(!0, 0).gen_index(bound), bound - 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_index_panic() {
+        rng(109).gen_index(0);
+    }
+
+    #[test]
+    fn test_sample_until() {
+        use distributions::Standard;
+        let mut r = StepRng::new(0, 1);
+        let x: u32 = r.sample_until(&Standard, |&x: &u32| x == 5);
+        assert_eq!(x, 5);
+        // stopped at exactly the accepting draw
+        assert_eq!(r.next_u32(), 6);
+    }
+
+    #[test]
+    fn test_sample_until_n() {
+        use distributions::Standard;
+        let mut r = StepRng::new(0, 1);
+        let x: Option<u32> = r.sample_until_n(&Standard, 3, |&x: &u32| x == 5);
+        assert_eq!(x, None);
+        assert_eq!(r.next_u32(), 3);
+
+        let mut r = StepRng::new(0, 1);
+        let x: Option<u32> = r.sample_until_n(&Standard, 6, |&x: &u32| x == 5);
+        assert_eq!(x, Some(5));
+        assert_eq!(r.next_u32(), 6);
+
+        let x: Option<u32> = r.sample_until_n(&Standard, 0, |_| true);
+        assert_eq!(x, None);
+        assert_eq!(r.next_u32(), 7);
+    }
+
     #[test]
     #[should_panic]
     fn test_gen_range_panic_int() {
@@ -1123,15 +1611,49 @@ mod test {
     }
 
     #[test]
+    fn test_gen_bools() {
+        let mut r = rng(464);
+        const N: usize = 100_000;
+        let mut bools = [false; 1000];
+        let mut count = 0;
+        for _ in 0..N / 1000 {
+            r.gen_bools(&mut bools);
+            count += bools.iter().filter(|&&b| b).count();
+        }
+        let p = count as f64 / N as f64;
+        assert!((p - 0.5).abs() < 5.0 * (0.25 / N as f64).sqrt(), "{}", p);
+
+        // Lengths which are not multiples of 64 are filled completely, using
+        // the most significant bits first.
+        for &len in [0, 1, 63, 64, 65, 130].iter() {
+            let mut ones = ::rngs::mock::StepRng::new(!0, 0);
+            let mut bools = [false; 130];
+            ones.gen_bools(&mut bools[..len]);
+            assert!(bools[..len].iter().all(|&b| b));
+            assert!(bools[len..].iter().all(|&b| !b));
+        }
+        let mut bits = ::rngs::mock::StepRng::new(0x8000_0000_0000_0001, 0);
+        let mut bools = [false; 65];
+        bits.gen_bools(&mut bools);
+        assert!(bools[0] && bools[63] && bools[64]);
+        assert_eq!(bools.iter().filter(|&&b| b).count(), 3);
+    }
+
+    #[test]
+    #[allow(deprecated)]
     fn test_choose() {
         let mut r = rng(107);
         assert_eq!(r.choose(&[1, 1, 1]).map(|&x|x), Some(1));
 
         let v: &[isize] = &[];
         assert_eq!(r.choose(v), None);
+        let v: &mut [isize] = &mut [];
+        assert_eq!(r.choose_mut(v), None);
+
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_shuffle() {
         let mut r = rng(108);
         let empty: &mut [isize] = &mut [];
@@ -1151,6 +1673,23 @@ mod test {
         assert_eq!(x, b);
     }
 
+    #[test]
+    #[cfg(feature="std")]
+    #[allow(deprecated)]
+    fn test_deprecated_slice_methods_reproducible() {
+        // The deprecated methods give the same results as in Rand 0.5.0.
+        use prng::ChaChaRng;
+        let mut r = ChaChaRng::from_seed([7; 32]);
+        let mut v: Vec<u32> = (0..12).collect();
+        r.shuffle(&mut v);
+        assert_eq!(v, [5, 10, 7, 11, 8, 9, 6, 0, 2, 4, 1, 3]);
+        let chosen: Vec<u32> = (0..10).map(|_| *r.choose(&v).unwrap()).collect();
+        assert_eq!(chosen, [10, 0, 0, 9, 3, 8, 6, 5, 11, 8]);
+        let mut w: Vec<u32> = (0..12).collect();
+        let chosen: Vec<u32> = (0..10).map(|_| *r.choose_mut(&mut w).unwrap()).collect();
+        assert_eq!(chosen, [3, 5, 6, 11, 8, 9, 7, 8, 5, 11]);
+    }
+
     #[test]
     fn test_rng_trait_object() {
         use distributions::{Distribution, Standard};
@@ -1159,7 +1698,7 @@ mod test {
         r.next_u32();
         r.gen::<i32>();
         let mut v = [1, 1, 1];
-        r.shuffle(&mut v);
+        v.shuffle(&mut r);
         let b: &[_] = &[1, 1, 1];
         assert_eq!(v, b);
         assert_eq!(r.gen_range(0, 1), 0);
@@ -1175,7 +1714,7 @@ mod test {
         r.next_u32();
         r.gen::<i32>();
         let mut v = [1, 1, 1];
-        r.shuffle(&mut v);
+        v.shuffle(&mut r);
         let b: &[_] = &[1, 1, 1];
         assert_eq!(v, b);
         assert_eq!(r.gen_range(0, 1), 0);
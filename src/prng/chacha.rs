@@ -12,10 +12,20 @@
 
 use core::fmt;
 use rand_core::{CryptoRng, RngCore, SeedableRng, Error, le};
+#[cfg(all(feature = "simd", feature = "std", target_feature = "sse2",
+          not(chacha_scalar),
+          any(target_arch = "x86", target_arch = "x86_64")))]
+use rand_core::impls;
 use rand_core::block::{BlockRngCore, BlockRng};
+use rngs::EntropySource;
+#[cfg(all(feature="alloc", not(feature="std")))] use alloc::Vec;
+#[cfg(feature="alloc")] use prng::state::{RngState, StateReader, StateWriter,
+                                          invalid};
 
 const SEED_WORDS: usize = 8; // 8 words for the 256-bit key
 const STATE_WORDS: usize = 16;
+const BUF_BLOCKS: usize = 4; // blocks generated at once
+const BUF_WORDS: usize = BUF_BLOCKS * STATE_WORDS;
 
 /// A cryptographically secure random number generator that uses the ChaCha
 /// algorithm.
@@ -27,16 +37,21 @@ const STATE_WORDS: usize = 16;
 ///
 /// ChaCha uses add-rotate-xor (ARX) operations as its basis. These are safe
 /// against timing attacks, although that is mostly a concern for ciphers and
-/// not for RNGs. Also it is very suitable for SIMD implementation.
-/// Here we do not provide a SIMD implementation yet, except for what is
-/// provided by auto-vectorisation.
+/// not for RNGs. Also it is very suitable for SIMD implementation. With the
+/// `simd` feature enabled, the four blocks of the buffer are generated at once
+/// using SSE2 on x86 and x86-64, and `fill_bytes` generates eight blocks at
+/// once using AVX2 when the CPU supports it (detected at runtime, with the
+/// `std` feature). The output is identical to that of the scalar
+/// implementation, which otherwise relies on auto-vectorisation.
 ///
 /// With the ChaCha algorithm it is possible to choose the number of rounds the
 /// core algorithm should run. The number of rounds is a tradeoff between
 /// performance and security, where 8 rounds is the minimum potentially
 /// secure configuration, and 20 rounds is widely used as a conservative choice.
-/// We use 20 rounds in this implementation, but hope to allow type-level
-/// configuration in the future.
+/// We use 20 rounds by default; [`new_with_rounds`] creates the reduced-round
+/// variants ChaCha8 and ChaCha12, which are about 2.5 and 1.6 times as fast.
+/// ChaCha8 still passes all statistical tests, so it is a good choice for
+/// simulations that need high-quality random numbers but not their security.
 ///
 /// We use a 64-bit counter and 64-bit stream identifier as in Benstein's
 /// implementation [1] except that we use a stream identifier in place of a
@@ -54,8 +69,8 @@ const STATE_WORDS: usize = 16;
 /// counter  counter  nonce    nonce
 /// ```
 ///
-/// This implementation uses an output buffer of sixteen `u32` words, and uses
-/// [`BlockRng`] to implement the [`RngCore`] methods.
+/// This implementation uses an output buffer of four blocks (64 `u32` words),
+/// and uses [`BlockRng`] to implement the [`RngCore`] methods.
 ///
 /// [1]: D. J. Bernstein, [*ChaCha, a variant of Salsa20*](
 ///      https://cr.yp.to/chacha.html)
@@ -63,11 +78,13 @@ const STATE_WORDS: usize = 16;
 /// [2]: [eSTREAM: the ECRYPT Stream Cipher Project](
 ///      http://www.ecrypt.eu.org/stream/)
 ///
+/// [`new_with_rounds`]: #method.new_with_rounds
 /// [`set_word_pos`]: #method.set_word_pos
 /// [`set_stream`]: #method.set_stream
 /// [`BlockRng`]: ../../../rand_core/block/struct.BlockRng.html
 /// [`RngCore`]: ../../trait.RngCore.html
 #[derive(Clone, Debug)]
+#[cfg_attr(feature="serde1", derive(Serialize, Deserialize))]
 pub struct ChaChaRng(BlockRng<ChaChaCore>);
 
 impl RngCore for ChaChaRng {
@@ -83,15 +100,18 @@ impl RngCore for ChaChaRng {
 
     #[inline]
     fn fill_bytes(&mut self, dest: &mut [u8]) {
-        self.0.fill_bytes(dest)
+        self.fill(dest)
     }
 
     #[inline]
     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
-        self.0.try_fill_bytes(dest)
+        self.fill(dest);
+        Ok(())
     }
 }
 
+impl EntropySource for ChaChaRng {}
+
 impl SeedableRng for ChaChaRng {
     type Seed = <ChaChaCore as SeedableRng>::Seed;
 
@@ -132,6 +152,35 @@ impl ChaChaRng {
         ChaChaRng::from_seed([0; SEED_WORDS*4])
     }
 
+    /// Create a ChaCha random number generator from `seed` which runs the
+    /// given number of rounds, for example 8 for ChaCha8 or 12 for ChaCha12.
+    /// `from_seed` uses 20 rounds.
+    ///
+    /// All other methods work the same for any number of rounds, and the
+    /// streams and word positions are the same as with 20 rounds.
+    ///
+    /// # Panics
+    ///
+    /// If `rounds` is odd or less than 8.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::Rng;
+    /// use rand::prng::ChaChaRng;
+    ///
+    /// let mut rng = ChaChaRng::new_with_rounds([42; 32], 8);
+    /// let x: f64 = rng.gen();
+    /// ```
+    pub fn new_with_rounds(seed: [u8; SEED_WORDS*4], rounds: u32) -> ChaChaRng {
+        ChaChaRng::from(ChaChaCore::new_with_rounds(seed, rounds))
+    }
+
+    /// Get the number of rounds of this generator.
+    pub fn rounds(&self) -> u32 {
+        self.0.core.rounds
+    }
+
     /// Get the offset from the start of the stream, in 32-bit words.
     /// 
     /// Since the generated blocks are 16 words (2<sup>4</sup>) long and the
@@ -139,20 +188,12 @@ impl ChaChaRng {
     /// not supported, hence the result can simply be multiplied by 4 to get a
     /// byte-offset.
     /// 
-    /// Note: this function is currently only available when the `i128_support`
-    /// feature is enabled. In the future this will be enabled by default.
-    #[cfg(feature = "i128_support")]
+    /// Note: this function is only available with Rust 1.26 or later, or when
+    /// the `i128_support` feature is enabled.
+    #[cfg(any(feature = "i128_support", rustc_1_26))]
     pub fn get_word_pos(&self) -> u128 {
-        let mut c = (self.0.core.state[13] as u64) << 32
-                  | (self.0.core.state[12] as u64);
-        let mut index = self.0.index();
-        // c is the end of the last block generated, unless index is at end
-        if index >= STATE_WORDS {
-            index = 0;
-        } else {
-            c = c.wrapping_sub(1);
-        }
-        ((c as u128) << 4) | (index as u128)
+        let (block, index) = self.position();
+        ((block as u128) << 4) | (index as u128)
     }
 
     /// Set the offset from the start of the stream, in 32-bit words.
@@ -160,20 +201,31 @@ impl ChaChaRng {
     /// As with `get_word_pos`, we use a 68-bit number. Since the generator
     /// simply cycles at the end of its period (1 ZiB), we ignore the upper
     /// 60 bits.
+    ///
+    /// The offset may point into the middle of a 16-word block, in which case
+    /// the block is generated immediately. Together with `get_word_pos` this
+    /// can be used to save and restore the exact position in the stream:
+    ///
+    /// ```
+    /// use rand::{RngCore, SeedableRng};
+    /// use rand::prng::ChaChaRng;
+    ///
+    /// let mut rng = ChaChaRng::from_seed([7; 32]);
+    /// rng.next_u32();
+    /// let checkpoint = rng.get_word_pos();
+    /// let x = rng.next_u64();
+    ///
+    /// rng.set_word_pos(checkpoint);
+    /// assert_eq!(rng.next_u64(), x);
+    /// ```
     /// 
-    /// Note: this function is currently only available when the `i128_support`
-    /// feature is enabled. In the future this will be enabled by default.
-    #[cfg(feature = "i128_support")]
+    /// Note: this function is only available with Rust 1.26 or later, or when
+    /// the `i128_support` feature is enabled.
+    #[cfg(any(feature = "i128_support", rustc_1_26))]
     pub fn set_word_pos(&mut self, word_offset: u128) {
         let index = (word_offset as usize) & 0xF;
-        let counter = (word_offset >> 4) as u64;
-        self.0.core.state[12] = counter as u32;
-        self.0.core.state[13] = (counter >> 32) as u32;
-        if index != 0 {
-            self.0.generate_and_set(index); // also increments counter
-        } else {
-            self.0.reset();
-        }
+        let block = (word_offset >> 4) as u64;
+        self.set_position(block, index);
     }
 
     /// Set the stream number.
@@ -188,27 +240,136 @@ impl ChaChaRng {
     /// the most significant half of the 64-bit counter (which may be set
     /// indirectly via `set_word_pos`), but this is not directly supported.
     pub fn set_stream(&mut self, stream: u64) {
-        let index = self.0.index();
+        // The buffered results are regenerated for the new stream.
+        let (block, index) = self.position();
         self.0.core.state[14] = stream as u32;
         self.0.core.state[15] = (stream >> 32) as u32;
-        if index < STATE_WORDS {
-            // we need to regenerate a partial result buffer
-            {
-                // reverse of counter adjustment in generate()
-                if self.0.core.state[12] == 0 {
-                    self.0.core.state[13] = self.0.core.state[13].wrapping_sub(1);
-                }
-                self.0.core.state[12] = self.0.core.state[12].wrapping_sub(1);
-            }
-            self.0.generate_and_set(index);
+        self.set_position(block, index);
+    }
+
+    /// The number of the block containing the next word, and the index of
+    /// the word in that block.
+    fn position(&self) -> (u64, usize) {
+        let counter = self.0.core.counter();
+        let index = self.0.index();
+        if index >= BUF_WORDS {
+            (counter, 0)
+        } else {
+            // The counter is the block after the buffered blocks.
+            let block = (BUF_BLOCKS - index / STATE_WORDS) as u64;
+            (counter.wrapping_sub(block), index % STATE_WORDS)
+        }
+    }
+
+    /// Continue with the word at `index` in block number `block`.
+    fn set_position(&mut self, block: u64, index: usize) {
+        self.0.core.set_counter(block);
+        if index != 0 {
+            self.0.generate_and_set(index); // also increments counter
+        } else {
+            self.0.reset();
         }
     }
 }
 
 /// The core of `ChaChaRng`, used with `BlockRng`.
+///
+/// Each call to `generate` produces four consecutive blocks.
 #[derive(Clone)]
+#[cfg_attr(feature="serde1", derive(Serialize, Deserialize))]
 pub struct ChaChaCore {
     state: [u32; STATE_WORDS],
+    rounds: u32,
+}
+
+impl ChaChaCore {
+    /// Create a new `ChaChaCore` from `seed` which runs the given number of
+    /// rounds. See [`ChaChaRng::new_with_rounds`].
+    ///
+    /// [`ChaChaRng::new_with_rounds`]: struct.ChaChaRng.html#method.new_with_rounds
+    pub fn new_with_rounds(seed: [u8; SEED_WORDS*4], rounds: u32) -> ChaChaCore {
+        assert!(valid_rounds(rounds),
+                "ChaChaCore::new_with_rounds called with an odd number of rounds or less than 8");
+        let mut core = ChaChaCore::from_seed(seed);
+        core.rounds = rounds;
+        core
+    }
+
+    /// The 64-bit block counter.
+    #[inline]
+    fn counter(&self) -> u64 {
+        (self.state[13] as u64) << 32 | self.state[12] as u64
+    }
+
+    #[inline]
+    fn set_counter(&mut self, counter: u64) {
+        self.state[12] = counter as u32;
+        self.state[13] = (counter >> 32) as u32;
+    }
+}
+
+/// The results of [`ChaChaCore`]: a buffer of four blocks.
+///
+/// [`ChaChaCore`]: struct.ChaChaCore.html
+// Arrays with more than 32 elements do not implement `Default` or the serde
+// traits.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature="serde1", derive(Serialize, Deserialize))]
+pub struct ChaChaResults {
+    #[cfg_attr(feature="serde1", serde(with="results_serde"))]
+    inner: [u32; BUF_WORDS],
+}
+
+impl AsRef<[u32]> for ChaChaResults {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u32] {
+        &self.inner[..]
+    }
+}
+
+impl AsMut<[u32]> for ChaChaResults {
+    #[inline(always)]
+    fn as_mut(&mut self) -> &mut [u32] {
+        &mut self.inner[..]
+    }
+}
+
+impl Default for ChaChaResults {
+    fn default() -> ChaChaResults {
+        ChaChaResults { inner: [0; BUF_WORDS] }
+    }
+}
+
+/// Serialize the results as an array of blocks, which serde supports.
+#[cfg(feature="serde1")]
+mod results_serde {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use super::{BUF_BLOCKS, BUF_WORDS, STATE_WORDS};
+
+    pub fn serialize<S>(results: &[u32; BUF_WORDS], ser: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        let mut blocks = [[0u32; STATE_WORDS]; BUF_BLOCKS];
+        for (block, words) in blocks.iter_mut().zip(results.chunks(STATE_WORDS)) {
+            block.copy_from_slice(words);
+        }
+        blocks.serialize(ser)
+    }
+
+    pub fn deserialize<'de, D>(de: D) -> Result<[u32; BUF_WORDS], D::Error>
+        where D: Deserializer<'de>
+    {
+        let blocks = <[[u32; STATE_WORDS]; BUF_BLOCKS]>::deserialize(de)?;
+        let mut results = [0; BUF_WORDS];
+        for (words, block) in results.chunks_mut(STATE_WORDS).zip(blocks.iter()) {
+            words.copy_from_slice(block);
+        }
+        Ok(results)
+    }
+}
+
+fn valid_rounds(rounds: u32) -> bool {
+    rounds >= 8 && rounds & 1 == 0
 }
 
 // Custom Debug implementation that does not expose the internal state
@@ -218,6 +379,13 @@ impl fmt::Debug for ChaChaCore {
     }
 }
 
+// Custom Debug implementation that does not expose the generated output
+impl fmt::Debug for ChaChaResults {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ChaChaResults {{}}")
+    }
+}
+
 macro_rules! quarter_round{
     ($a: expr, $b: expr, $c: expr, $d: expr) => {{
         $a = $a.wrapping_add($b); $d ^= $a; $d = $d.rotate_left(16);
@@ -242,32 +410,208 @@ macro_rules! double_round{
     }}
 }
 
-impl BlockRngCore for ChaChaCore {
-    type Item = u32;
-    type Results = [u32; STATE_WORDS];
+#[cfg(all(feature = "simd", target_feature = "sse2", not(chacha_scalar),
+          any(target_arch = "x86", target_arch = "x86_64")))]
+mod simd {
+    #[cfg(target_arch = "x86")] use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")] use core::arch::x86_64::*;
+    use core::ops::BitXorAssign;
+    use super::{STATE_WORDS, BUF_BLOCKS, BUF_WORDS};
 
-    fn generate(&mut self, results: &mut Self::Results) {
-        // For some reason extracting this part into a separate function
-        // improves performance by 50%.
-        fn core(results: &mut [u32; STATE_WORDS],
-                state: &[u32; STATE_WORDS])
-        {
-            let mut tmp = *state;
-            let rounds = 20;
+    // Each vector holds the same state word of consecutive blocks, so the
+    // blocks are generated in parallel by the scalar algorithm.
+
+    /// Four `u32` lanes, with the operations needed by `double_round!`.
+    #[derive(Clone, Copy)]
+    struct U32x4(__m128i);
+
+    impl U32x4 {
+        #[inline(always)]
+        fn wrapping_add(self, other: U32x4) -> U32x4 {
+            unsafe { U32x4(_mm_add_epi32(self.0, other.0)) }
+        }
+
+        #[inline(always)]
+        fn rotate_left(self, n: u32) -> U32x4 {
+            unsafe {
+                let left = _mm_sll_epi32(self.0, _mm_cvtsi32_si128(n as i32));
+                let right = _mm_srl_epi32(self.0, _mm_cvtsi32_si128(32 - n as i32));
+                U32x4(_mm_or_si128(left, right))
+            }
+        }
+    }
+
+    impl BitXorAssign for U32x4 {
+        #[inline(always)]
+        fn bitxor_assign(&mut self, other: U32x4) {
+            unsafe { self.0 = _mm_xor_si128(self.0, other.0) }
+        }
+    }
+
+    /// Generate the four consecutive blocks of the buffer, starting with the
+    /// counter in `state`, into `results`. Does not update `state`.
+    pub fn generate(state: &[u32; STATE_WORDS], rounds: u32,
+                    results: &mut [u32; BUF_WORDS])
+    {
+        unsafe {
+            let mut init = [U32x4(_mm_setzero_si128()); STATE_WORDS];
+            for (x, &word) in init.iter_mut().zip(state.iter()) {
+                *x = U32x4(_mm_set1_epi32(word as i32));
+            }
+            let (low, high) = counters::<[u32; BUF_BLOCKS]>(state);
+            init[12] = U32x4(_mm_loadu_si128(low.as_ptr() as *const __m128i));
+            init[13] = U32x4(_mm_loadu_si128(high.as_ptr() as *const __m128i));
+
+            let mut x = init;
             for _ in 0..rounds / 2 {
-                double_round!(tmp);
+                double_round!(x);
             }
+
+            let mut words = [[0u32; BUF_BLOCKS]; STATE_WORDS];
             for i in 0..STATE_WORDS {
-                results[i] = tmp[i].wrapping_add(state[i]);
+                let sum = x[i].wrapping_add(init[i]);
+                _mm_storeu_si128(words[i].as_mut_ptr() as *mut __m128i, sum.0);
+            }
+            write_blocks(&words, results);
+        }
+    }
+
+    /// The low and high words of the counters of consecutive blocks.
+    #[inline(always)]
+    fn counters<A: Default + AsMut<[u32]>>(state: &[u32; STATE_WORDS]) -> (A, A) {
+        let counter = (state[13] as u64) << 32 | state[12] as u64;
+        let (mut low, mut high) = (A::default(), A::default());
+        for (i, (l, h)) in low.as_mut().iter_mut()
+            .zip(high.as_mut().iter_mut()).enumerate()
+        {
+            let c = counter.wrapping_add(i as u64);
+            *l = c as u32;
+            *h = (c >> 32) as u32;
+        }
+        (low, high)
+    }
+
+    /// Write blocks stored by word as consecutive blocks.
+    #[inline(always)]
+    fn write_blocks<A: AsRef<[u32]>>(words: &[A; STATE_WORDS], results: &mut [u32]) {
+        for (block, out) in results.chunks_mut(STATE_WORDS).enumerate() {
+            for (i, word) in out.iter_mut().enumerate() {
+                *word = words[i].as_ref()[block];
             }
         }
+    }
+
+    /// The number of blocks generated at once with AVX2.
+    #[cfg(feature = "std")]
+    pub const AVX2_BLOCKS: usize = 8;
+
+    /// True if the CPU supports AVX2, detected at runtime.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn has_avx2() -> bool {
+        is_x86_feature_detected!("avx2")
+    }
+
+    /// Eight `u32` lanes, with the operations needed by `double_round!`.
+    #[cfg(feature = "std")]
+    #[derive(Clone, Copy)]
+    struct U32x8(__m256i);
+
+    #[cfg(feature = "std")]
+    impl U32x8 {
+        #[inline(always)]
+        fn wrapping_add(self, other: U32x8) -> U32x8 {
+            unsafe { U32x8(_mm256_add_epi32(self.0, other.0)) }
+        }
+
+        #[inline(always)]
+        fn rotate_left(self, n: u32) -> U32x8 {
+            unsafe {
+                let left = _mm256_sll_epi32(self.0, _mm_cvtsi32_si128(n as i32));
+                let right = _mm256_srl_epi32(self.0, _mm_cvtsi32_si128(32 - n as i32));
+                U32x8(_mm256_or_si256(left, right))
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl BitXorAssign for U32x8 {
+        #[inline(always)]
+        fn bitxor_assign(&mut self, other: U32x8) {
+            unsafe { self.0 = _mm256_xor_si256(self.0, other.0) }
+        }
+    }
+
+    /// Generate `AVX2_BLOCKS` consecutive blocks like `generate`.
+    ///
+    /// This is only safe to call if `has_avx2()` is true.
+    #[cfg(feature = "std")]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn generate_avx2(state: &[u32; STATE_WORDS], rounds: u32,
+                                results: &mut [u32; AVX2_BLOCKS * STATE_WORDS])
+    {
+        let mut init = [U32x8(_mm256_setzero_si256()); STATE_WORDS];
+        for (x, &word) in init.iter_mut().zip(state.iter()) {
+            *x = U32x8(_mm256_set1_epi32(word as i32));
+        }
+        let (low, high) = counters::<[u32; AVX2_BLOCKS]>(state);
+        init[12] = U32x8(_mm256_loadu_si256(low.as_ptr() as *const __m256i));
+        init[13] = U32x8(_mm256_loadu_si256(high.as_ptr() as *const __m256i));
+
+        let mut x = init;
+        for _ in 0..rounds / 2 {
+            double_round!(x);
+        }
+
+        let mut words = [[0u32; AVX2_BLOCKS]; STATE_WORDS];
+        for i in 0..STATE_WORDS {
+            let sum = x[i].wrapping_add(init[i]);
+            _mm256_storeu_si256(words[i].as_mut_ptr() as *mut __m256i, sum.0);
+        }
+        write_blocks(&words, results);
+    }
+}
+
+/// Generate a single block into `results`. Does not update `state`.
+// For some reason having this as a separate function improves performance by
+// 50%.
+#[cfg_attr(all(feature = "simd", target_feature = "sse2", not(chacha_scalar),
+               any(target_arch = "x86", target_arch = "x86_64")),
+           allow(dead_code))]
+fn block(state: &[u32; STATE_WORDS], rounds: u32, results: &mut [u32]) {
+    let mut tmp = *state;
+    for _ in 0..rounds / 2 {
+        double_round!(tmp);
+    }
+    for i in 0..STATE_WORDS {
+        results[i] = tmp[i].wrapping_add(state[i]);
+    }
+}
+
+impl BlockRngCore for ChaChaCore {
+    type Item = u32;
+    type Results = ChaChaResults;
 
-        core(results, &self.state);
+    fn generate(&mut self, results: &mut Self::Results) {
+        #[cfg(all(feature = "simd", target_feature = "sse2", not(chacha_scalar),
+                  any(target_arch = "x86", target_arch = "x86_64")))] {
+            simd::generate(&self.state, self.rounds, &mut results.inner);
+        }
+        #[cfg(not(all(feature = "simd", target_feature = "sse2", not(chacha_scalar),
+                      any(target_arch = "x86", target_arch = "x86_64"))))] {
+            let mut state = self.state;
+            for words in results.inner.chunks_mut(STATE_WORDS) {
+                block(&state, self.rounds, words);
+                let counter = ((state[13] as u64) << 32 | state[12] as u64)
+                    .wrapping_add(1);
+                state[12] = counter as u32;
+                state[13] = (counter >> 32) as u32;
+            }
+        }
 
         // update 64-bit counter
-        self.state[12] = self.state[12].wrapping_add(1);
-        if self.state[12] != 0 { return; };
-        self.state[13] = self.state[13].wrapping_add(1);
+        let counter = self.counter().wrapping_add(BUF_BLOCKS as u64);
+        self.set_counter(counter);
     }
 }
 
@@ -282,18 +626,122 @@ impl SeedableRng for ChaChaCore {
                     seed_le[0], seed_le[1], seed_le[2], seed_le[3], // seed
                     seed_le[4], seed_le[5], seed_le[6], seed_le[7], // seed
                     0, 0, 0, 0], // counter
+            rounds: 20,
          }
     }
 }
 
 impl CryptoRng for ChaChaCore {}
 
+impl ChaChaRng {
+    #[cfg(not(all(feature = "simd", feature = "std", target_feature = "sse2",
+                  not(chacha_scalar),
+                  any(target_arch = "x86", target_arch = "x86_64"))))]
+    #[inline]
+    fn fill(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    #[cfg(all(feature = "simd", feature = "std", target_feature = "sse2",
+              not(chacha_scalar),
+              any(target_arch = "x86", target_arch = "x86_64")))]
+    fn fill(&mut self, dest: &mut [u8]) {
+        const CHUNK: usize = simd::AVX2_BLOCKS * STATE_WORDS * 4;
+        if dest.len() < CHUNK || !simd::has_avx2() {
+            return self.0.fill_bytes(dest);
+        }
+
+        // First use up the buffered results, as `BlockRng` would.
+        let buffered = (BUF_WORDS - self.0.index().min(BUF_WORDS)) * 4;
+        let (head, rest) = dest.split_at_mut(buffered.min(dest.len()));
+        self.0.fill_bytes(head);
+
+        // The buffer is now empty, so we can generate whole blocks directly
+        // into `dest` and advance the counter past them.
+        let split = rest.len() - rest.len() % CHUNK;
+        let (middle, tail) = rest.split_at_mut(split);
+        let mut words = [0u32; simd::AVX2_BLOCKS * STATE_WORDS];
+        for chunk in middle.chunks_mut(CHUNK) {
+            let core = &mut self.0.core;
+            unsafe { simd::generate_avx2(&core.state, core.rounds, &mut words) };
+            impls::fill_via_u32_chunks(&words, chunk);
+            let counter = core.counter().wrapping_add(simd::AVX2_BLOCKS as u64);
+            core.set_counter(counter);
+        }
+
+        self.0.fill_bytes(tail);
+    }
+}
+
 impl From<ChaChaCore> for ChaChaRng {
     fn from(core: ChaChaCore) -> Self {
         ChaChaRng(BlockRng::new(core))
     }
 }
 
+#[cfg(feature="alloc")]
+impl RngState for ChaChaRng {
+    fn to_bytes(&self) -> Vec<u8> {
+        let rounds = self.0.core.rounds;
+        let mut writer = if rounds == 20 {
+            StateWriter::new(b"CHCH", 4 * (2 * STATE_WORDS + 1))
+        } else {
+            // Reduced-round variants also store the number of rounds.
+            let mut writer = StateWriter::new(b"CHCR", 4 * (2 * STATE_WORDS + 2));
+            writer.write_u32(rounds);
+            writer
+        };
+        // The state is written as if only the block containing the next word
+        // was buffered, so the format does not depend on the buffer size.
+        let (block, index) = self.position();
+        let mut state = self.0.core.state;
+        let (counter, words, index) = if index == 0 {
+            // Nothing of the block was used, so store it as not generated.
+            (block, &self.0.results().inner[BUF_WORDS - STATE_WORDS..], STATE_WORDS)
+        } else {
+            let start = self.0.index() - index;
+            (block.wrapping_add(1), &self.0.results().inner[start..start + STATE_WORDS],
+             index)
+        };
+        state[12] = counter as u32;
+        state[13] = (counter >> 32) as u32;
+        writer.write_u32s(&state);
+        writer.write_u32s(words);
+        writer.write_u32(index as u32);
+        writer.finish()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let (mut reader, rounds) = if bytes.starts_with(b"CHCR") {
+            let mut reader = StateReader::new(bytes, b"CHCR")?;
+            let rounds = reader.read_u32()?;
+            if !valid_rounds(rounds) {
+                return Err(invalid("RngState: invalid number of ChaCha rounds"));
+            }
+            (reader, rounds)
+        } else {
+            (StateReader::new(bytes, b"CHCH")?, 20)
+        };
+        let mut core = ChaChaCore { state: [0; STATE_WORDS], rounds };
+        reader.read_u32s(&mut core.state)?;
+        // The results of the current block follow from the state, so they
+        // are generated again instead of being restored.
+        let mut results = [0; STATE_WORDS];
+        reader.read_u32s(&mut results)?;
+        let index = reader.read_index(STATE_WORDS)?;
+        reader.finish()?;
+
+        let counter = core.counter();
+        let mut rng = ChaChaRng(BlockRng::new(core));
+        if index < STATE_WORDS {
+            rng.set_position(counter.wrapping_sub(1), index);
+        } else {
+            rng.set_position(counter, 0);
+        }
+        Ok(rng)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use {RngCore, SeedableRng};
@@ -358,7 +806,7 @@ mod test {
     }
 
     #[test]
-    #[cfg(feature = "i128_support")]
+    #[cfg(any(feature = "i128_support", rustc_1_26))]
     fn test_chacha_true_values_c() {
         // Test vector 4 from
         // https://tools.ietf.org/html/draft-nir-cfrg-chacha20-poly1305-04
@@ -402,6 +850,159 @@ mod test {
         assert_eq!(rng2.get_word_pos(), expected_end + 21);
     }
 
+    #[cfg(any(feature = "i128_support", rustc_1_26))]
+    #[test]
+    fn test_chacha_word_pos_round_trip() {
+        let seed = [0,0,0,0, 1,0,0,0, 2,0,0,0, 3,0,0,0, 4,0,0,0, 5,0,0,0, 6,0,0,0, 7,0,0,0];
+        let mut rng = ChaChaRng::from_seed(seed);
+        rng.set_stream(5);
+        let mut reference = rng.clone();
+        let mut words = [0u32; 100];
+        for w in words.iter_mut() { *w = reference.next_u32(); }
+
+        // Every position, including those in the middle of blocks and on
+        // block boundaries, continues with the same words.
+        for pos in 0..60 {
+            rng.set_word_pos(pos as u128);
+            assert_eq!(rng.get_word_pos(), pos as u128);
+            for i in pos..pos + 40 {
+                assert_eq!(rng.next_u32(), words[i]);
+            }
+            assert_eq!(rng.get_word_pos(), (pos + 40) as u128);
+            // Saving and restoring the position gives the same output again.
+            let saved = rng.get_word_pos();
+            let a = rng.next_u64();
+            rng.set_word_pos(saved);
+            assert_eq!(rng.next_u64(), a);
+        }
+
+        // Positions near the end of the period wrap around to the start.
+        let end = 1u128 << 68;
+        rng.set_word_pos(end - 3);
+        assert_eq!(rng.get_word_pos(), end - 3);
+        rng.next_u64();
+        rng.next_u32();
+        assert_eq!(rng.get_word_pos(), 0);
+        for i in 0..20 {
+            assert_eq!(rng.next_u32(), words[i]);
+        }
+        // The upper bits are ignored.
+        rng.set_word_pos(end + 17);
+        assert_eq!(rng.get_word_pos(), 17);
+        assert_eq!(rng.next_u32(), words[17]);
+    }
+
+    #[test]
+    fn test_chacha_streams_independent() {
+        let seed = [9; 32];
+        let mut rng0 = ChaChaRng::from_seed(seed);
+        let mut rng1 = ChaChaRng::from_seed(seed);
+        rng1.set_stream(1);
+        let mut rng_high = ChaChaRng::from_seed(seed);
+        rng_high.set_stream(1 << 63);
+
+        // The first values of stream 0 do not appear anywhere in the other
+        // streams, so those are not shifted copies of it either.
+        let mut first = [0u64; 1024];
+        for x in first.iter_mut() { *x = rng0.next_u64(); }
+        first.sort_unstable();
+        for _ in 0..100_000 {
+            let (a, b, c) = (rng0.next_u64(), rng1.next_u64(), rng_high.next_u64());
+            assert!(a != b && a != c && b != c);
+            assert!(first.binary_search(&b).is_err());
+            assert!(first.binary_search(&c).is_err());
+        }
+    }
+
+    #[test]
+    fn test_chacha_reduced_rounds_true_values() {
+        // Test vectors TC1 (all-zero key and IV) for 8 and 12 rounds from
+        // https://tools.ietf.org/html/draft-strombergson-chacha-test-vectors-00
+        let mut rng = ChaChaRng::new_with_rounds([0; 32], 8);
+        assert_eq!(rng.rounds(), 8);
+        let mut results = [0u8; 64];
+        rng.fill_bytes(&mut results);
+        let expected = [0x3e, 0x00, 0xef, 0x2f, 0x89, 0x5f, 0x40, 0xd6,
+                        0x7f, 0x5b, 0xb8, 0xe8, 0x1f, 0x09, 0xa5, 0xa1,
+                        0x2c, 0x84, 0x0e, 0xc3, 0xce, 0x9a, 0x7f, 0x3b,
+                        0x18, 0x1b, 0xe1, 0x88, 0xef, 0x71, 0x1a, 0x1e,
+                        0x98, 0x4c, 0xe1, 0x72, 0xb9, 0x21, 0x6f, 0x41,
+                        0x9f, 0x44, 0x53, 0x67, 0x45, 0x6d, 0x56, 0x19,
+                        0x31, 0x4a, 0x42, 0xa3, 0xda, 0x86, 0xb0, 0x01,
+                        0x38, 0x7b, 0xfd, 0xb8, 0x0e, 0x0c, 0xfe, 0x42];
+        assert_eq!(&results[..], &expected[..]);
+
+        let mut rng = ChaChaRng::new_with_rounds([0; 32], 12);
+        assert_eq!(rng.rounds(), 12);
+        rng.fill_bytes(&mut results);
+        let expected = [0x9b, 0xf4, 0x9a, 0x6a, 0x07, 0x55, 0xf9, 0x53,
+                        0x81, 0x1f, 0xce, 0x12, 0x5f, 0x26, 0x83, 0xd5,
+                        0x04, 0x29, 0xc3, 0xbb, 0x49, 0xe0, 0x74, 0x14,
+                        0x7e, 0x00, 0x89, 0xa5, 0x2e, 0xae, 0x15, 0x5f,
+                        0x05, 0x64, 0xf8, 0x79, 0xd2, 0x7a, 0xe3, 0xc0,
+                        0x2c, 0xe8, 0x28, 0x34, 0xac, 0xfa, 0x8c, 0x79,
+                        0x3a, 0x62, 0x9f, 0x2c, 0xa0, 0xde, 0x69, 0x19,
+                        0x61, 0x0b, 0xe8, 0x2f, 0x41, 0x13, 0x26, 0xbe];
+        assert_eq!(&results[..], &expected[..]);
+
+        // 20 rounds is the default
+        let mut rng = ChaChaRng::new_with_rounds([0; 32], 20);
+        assert_eq!(rng.rounds(), 20);
+        assert_eq!(rng.next_u32(), 0xade0b876);
+        assert_eq!(ChaChaRng::from_seed([0; 32]).rounds(), 20);
+    }
+
+    #[test]
+    fn test_chacha_reduced_rounds_stream() {
+        // Streams and positions work the same as with 20 rounds.
+        let seed = [0,0,0,0, 1,0,0,0, 2,0,0,0, 3,0,0,0, 4,0,0,0, 5,0,0,0, 6,0,0,0, 7,0,0,0];
+        let mut rng = ChaChaRng::new_with_rounds(seed, 8);
+        rng.set_stream(7);
+        let mut clone = rng.clone();
+        for _ in 0..20 {
+            clone.next_u32();
+        }
+        let mut skipped = ChaChaRng::new_with_rounds(seed, 8);
+        for _ in 0..20 {
+            skipped.next_u32();
+        }
+        skipped.set_stream(7);
+        for _ in 0..40 {
+            assert_eq!(clone.next_u32(), skipped.next_u32());
+        }
+
+        let mut full = ChaChaRng::from_seed(seed);
+        full.set_stream(7);
+        assert!(rng.next_u64() != full.next_u64());
+    }
+
+    #[cfg(any(feature = "i128_support", rustc_1_26))]
+    #[test]
+    fn test_chacha_reduced_rounds_word_pos() {
+        let mut rng = ChaChaRng::new_with_rounds([3; 32], 12);
+        let mut words = [0u32; 40];
+        for w in words.iter_mut() { *w = rng.next_u32(); }
+        for &pos in [0, 1, 15, 16, 17].iter() {
+            rng.set_word_pos(pos as u128);
+            for i in pos..pos + 20 {
+                assert_eq!(rng.next_u32(), words[i]);
+            }
+            assert_eq!(rng.get_word_pos(), (pos + 20) as u128);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "called with an odd number of rounds")]
+    fn test_chacha_rounds_odd() {
+        ChaChaRng::new_with_rounds([0; 32], 9);
+    }
+
+    #[test]
+    #[should_panic(expected = "called with an odd number of rounds")]
+    fn test_chacha_rounds_too_few() {
+        ChaChaRng::new_with_rounds([0; 32], 6);
+    }
+
     #[test]
     fn test_chacha_multiple_blocks() {
         let seed = [0,0,0,0, 1,0,0,0, 2,0,0,0, 3,0,0,0, 4,0,0,0, 5,0,0,0, 6,0,0,0, 7,0,0,0];
@@ -474,4 +1075,150 @@ mod test {
             assert_eq!(rng.next_u32(), clone.next_u32());
         }
     }
+
+    #[cfg(feature="alloc")]
+    #[test]
+    fn test_chacha_state() {
+        use prng::RngState;
+        let seed = [0,0,0,0, 1,0,0,0, 2,0,0,0, 3,0,0,0, 4,0,0,0, 5,0,0,0, 6,0,0,0, 7,0,0,0];
+        let mut rng = ChaChaRng::from_seed(seed);
+        rng.set_stream(3);
+        // Save at the start, part way through and at the end of a block
+        for &n in [0, 5, 11, 30, 18, 33].iter() {
+            for _ in 0..n {
+                rng.next_u32();
+            }
+            let bytes = rng.to_bytes();
+            assert_eq!(bytes.len(), 5 + 4 * 33);
+            let mut restored = ChaChaRng::from_bytes(&bytes).unwrap();
+            for _ in 0..40 {
+                assert_eq!(rng.next_u32(), restored.next_u32());
+            }
+        }
+
+        let bytes = rng.to_bytes();
+        let mut bad_index = bytes.clone();
+        bad_index[5 + 4 * 32] = 17;
+        assert!(ChaChaRng::from_bytes(&bad_index).is_err());
+        assert!(ChaChaRng::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+
+        // The number of rounds is restored too
+        let mut rng = ChaChaRng::new_with_rounds(seed, 8);
+        rng.next_u32();
+        let bytes = rng.to_bytes();
+        assert_eq!(bytes.len(), 5 + 4 * 34);
+        let mut restored = ChaChaRng::from_bytes(&bytes).unwrap();
+        assert_eq!(restored.rounds(), 8);
+        for _ in 0..40 {
+            assert_eq!(rng.next_u32(), restored.next_u32());
+        }
+        let mut bad_rounds = bytes.clone();
+        bad_rounds[5] = 7;
+        assert!(ChaChaRng::from_bytes(&bad_rounds).is_err());
+    }
+
+    #[cfg(all(feature="serde1", feature="std"))]
+    #[test]
+    fn test_chacha_serde() {
+        use bincode;
+        use std::io::{BufWriter, BufReader};
+
+        let mut rng = ChaChaRng::new_with_rounds([5; 32], 12);
+        rng.set_stream(3);
+        rng.next_u32();
+
+        let buf: Vec<u8> = Vec::new();
+        let mut buf = BufWriter::new(buf);
+        bincode::serialize_into(&mut buf, &rng).expect("Could not serialize");
+
+        let buf = buf.into_inner().unwrap();
+        let mut read = BufReader::new(&buf[..]);
+        let mut deserialized: ChaChaRng = bincode::deserialize_from(&mut read).expect("Could not deserialize");
+
+        assert_eq!(deserialized.rounds(), 12);
+        for _ in 0..40 {
+            assert_eq!(rng.next_u32(), deserialized.next_u32());
+        }
+    }
+
+    #[cfg(all(feature = "simd", target_feature = "sse2", not(chacha_scalar),
+              any(target_arch = "x86", target_arch = "x86_64")))]
+    #[test]
+    fn test_chacha_simd_block() {
+        // The SIMD implementations produce the same blocks as the scalar one.
+        // The known-answer tests above use the SIMD implementation with the
+        // `simd` feature; the scalar one can be tested with
+        // `RUSTFLAGS="--cfg chacha_scalar"`.
+        use Rng;
+        use super::{simd, block, STATE_WORDS, BUF_WORDS};
+        let mut rng = ::test::rng(466);
+        for &rounds in [8, 12, 20].iter() {
+            for i in 0..100 {
+                let mut state: [u32; STATE_WORDS] = rng.gen();
+                if i == 0 {
+                    // All-zero key and nonce, as in test vector 1
+                    state = [0x61707865, 0x3320646E, 0x79622D32, 0x6B206574,
+                             0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+                } else if i == 1 {
+                    // A carry into the upper word of the counter
+                    state[12] = 0xffff_fffd;
+                }
+                let mut expected = [0u32; 8 * STATE_WORDS];
+                let mut s = state;
+                for words in expected.chunks_mut(STATE_WORDS) {
+                    block(&s, rounds, words);
+                    let counter = ((s[13] as u64) << 32 | s[12] as u64).wrapping_add(1);
+                    s[12] = counter as u32;
+                    s[13] = (counter >> 32) as u32;
+                }
+                if i == 0 && rounds == 20 {
+                    assert_eq!(expected[0], 0xade0b876);
+                }
+
+                let mut results = [0u32; BUF_WORDS];
+                simd::generate(&state, rounds, &mut results);
+                assert_eq!(&results[..], &expected[..BUF_WORDS]);
+
+                #[cfg(feature = "std")] {
+                    if simd::has_avx2() {
+                        let mut results = [0u32; simd::AVX2_BLOCKS * STATE_WORDS];
+                        unsafe { simd::generate_avx2(&state, rounds, &mut results) };
+                        assert_eq!(&results[..], &expected[..]);
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(all(feature = "simd", feature = "std", target_feature = "sse2",
+              not(chacha_scalar),
+              any(target_arch = "x86", target_arch = "x86_64")))]
+    #[test]
+    fn test_chacha_simd_fill_bytes() {
+        // Compare filling large buffers directly (with AVX2 if supported)
+        // with copying from the buffered results in `BlockRng`.
+        let seed = [0,0,0,0, 1,0,0,0, 2,0,0,0, 3,0,0,0, 4,0,0,0, 5,0,0,0, 6,0,0,0, 7,0,0,0];
+        for &(offset, len) in [(0, 0), (0, 512), (0, 1000), (1, 511), (1, 512),
+                               (3, 1024), (7, 4099), (16, 600), (15, 3),
+                               (40, 1536), (63, 777), (64, 2048)].iter() {
+            let rounds = if len % 2 == 0 { 20 } else { 8 };
+            let mut direct = ChaChaRng::new_with_rounds(seed, rounds);
+            let mut buffered = ChaChaRng::new_with_rounds(seed, rounds);
+            // Start the counter just below a carry into the upper word
+            direct.0.core.state[12] = 0xffff_fffa;
+            buffered.0.core.state[12] = 0xffff_fffa;
+            for _ in 0..offset {
+                assert_eq!(direct.next_u32(), buffered.next_u32());
+            }
+
+            let mut a = vec![0u8; len];
+            let mut b = vec![0u8; len];
+            direct.fill_bytes(&mut a);
+            buffered.0.fill_bytes(&mut b);
+            assert_eq!(a, b);
+            for _ in 0..100 {
+                assert_eq!(direct.next_u32(), buffered.next_u32());
+            }
+        }
+    }
 }
@@ -13,6 +13,10 @@
 use core::fmt;
 use rand_core::{CryptoRng, RngCore, SeedableRng, Error, le};
 use rand_core::block::{BlockRngCore, BlockRng};
+use rngs::EntropySource;
+#[cfg(all(feature="alloc", not(feature="std")))] use alloc::Vec;
+#[cfg(feature="alloc")] use prng::state::{RngState, StateReader, StateWriter,
+                                          invalid};
 
 const SEED_WORDS: usize = 8; // 128 bit key followed by 128 bit iv
 
@@ -44,6 +48,14 @@ const SEED_WORDS: usize = 8; // 128 bit key followed by 128 bit iv
 /// We support seeding with a 256-bit array, which matches the 128-bit key
 /// concatenated with a 128-bit IV from the stream cipher.
 ///
+/// The output is the keystream of the cipher as specified, so it can be
+/// checked against other implementations and the published test vectors:
+/// bytes 0 to 15 of the seed are the key and bytes 16 to 31 the IV, both in
+/// the byte order of the eSTREAM API (the words `K[i]` and `IV[i]` of the
+/// specification are read from them as little-endian). `next_u32` returns the
+/// keystream words `s[i]`, and `fill_bytes` produces the keystream bytes, i.e.
+/// the words in little-endian byte order. See also [`from_key_iv`].
+///
 /// This implementation uses an output buffer of sixteen `u32` words, and uses
 /// [`BlockRng`] to implement the [`RngCore`] methods.
 ///
@@ -67,6 +79,7 @@ const SEED_WORDS: usize = 8; // 128 bit key followed by 128 bit iv
 ///
 /// [`BlockRng`]: ../../../rand_core/block/struct.BlockRng.html
 /// [`RngCore`]: ../../trait.RngCore.html
+/// [`from_key_iv`]: #method.from_key_iv
 #[derive(Clone, Debug)]
 pub struct Hc128Rng(BlockRng<Hc128Core>);
 
@@ -90,6 +103,8 @@ impl RngCore for Hc128Rng {
     }
 }
 
+impl EntropySource for Hc128Rng {}
+
 impl SeedableRng for Hc128Rng {
     type Seed = <Hc128Core as SeedableRng>::Seed;
 
@@ -104,11 +119,102 @@ impl SeedableRng for Hc128Rng {
 
 impl CryptoRng for Hc128Rng {}
 
+impl Hc128Rng {
+    /// Create a generator producing the keystream of the HC-128 cipher for
+    /// the given 128-bit `key` and `iv`.
+    ///
+    /// This is equivalent to `from_seed` with the key followed by the IV as
+    /// seed, and is meant for checking the output against other
+    /// implementations of the cipher.
+    ///
+    /// ```
+    /// use rand::RngCore;
+    /// use rand::prng::Hc128Rng;
+    ///
+    /// // eSTREAM test vectors, set 1, vector 0
+    /// let mut key = [0u8; 16];
+    /// key[0] = 0x80;
+    /// let mut rng = Hc128Rng::from_key_iv(key, [0; 16]);
+    /// let mut stream = [0u8; 8];
+    /// rng.fill_bytes(&mut stream);
+    /// assert_eq!(stream, [0x37, 0x86, 0x02, 0xB9, 0x8F, 0x32, 0xA7, 0x48]);
+    /// ```
+    pub fn from_key_iv(key: [u8; 16], iv: [u8; 16]) -> Hc128Rng {
+        let mut seed = [0u8; SEED_WORDS*4];
+        seed[..16].copy_from_slice(&key);
+        seed[16..].copy_from_slice(&iv);
+        Hc128Rng::from_seed(seed)
+    }
+
+    /// Get the position in the output stream, in 32-bit words.
+    ///
+    /// This is the number of `u32` words that have been consumed since the
+    /// generator was seeded. A `u64` consumes two words, and `fill_bytes`
+    /// consumes a whole word for every started group of four bytes.
+    pub fn get_word_pos(&self) -> u64 {
+        let buffered = 16 - self.0.index().min(16);
+        self.0.core.word_pos - buffered as u64
+    }
+
+    /// Advance the generator to the given position in the output stream, in
+    /// 32-bit words, so that the next word output is the one at `word_pos`.
+    ///
+    /// HC-128 has no random access into its keystream: the P and Q tables
+    /// change with every output word, so seeking has to run the cipher for
+    /// every skipped block of sixteen words. This is cheaper than generating
+    /// the skipped output (the table lookups of the output function are
+    /// omitted), but the cost is still linear in the distance skipped.
+    ///
+    /// Seeking backwards is not possible; to return to an earlier position,
+    /// create a new generator from the same seed and seek that forward.
+    ///
+    /// # Panics
+    ///
+    /// If `word_pos` is smaller than the current position as returned by
+    /// [`get_word_pos`].
+    ///
+    /// [`get_word_pos`]: #method.get_word_pos
+    pub fn set_word_pos(&mut self, word_pos: u64) {
+        let current = self.get_word_pos();
+        assert!(word_pos >= current,
+                "Hc128Rng::set_word_pos called with a position before the current one");
+        if word_pos < self.0.core.word_pos {
+            // The target is in the current buffer.
+            for _ in current..word_pos {
+                self.0.next_u32();
+            }
+            return;
+        }
+        while self.0.core.word_pos + 16 <= word_pos {
+            self.0.core.skip_block();
+        }
+        let index = (word_pos - self.0.core.word_pos) as usize;
+        if index == 0 {
+            self.0.reset();
+        } else {
+            self.0.generate_and_set(index);
+        }
+    }
+
+    /// Skip the next `n` 32-bit words of the output stream.
+    ///
+    /// This is equivalent to `self.set_word_pos(self.get_word_pos() + n)`; see
+    /// [`set_word_pos`] for the performance characteristics.
+    ///
+    /// [`set_word_pos`]: #method.set_word_pos
+    pub fn skip(&mut self, n: u64) {
+        let word_pos = self.get_word_pos() + n;
+        self.set_word_pos(word_pos);
+    }
+}
+
 /// The core of `Hc128Rng`, used with `BlockRng`.
 #[derive(Clone)]
 pub struct Hc128Core {
     t: [u32; 1024],
     counter1024: usize,
+    // number of output words generated, used for seeking
+    word_pos: u64,
 }
 
 // Custom Debug implementation that does not expose the internal state
@@ -167,6 +273,7 @@ impl BlockRngCore for Hc128Core {
             results[15] = self.step_q(cc+15, dd+0,  cc+12, cc+5,  cc+3);
         }
         self.counter1024 = self.counter1024.wrapping_add(16);
+        self.word_pos = self.word_pos.wrapping_add(16);
     }
 }
 
@@ -222,6 +329,29 @@ impl Hc128Core {
         }
     }
 
+    // Do the table updates of sixteen steps, like `generate` but without
+    // computing any output.
+    fn skip_block(&mut self) {
+        let cc = self.counter1024 % 512;
+        let is_p = self.counter1024 & 512 == 0;
+        let table = if is_p { &mut self.t[..512] } else { &mut self.t[512..] };
+        for i in cc..cc+16 {
+            let x511 = table[(i + 1) % 512];
+            let x3 = table[(i + 512 - 3) % 512];
+            let x10 = table[(i + 512 - 10) % 512];
+            let update = if is_p {
+                x10.rotate_right(8)
+                   .wrapping_add(x511.rotate_right(23) ^ x3.rotate_right(10))
+            } else {
+                x10.rotate_left(8)
+                   .wrapping_add(x511.rotate_left(23) ^ x3.rotate_left(10))
+            };
+            table[i] = table[i].wrapping_add(update);
+        }
+        self.counter1024 = self.counter1024.wrapping_add(16);
+        self.word_pos = self.word_pos.wrapping_add(16);
+    }
+
     fn sixteen_steps(&mut self) {
         assert!(self.counter1024 % 16 == 0);
 
@@ -309,7 +439,7 @@ impl Hc128Core {
                    .wrapping_add(t[i-16]).wrapping_add(256 + i as u32);
         }
 
-        let mut core = Self { t, counter1024: 0 };
+        let mut core = Self { t, counter1024: 0, word_pos: 0 };
 
         // run the cipher 1024 steps
         for _ in 0..64 { core.sixteen_steps() };
@@ -333,6 +463,40 @@ impl SeedableRng for Hc128Core {
 
 impl CryptoRng for Hc128Core {}
 
+#[cfg(feature="alloc")]
+impl RngState for Hc128Rng {
+    fn to_bytes(&self) -> Vec<u8> {
+        let core = &self.0.core;
+        let mut writer = StateWriter::new(b"H128", 4 * (1024 + 1 + 2 + 16 + 1));
+        writer.write_u32s(&core.t);
+        // Only the position within the two tables matters
+        writer.write_u32((core.counter1024 % 1024) as u32);
+        writer.write_u64(core.word_pos);
+        writer.write_u32s(self.0.results());
+        writer.write_u32(self.0.index() as u32);
+        writer.finish()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = StateReader::new(bytes, b"H128")?;
+        let mut core = Hc128Core { t: [0; 1024], counter1024: 0, word_pos: 0 };
+        reader.read_u32s(&mut core.t)?;
+        core.counter1024 = reader.read_u32()? as usize;
+        if core.counter1024 >= 1024 || core.counter1024 % 16 != 0 {
+            return Err(invalid("RngState: invalid HC-128 counter"));
+        }
+        core.word_pos = reader.read_u64()?;
+        let mut results = [0; 16];
+        reader.read_u32s(&mut results)?;
+        let index = reader.read_index(16)?;
+        reader.finish()?;
+
+        let mut rng = BlockRng::new(core);
+        rng.restore(results, index);
+        Ok(Hc128Rng(rng))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use {RngCore, SeedableRng};
@@ -386,6 +550,39 @@ mod test {
         assert_eq!(results, expected);
     }
 
+    #[test]
+    // eSTREAM test vectors, set 1, vector 0: the key is `0x80` followed by
+    // zero bytes, and the IV is zero. Checks the byte order of the key and
+    // of the keystream.
+    fn test_hc128_estream_set1_vector0() {
+        let mut key = [0u8; 16];
+        key[0] = 0x80;
+        let mut rng = Hc128Rng::from_key_iv(key, [0; 16]);
+
+        let mut stream = [0u8; 32];
+        rng.fill_bytes(&mut stream);
+        let expected = [0x37, 0x86, 0x02, 0xB9, 0x8F, 0x32, 0xA7, 0x48,
+                        0x47, 0x51, 0x56, 0x54, 0xAE, 0x0D, 0xE7, 0xED,
+                        0x8F, 0x72, 0xBC, 0x34, 0x77, 0x6A, 0x06, 0x51,
+                        0x03, 0xE5, 0x15, 0x95, 0x52, 0x1F, 0xFE, 0x47];
+        assert_eq!(stream, expected);
+    }
+
+    #[test]
+    fn test_hc128_from_key_iv() {
+        let key = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+        let iv = [17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32];
+        let mut seed = [0u8; 32];
+        for (i, x) in seed.iter_mut().enumerate() {
+            *x = i as u8 + 1;
+        }
+        let mut rng1 = Hc128Rng::from_key_iv(key, iv);
+        let mut rng2 = Hc128Rng::from_seed(seed);
+        for _ in 0..40 {
+            assert_eq!(rng1.next_u32(), rng2.next_u32());
+        }
+    }
+
     #[test]
     fn test_hc128_true_values_u64() {
         let seed = [0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, // key
@@ -460,4 +657,76 @@ mod test {
             assert_eq!(rng1.next_u32(), rng2.next_u32());
         }
     }
+
+    #[test]
+    fn test_hc128_word_pos() {
+        let seed = [0x55,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, // key
+                    0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0]; // iv
+        let mut rng = Hc128Rng::from_seed(seed);
+        assert_eq!(rng.get_word_pos(), 0);
+        let mut words = [0u32; 2100];
+        for (i, w) in words.iter_mut().enumerate() {
+            assert_eq!(rng.get_word_pos(), i as u64);
+            *w = rng.next_u32();
+        }
+        rng.next_u64();
+        assert_eq!(rng.get_word_pos(), 2102);
+
+        // Seek a fresh generator, crossing the switches between P and Q.
+        for &n in [0, 1, 15, 16, 17, 100, 511, 512, 513, 1024, 1030, 2000].iter() {
+            let mut seeked = Hc128Rng::from_seed(seed);
+            seeked.set_word_pos(n);
+            assert_eq!(seeked.get_word_pos(), n);
+            for w in words[n as usize..].iter() {
+                assert_eq!(seeked.next_u32(), *w);
+            }
+        }
+
+        // Skip from a partially consumed buffer.
+        let mut skipped = Hc128Rng::from_seed(seed);
+        skipped.next_u32();
+        for &(n, pos) in [(3, 4), (10, 15), (0, 16), (1, 18), (5, 24),
+                          (1000, 1025)].iter() {
+            skipped.skip(n);
+            assert_eq!(skipped.get_word_pos(), pos);
+            assert_eq!(skipped.next_u32(), words[pos as usize]);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_hc128_set_word_pos_backwards() {
+        let mut rng = Hc128Rng::from_seed([0; 32]);
+        rng.skip(20);
+        rng.set_word_pos(19);
+    }
+
+    #[cfg(feature="alloc")]
+    #[test]
+    fn test_hc128_state() {
+        use prng::RngState;
+        let seed = [55,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0, // key
+                    0,0,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0]; // iv
+        let mut rng = Hc128Rng::from_seed(seed);
+        // Save at the start, part way through and at the end of a block, and
+        // in both halves of the table update cycle
+        for &n in [0, 3, 13, 600].iter() {
+            for _ in 0..n {
+                rng.next_u32();
+            }
+            let bytes = rng.to_bytes();
+            assert_eq!(bytes.len(), 5 + 4 * 1044);
+            let mut restored = Hc128Rng::from_bytes(&bytes).unwrap();
+            assert_eq!(restored.get_word_pos(), rng.get_word_pos());
+            for _ in 0..1100 {
+                assert_eq!(rng.next_u32(), restored.next_u32());
+            }
+        }
+
+        let bytes = rng.to_bytes();
+        let mut bad_counter = bytes.clone();
+        bad_counter[5 + 4 * 1024] = 1;
+        assert!(Hc128Rng::from_bytes(&bad_counter).is_err());
+        assert!(::prng::ChaChaRng::from_bytes(&bytes).is_err());
+    }
 }
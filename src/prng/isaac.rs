@@ -15,6 +15,9 @@ use core::num::Wrapping as w;
 use rand_core::{RngCore, SeedableRng, Error, le};
 use rand_core::block::{BlockRngCore, BlockRng};
 use prng::isaac_array::IsaacArray;
+use rngs::EntropySource;
+#[cfg(all(feature="alloc", not(feature="std")))] use alloc::Vec;
+#[cfg(feature="alloc")] use prng::state::{RngState, StateReader, StateWriter};
 
 #[allow(non_camel_case_types)]
 type w32 = w<u32>;
@@ -114,6 +117,8 @@ impl RngCore for IsaacRng {
     }
 }
 
+impl EntropySource for IsaacRng {}
+
 impl SeedableRng for IsaacRng {
     type Seed = <IsaacCore as SeedableRng>::Seed;
 
@@ -361,6 +366,42 @@ impl SeedableRng for IsaacCore {
     }
 }
 
+#[cfg(feature="alloc")]
+impl RngState for IsaacRng {
+    fn to_bytes(&self) -> Vec<u8> {
+        let core = &self.0.core;
+        let mut writer = StateWriter::new(b"ISAC", 4 * (2 * RAND_SIZE + 4));
+        for x in core.mem.iter() {
+            writer.write_u32(x.0);
+        }
+        writer.write_u32s(&[core.a.0, core.b.0, core.c.0]);
+        writer.write_u32s(self.0.results().as_ref());
+        writer.write_u32(self.0.index() as u32);
+        writer.finish()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = StateReader::new(bytes, b"ISAC")?;
+        let mut core = IsaacCore {
+            mem: [w(0); RAND_SIZE], a: w(0), b: w(0), c: w(0)
+        };
+        for x in core.mem.iter_mut() {
+            *x = w(reader.read_u32()?);
+        }
+        core.a = w(reader.read_u32()?);
+        core.b = w(reader.read_u32()?);
+        core.c = w(reader.read_u32()?);
+        let mut results = IsaacArray::<u32>::default();
+        reader.read_u32s(results.as_mut())?;
+        let index = reader.read_index(RAND_SIZE)?;
+        reader.finish()?;
+
+        let mut rng = BlockRng::new(core);
+        rng.restore(results, index);
+        Ok(IsaacRng(rng))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use {RngCore, SeedableRng};
@@ -483,4 +524,32 @@ mod test {
             assert_eq!(rng.next_u32(), deserialized.next_u32());
         }
     }
+
+    #[cfg(feature="alloc")]
+    #[test]
+    fn test_isaac_state() {
+        use prng::RngState;
+        let seed = [1,0,0,0, 23,0,0,0, 200,1,0,0, 210,30,0,0,
+                    57,48,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0];
+        let mut rng = IsaacRng::from_seed(seed);
+        // Save at the start, part way through and at the end of a block
+        for &n in [0, 1, 100, 155, 300].iter() {
+            for _ in 0..n {
+                rng.next_u32();
+            }
+            let bytes = rng.to_bytes();
+            assert_eq!(bytes.len(), 5 + 4 * (2 * 256 + 4));
+            let mut restored = IsaacRng::from_bytes(&bytes).unwrap();
+            for _ in 0..300 {
+                assert_eq!(rng.next_u32(), restored.next_u32());
+                assert_eq!(rng.next_u64(), restored.next_u64());
+            }
+        }
+
+        let bytes = rng.to_bytes();
+        let mut bad_index = bytes.clone();
+        bad_index[bytes.len() - 3] = 0x10;
+        assert!(IsaacRng::from_bytes(&bad_index).is_err());
+        assert!(IsaacRng::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
 }
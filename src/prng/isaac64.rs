@@ -15,6 +15,10 @@ use core::num::Wrapping as w;
 use rand_core::{RngCore, SeedableRng, Error, le};
 use rand_core::block::{BlockRngCore, BlockRng64};
 use prng::isaac_array::IsaacArray;
+use rngs::EntropySource;
+#[cfg(all(feature="alloc", not(feature="std")))] use alloc::Vec;
+#[cfg(feature="alloc")] use prng::state::{RngState, StateReader, StateWriter,
+                                          invalid};
 
 #[allow(non_camel_case_types)]
 type w64 = w<u64>;
@@ -104,6 +108,8 @@ impl RngCore for Isaac64Rng {
     }
 }
 
+impl EntropySource for Isaac64Rng {}
+
 impl SeedableRng for Isaac64Rng {
     type Seed = <Isaac64Core as SeedableRng>::Seed;
 
@@ -324,6 +330,48 @@ impl SeedableRng for Isaac64Core {
     }
 }
 
+#[cfg(feature="alloc")]
+impl RngState for Isaac64Rng {
+    fn to_bytes(&self) -> Vec<u8> {
+        let core = &self.0.core;
+        let mut writer = StateWriter::new(b"IS64", 8 * (2 * RAND_SIZE + 3) + 8);
+        for x in core.mem.iter() {
+            writer.write_u64(x.0);
+        }
+        writer.write_u64s(&[core.a.0, core.b.0, core.c.0]);
+        writer.write_u64s(self.0.results().as_ref());
+        writer.write_u32(self.0.index() as u32);
+        writer.write_u32(self.0.half_used() as u32);
+        writer.finish()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = StateReader::new(bytes, b"IS64")?;
+        let mut core = Isaac64Core {
+            mem: [w(0); RAND_SIZE], a: w(0), b: w(0), c: w(0)
+        };
+        for x in core.mem.iter_mut() {
+            *x = w(reader.read_u64()?);
+        }
+        core.a = w(reader.read_u64()?);
+        core.b = w(reader.read_u64()?);
+        core.c = w(reader.read_u64()?);
+        let mut results = IsaacArray::<u64>::default();
+        reader.read_u64s(results.as_mut())?;
+        let index = reader.read_index(RAND_SIZE)?;
+        let half_used = match reader.read_u32()? {
+            0 => false,
+            1 if index > 0 => true,
+            _ => return Err(invalid("RngState: invalid ISAAC-64 half_used")),
+        };
+        reader.finish()?;
+
+        let mut rng = BlockRng64::new(core);
+        rng.restore(results, index, half_used);
+        Ok(Isaac64Rng(rng))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use {RngCore, SeedableRng};
@@ -475,4 +523,32 @@ mod test {
             assert_eq!(rng.next_u64(), deserialized.next_u64());
         }
     }
+
+    #[cfg(feature="alloc")]
+    #[test]
+    fn test_isaac64_state() {
+        use prng::RngState;
+        let seed = [1,0,0,0, 23,0,0,0, 200,1,0,0, 210,30,0,0,
+                    57,48,0,0, 0,0,0,0, 0,0,0,0, 0,0,0,0];
+        let mut rng = Isaac64Rng::from_seed(seed);
+        // Save at the start, part way through and at the end of a block
+        for &n in [0, 1, 100, 155, 300].iter() {
+            for _ in 0..n {
+                rng.next_u32();
+            }
+            let bytes = rng.to_bytes();
+            assert_eq!(bytes.len(), 5 + 8 * (2 * 256 + 3) + 8);
+            let mut restored = Isaac64Rng::from_bytes(&bytes).unwrap();
+            for _ in 0..300 {
+                assert_eq!(rng.next_u32(), restored.next_u32());
+                assert_eq!(rng.next_u64(), restored.next_u64());
+            }
+        }
+
+        let bytes = rng.to_bytes();
+        let mut bad_half_used = bytes.clone();
+        bad_half_used[bytes.len() - 4] = 2;
+        assert!(Isaac64Rng::from_bytes(&bad_half_used).is_err());
+        assert!(Isaac64Rng::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
 }
@@ -43,11 +43,13 @@
 //! where there is a risk of players predicting the next output value from
 //! previous values, in which case a CSPRNG should be used).
 //!
-//! Currently Rand provides only one PRNG, and not a very good one at that:
+//! Currently Rand provides the following basic PRNGs:
 //!
 //! | name | full name | performance | memory | quality | period | features |
 //! |------|-----------|-------------|--------|---------|--------|----------|
 //! | [`XorShiftRng`] | Xorshift 32/128 | ★★★☆☆ | 16 bytes | ★☆☆☆☆ | `u32` * 2<sup>128</sup> - 1 | — |
+//! | [`Xoroshiro128StarStarRng`] | Xoroshiro128** | ★★★★☆ | 16 bytes | ★★★☆☆ | `u64` * 2<sup>128</sup> - 1 | — |
+//! | [`Pcg64`] | PCG XSL RR 128/64 (LCG) | ★★★☆☆ | 32 bytes | ★★★★☆ | `u64` * 2<sup>128</sup> | streams, requires `u128` |
 //!
 // Quality stars [not rendered in documentation]:
 // 5. reserved for crypto-level (e.g. ChaCha8, ISAAC)
@@ -87,11 +89,14 @@
 //!
 //! | name | full name |  performance | initialization | memory | predictability | forward secrecy |
 //! |------|-----------|--------------|--------------|----------|----------------|-------------------------|
-//! | [`ChaChaRng`] | ChaCha20 | ★☆☆☆☆ | fast | 136 bytes | secure | no |
+//! | [`ChaChaRng`] | ChaCha20 | ★☆☆☆☆ | fast | 336 bytes | secure | no |
 //! | [`Hc128Rng`] | HC-128 | ★★☆☆☆ | slow | 4176 bytes | secure | no |
 //! | [`IsaacRng`] | ISAAC | ★★☆☆☆ | slow | 2072 bytes | unknown | unknown |
 //! | [`Isaac64Rng`] | ISAAC-64 | ★★☆☆☆ | slow | 4136 bytes| unknown | unknown |
 //!
+//! [`ChaChaRng`] can also run 8 or 12 rounds instead of 20, which is two to
+//! three times as fast, with a smaller security margin.
+//!
 //! It should be noted that the ISAAC generators are only included for
 //! historical reasons, they have been with the Rust language since the very
 //! beginning. They have good quality output and no attacks are known, but have
@@ -135,7 +140,7 @@
 //! to be at least 192 bits and some more may be required for the algorithm,
 //! 256 bits would be approximately the minimum secure size. In practice,
 //! CSPRNGs tend to use quite a bit more, [`ChaChaRng`] is relatively small with
-//! 136 bytes of state.
+//! 336 bytes, most of which buffer four blocks of output.
 //! 
 //! ## Initialization time
 //!
@@ -284,6 +289,9 @@
 //! - The ability to jump or seek around in the random number stream;
 //!   with large periood this can be used as an alternative to streams.
 //!
+//! All PRNGs in this module implement [`RngState`], which allows saving the
+//! complete state of a generator as bytes and restoring it later.
+//!
 //!
 //! # Further reading
 //!
@@ -296,9 +304,12 @@
 //!
 //!
 //! [`rngs` module]: ../rngs/index.html
+//! [`RngState`]: trait.RngState.html
 //! [basic PRNGs]: #basic-pseudo-random-number-generators-prngs
 //! [CSPRNGs]: #cryptographically-secure-pseudo-random-number-generators-csprngs
 //! [`XorShiftRng`]: struct.XorShiftRng.html
+//! [`Xoroshiro128StarStarRng`]: struct.Xoroshiro128StarStarRng.html
+//! [`Pcg64`]: struct.Pcg64.html
 //! [`ChaChaRng`]: chacha/struct.ChaChaRng.html
 //! [`Hc128Rng`]: hc128/struct.Hc128Rng.html
 //! [`IsaacRng`]: isaac/struct.IsaacRng.html
@@ -320,11 +331,17 @@ pub mod hc128;
 pub mod isaac;
 pub mod isaac64;
 mod xorshift;
+mod xoroshiro;
+#[cfg(any(feature = "i128_support", rustc_1_26))] mod pcg64;
 
 mod isaac_array;
+#[cfg(feature="alloc")] mod state;
 
 pub use self::chacha::ChaChaRng;
 pub use self::hc128::Hc128Rng;
 pub use self::isaac::IsaacRng;
 pub use self::isaac64::Isaac64Rng;
 pub use self::xorshift::XorShiftRng;
+pub use self::xoroshiro::Xoroshiro128StarStarRng;
+#[cfg(any(feature = "i128_support", rustc_1_26))] pub use self::pcg64::Pcg64;
+#[cfg(feature="alloc")] pub use self::state::RngState;
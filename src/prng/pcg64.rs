@@ -0,0 +1,309 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The PCG XSL RR 128/64 generator
+
+use core::fmt;
+use rand_core::{RngCore, SeedableRng, Error, impls, le};
+use rngs::EntropySource;
+#[cfg(all(feature="alloc", not(feature="std")))] use alloc::Vec;
+#[cfg(feature="alloc")] use prng::state::{RngState, StateReader, StateWriter,
+                                          invalid};
+
+// This is the default multiplier used by PCG for 128-bit state.
+const MULTIPLIER: u128 = 2549297995355413924 << 64 | 4865540595714422341;
+
+/// A PCG random number generator, the XSL RR 128/64 variant (also known as
+/// `pcg64`)[1].
+///
+/// PCG is a permuted congruential generator: a 128-bit linear congruential
+/// generator whose state is permuted into a 64-bit output with an "xorshift
+/// low" (XSL) and a random rotation (RR). It has a period of 2<sup>128</sup>,
+/// a state of 128 bits plus a 128-bit increment, and passes the TestU01 and
+/// PractRand test suites. `next_u32` uses the lower half of a 64-bit value.
+///
+/// The increment selects one of 2<sup>127</sup> streams. Generators with the
+/// same state but different streams produce different sequences, so the
+/// stream can be used to create independent generators from the same seed.
+/// See [`new`]. Note that the streams are not fully independent: streams
+/// which differ only in their highest bits produce strongly correlated
+/// sequences, so prefer consecutive or randomly chosen streams.
+///
+/// The algorithm is not suitable for cryptographic purposes. If you do not
+/// know for sure that it fits your requirements, use a more secure one such
+/// as `IsaacRng` or `OsRng`.
+///
+/// This generator requires the `u128` type, which is available since Rust
+/// 1.26 or with the `i128_support` feature.
+///
+/// # Example
+///
+/// ```
+/// use rand::Rng;
+/// use rand::prng::Pcg64;
+///
+/// // The same seed with two streams, for two workers
+/// let mut rng0 = Pcg64::new(0xcafef00dd15ea5e5, 0);
+/// let mut rng1 = Pcg64::new(0xcafef00dd15ea5e5, 1);
+/// assert!(rng0.gen::<u64>() != rng1.gen::<u64>());
+/// ```
+///
+/// [1]: Melissa E. O'Neill, ["PCG: A Family of Simple Fast Space-Efficient
+/// Statistically Good Algorithms for Random Number Generation"](
+/// http://www.pcg-random.org/pdf/hmc-cs-2014-0905.pdf), 2014.
+///
+/// [`new`]: #method.new
+#[derive(Clone)]
+#[cfg_attr(feature="serde1", derive(Serialize,Deserialize))]
+pub struct Pcg64 {
+    state: u128,
+    increment: u128,
+}
+
+// Custom Debug implementation that does not expose the internal state
+impl fmt::Debug for Pcg64 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Pcg64 {{}}")
+    }
+}
+
+impl Pcg64 {
+    /// Construct an instance with the given initial `state` and `stream`.
+    ///
+    /// The increment of the generator is `2 * stream + 1`, so the highest
+    /// bit of `stream` is ignored. This is the same initialization as
+    /// `pcg64_srandom_r` of the reference implementation, and produces the
+    /// same output.
+    pub fn new(state: u128, stream: u128) -> Pcg64 {
+        let increment = (stream << 1) | 1;
+        let mut pcg = Pcg64 {
+            state: state.wrapping_add(increment),
+            increment,
+        };
+        pcg.step();
+        pcg
+    }
+
+    #[inline]
+    fn step(&mut self) {
+        self.state = self.state
+            .wrapping_mul(MULTIPLIER)
+            .wrapping_add(self.increment);
+    }
+}
+
+impl RngCore for Pcg64 {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.step();
+        // Output function XSL RR: xorshift low (bits), random rotation.
+        // The rotation uses the 6 highest bits of the state.
+        let rot = (self.state >> 122) as u32;
+        let xsl = ((self.state >> 64) as u64) ^ (self.state as u64);
+        xsl.rotate_right(rot)
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl EntropySource for Pcg64 {}
+
+impl SeedableRng for Pcg64 {
+    /// The first 16 bytes are the initial state, and the last 16 bytes the
+    /// stream, both in little-endian order.
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let mut seed_u64 = [0u64; 4];
+        le::read_u64_into(&seed, &mut seed_u64);
+        let state = u128::from(seed_u64[0]) | u128::from(seed_u64[1]) << 64;
+        let stream = u128::from(seed_u64[2]) | u128::from(seed_u64[3]) << 64;
+        Pcg64::new(state, stream)
+    }
+}
+
+#[cfg(feature="alloc")]
+impl RngState for Pcg64 {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = StateWriter::new(b"PC64", 32);
+        writer.write_u64s(&[self.state as u64, (self.state >> 64) as u64,
+                            self.increment as u64, (self.increment >> 64) as u64]);
+        writer.finish()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = StateReader::new(bytes, b"PC64")?;
+        let mut words = [0u64; 4];
+        reader.read_u64s(&mut words)?;
+        reader.finish()?;
+        if words[2] & 1 == 0 {
+            return Err(invalid("RngState: even PCG increment"));
+        }
+        Ok(Pcg64 {
+            state: u128::from(words[0]) | u128::from(words[1]) << 64,
+            increment: u128::from(words[2]) | u128::from(words[3]) << 64,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {RngCore, SeedableRng};
+    use super::Pcg64;
+
+    #[test]
+    fn test_pcg64_construction() {
+        // Test that various construction techniques produce a working RNG.
+        let seed = [1,2,3,4, 5,6,7,8, 9,10,11,12, 13,14,15,16,
+                    17,18,19,20, 21,22,23,24, 25,26,27,28, 29,30,31,32];
+        let mut rng1 = Pcg64::from_seed(seed);
+        let mut rng2 = Pcg64::new(0x100f0e0d0c0b0a090807060504030201,
+                                  0x201f1e1d1c1b1a191817161514131211);
+        assert_eq!(rng1.next_u64(), rng2.next_u64());
+
+        let _rng3 = Pcg64::from_rng(rng1).unwrap();
+    }
+
+    #[test]
+    fn test_pcg64_reference() {
+        // From the reference implementation, `pcg64-demo` with the state 42
+        // and the stream 54 (`check-pcg64.out`)
+        let mut rng = Pcg64::new(42, 54);
+        let mut results = [0u64; 6];
+        for i in results.iter_mut() { *i = rng.next_u64(); }
+        let expected: [u64; 6] = [
+            0x86b1da1d72062b68, 0x1304aa46c9853d39, 0xa3670e9e0dd50358,
+            0xf9090e529a7dae00, 0xc85b9fd837996f2c, 0x606121f8e3919196];
+        assert_eq!(results, expected);
+    }
+
+    #[test]
+    fn test_pcg64_true_values() {
+        let mut rng = Pcg64::new(42, 54);
+        let mut results = [0u32; 2];
+        for i in results.iter_mut() { *i = rng.next_u32(); }
+        assert_eq!(results, [0x72062b68, 0xc9853d39]);
+
+        let mut results = [0u8; 12];
+        rng.fill_bytes(&mut results);
+        let expected = [0x58, 0x03, 0xd5, 0x0d, 0x9e, 0x0e, 0x67, 0xa3,
+                        0x00, 0xae, 0x7d, 0x9a];
+        assert_eq!(results, expected);
+    }
+
+    #[test]
+    fn test_pcg64_streams() {
+        // The same state with different streams gives uncorrelated output.
+        const N: usize = 10_000;
+        for &(a, b) in [(0, 1), (1, 2), (54, 55),
+                            (0, 0x9e3779b97f4a7c15f39cc0605cedc834)].iter() {
+            let mut rng_a = Pcg64::new(42, a);
+            let mut rng_b = Pcg64::new(42, b);
+            let mut equal = 0;
+            let mut bits = 0;
+            let (mut sum_a, mut sum_b, mut sum_ab) = (0.0, 0.0, 0.0);
+            let (mut sum_aa, mut sum_bb) = (0.0, 0.0);
+            for _ in 0..N {
+                let (x, y) = (rng_a.next_u64(), rng_b.next_u64());
+                if x == y { equal += 1; }
+                bits += (x ^ y).count_ones();
+                let (x, y) = ((x >> 11) as f64, (y >> 11) as f64);
+                sum_a += x;
+                sum_b += y;
+                sum_ab += x * y;
+                sum_aa += x * x;
+                sum_bb += y * y;
+            }
+            assert_eq!(equal, 0);
+
+            // Each bit differs with probability 1/2, so the number of
+            // different bits has mean 32 and standard deviation 4 per value.
+            let mean_bits = bits as f64 / N as f64;
+            let err = 5.0 * 4.0 / (N as f64).sqrt();
+            assert!((mean_bits - 32.0).abs() < err,
+                    "streams {} and {}: {} bits differ", a, b, mean_bits);
+
+            let n = N as f64;
+            let cov = sum_ab / n - sum_a / n * sum_b / n;
+            let var_a = sum_aa / n - (sum_a / n) * (sum_a / n);
+            let var_b = sum_bb / n - (sum_b / n) * (sum_b / n);
+            let corr = cov / (var_a * var_b).sqrt();
+            assert!(corr.abs() < 5.0 / n.sqrt(),
+                    "streams {} and {}: correlation {}", a, b, corr);
+        }
+
+        // The highest bit of the stream is ignored.
+        let mut rng_a = Pcg64::new(42, 54);
+        let mut rng_b = Pcg64::new(42, 54 | 1 << 127);
+        assert_eq!(rng_a.next_u64(), rng_b.next_u64());
+    }
+
+    #[test]
+    fn test_pcg64_clone() {
+        let mut rng1 = Pcg64::new(42, 54);
+        let mut rng2 = rng1.clone();
+        for _ in 0..16 {
+            assert_eq!(rng1.next_u64(), rng2.next_u64());
+        }
+    }
+
+    #[cfg(all(feature="serde1", feature="std"))]
+    #[test]
+    fn test_pcg64_serde() {
+        use bincode;
+        use std::io::{BufWriter, BufReader};
+
+        let mut rng = Pcg64::new(42, 54);
+
+        let buf: Vec<u8> = Vec::new();
+        let mut buf = BufWriter::new(buf);
+        bincode::serialize_into(&mut buf, &rng).expect("Could not serialize");
+
+        let buf = buf.into_inner().unwrap();
+        let mut read = BufReader::new(&buf[..]);
+        let mut deserialized: Pcg64 = bincode::deserialize_from(&mut read).expect("Could not deserialize");
+
+        for _ in 0..16 {
+            assert_eq!(rng.next_u64(), deserialized.next_u64());
+        }
+    }
+
+    #[cfg(feature="alloc")]
+    #[test]
+    fn test_pcg64_state() {
+        use prng::RngState;
+        let mut rng = Pcg64::new(42, 54);
+        rng.next_u32();
+        let bytes = rng.to_bytes();
+        assert_eq!(bytes.len(), 5 + 32);
+        let mut restored = Pcg64::from_bytes(&bytes).unwrap();
+        for _ in 0..16 {
+            assert_eq!(rng.next_u64(), restored.next_u64());
+        }
+
+        let mut even = bytes.clone();
+        even[5 + 16] &= !1;
+        assert!(Pcg64::from_bytes(&even).is_err());
+        assert!(Pcg64::from_bytes(&bytes[..36]).is_err());
+    }
+}
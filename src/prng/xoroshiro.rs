@@ -0,0 +1,240 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The xoroshiro128** generator
+
+use core::fmt;
+use rand_core::{RngCore, SeedableRng, Error, impls, le};
+use rngs::EntropySource;
+#[cfg(all(feature="alloc", not(feature="std")))] use alloc::Vec;
+#[cfg(feature="alloc")] use prng::state::{RngState, StateReader, StateWriter,
+                                          invalid};
+
+/// The xoroshiro128** random number generator[1].
+///
+/// Xoroshiro128** has a state of 128 bits and a period of 2<sup>128</sup> - 1.
+/// It natively generates 64-bit values, making it one of the fastest
+/// generators on 64-bit platforms, and it passes the TestU01 and PractRand
+/// test suites. `next_u32` uses the upper half of a 64-bit value.
+///
+/// The algorithm is not suitable for cryptographic purposes. If you do not
+/// know for sure that it fits your requirements, use a more secure one such
+/// as `IsaacRng` or `OsRng`.
+///
+/// [1]: David Blackman and Sebastiano Vigna, ["Scrambled Linear
+/// Pseudorandom Number Generators"](http://xoshiro.di.unimi.it/), 2018.
+#[derive(Clone)]
+#[cfg_attr(feature="serde1", derive(Serialize,Deserialize))]
+pub struct Xoroshiro128StarStarRng {
+    s0: u64,
+    s1: u64,
+}
+
+// Custom Debug implementation that does not expose the internal state
+impl fmt::Debug for Xoroshiro128StarStarRng {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Xoroshiro128StarStarRng {{}}")
+    }
+}
+
+impl RngCore for Xoroshiro128StarStarRng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        // The upper bits have the best quality.
+        (self.next_u64() >> 32) as u32
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        let s0 = self.s0;
+        let mut s1 = self.s1;
+        let result = s0.wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+
+        s1 ^= s0;
+        self.s0 = s0.rotate_left(24) ^ s1 ^ (s1 << 16);
+        self.s1 = s1.rotate_left(37);
+        result
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl EntropySource for Xoroshiro128StarStarRng {}
+
+impl SeedableRng for Xoroshiro128StarStarRng {
+    type Seed = [u8; 16];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let mut seed_u64 = [0u64; 2];
+        le::read_u64_into(&seed, &mut seed_u64);
+
+        // Like Xorshift, xoroshiro cannot be seeded with 0; use a preset
+        // value instead.
+        if seed_u64 == [0, 0] {
+            seed_u64 = [0xBAD_5EED, 0xBAD_5EED];
+        }
+
+        Xoroshiro128StarStarRng {
+            s0: seed_u64[0],
+            s1: seed_u64[1],
+        }
+    }
+}
+
+#[cfg(feature="alloc")]
+impl RngState for Xoroshiro128StarStarRng {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = StateWriter::new(b"XRSS", 16);
+        writer.write_u64s(&[self.s0, self.s1]);
+        writer.finish()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = StateReader::new(bytes, b"XRSS")?;
+        let mut state = [0u64; 2];
+        reader.read_u64s(&mut state)?;
+        reader.finish()?;
+        if state == [0, 0] {
+            return Err(invalid("RngState: all-zero xoroshiro state"));
+        }
+        Ok(Xoroshiro128StarStarRng {
+            s0: state[0],
+            s1: state[1],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {RngCore, SeedableRng};
+    use super::Xoroshiro128StarStarRng;
+
+    #[test]
+    fn test_xoroshiro_construction() {
+        // Test that various construction techniques produce a working RNG.
+        let seed = [1,2,3,4, 5,6,7,8, 9,10,11,12, 13,14,15,16];
+        let mut rng1 = Xoroshiro128StarStarRng::from_seed(seed);
+        assert_eq!(rng1.next_u64(), 11387194265570187060);
+
+        let _rng2 = Xoroshiro128StarStarRng::from_rng(rng1).unwrap();
+    }
+
+    #[test]
+    fn test_xoroshiro_reference() {
+        // From the reference implementation, with the state `s = {1, 2}`
+        let mut rng = Xoroshiro128StarStarRng::from_seed(
+            [1,0,0,0, 0,0,0,0, 2,0,0,0, 0,0,0,0]);
+        let mut results = [0u64; 10];
+        for i in results.iter_mut() { *i = rng.next_u64(); }
+        let expected: [u64; 10] = [
+            5760, 97769243520, 9706862127477703552, 9223447511460779954,
+            8358291023205304566, 15695619998649302768, 8517900938696309774,
+            16586480348202605369, 6959129367028440372, 16822147227405758281];
+        assert_eq!(results, expected);
+    }
+
+    #[test]
+    fn test_xoroshiro_true_values() {
+        let seed = [16,15,14,13, 12,11,10,9, 8,7,6,5, 4,3,2,1];
+        let mut rng = Xoroshiro128StarStarRng::from_seed(seed);
+
+        let mut results = [0u32; 6];
+        for i in results.iter_mut() { *i = rng.next_u32(); }
+        let expected: [u32; 6] = [
+            1643679525, 633098986, 359809559, 3087652787, 3637327248,
+            1423931792];
+        assert_eq!(results, expected);
+
+        let mut results = [0u64; 6];
+        for i in results.iter_mut() { *i = rng.next_u64(); }
+        let expected: [u64; 6] = [
+            17493380874828433475, 9291955131772343968, 9470551284613364698,
+            2238316068360879650, 16071356770335797120, 6952931702179972033];
+        assert_eq!(results, expected);
+
+        let mut results = [0u8; 32];
+        rng.fill_bytes(&mut results);
+        let expected = [63, 121, 118, 117, 137, 15, 168, 11,
+                        230, 61, 165, 190, 231, 16, 236, 18,
+                        111, 71, 185, 162, 138, 210, 3, 154,
+                        109, 251, 32, 145, 163, 225, 150, 37];
+        assert_eq!(results, expected);
+    }
+
+    #[test]
+    fn test_xoroshiro_zero_seed() {
+        // Xoroshiro does not work with an all zero seed.
+        // Assert it does not panic.
+        let mut rng = Xoroshiro128StarStarRng::from_seed([0; 16]);
+        let a = rng.next_u64();
+        let b = rng.next_u64();
+        assert!(a != 0);
+        assert!(b != a);
+    }
+
+    #[test]
+    fn test_xoroshiro_clone() {
+        let seed = [1,2,3,4, 5,5,7,8, 8,7,6,5, 4,3,2,1];
+        let mut rng1 = Xoroshiro128StarStarRng::from_seed(seed);
+        let mut rng2 = rng1.clone();
+        for _ in 0..16 {
+            assert_eq!(rng1.next_u64(), rng2.next_u64());
+        }
+    }
+
+    #[cfg(all(feature="serde1", feature="std"))]
+    #[test]
+    fn test_xoroshiro_serde() {
+        use bincode;
+        use std::io::{BufWriter, BufReader};
+
+        let seed = [1,2,3,4, 5,6,7,8, 9,10,11,12, 13,14,15,16];
+        let mut rng = Xoroshiro128StarStarRng::from_seed(seed);
+
+        let buf: Vec<u8> = Vec::new();
+        let mut buf = BufWriter::new(buf);
+        bincode::serialize_into(&mut buf, &rng).expect("Could not serialize");
+
+        let buf = buf.into_inner().unwrap();
+        let mut read = BufReader::new(&buf[..]);
+        let mut deserialized: Xoroshiro128StarStarRng = bincode::deserialize_from(&mut read).expect("Could not deserialize");
+
+        for _ in 0..16 {
+            assert_eq!(rng.next_u64(), deserialized.next_u64());
+        }
+    }
+
+    #[cfg(feature="alloc")]
+    #[test]
+    fn test_xoroshiro_state() {
+        use prng::RngState;
+        let mut rng = Xoroshiro128StarStarRng::from_seed([3; 16]);
+        rng.next_u32();
+        let bytes = rng.to_bytes();
+        assert_eq!(bytes.len(), 5 + 16);
+        let mut restored = Xoroshiro128StarStarRng::from_bytes(&bytes).unwrap();
+        for _ in 0..16 {
+            assert_eq!(rng.next_u64(), restored.next_u64());
+        }
+
+        let mut zero = bytes.clone();
+        for b in zero[5..].iter_mut() { *b = 0; }
+        assert!(Xoroshiro128StarStarRng::from_bytes(&zero).is_err());
+        assert!(Xoroshiro128StarStarRng::from_bytes(&bytes[..20]).is_err());
+    }
+}
@@ -13,6 +13,10 @@
 use core::num::Wrapping as w;
 use core::{fmt, slice};
 use rand_core::{RngCore, SeedableRng, Error, impls, le};
+use rngs::EntropySource;
+#[cfg(all(feature="alloc", not(feature="std")))] use alloc::Vec;
+#[cfg(feature="alloc")] use prng::state::{RngState, StateReader, StateWriter,
+                                          invalid};
 
 /// An Xorshift[1] random number
 /// generator.
@@ -86,6 +90,8 @@ impl RngCore for XorShiftRng {
     }
 }
 
+impl EntropySource for XorShiftRng {}
+
 impl SeedableRng for XorShiftRng {
     type Seed = [u8; 16];
 
@@ -129,6 +135,31 @@ impl SeedableRng for XorShiftRng {
     }
 }
 
+#[cfg(feature="alloc")]
+impl RngState for XorShiftRng {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = StateWriter::new(b"XSHF", 16);
+        writer.write_u32s(&[self.x.0, self.y.0, self.z.0, self.w.0]);
+        writer.finish()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = StateReader::new(bytes, b"XSHF")?;
+        let mut state = [0u32; 4];
+        reader.read_u32s(&mut state)?;
+        reader.finish()?;
+        if state.iter().all(|&x| x == 0) {
+            return Err(invalid("RngState: all-zero Xorshift state"));
+        }
+        Ok(XorShiftRng {
+            x: w(state[0]),
+            y: w(state[1]),
+            z: w(state[2]),
+            w: w(state[3]),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use {RngCore, SeedableRng};
@@ -223,4 +254,23 @@ mod tests {
             assert_eq!(rng.next_u64(), deserialized.next_u64());
         }
     }
+
+    #[cfg(feature="alloc")]
+    #[test]
+    fn test_xorshift_state() {
+        use prng::RngState;
+        let mut rng = XorShiftRng::from_seed([3; 16]);
+        rng.next_u32();
+        let bytes = rng.to_bytes();
+        assert_eq!(bytes.len(), 5 + 16);
+        let mut restored = XorShiftRng::from_bytes(&bytes).unwrap();
+        for _ in 0..16 {
+            assert_eq!(rng.next_u64(), restored.next_u64());
+        }
+
+        let mut zero = bytes.clone();
+        for b in zero[5..].iter_mut() { *b = 0; }
+        assert!(XorShiftRng::from_bytes(&zero).is_err());
+        assert!(XorShiftRng::from_bytes(&bytes[..20]).is_err());
+    }
 }
@@ -14,4 +14,4 @@
 mod reseeding;
 
 #[cfg(feature="std")] pub use self::read::ReadRng;
-pub use self::reseeding::ReseedingRng;
+pub use self::reseeding::{ReseedingRng, ThresholdMode};
@@ -9,7 +9,8 @@
 // except according to those terms.
 
 //! A wrapper around another PRNG that reseeds it after it
-//! generates a certain number of random bytes.
+//! generates a certain number of random bytes, or after a certain number of
+//! calls.
 
 use core::mem::size_of;
 
@@ -54,6 +55,27 @@ use rand_core::block::{BlockRngCore, BlockRng};
 /// through some combination of retrying and delaying reseeding until later.
 /// If handling the source error fails `ReseedingRng` will continue generating
 /// data from the wrapped PRNG without reseeding.
+///
+/// # Fork detection
+///
+/// After `fork()` the child process inherits the state of the parent's RNG,
+/// so both processes would continue with the same output. On Unix,
+/// [`with_fork_detection`] makes `ReseedingRng` compare the process ID with
+/// the one it saw last before every request for random data, and reseed
+/// before producing any more output if it changed. This costs one call to
+/// `getpid()` per request.
+///
+/// # Counting calls
+///
+/// By default the threshold is a number of generated bytes. With
+/// [`with_threshold_mode`]`(ThresholdMode::Calls)` it is instead a number of
+/// calls to `next_u32`, `next_u64`, `fill_bytes` and `try_fill_bytes`, each
+/// of which counts as one regardless of how many bytes it produces. The
+/// reseed then happens at the start of the call after the threshold is used
+/// up, before producing any more output.
+///
+/// [`with_fork_detection`]: #method.with_fork_detection
+/// [`with_threshold_mode`]: #method.with_threshold_mode
 #[derive(Debug)]
 pub struct ReseedingRng<R, Rsdr>(BlockRng<ReseedingCore<R, Rsdr>>)
 where R: BlockRngCore + SeedableRng,
@@ -78,6 +100,104 @@ where R: BlockRngCore + SeedableRng,
     pub fn reseed(&mut self) -> Result<(), Error> {
         self.0.core.reseed()
     }
+
+    /// Change the number of generated bytes (or calls, see
+    /// [`with_threshold_mode`]) after which to reseed the RNG.
+    ///
+    /// The new threshold is used from the next reseed on. If fewer than
+    /// `threshold` bytes remain until the next reseed it stays scheduled as
+    /// is; otherwise it is brought forward, so the RNG never goes longer than
+    /// `threshold` bytes without reseeding.
+    ///
+    /// [`with_threshold_mode`]: #method.with_threshold_mode
+    pub fn set_threshold(&mut self, threshold: u64) {
+        self.0.core.set_threshold(threshold)
+    }
+
+    /// Choose whether the threshold counts generated bytes (the default) or
+    /// calls.
+    ///
+    /// The count restarts from the full threshold in the new mode.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::prelude::*;
+    /// use rand::prng::chacha::ChaChaCore;
+    /// use rand::rngs::OsRng;
+    /// use rand::rngs::adapter::{ReseedingRng, ThresholdMode};
+    ///
+    /// // Reseed after every 1024 draws.
+    /// let prng = ChaChaCore::from_entropy();
+    /// let mut rng = ReseedingRng::new(prng, 1024, OsRng::new().unwrap())
+    ///     .with_threshold_mode(ThresholdMode::Calls);
+    /// println!("{}", rng.gen::<u64>());
+    /// ```
+    pub fn with_threshold_mode(mut self, mode: ThresholdMode) -> Self {
+        self.0.core.mode = mode;
+        self.0.core.until_reseed = self.0.core.threshold;
+        self.0.core.update_check_calls();
+        self
+    }
+
+    /// Reseed the RNG if the process has forked since it was last used.
+    ///
+    /// The process ID is checked before every request for random data, and
+    /// any results buffered before a fork are discarded.
+    #[cfg(all(unix, feature = "std"))]
+    pub fn with_fork_detection(self) -> Self {
+        self.with_pid_source(getpid)
+    }
+
+    /// Enable fork detection, using `get_pid` to find the process ID.
+    #[cfg(any(test, all(unix, feature = "std")))]
+    fn with_pid_source(mut self, get_pid: fn() -> u32) -> Self {
+        self.0.core.fork_check = Some(ForkCheck { get_pid, pid: get_pid() });
+        self.0.core.update_check_calls();
+        self
+    }
+
+    #[inline(always)]
+    fn before_call(&mut self) {
+        // By default there is nothing to do, so keep that to a single branch.
+        if !self.0.core.check_calls {
+            return;
+        }
+        let forked = self.0.core.fork_detected();
+        if self.0.core.count_call() || forked {
+            // Discard the buffered results, so the next output comes from
+            // the reseeded PRNG.
+            self.0.reset();
+        }
+    }
+}
+
+/// The unit in which the threshold of a [`ReseedingRng`] is measured.
+///
+/// [`ReseedingRng`]: struct.ReseedingRng.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ThresholdMode {
+    /// Count generated bytes. This is the default.
+    Bytes,
+    /// Count calls to `next_u32`, `next_u64`, `fill_bytes` and
+    /// `try_fill_bytes`, independent of the number of bytes they generate.
+    Calls,
+}
+
+#[cfg(feature = "log")]
+impl ThresholdMode {
+    fn unit(&self) -> &'static str {
+        match *self {
+            ThresholdMode::Bytes => "bytes",
+            ThresholdMode::Calls => "calls",
+        }
+    }
+}
+
+#[cfg(all(unix, feature = "std"))]
+fn getpid() -> u32 {
+    extern crate libc;
+    unsafe { libc::getpid() as u32 }
 }
 
 // TODO: this should be implemented for any type where the inner type
@@ -88,19 +208,23 @@ where R: BlockRngCore<Item = u32> + SeedableRng,
 {
     #[inline(always)]
     fn next_u32(&mut self) -> u32 {
+        self.before_call();
         self.0.next_u32()
     }
 
     #[inline(always)]
     fn next_u64(&mut self) -> u64 {
+        self.before_call();
         self.0.next_u64()
     }
 
     fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.before_call();
         self.0.fill_bytes(dest)
     }
 
     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.before_call();
         self.0.try_fill_bytes(dest)
     }
 }
@@ -125,7 +249,20 @@ struct ReseedingCore<R, Rsdr> {
     inner: R,
     reseeder: Rsdr,
     threshold: i64,
-    bytes_until_reseed: i64,
+    /// Bytes or calls until the next reseed, depending on `mode`
+    until_reseed: i64,
+    mode: ThresholdMode,
+    fork_check: Option<ForkCheck>,
+    /// Whether every call must be checked, because of fork detection or
+    /// because the threshold counts calls
+    check_calls: bool,
+}
+
+/// The process ID last seen, and how to get the current one.
+#[derive(Clone, Copy, Debug)]
+struct ForkCheck {
+    get_pid: fn() -> u32,
+    pid: u32,
 }
 
 impl<R, Rsdr> BlockRngCore for ReseedingCore<R, Rsdr>
@@ -136,14 +273,23 @@ where R: BlockRngCore + SeedableRng,
     type Results = <R as BlockRngCore>::Results;
 
     fn generate(&mut self, results: &mut Self::Results) {
-        if self.bytes_until_reseed <= 0 {
+        // When counting calls, the count reaching zero during a call must not
+        // reseed yet; `count_call` makes it negative at the start of the next
+        // call.
+        let reseed_due = match self.mode {
+            ThresholdMode::Bytes => self.until_reseed <= 0,
+            ThresholdMode::Calls => self.until_reseed < 0,
+        };
+        if reseed_due {
             // We get better performance by not calling only `auto_reseed` here
             // and continuing with the rest of the function, but by directly
             // returning from a non-inlined function.
             return self.reseed_and_generate(results);
         }
-        let num_bytes = results.as_ref().len() * size_of::<Self::Item>();
-        self.bytes_until_reseed -= num_bytes as i64;
+        if self.mode == ThresholdMode::Bytes {
+            let num_bytes = results.as_ref().len() * size_of::<Self::Item>();
+            self.until_reseed -= num_bytes as i64;
+        }
         self.inner.generate(results);
     }
 }
@@ -165,14 +311,60 @@ where R: BlockRngCore + SeedableRng,
             inner: rng,
             reseeder,
             threshold: threshold as i64,
-            bytes_until_reseed: threshold as i64,
+            until_reseed: threshold as i64,
+            mode: ThresholdMode::Bytes,
+            fork_check: None,
+            check_calls: false,
+        }
+    }
+
+    fn update_check_calls(&mut self) {
+        self.check_calls = self.fork_check.is_some() ||
+                           self.mode == ThresholdMode::Calls;
+    }
+
+    /// Check whether the process ID changed since the last check, and if so
+    /// schedule a reseed.
+    #[inline(always)]
+    fn fork_detected(&mut self) -> bool {
+        if let Some(ref mut check) = self.fork_check {
+            let pid = (check.get_pid)();
+            if pid != check.pid {
+                check.pid = pid;
+                self.until_reseed = 0;
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Count a call if the threshold counts calls, and return whether the
+    /// threshold is used up, so the RNG must be reseeded before this call
+    /// produces any output.
+    #[inline(always)]
+    fn count_call(&mut self) -> bool {
+        if self.mode == ThresholdMode::Calls {
+            if self.until_reseed <= 0 {
+                self.until_reseed = -1;
+                return true;
+            }
+            self.until_reseed -= 1;
+        }
+        false
+    }
+
+    fn set_threshold(&mut self, threshold: u64) {
+        assert!(threshold <= ::core::i64::MAX as u64);
+        self.threshold = threshold as i64;
+        if self.until_reseed > self.threshold {
+            self.until_reseed = self.threshold;
         }
     }
 
     /// Reseed the internal PRNG.
     fn reseed(&mut self) -> Result<(), Error> {
         R::from_rng(&mut self.reseeder).map(|result| {
-            self.bytes_until_reseed = self.threshold;
+            self.until_reseed = self.threshold;
             self.inner = result
         })
     }
@@ -181,23 +373,30 @@ where R: BlockRngCore + SeedableRng,
     fn reseed_and_generate(&mut self,
                            results: &mut <Self as BlockRngCore>::Results)
     {
-        trace!("Reseeding RNG after {} generated bytes",
-               self.threshold - self.bytes_until_reseed);
+        trace!("Reseeding RNG after {} {}",
+               self.threshold - self.until_reseed, self.mode.unit());
         let threshold = if let Err(e) = self.reseed()  {
             let delay = match e.kind {
                 ErrorKind::Transient => 0,
                 kind @ _ if kind.should_retry() => self.threshold >> 8,
                 _ => self.threshold,
             };
-            warn!("Reseeding RNG delayed reseeding by {} bytes due to \
-                    error from source: {}", delay, e);
+            warn!("Reseeding RNG delayed reseeding by {} {} due to \
+                    error from source: {}", delay, self.mode.unit(), e);
             delay
         } else {
             self.threshold
         };
         
-        let num_bytes = results.as_ref().len() * size_of::<<R as BlockRngCore>::Item>();
-        self.bytes_until_reseed = threshold - num_bytes as i64;
+        self.until_reseed = match self.mode {
+            ThresholdMode::Bytes => {
+                let num_bytes = results.as_ref().len() *
+                                size_of::<<R as BlockRngCore>::Item>();
+                threshold - num_bytes as i64
+            }
+            // The current call counts towards the new threshold.
+            ThresholdMode::Calls => threshold - 1,
+        };
         self.inner.generate(results);
     }
 }
@@ -211,7 +410,10 @@ where R: BlockRngCore + SeedableRng + Clone,
             inner: self.inner.clone(),
             reseeder: self.reseeder.clone(),
             threshold: self.threshold,
-            bytes_until_reseed: 0, // reseed clone on first use
+            until_reseed: 0, // reseed clone on first use
+            mode: self.mode,
+            fork_check: self.fork_check,
+            check_calls: self.check_calls,
         }
     }
 }
@@ -222,15 +424,35 @@ where R: BlockRngCore + SeedableRng + CryptoRng,
 
 #[cfg(test)]
 mod test {
-    use {Rng, SeedableRng};
-    use prng::chacha::ChaChaCore;
-    use rngs::mock::StepRng;
-    use super::ReseedingRng;
+    use core::cell::Cell;
+    use {Rng, RngCore, SeedableRng, Error, ErrorKind};
+    use prng::hc128::Hc128Core;
+    use rngs::mock::{StepRng, FaultyRng};
+    use super::{ReseedingRng, ThresholdMode};
+
+    // A reseeder counting the number of times it is used
+    #[derive(Clone)]
+    struct CountingRng<'a> {
+        inner: StepRng,
+        count: &'a Cell<usize>,
+    }
+
+    impl<'a> RngCore for CountingRng<'a> {
+        fn next_u32(&mut self) -> u32 { self.inner.next_u32() }
+        fn next_u64(&mut self) -> u64 { self.inner.next_u64() }
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            self.count.set(self.count.get() + 1);
+            self.inner.fill_bytes(dest)
+        }
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+            Ok(self.fill_bytes(dest))
+        }
+    }
 
     #[test]
     fn test_reseeding() {
         let mut zero = StepRng::new(0, 0);
-        let rng = ChaChaCore::from_rng(&mut zero).unwrap();
+        let rng = Hc128Core::from_rng(&mut zero).unwrap();
         let mut reseeding = ReseedingRng::new(rng, 32*4, zero);
 
         // Currently we only support for arrays up to length 32.
@@ -245,10 +467,50 @@ mod test {
         }
     }
 
+    // Generate `blocks` blocks of the HC-128 core, and return the number of
+    // reseeding attempts with a reseeder failing every `period`-th time.
+    fn reseed_attempts(blocks: usize, threshold: u64, period: u64, kind: ErrorKind)
+        -> (u64, u64)
+    {
+        let mut reseeder = FaultyRng::new(StepRng::new(0, 1), period, kind);
+        {
+            let rng = Hc128Core::from_seed([0; 32]);
+            let mut reseeding = ReseedingRng::new(rng, threshold, &mut reseeder);
+            let mut buf = [0u8; 64];
+            for _ in 0..blocks {
+                reseeding.fill_bytes(&mut buf);
+            }
+        }
+        (reseeder.calls(), reseeder.failures())
+    }
+
+    #[test]
+    fn test_reseeding_errors() {
+        // One block of `Hc128Core` is 64 bytes, so a threshold of 512 blocks
+        // is used up after 512 blocks; the 513th block attempts to reseed.
+        let threshold = 64 * 512;
+        assert_eq!(reseed_attempts(612, threshold, 0, ErrorKind::Transient), (1, 0));
+
+        // Transient errors are retried with the next block.
+        assert_eq!(reseed_attempts(612, threshold, 1, ErrorKind::Transient), (100, 100));
+        // Other recoverable errors delay reseeding by `threshold / 256` bytes,
+        // i.e. two blocks.
+        assert_eq!(reseed_attempts(612, threshold, 1, ErrorKind::NotReady), (50, 50));
+        assert_eq!(reseed_attempts(612, threshold, 1, ErrorKind::Unexpected), (50, 50));
+        // Unrecoverable errors delay reseeding by the full threshold.
+        assert_eq!(reseed_attempts(612, threshold, 1, ErrorKind::Unavailable), (1, 1));
+        assert_eq!(reseed_attempts(1025, threshold, 1, ErrorKind::Unavailable), (2, 2));
+
+        // After a failed attempt is retried successfully, the next reseed
+        // only happens after the full threshold.
+        assert_eq!(reseed_attempts(514, threshold, 1, ErrorKind::Transient), (2, 2));
+        assert_eq!(reseed_attempts(1026, threshold, 2, ErrorKind::Transient), (3, 1));
+    }
+
     #[test]
     fn test_clone_reseeding() {
         let mut zero = StepRng::new(0, 0);
-        let rng = ChaChaCore::from_rng(&mut zero).unwrap();
+        let rng = Hc128Core::from_rng(&mut zero).unwrap();
         let mut rng1 = ReseedingRng::new(rng, 32*4, zero);
 
         let first: u32 = rng1.gen();
@@ -257,4 +519,148 @@ mod test {
         let mut rng2 = rng1.clone();
         assert_eq!(first, rng2.gen::<u32>());
     }
+
+    #[test]
+    fn test_set_threshold() {
+        let count = Cell::new(0);
+        let reseeder = CountingRng { inner: StepRng::new(0, 1), count: &count };
+        let rng = Hc128Core::from_rng(StepRng::new(0, 0)).unwrap();
+        let mut reseeding = ReseedingRng::new(rng, 1024 * 1024, reseeder);
+
+        // HC-128 generates blocks of 64 bytes
+        let mut buf = [0u8; 64];
+        for _ in 0..100 {
+            reseeding.fill_bytes(&mut buf);
+        }
+        assert_eq!(count.get(), 0);
+
+        // A smaller threshold applies immediately
+        reseeding.set_threshold(64 * 4);
+        for _ in 0..4 {
+            reseeding.fill_bytes(&mut buf);
+        }
+        assert_eq!(count.get(), 0);
+        reseeding.fill_bytes(&mut buf);
+        assert_eq!(count.get(), 1);
+        for _ in 0..40 {
+            reseeding.fill_bytes(&mut buf);
+        }
+        assert_eq!(count.get(), 11);
+
+        // A larger threshold applies from the next reseed on
+        reseeding.set_threshold(64 * 100);
+        for _ in 0..4 {
+            reseeding.fill_bytes(&mut buf);
+        }
+        assert_eq!(count.get(), 12);
+        for _ in 0..99 {
+            reseeding.fill_bytes(&mut buf);
+        }
+        assert_eq!(count.get(), 12);
+        reseeding.fill_bytes(&mut buf);
+        assert_eq!(count.get(), 13);
+    }
+
+    #[test]
+    fn test_threshold_calls() {
+        let count = Cell::new(0);
+        let reseeder = CountingRng { inner: StepRng::new(0, 1), count: &count };
+        let rng = Hc128Core::from_rng(StepRng::new(0, 0)).unwrap();
+        let mut reseeding = ReseedingRng::new(rng, 3, reseeder)
+            .with_threshold_mode(ThresholdMode::Calls);
+        let mut reference = ReseedingRng::new(
+            Hc128Core::from_rng(StepRng::new(0, 0)).unwrap(), 1024 * 1024,
+            StepRng::new(0, 0));
+
+        // Every call counts as one, regardless of the number of bytes.
+        let mut buf = [0u8; 100];
+        let mut ref_buf = [0u8; 100];
+        assert_eq!(reseeding.next_u32(), reference.next_u32());
+        reseeding.fill_bytes(&mut buf);
+        reference.fill_bytes(&mut ref_buf);
+        assert_eq!(&buf[..], &ref_buf[..]);
+        assert_eq!(reseeding.next_u64(), reference.next_u64());
+        assert_eq!(count.get(), 0);
+
+        // The fourth call reseeds before producing output.
+        assert!(reseeding.next_u32() != reference.next_u32());
+        assert_eq!(count.get(), 1);
+        reseeding.next_u64();
+        reseeding.try_fill_bytes(&mut buf).unwrap();
+        assert_eq!(count.get(), 1);
+        reseeding.next_u32();
+        assert_eq!(count.get(), 2);
+        for _ in 0..30 {
+            reseeding.next_u32();
+        }
+        assert_eq!(count.get(), 12);
+
+        // A clone is reseeded on first use, and keeps counting calls.
+        let count2 = Cell::new(0);
+        let mut clone = ReseedingRng::new(
+            Hc128Core::from_rng(StepRng::new(0, 0)).unwrap(), 3,
+            CountingRng { inner: StepRng::new(0, 1), count: &count2 })
+            .with_threshold_mode(ThresholdMode::Calls)
+            .clone();
+        for _ in 0..4 {
+            clone.fill_bytes(&mut buf);
+        }
+        assert_eq!(count2.get(), 2);
+    }
+
+    #[cfg(feature="std")]
+    thread_local!(static PID: Cell<u32> = Cell::new(1));
+
+    #[cfg(feature="std")]
+    fn fake_pid() -> u32 {
+        PID.with(|pid| pid.get())
+    }
+
+    #[test]
+    #[cfg(feature="std")]
+    fn test_fork_detection() {
+        let count = Cell::new(0);
+        let reseeder = CountingRng { inner: StepRng::new(0, 1), count: &count };
+        let rng = Hc128Core::from_rng(StepRng::new(0, 0)).unwrap();
+        let mut reseeding = ReseedingRng::new(rng, 1024 * 1024, reseeder)
+            .with_pid_source(fake_pid);
+        let mut reference = ReseedingRng::new(
+            Hc128Core::from_rng(StepRng::new(0, 0)).unwrap(), 1024 * 1024,
+            StepRng::new(0, 0));
+
+        for _ in 0..10 {
+            assert_eq!(reseeding.next_u32(), reference.next_u32());
+        }
+        assert_eq!(count.get(), 0);
+
+        // The next output after a change of the PID comes from the reseeded
+        // PRNG, even though results are still buffered.
+        PID.with(|pid| pid.set(2));
+        assert!(reseeding.next_u32() != reference.next_u32());
+        assert_eq!(count.get(), 1);
+        let mut buf = [0u8; 64];
+        for _ in 0..10 {
+            reseeding.fill_bytes(&mut buf);
+        }
+        assert_eq!(count.get(), 1);
+
+        PID.with(|pid| pid.set(3));
+        reseeding.next_u64();
+        assert_eq!(count.get(), 2);
+    }
+
+    #[test]
+    #[cfg(all(unix, feature="std"))]
+    fn test_fork_detection_getpid() {
+        let count = Cell::new(0);
+        let reseeder = CountingRng { inner: StepRng::new(0, 1), count: &count };
+        let rng = Hc128Core::from_rng(StepRng::new(0, 0)).unwrap();
+        let mut reseeding = ReseedingRng::new(rng, 1024 * 1024, reseeder)
+            .with_fork_detection();
+        let mut buf = [0u8; 64];
+        for _ in 0..10 {
+            reseeding.fill_bytes(&mut buf);
+        }
+        assert_eq!(count.get(), 0);
+    }
 }
@@ -11,7 +11,7 @@
 //! Entropy generator, or wrapper around external generators
 
 use rand_core::{RngCore, CryptoRng, Error, impls};
-use rngs::{OsRng, JitterRng};
+use rngs::{OsRng, JitterRng, EntropySource};
 
 /// An interface returning random data from external source(s), provided
 /// specifically for securely seeding algorithmic generators (PRNGs).
@@ -46,11 +46,11 @@ use rngs::{OsRng, JitterRng};
 /// [`try_fill_bytes`]: ../trait.RngCore.html#method.tymethod.try_fill_bytes
 #[derive(Debug)]
 pub struct EntropyRng {
-    rng: EntropySource,
+    rng: Source,
 }
 
 #[derive(Debug)]
-enum EntropySource {
+enum Source {
     Os(OsRng),
     Jitter(JitterRng),
     None,
@@ -63,7 +63,7 @@ impl EntropyRng {
     /// those are done on first use. This is done to make `new` infallible,
     /// and `try_fill_bytes` the only place to report errors.
     pub fn new() -> Self {
-        EntropyRng { rng: EntropySource::None }
+        EntropyRng { rng: Source::None }
     }
 }
 
@@ -104,12 +104,12 @@ impl RngCore for EntropyRng {
 
         let mut switch_rng = None;
         match self.rng {
-            EntropySource::None => {
+            Source::None => {
                 let os_rng_result = try_os_new(dest);
                 match os_rng_result {
                     Ok(os_rng) => {
                         debug!("EntropyRng: using OsRng");
-                        switch_rng = Some(EntropySource::Os(os_rng));
+                        switch_rng = Some(Source::Os(os_rng));
                     }
                     Err(os_rng_error) => {
                         warn!("EntropyRng: OsRng failed [falling back to JitterRng]: {}",
@@ -117,7 +117,7 @@ impl RngCore for EntropyRng {
                         match try_jitter_new(dest) {
                             Ok(jitter_rng) => {
                                 debug!("EntropyRng: using JitterRng");
-                                switch_rng = Some(EntropySource::Jitter(jitter_rng));
+                                switch_rng = Some(Source::Jitter(jitter_rng));
                             }
                             Err(_jitter_error) => {
                                 warn!("EntropyRng: JitterRng failed: {}",
@@ -128,7 +128,7 @@ impl RngCore for EntropyRng {
                     }
                 }
             }
-            EntropySource::Os(ref mut rng) => {
+            Source::Os(ref mut rng) => {
                 let os_rng_result = rng.try_fill_bytes(dest);
                 if let Err(os_rng_error) = os_rng_result {
                     warn!("EntropyRng: OsRng failed [falling back to JitterRng]: {}",
@@ -136,7 +136,7 @@ impl RngCore for EntropyRng {
                     match try_jitter_new(dest) {
                         Ok(jitter_rng) => {
                             debug!("EntropyRng: using JitterRng");
-                            switch_rng = Some(EntropySource::Jitter(jitter_rng));
+                            switch_rng = Some(Source::Jitter(jitter_rng));
                         }
                         Err(_jitter_error) => {
                             warn!("EntropyRng: JitterRng failed: {}",
@@ -146,10 +146,10 @@ impl RngCore for EntropyRng {
                     }
                 }
             }
-            EntropySource::Jitter(ref mut rng) => {
+            Source::Jitter(ref mut rng) => {
                 if let Ok(os_rng) = try_os_new(dest) {
                     debug!("EntropyRng: using OsRng");
-                    switch_rng = Some(EntropySource::Os(os_rng));
+                    switch_rng = Some(Source::Os(os_rng));
                 } else {
                     return rng.try_fill_bytes(dest); // use JitterRng
                 }
@@ -164,6 +164,17 @@ impl RngCore for EntropyRng {
 
 impl CryptoRng for EntropyRng {}
 
+impl EntropySource for EntropyRng {
+    /// The estimate of the source in use, or `None` before the first use.
+    fn estimated_bits_per_byte(&self) -> Option<f32> {
+        match self.rng {
+            Source::Os(ref rng) => rng.estimated_bits_per_byte(),
+            Source::Jitter(ref rng) => rng.estimated_bits_per_byte(),
+            Source::None => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -174,4 +185,19 @@ mod test {
         let n = (rng.next_u32() ^ rng.next_u32()).count_ones();
         assert!(n >= 2);    // p(failure) approx 1e-7
     }
+
+    #[test]
+    fn test_entropy_estimated_entropy() {
+        let mut rng = EntropyRng::new();
+        assert_eq!(rng.estimated_bits_per_byte(), None);
+        rng.next_u32();
+        let estimate = rng.estimated_bits_per_byte();
+        if OsRng::new().is_ok() {
+            // `OsRng` is used where available.
+            assert_eq!(estimate, Some(8.0));
+        } else {
+            // Otherwise `JitterRng` gives its own estimate.
+            assert!(estimate.map_or(false, |e| e > 0.0 && e <= 8.0));
+        }
+    }
 }
@@ -22,6 +22,7 @@
 // technically dead code.
 
 use rand_core::{RngCore, CryptoRng, Error, ErrorKind, impls};
+use rngs::EntropySource;
 
 use core::{fmt, mem, ptr};
 #[cfg(feature="std")]
@@ -135,6 +136,9 @@ pub struct JitterRng {
     data: u64, // Actual random number
     // Number of rounds to run the entropy collector per 64 bits
     rounds: u8,
+    // Number of rounds needed for 64 bits of entropy according to
+    // `test_timer`, or 0 if the timer is not tested
+    min_rounds: u8,
     // Timer used by `measure_jitter`
     timer: fn() -> u64,
     // Memory for the Memory Access noise source
@@ -204,6 +208,7 @@ impl Clone for JitterRng {
         JitterRng {
             data: self.data,
             rounds: self.rounds,
+            min_rounds: self.min_rounds,
             timer: self.timer,
             mem_prev_index: self.mem_prev_index,
             // The 32 bits that may still be unused from the previous round are
@@ -291,6 +296,7 @@ impl JitterRng {
             info!("JitterRng: using {} rounds per u64 output", rounds);
         }
         state.set_rounds(rounds);
+        state.min_rounds = rounds;
 
         // Fill `data` with a non-zero value.
         state.gen_entropy();
@@ -346,6 +352,7 @@ impl JitterRng {
         JitterRng {
             data: 0,
             rounds: 64,
+            min_rounds: 0,
             timer,
             mem_prev_index: 0,
             data_half_used: false,
@@ -730,10 +737,10 @@ impl JitterRng {
         // of entropy is `64 / bits_of_entropy`.
         let delta_average = delta_sum / TESTLOOPCOUNT;
 
-        if delta_average >= 16 {
+        let min_rounds = if delta_average >= 16 {
             let log2 = 64 - delta_average.leading_zeros();
             // Do something similar to roundup(64/(log2/2)):
-            Ok( ((64u32 * 2 + log2 - 1) / log2) as u8)
+            ((64u32 * 2 + log2 - 1) / log2) as u8
         } else {
             // For values < 16 the rounding error becomes too large, use a
             // lookup table.
@@ -741,8 +748,11 @@ impl JitterRng {
             // `delta_sum < TESTLOOPCOUNT` test above.
             let log2_lookup = [0,  0, 128, 81, 64, 56, 50, 46,
                                43, 41, 39, 38, 36, 35, 34, 33];
-            Ok(log2_lookup[delta_average as usize])
-        }
+            log2_lookup[delta_average as usize]
+        };
+        // Remembered for `estimated_bits_per_byte`
+        self.min_rounds = min_rounds;
+        Ok(min_rounds)
     }
     #[cfg(all(target_arch = "wasm32", not(target_os = "emscripten")))]
     pub fn test_timer(&mut self) -> Result<u8, TimerError> {
@@ -862,6 +872,23 @@ impl RngCore for JitterRng {
 
 impl CryptoRng for JitterRng {}
 
+impl EntropySource for JitterRng {
+    /// The estimate of [`test_timer`]: each round collects
+    /// `64 / test_timer()` bits of entropy, up to 64 bits per 64-bit output.
+    /// Returns `None` if the timer has not been tested, as when the
+    /// `JitterRng` is created with [`new_with_timer`].
+    ///
+    /// [`test_timer`]: struct.JitterRng.html#method.test_timer
+    /// [`new_with_timer`]: struct.JitterRng.html#method.new_with_timer
+    fn estimated_bits_per_byte(&self) -> Option<f32> {
+        if self.min_rounds == 0 {
+            return None;
+        }
+        let fraction = self.rounds as f32 / self.min_rounds as f32;
+        Some(8.0 * if fraction < 1.0 { fraction } else { 1.0 })
+    }
+}
+
 #[cfg(test)]
 mod test_jitter_init {
     use jitter::JitterRng;
@@ -890,4 +917,31 @@ mod test_jitter_init {
         let mut rng = JitterRng::new_with_timer(bad_timer);
         assert!(rng.test_timer().is_err());
     }
+
+    #[test]
+    fn test_jitter_estimated_entropy() {
+        use rngs::EntropySource;
+        fn bad_timer() -> u64 { 0 }
+        let mut rng = JitterRng::new_with_timer(bad_timer);
+        assert_eq!(rng.estimated_bits_per_byte(), None);
+        assert!(rng.test_timer().is_err());
+        assert_eq!(rng.estimated_bits_per_byte(), None);
+
+        // As if `test_timer` had returned 32 rounds
+        rng.min_rounds = 32;
+        assert_eq!(rng.estimated_bits_per_byte(), Some(8.0));
+        rng.set_rounds(32);
+        assert_eq!(rng.estimated_bits_per_byte(), Some(8.0));
+        rng.set_rounds(8);
+        assert_eq!(rng.estimated_bits_per_byte(), Some(2.0));
+        assert_eq!(rng.clone().estimated_bits_per_byte(), Some(2.0));
+
+        #[cfg(feature="std")]
+        {
+            // The rounds found by the test give full entropy.
+            if let Ok(rng) = JitterRng::new() {
+                assert_eq!(rng.estimated_bits_per_byte(), Some(8.0));
+            }
+        }
+    }
 }
@@ -10,7 +10,7 @@
 
 //! Mock random number generator
 
-use rand_core::{RngCore, Error, impls};
+use rand_core::{RngCore, Error, ErrorKind, impls};
 
 /// A simple implementation of `RngCore` for testing purposes.
 /// 
@@ -59,3 +59,82 @@ impl RngCore for StepRng {
         Ok(self.fill_bytes(dest))
     }
 }
+
+/// A wrapper around an RNG which makes `try_fill_bytes` fail on a fixed
+/// schedule, for testing code which handles RNG errors.
+///
+/// Calls to `try_fill_bytes` are counted from 1, and every call whose number
+/// is a multiple of `period` returns an error of the given kind instead of
+/// filling the buffer; a `period` of 1 fails every call, and a `period` of 0
+/// never fails. All other calls, and all calls to `next_u32`, `next_u64` and
+/// `fill_bytes`, are delegated to the wrapped RNG.
+///
+/// `SeedableRng::from_rng` uses `try_fill_bytes`, so a `FaultyRng` can be
+/// used as the reseeder of a [`ReseedingRng`] to exercise its error handling.
+///
+/// ```
+/// use rand::{Rng, ErrorKind};
+/// use rand::rngs::mock::{StepRng, FaultyRng};
+///
+/// let mut rng = FaultyRng::new(StepRng::new(0, 1), 3, ErrorKind::NotReady);
+/// let mut buf = [0u8; 4];
+/// assert!(rng.try_fill(&mut buf).is_ok());
+/// assert!(rng.try_fill(&mut buf).is_ok());
+/// assert_eq!(rng.try_fill(&mut buf).unwrap_err().kind, ErrorKind::NotReady);
+/// assert!(rng.try_fill(&mut buf).is_ok());
+/// assert_eq!((rng.calls(), rng.failures()), (4, 1));
+/// ```
+///
+/// [`ReseedingRng`]: ../adapter/struct.ReseedingRng.html
+#[derive(Debug, Clone)]
+pub struct FaultyRng<R> {
+    inner: R,
+    period: u64,
+    kind: ErrorKind,
+    calls: u64,
+}
+
+impl<R: RngCore> FaultyRng<R> {
+    /// Create a `FaultyRng` wrapping `inner`, which fails every `period`-th
+    /// call to `try_fill_bytes` with an error of kind `kind`.
+    pub fn new(inner: R, period: u64, kind: ErrorKind) -> Self {
+        FaultyRng { inner, period, kind, calls: 0 }
+    }
+
+    /// The number of calls to `try_fill_bytes` so far.
+    pub fn calls(&self) -> u64 {
+        self.calls
+    }
+
+    /// The number of calls to `try_fill_bytes` which returned an error.
+    pub fn failures(&self) -> u64 {
+        self.calls.checked_div(self.period).unwrap_or(0)
+    }
+
+    /// Unwrap the inner RNG.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: RngCore> RngCore for FaultyRng<R> {
+    fn next_u32(&mut self) -> u32 {
+        self.inner.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.inner.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.inner.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.calls += 1;
+        if self.calls.checked_rem(self.period) == Some(0) {
+            return Err(Error::new(self.kind, "FaultyRng: scheduled failure"));
+        }
+        self.inner.try_fill_bytes(dest)
+    }
+}
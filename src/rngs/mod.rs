@@ -48,10 +48,11 @@
 //! default algorithm from each class:
 //!
 //! - [`SmallRng`] is a PRNG chosen for low memory usage, high performance and
-//!   good statistical quality.
-//!   The current algorithm (plain Xorshift) unfortunately performs
-//!   poorly in statistical quality test suites (TestU01 and PractRand) and will
-//!   be replaced in the next major release.
+//!   good statistical quality. The algorithm depends on the platform: it is
+//!   xoroshiro128** on 64-bit platforms, and plain Xorshift elsewhere.
+//!   Xorshift unfortunately performs poorly in statistical quality test
+//!   suites (TestU01 and PractRand) and will be replaced in the next major
+//!   release.
 //! - [`StdRng`] is a CSPRNG chosen for good performance and trust of security
 //!   (based on reviews, maturity and usage). The current algorithm is HC-128,
 //!   which is one of the recommendations by ECRYPT's eSTREAM project.
@@ -181,4 +182,47 @@ pub use self::jitter::{JitterRng, TimerError};
 
 pub use self::small::SmallRng;
 pub use self::std::StdRng;
-#[cfg(feature="std")] pub use self::thread::ThreadRng;
+#[cfg(feature="std")] pub use self::thread::{ThreadRng, with_seeded_thread_rng};
+
+use rand_core::RngCore;
+
+/// A source of random numbers which can report how much entropy it claims to
+/// provide.
+///
+/// This is informational only: the estimate is the source's own claim, and
+/// not a guarantee. It can be used, for example, to decide whether a source
+/// is good enough to seed a CSPRNG, or how many bytes to collect from it.
+///
+/// True entropy sources like [`OsRng`] and [`JitterRng`] report an estimate.
+/// Pseudo-random number generators are deterministic, so the entropy of their
+/// output is that of their seed, which they do not know; they use the default
+/// implementation, which returns `None`.
+///
+/// # Example
+///
+/// ```
+/// use rand::rngs::{EntropySource, JitterRng, StdRng};
+/// use rand::FromEntropy;
+///
+/// fn describe<R: EntropySource>(rng: &R) {
+///     match rng.estimated_bits_per_byte() {
+///         Some(bits) => println!("{} bits of entropy per byte", bits),
+///         None => println!("unknown entropy"),
+///     }
+/// }
+///
+/// if let Ok(rng) = JitterRng::new() {
+///     describe(&rng);
+/// }
+/// describe(&StdRng::from_entropy());
+/// ```
+///
+/// [`OsRng`]: struct.OsRng.html
+/// [`JitterRng`]: struct.JitterRng.html
+pub trait EntropySource: RngCore {
+    /// The estimated number of bits of entropy in each byte of output,
+    /// between 0 and 8, or `None` if the source does not know.
+    fn estimated_bits_per_byte(&self) -> Option<f32> {
+        None
+    }
+}
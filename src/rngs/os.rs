@@ -12,6 +12,7 @@
 
 use std::fmt;
 use rand_core::{CryptoRng, RngCore, Error, impls};
+use rngs::EntropySource;
 
 /// A random number generator that retrieves randomness straight from the
 /// operating system.
@@ -132,6 +133,15 @@ impl OsRng {
 
 impl CryptoRng for OsRng {}
 
+impl EntropySource for OsRng {
+    /// The operating system's generator is assumed to be fully seeded, which
+    /// `OsRng` checks where the platform allows, so its output has full
+    /// entropy.
+    fn estimated_bits_per_byte(&self) -> Option<f32> {
+        Some(8.0)
+    }
+}
+
 impl RngCore for OsRng {
     fn next_u32(&mut self) -> u32 {
         impls::next_u32_via_fill(self)
@@ -1130,6 +1140,13 @@ mod test {
         assert!(n_diff_bits >= v1.len() as u32);
     }
 
+    #[test]
+    fn test_os_rng_estimated_entropy() {
+        use rngs::EntropySource;
+        let r = OsRng::new().unwrap();
+        assert_eq!(r.estimated_bits_per_byte(), Some(8.0));
+    }
+
     #[test]
     fn test_os_rng_empty() {
         let mut r = OsRng::new().unwrap();
@@ -11,7 +11,13 @@
 //! A small fast RNG
 
 use {RngCore, SeedableRng, Error};
-use prng::XorShiftRng;
+use rngs::EntropySource;
+#[cfg(not(target_pointer_width = "64"))]
+use prng::XorShiftRng as Rng;
+#[cfg(target_pointer_width = "64")]
+use prng::Xoroshiro128StarStarRng as Rng;
+#[cfg(all(feature="alloc", not(feature="std")))] use alloc::Vec;
+#[cfg(feature="alloc")] use prng::RngState;
 
 /// An RNG recommended when small state, cheap initialization and good
 /// performance are required. The PRNG algorithm in `SmallRng` is chosen to be
@@ -20,11 +26,13 @@ use prng::XorShiftRng;
 ///
 /// Reproducibility of output from this generator is however not required, thus
 /// future library versions may use a different internal generator with
-/// different output. Further, this generator may not be portable and can
-/// produce different output depending on the architecture. If you require
+/// different output. Further, this generator is not portable and produces
+/// different output depending on the architecture. If you require
 /// reproducible output, use a named RNG, for example [`XorShiftRng`].
 ///
-/// The current algorithm used on all platforms is [Xorshift].
+/// The current algorithm is [xoroshiro128**] on 64-bit platforms, and
+/// [Xorshift] on other platforms. Both natively generate values of the
+/// platform's word size and take a seed of 16 bytes.
 ///
 /// # Examples
 ///
@@ -64,9 +72,10 @@ use prng::XorShiftRng;
 /// [`StdRng`]: struct.StdRng.html
 /// [`thread_rng`]: ../fn.thread_rng.html
 /// [Xorshift]: ../prng/struct.XorShiftRng.html
+/// [xoroshiro128**]: ../prng/struct.Xoroshiro128StarStarRng.html
 /// [`XorShiftRng`]: ../prng/struct.XorShiftRng.html
 #[derive(Clone, Debug)]
-pub struct SmallRng(XorShiftRng);
+pub struct SmallRng(Rng);
 
 impl RngCore for SmallRng {
     #[inline(always)]
@@ -88,14 +97,82 @@ impl RngCore for SmallRng {
     }
 }
 
+impl EntropySource for SmallRng {}
+
 impl SeedableRng for SmallRng {
-    type Seed = <XorShiftRng as SeedableRng>::Seed;
+    type Seed = <Rng as SeedableRng>::Seed;
 
     fn from_seed(seed: Self::Seed) -> Self {
-        SmallRng(XorShiftRng::from_seed(seed))
+        SmallRng(Rng::from_seed(seed))
     }
 
     fn from_rng<R: RngCore>(rng: R) -> Result<Self, Error> {
-        XorShiftRng::from_rng(rng).map(SmallRng)
+        Rng::from_rng(rng).map(SmallRng)
+    }
+}
+
+/// The state is stored using the representation of the current algorithm, so
+/// a state saved by one library version can only be restored by a version
+/// and on a platform using the same algorithm.
+#[cfg(feature="alloc")]
+impl RngState for SmallRng {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_bytes()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Rng::from_bytes(bytes).map(SmallRng)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {RngCore, SeedableRng};
+    use rngs::SmallRng;
+
+    #[test]
+    fn test_smallrng_construction() {
+        let seed = [1,2,3,4, 5,6,7,8, 9,10,11,12, 13,14,15,16];
+        let mut rng1 = SmallRng::from_seed(seed);
+        let mut rng2 = SmallRng::from_seed(seed);
+        for _ in 0..16 {
+            assert_eq!(rng1.next_u64(), rng2.next_u64());
+        }
+
+        let mut rng3 = SmallRng::from_rng(&mut rng1).unwrap();
+        let mut rng4 = SmallRng::from_rng(&mut rng1).unwrap();
+        assert!(rng3.next_u64() != rng4.next_u64());
+
+        // The all-zero seed also gives a working generator.
+        let mut zero = SmallRng::from_seed([0; 16]);
+        assert!(zero.next_u64() != zero.next_u64());
+    }
+
+    #[test]
+    fn test_smallrng_distinct_streams() {
+        // Seeds differing in a single bit give unrelated streams.
+        let mut seed = [0x55; 16];
+        let mut rng1 = SmallRng::from_seed(seed);
+        seed[15] ^= 1;
+        let mut rng2 = SmallRng::from_seed(seed);
+        let mut a = [0u64; 8];
+        let mut b = [0u64; 8];
+        for i in 0..8 {
+            a[i] = rng1.next_u64();
+            b[i] = rng2.next_u64();
+        }
+        assert!(a[2..].iter().all(|x| !b.contains(x)));
+    }
+
+    #[cfg(feature="alloc")]
+    #[test]
+    fn test_smallrng_state() {
+        use prng::RngState;
+        let mut rng = SmallRng::from_seed([9; 16]);
+        rng.next_u32();
+        let mut restored = SmallRng::from_bytes(&rng.to_bytes()).unwrap();
+        for _ in 0..20 {
+            assert_eq!(rng.next_u64(), restored.next_u64());
+        }
     }
 }
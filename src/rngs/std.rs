@@ -12,6 +12,9 @@
 
 use {RngCore, CryptoRng, Error, SeedableRng};
 use prng::Hc128Rng;
+use rngs::EntropySource;
+#[cfg(all(feature="alloc", not(feature="std")))] use alloc::Vec;
+#[cfg(feature="alloc")] use prng::RngState;
 
 /// The standard RNG. The PRNG algorithm in `StdRng` is chosen to be efficient
 /// on the current platform, to be statistically strong and unpredictable
@@ -50,6 +53,8 @@ impl RngCore for StdRng {
     }
 }
 
+impl EntropySource for StdRng {}
+
 impl SeedableRng for StdRng {
     type Seed = <Hc128Rng as SeedableRng>::Seed;
 
@@ -64,11 +69,25 @@ impl SeedableRng for StdRng {
 
 impl CryptoRng for StdRng {}
 
+/// The state is stored using the representation of the current algorithm, so
+/// a state saved by one library version can only be restored by a version
+/// using the same algorithm.
+#[cfg(feature="alloc")]
+impl RngState for StdRng {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_bytes()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Hc128Rng::from_bytes(bytes).map(StdRng)
+    }
+}
+
 
 #[cfg(test)]
 mod test {
     use {RngCore, SeedableRng};
-    use rngs::StdRng;
+    use rngs::{StdRng, EntropySource};
 
     #[test]
     fn test_stdrng_construction() {
@@ -80,4 +99,32 @@ mod test {
         let mut rng2 = StdRng::from_rng(rng1).unwrap();
         assert_eq!(rng2.next_u64(), 6766915756997287454);
     }
+
+    #[cfg(feature="alloc")]
+    #[test]
+    fn test_stdrng_state() {
+        use prng::RngState;
+        let mut rng = StdRng::from_seed([9; 32]);
+        rng.next_u32();
+        let mut restored = StdRng::from_bytes(&rng.to_bytes()).unwrap();
+        for _ in 0..20 {
+            assert_eq!(rng.next_u64(), restored.next_u64());
+        }
+    }
+
+    #[test]
+    fn test_prng_estimated_entropy() {
+        // PRNGs do not know the entropy of their seed.
+        use rngs::SmallRng;
+        use prng::{ChaChaRng, Hc128Rng, IsaacRng, Isaac64Rng, XorShiftRng};
+        assert_eq!(StdRng::from_seed([0; 32]).estimated_bits_per_byte(), None);
+        assert_eq!(SmallRng::from_seed([1; 16]).estimated_bits_per_byte(), None);
+        assert_eq!(ChaChaRng::from_seed([0; 32]).estimated_bits_per_byte(), None);
+        assert_eq!(Hc128Rng::from_seed([0; 32]).estimated_bits_per_byte(), None);
+        assert_eq!(IsaacRng::from_seed([0; 32]).estimated_bits_per_byte(), None);
+        assert_eq!(Isaac64Rng::from_seed([0; 32]).estimated_bits_per_byte(), None);
+        assert_eq!(XorShiftRng::from_seed([1; 16]).estimated_bits_per_byte(), None);
+        #[cfg(feature="std")]
+        assert_eq!(::thread_rng().estimated_bits_per_byte(), None);
+    }
 }
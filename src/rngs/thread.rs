@@ -11,11 +11,12 @@
 //! Thread-local random number generator
 
 use std::cell::UnsafeCell;
+use std::mem;
 use std::rc::Rc;
 
 use {RngCore, CryptoRng, SeedableRng, Error};
 use rngs::adapter::ReseedingRng;
-use rngs::EntropyRng;
+use rngs::{EntropyRng, EntropySource};
 use prng::hc128::Hc128Core;
 
 // Rationale for using `UnsafeCell` in `ThreadRng`:
@@ -48,7 +49,8 @@ const THREAD_RNG_RESEED_THRESHOLD: u64 = 32*1024*1024; // 32 MiB
 /// PRNG in thread-local memory.
 ///
 /// `ThreadRng` uses [`ReseedingRng`] wrapping the same PRNG as [`StdRng`],
-/// which is reseeded after generating 32 MiB of random data. A single instance
+/// which is reseeded after generating 32 MiB of random data; this threshold
+/// can be changed per thread with [`set_reseed_threshold`]. A single instance
 /// is cached per thread and the returned `ThreadRng` is a reference to this
 /// instance — hence `ThreadRng` is neither `Send` nor `Sync` but is safe to use
 /// within a single thread. This RNG is seeded and reseeded via [`EntropyRng`]
@@ -68,6 +70,7 @@ const THREAD_RNG_RESEED_THRESHOLD: u64 = 32*1024*1024; // 32 MiB
 /// generator.
 /// 
 /// [`thread_rng`]: ../fn.thread_rng.html
+/// [`set_reseed_threshold`]: #method.set_reseed_threshold
 /// [`ReseedingRng`]: adapter/struct.ReseedingRng.html
 /// [`StdRng`]: struct.StdRng.html
 /// [`EntropyRng`]: struct.EntropyRng.html
@@ -94,13 +97,100 @@ thread_local!(
 /// chaining style, e.g. `thread_rng().gen::<i32>()`, or cached locally, e.g.
 /// `let mut rng = thread_rng();`.
 ///
-/// For more information see [`ThreadRng`].
+/// For more information see [`ThreadRng`]. To make code using `thread_rng`
+/// reproducible in tests, see [`with_seeded_thread_rng`].
 ///
 /// [`ThreadRng`]: rngs/struct.ThreadRng.html
+/// [`with_seeded_thread_rng`]: rngs/fn.with_seeded_thread_rng.html
 pub fn thread_rng() -> ThreadRng {
     ThreadRng { rng: THREAD_RNG_KEY.with(|t| t.clone()) }
 }
 
+/// Run `f` with the generator of [`thread_rng`] on the current thread
+/// replaced by one seeded with `seed`, and return its result.
+///
+/// This makes code which calls `thread_rng` reproducible in tests, without
+/// passing a generator to it. The seeded generator is the same algorithm as
+/// [`StdRng`], but it is never reseeded. The override only applies to the
+/// current thread, so tests running in parallel do not affect each other.
+/// All `ThreadRng` handles of the thread, including those obtained before
+/// the call, use the seeded generator until `f` returns. Afterwards, or if
+/// `f` panics, the previous generator is restored with its state; calls can
+/// be nested.
+///
+/// The seeded generator is of course not suitable for cryptographic
+/// purposes, as its output is determined by `seed`.
+///
+/// # Example
+///
+/// ```
+/// use rand::{thread_rng, Rng};
+/// use rand::rngs::with_seeded_thread_rng;
+///
+/// fn roll_dice() -> u32 {
+///     thread_rng().gen_range(1, 7)
+/// }
+///
+/// let first = with_seeded_thread_rng([42; 32], || (roll_dice(), roll_dice()));
+/// let again = with_seeded_thread_rng([42; 32], || (roll_dice(), roll_dice()));
+/// assert_eq!(first, again);
+/// ```
+///
+/// [`thread_rng`]: ../fn.thread_rng.html
+/// [`StdRng`]: struct.StdRng.html
+pub fn with_seeded_thread_rng<F, T>(seed: [u8; 32], f: F) -> T
+    where F: FnOnce() -> T
+{
+    // Restores the previous generator when dropped, also when unwinding.
+    struct Restore(Option<ReseedingRng<Hc128Core, EntropyRng>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            if let Some(rng) = self.0.take() {
+                THREAD_RNG_KEY.with(|t| unsafe { *t.get() = rng });
+            }
+        }
+    }
+
+    // A threshold this large is never reached in practice.
+    let seeded = ReseedingRng::new(Hc128Core::from_seed(seed),
+                                   ::core::i64::MAX as u64,
+                                   EntropyRng::new());
+    // No reference into the `UnsafeCell` is alive outside the methods of
+    // `ThreadRng`, so the generator can be replaced here.
+    let previous = THREAD_RNG_KEY.with(|t| unsafe {
+        mem::replace(&mut *t.get(), seeded)
+    });
+    let _restore = Restore(Some(previous));
+    f()
+}
+
+impl ThreadRng {
+    /// Change the number of generated bytes after which the generator of the
+    /// current thread is reseeded; the default is 32 MiB.
+    ///
+    /// A smaller threshold reseeds more often, as an extra precaution, at the
+    /// cost of performance. The setting applies to all `ThreadRng` handles
+    /// of the current thread, but not to other threads. See
+    /// [`ReseedingRng::set_threshold`] for when the new threshold takes effect.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// // Reseed after every 1 MiB of generated data
+    /// rng.set_reseed_threshold(1024 * 1024);
+    /// let x: u32 = rng.gen();
+    /// ```
+    ///
+    /// [`ReseedingRng::set_threshold`]: adapter/struct.ReseedingRng.html#method.set_threshold
+    pub fn set_reseed_threshold(&mut self, threshold: u64) {
+        unsafe { (*self.rng.get()).set_threshold(threshold) }
+    }
+}
+
 impl RngCore for ThreadRng {
     #[inline(always)]
     fn next_u32(&mut self) -> u32 {
@@ -123,6 +213,8 @@ impl RngCore for ThreadRng {
 
 impl CryptoRng for ThreadRng {}
 
+impl EntropySource for ThreadRng {}
+
 
 #[cfg(test)]
 mod test {
@@ -130,12 +222,103 @@ mod test {
     #[cfg(not(feature="stdweb"))]
     fn test_thread_rng() {
         use Rng;
+        use seq::SliceRandom;
         let mut r = ::thread_rng();
         r.gen::<i32>();
         let mut v = [1, 1, 1];
-        r.shuffle(&mut v);
+        v.shuffle(&mut r);
         let b: &[_] = &[1, 1, 1];
         assert_eq!(v, b);
         assert_eq!(r.gen_range(0, 1), 0);
     }
+
+    #[test]
+    #[cfg(not(feature="stdweb"))]
+    fn test_thread_rng_reseed_threshold() {
+        use {RngCore, SeedableRng};
+        use prng::Hc128Rng;
+        use super::with_seeded_thread_rng;
+        let seed = [7; 32];
+        let mut expected = [0u8; 128];
+        Hc128Rng::from_seed(seed).fill_bytes(&mut expected);
+
+        // Until it is reseeded, the generator of `thread_rng` produces the
+        // output of `Hc128Rng` with the same seed.
+        let buf = with_seeded_thread_rng(seed, || {
+            let mut buf = [0u8; 128];
+            ::thread_rng().fill_bytes(&mut buf);
+            buf
+        });
+        assert_eq!(&buf[..], &expected[..]);
+
+        // Reseeds after the first block of 64 bytes. The threshold applies to
+        // all handles of the thread.
+        let buf = with_seeded_thread_rng(seed, || {
+            ::thread_rng().set_reseed_threshold(64);
+            let mut buf = [0u8; 128];
+            ::thread_rng().fill_bytes(&mut buf);
+            buf
+        });
+        assert_eq!(&buf[..64], &expected[..64]);
+        assert!(&buf[64..] != &expected[64..]);
+    }
+
+    #[test]
+    #[cfg(not(feature="stdweb"))]
+    fn test_with_seeded_thread_rng() {
+        use Rng;
+        use super::with_seeded_thread_rng;
+        fn draw() -> [u64; 4] {
+            let mut buf = [0u64; 4];
+            for x in buf.iter_mut() {
+                *x = ::thread_rng().gen();
+            }
+            buf
+        }
+
+        let a = with_seeded_thread_rng([1; 32], draw);
+        let mut outer = ::thread_rng();
+        let b = with_seeded_thread_rng([1; 32], || {
+            // Handles obtained earlier use the seeded generator too.
+            let x: u64 = outer.gen();
+            let y: u64 = ::thread_rng().gen();
+            [x, y]
+        });
+        assert_eq!(b, [a[0], a[1]]);
+        assert!(with_seeded_thread_rng([2; 32], draw) != a);
+
+        // Nested overrides restore the outer override.
+        let nested = with_seeded_thread_rng([1; 32], || {
+            let first: u64 = ::thread_rng().gen();
+            with_seeded_thread_rng([3; 32], draw);
+            let second: u64 = ::thread_rng().gen();
+            [first, second]
+        });
+        assert_eq!(nested, [a[0], a[1]]);
+
+        // Afterwards the original generator continues, and other threads are
+        // not affected.
+        let handle = ::std::thread::spawn(|| {
+            with_seeded_thread_rng([1; 32], || {
+                ::std::thread::sleep(::std::time::Duration::from_millis(10));
+                draw()
+            })
+        });
+        assert!(draw() != a);
+        assert_eq!(handle.join().unwrap(), a);
+    }
+
+    #[test]
+    #[cfg(not(feature="stdweb"))]
+    fn test_with_seeded_thread_rng_panic() {
+        use Rng;
+        use super::with_seeded_thread_rng;
+        let result = ::std::panic::catch_unwind(|| {
+            with_seeded_thread_rng([1; 32], || panic!("test"))
+        });
+        assert!(result.is_err());
+        let expected = with_seeded_thread_rng([1; 32], || ::thread_rng().gen::<u64>());
+        // The seeded generator was removed again.
+        assert!(::thread_rng().gen::<u64>() != expected);
+    }
 }
@@ -0,0 +1,182 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Sampling into arrays with a length known at compile time.
+//!
+//! This module uses const generics, so it is only compiled with Rust 1.55 or
+//! later (which also stabilized `array::map`). Compilers before Rust 1.51
+//! cannot parse const generics, so `SliceRandom::choose_multiple_array` is
+//! declared and implemented with the macros below.
+
+use Rng;
+
+/// Randomly sample exactly `N` distinct indices from `0..length`, and return
+/// them in random order (fully shuffled).
+///
+/// Returns `None` if `N > length`. This uses Floyd's combination algorithm,
+/// which makes exactly `N` random draws and takes `O(N²)` time. Unlike
+/// [`sample`], it does not allocate, and is available without the `alloc`
+/// feature.
+///
+/// This function requires Rust 1.55 or later.
+///
+/// # Example
+///
+/// ```
+/// use rand::thread_rng;
+/// use rand::seq::index;
+///
+/// let [a, b, c] = index::sample_array(&mut thread_rng(), 52).unwrap();
+/// assert!(a != b && b != c && a != c);
+/// assert!(index::sample_array::<_, 3>(&mut thread_rng(), 2).is_none());
+/// ```
+///
+/// [`sample`]: fn.sample.html
+pub fn sample_array<R, const N: usize>(rng: &mut R, length: usize)
+    -> Option<[usize; N]>
+    where R: Rng + ?Sized
+{
+    if N > length {
+        return None;
+    }
+    let mut indices = [0usize; N];
+    for (n, j) in ((length - N)..length).enumerate() {
+        let t = rng.gen_index(j + 1);
+        match indices[..n].iter().position(|&x| x == t) {
+            Some(pos) => {
+                // Insert `j` at `pos`, as `t` was already chosen.
+                for k in (pos..n).rev() {
+                    indices[k + 1] = indices[k];
+                }
+                indices[pos] = j;
+            }
+            None => indices[n] = t,
+        }
+    }
+    Some(indices)
+}
+
+macro_rules! choose_multiple_array_decl {
+    () => {
+        /// Return an array of `N` distinct elements chosen at random from
+        /// the slice, in random order.
+        ///
+        /// Returns `None` if the slice has fewer than `N` elements. The
+        /// indices are sampled with [`index::sample_array`], which makes
+        /// exactly `N` random draws and does not allocate, so this method is
+        /// available without the `alloc` feature.
+        ///
+        /// This method requires Rust 1.55 or later.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use rand::thread_rng;
+        /// use rand::seq::SliceRandom;
+        ///
+        /// let validators = ["alice", "bob", "carol", "dave"];
+        /// let [first, second] = validators.choose_multiple_array(&mut thread_rng()).unwrap();
+        /// assert!(first != second);
+        /// ```
+        ///
+        /// [`index::sample_array`]: index/fn.sample_array.html
+        fn choose_multiple_array<R, const N: usize>(&self, rng: &mut R)
+            -> Option<[&Self::Item; N]> where R: Rng + ?Sized;
+    }
+}
+
+macro_rules! choose_multiple_array_impl {
+    () => {
+        fn choose_multiple_array<R, const N: usize>(&self, rng: &mut R)
+            -> Option<[&T; N]> where R: Rng + ?Sized
+        {
+            let indices: [usize; N] = index::sample_array(rng, self.len())?;
+            Some(indices.map(|i| &self[i]))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use seq::SliceRandom;
+
+    #[test]
+    fn test_sample_array() {
+        let mut r = ::test::rng(458);
+        assert_eq!(sample_array::<_, 0>(&mut r, 0), Some([]));
+        assert_eq!(sample_array::<_, 1>(&mut r, 0), None);
+        assert_eq!(sample_array::<_, 1>(&mut r, 1), Some([0]));
+        assert_eq!(sample_array::<_, 4>(&mut r, 3), None);
+        for length in 5..50 {
+            let a: [usize; 5] = sample_array(&mut r, length).unwrap();
+            for i in 0..5 {
+                assert!(a[i] < length);
+                for j in 0..i {
+                    assert!(a[i] != a[j]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sample_array_uniform() {
+        // Each of the 5 * 4 * 3 = 60 ordered selections of 3 of 5 indices is
+        // equally likely, so each of the 10 subsets is too.
+        let mut r = ::test::rng(459);
+        const N: usize = 60_000;
+        let mut ordered = [0usize; 125];
+        let mut subsets = [0usize; 32];
+        for _ in 0..N {
+            let [a, b, c] = sample_array(&mut r, 5).unwrap();
+            ordered[a * 25 + b * 5 + c] += 1;
+            subsets[1 << a | 1 << b | 1 << c] += 1;
+        }
+        let count_ordered = ordered.iter().filter(|&&c| c > 0).count();
+        assert_eq!(count_ordered, 60);
+        for &count in ordered.iter().filter(|&&c| c > 0) {
+            let p = 1.0 / 60.0;
+            let err = 5.0 * (N as f64 * p * (1.0 - p)).sqrt();
+            assert!((count as f64 - N as f64 * p).abs() < err, "count {}", count);
+        }
+        for (mask, &count) in subsets.iter().enumerate() {
+            if (mask as u32).count_ones() != 3 {
+                assert_eq!(count, 0);
+                continue;
+            }
+            let p = 1.0 / 10.0;
+            let err = 5.0 * (N as f64 * p * (1.0 - p)).sqrt();
+            assert!((count as f64 - N as f64 * p).abs() < err, "subset {:b}: {}", mask, count);
+        }
+    }
+
+    #[test]
+    fn test_choose_multiple_array() {
+        let mut r = ::test::rng(460);
+        let values = [10, 11, 12, 13, 14, 15];
+        for _ in 0..100 {
+            let [a, b, c] = values.choose_multiple_array(&mut r).unwrap();
+            assert!(a != b && b != c && a != c);
+            assert!(values.contains(a) && values.contains(b) && values.contains(c));
+        }
+        let all: [&u8; 0] = [1u8, 2][..].choose_multiple_array(&mut r).unwrap();
+        assert_eq!(all.len(), 0);
+        assert!(values.choose_multiple_array::<_, 7>(&mut r).is_none());
+        let empty: [u8; 0] = [];
+        assert!(empty.choose_multiple_array::<_, 1>(&mut r).is_none());
+
+        // The same elements as the indices from `sample_array`
+        let mut r1 = ::test::rng(461);
+        let mut r2 = ::test::rng(461);
+        let chosen: [&u32; 2] = [5u32, 6, 7, 8][..].choose_multiple_array(&mut r1).unwrap();
+        let [i, j] = sample_array(&mut r2, 4).unwrap();
+        assert_eq!(chosen, [&(i as u32 + 5), &(j as u32 + 5)]);
+    }
+}
@@ -0,0 +1,709 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Index sampling
+
+use core::hash::Hash;
+use core::slice;
+
+#[cfg(feature="std")] use std::vec;
+#[cfg(not(feature="std"))] use alloc::vec::{self, Vec};
+// BTreeSet is not as fast in tests, but better than nothing.
+#[cfg(feature="std")] use std::collections::{HashSet, BinaryHeap};
+#[cfg(not(feature="std"))] use alloc::btree_set::BTreeSet;
+#[cfg(feature="std")] use core::cmp::Ordering;
+
+use Rng;
+use distributions::{Distribution, Uniform};
+#[cfg(feature="std")] use distributions::{OpenClosed01, WeightedError};
+use distributions::uniform::SampleUniform;
+
+#[cfg(rustc_1_55)] pub use super::array::sample_array;
+
+/// A vector of indices.
+///
+/// Multiple internal representations are possible: indices are stored as
+/// `u32` when they all fit, which halves the memory use on 64-bit platforms,
+/// and as `usize` otherwise. Use [`iter`] or [`into_vec`] to access the
+/// indices as `usize`.
+///
+/// [`iter`]: #method.iter
+/// [`into_vec`]: #method.into_vec
+#[derive(Clone, Debug)]
+pub enum IndexVec {
+    #[doc(hidden)] U32(Vec<u32>),
+    #[doc(hidden)] USize(Vec<usize>),
+}
+
+impl IndexVec {
+    /// Returns the number of indices
+    pub fn len(&self) -> usize {
+        match *self {
+            IndexVec::U32(ref v) => v.len(),
+            IndexVec::USize(ref v) => v.len(),
+        }
+    }
+
+    /// Returns `true` if there are no indices
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the value at the given `index`.
+    ///
+    /// (Note: we cannot implement `std::ops::Index` because of lifetime
+    /// restrictions.)
+    pub fn index(&self, index: usize) -> usize {
+        match *self {
+            IndexVec::U32(ref v) => v[index] as usize,
+            IndexVec::USize(ref v) => v[index],
+        }
+    }
+
+    /// Return the result as a `Vec<usize>`.
+    pub fn into_vec(self) -> Vec<usize> {
+        match self {
+            IndexVec::U32(v) => v.into_iter().map(|i| i as usize).collect(),
+            IndexVec::USize(v) => v,
+        }
+    }
+
+    /// Iterate over the indices as a sequence of `usize` values
+    pub fn iter<'a>(&'a self) -> IndexVecIter<'a> {
+        match *self {
+            IndexVec::U32(ref v) => IndexVecIter::U32(v.iter()),
+            IndexVec::USize(ref v) => IndexVecIter::USize(v.iter()),
+        }
+    }
+}
+
+impl IntoIterator for IndexVec {
+    type Item = usize;
+    type IntoIter = IndexVecIntoIter;
+
+    /// Convert into an iterator over the indices as a sequence of `usize` values
+    fn into_iter(self) -> IndexVecIntoIter {
+        match self {
+            IndexVec::U32(v) => IndexVecIntoIter::U32(v.into_iter()),
+            IndexVec::USize(v) => IndexVecIntoIter::USize(v.into_iter()),
+        }
+    }
+}
+
+impl PartialEq for IndexVec {
+    fn eq(&self, other: &IndexVec) -> bool {
+        use self::IndexVec::*;
+        match (self, other) {
+            (&U32(ref v1), &U32(ref v2)) => v1 == v2,
+            (&USize(ref v1), &USize(ref v2)) => v1 == v2,
+            _ => self.len() == other.len() &&
+                 self.iter().zip(other.iter()).all(|(x, y)| x == y),
+        }
+    }
+}
+
+impl From<Vec<u32>> for IndexVec {
+    fn from(v: Vec<u32>) -> Self {
+        IndexVec::U32(v)
+    }
+}
+
+impl From<Vec<usize>> for IndexVec {
+    fn from(v: Vec<usize>) -> Self {
+        IndexVec::USize(v)
+    }
+}
+
+/// Return type of `IndexVec::iter`.
+#[derive(Debug)]
+pub enum IndexVecIter<'a> {
+    #[doc(hidden)] U32(slice::Iter<'a, u32>),
+    #[doc(hidden)] USize(slice::Iter<'a, usize>),
+}
+
+impl<'a> Iterator for IndexVecIter<'a> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        match *self {
+            IndexVecIter::U32(ref mut iter) => iter.next().map(|i| *i as usize),
+            IndexVecIter::USize(ref mut iter) => iter.next().cloned(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match *self {
+            IndexVecIter::U32(ref v) => v.size_hint(),
+            IndexVecIter::USize(ref v) => v.size_hint(),
+        }
+    }
+}
+
+impl<'a> ExactSizeIterator for IndexVecIter<'a> {}
+
+/// Return type of `IndexVec::into_iter`.
+#[derive(Clone, Debug)]
+pub enum IndexVecIntoIter {
+    #[doc(hidden)] U32(vec::IntoIter<u32>),
+    #[doc(hidden)] USize(vec::IntoIter<usize>),
+}
+
+impl Iterator for IndexVecIntoIter {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        match *self {
+            IndexVecIntoIter::U32(ref mut v) => v.next().map(|i| i as usize),
+            IndexVecIntoIter::USize(ref mut v) => v.next(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match *self {
+            IndexVecIntoIter::U32(ref v) => v.size_hint(),
+            IndexVecIntoIter::USize(ref v) => v.size_hint(),
+        }
+    }
+}
+
+impl ExactSizeIterator for IndexVecIntoIter {}
+
+
+/// Randomly sample exactly `amount` distinct indices from `0..length`, and
+/// return them in random order (fully shuffled).
+///
+/// This method is used internally by the slice sampling methods, but it can
+/// sometimes be useful to have the indices themselves so this is provided as
+/// an alternative.
+///
+/// The implementation used is chosen automatically based on `length` and
+/// `amount`, from benchmarks of their relative speed:
+///
+/// - Floyd's combination algorithm is used for small `amount`, up to 48.
+///   It takes `O(amount²)` time and `O(amount)` memory.
+/// - If `length` is less than about 130 times `amount` (or, for small
+///   `amount`, less than about `2.6 * amount²`), an in-place partial
+///   Fisher-Yates shuffle of all indices is used. It takes `O(length)` time
+///   and memory.
+/// - Otherwise, rejection sampling is used: random indices are drawn from
+///   the whole range, and duplicates are rejected with a `HashSet` (or a
+///   `BTreeSet` without `std`). It takes `O(amount)` time and memory.
+///
+/// Indices are stored as `u32` if `length` fits in a `u32`, and as `usize`
+/// otherwise.
+///
+/// Panics if `amount > length`.
+///
+/// # Example
+///
+/// ```
+/// use rand::thread_rng;
+/// use rand::seq::index;
+///
+/// let indices = index::sample(&mut thread_rng(), 100, 5);
+/// assert_eq!(indices.len(), 5);
+/// for i in indices.iter() {
+///     assert!(i < 100);
+/// }
+/// ```
+pub fn sample<R>(rng: &mut R, length: usize, amount: usize) -> IndexVec
+    where R: Rng + ?Sized,
+{
+    if amount > length {
+        panic!("`amount` of samples must be less than or equal to `length`");
+    }
+    if length > (::core::u32::MAX as usize) {
+        IndexVec::USize(sample_impl(rng, length, amount))
+    } else {
+        IndexVec::U32(sample_impl(rng, length, amount))
+    }
+}
+
+// Above this amount, Floyd's algorithm is slower than rejection sampling
+// because of its quadratic time complexity.
+const FLOYD_MAX_AMOUNT: usize = 48;
+
+// The in-place shuffle is faster than rejection sampling if `length` is less
+// than this many times `amount`: the cost of initializing `length` indices is
+// less than the cost of the set lookups.
+const INPLACE_MAX_RATIO: usize = 130;
+
+fn sample_impl<X, R>(rng: &mut R, length: usize, amount: usize) -> Vec<X>
+    where X: IndexType, R: Rng + ?Sized,
+{
+    // The crossover points are measured with the `misc_sample_indices`
+    // benchmarks, which cover each side of them. Roughly, Floyd's algorithm
+    // takes `amount² / 2` ns, the in-place shuffle `length / 5 + 5 * amount`
+    // ns, and rejection sampling `30 * amount` ns.
+    if amount <= FLOYD_MAX_AMOUNT {
+        // The in-place shuffle is faster than Floyd's algorithm if
+        // `length < 2.6 * amount * (amount - 10)`.
+        if length < amount * amount.saturating_sub(10) * 13 / 5 {
+            sample_inplace(rng, length, amount)
+        } else {
+            sample_floyd(rng, length, amount)
+        }
+    } else if length / INPLACE_MAX_RATIO < amount {
+        sample_inplace(rng, length, amount)
+    } else {
+        sample_rejection(rng, length, amount)
+    }
+}
+
+/// Randomly sample exactly `amount` distinct indices from `0..length`, with
+/// the probability of choosing each index proportional to its weight, as
+/// returned by `weight`.
+///
+/// This is weighted sampling without replacement: the indices are returned
+/// in the order in which they would be drawn one at a time, each draw
+/// choosing among the remaining indices with probability proportional to
+/// their weights. Indices with a weight of zero are never chosen.
+///
+/// The weights can be any type which converts to `f64`. Returns
+/// `WeightedError::NegativeWeight` or `WeightedError::InvalidWeight` if any
+/// weight is negative, NaN or infinite, and
+/// `WeightedError::InsufficientNonZero` if fewer than `amount` weights are
+/// positive.
+///
+/// This uses the A-ExpJ algorithm of Efraimidis and Spirakis, a reservoir
+/// algorithm with exponential jumps. It makes a single pass over the
+/// weights, taking `O(length)` time but only `O(amount * log(length / amount))`
+/// random numbers and `O(amount)` memory. It needs the `std` feature.
+///
+/// Panics if `amount > length`.
+///
+/// # Example
+///
+/// ```
+/// use rand::thread_rng;
+/// use rand::seq::index;
+///
+/// let weights = [1.0, 0.0, 3.0, 2.5, 0.5];
+/// let indices = index::sample_weighted(&mut thread_rng(), weights.len(),
+///                                      |i| weights[i], 3).unwrap();
+/// assert_eq!(indices.len(), 3);
+/// assert!(indices.iter().all(|i| i != 1));
+/// ```
+///
+/// P. S. Efraimidis and P. G. Spirakis, *Weighted random sampling with a
+/// reservoir*, Information Processing Letters 97(5), 2006, 181-185.
+#[cfg(feature="std")]
+pub fn sample_weighted<R, F, X>(rng: &mut R, length: usize, weight: F, amount: usize)
+    -> Result<IndexVec, WeightedError>
+    where R: Rng + ?Sized,
+          F: Fn(usize) -> X,
+          X: Into<f64>,
+{
+    if amount > length {
+        panic!("`amount` of samples must be less than or equal to `length`");
+    }
+
+    // Each index gets the key `ln(u) / weight`, the logarithm of the key
+    // `u^(1 / weight)` of the paper, and the reservoir holds the `amount`
+    // largest keys. Once it is full, the weight to skip before the next
+    // replacement is drawn at once, rather than a key for every index.
+    let mut heap = BinaryHeap::with_capacity(amount + 1);
+    let mut positive = 0;
+    let mut skip = 0.0;
+    for index in 0..length {
+        let w: f64 = weight(index).into();
+        if !w.is_finite() {
+            return Err(WeightedError::InvalidWeight);
+        }
+        if w < 0.0 {
+            return Err(WeightedError::NegativeWeight);
+        }
+        if w == 0.0 {
+            continue;
+        }
+        positive += 1;
+
+        let key = if heap.len() < amount {
+            let u: f64 = rng.sample(OpenClosed01);
+            u.ln() / w
+        } else {
+            skip -= w;
+            if amount == 0 || skip > 0.0 {
+                continue;
+            }
+            // The new key is conditioned to be larger than the smallest key
+            // `t` of the reservoir: `u` is uniform in `(e^(t * w), 1)`.
+            let min: WeightedKey = heap.pop().unwrap();
+            let threshold = (min.key * w).exp();
+            let u = threshold + (1.0 - threshold) * rng.gen::<f64>();
+            u.ln() / w
+        };
+        heap.push(WeightedKey { positive: true, key, index });
+        if heap.len() == amount {
+            let r: f64 = rng.sample(OpenClosed01);
+            skip = r.ln() / heap.peek().unwrap().key;
+        }
+    }
+    if positive < amount {
+        return Err(WeightedError::InsufficientNonZero);
+    }
+
+    // The heap is ordered by decreasing keys, so the sorted vector starts
+    // with the largest key.
+    let indices: Vec<usize> = heap.into_sorted_vec().into_iter()
+        .map(|key| key.index).collect();
+    Ok(IndexVec::from(indices))
+}
+
+/// The key of an element in weighted sampling without replacement, used by
+/// [`sample_weighted`] and `SliceRandom::choose_multiple_weighted`.
+///
+/// Keys are ordered by decreasing value, so a `BinaryHeap` of them is a
+/// min-heap. Elements with a weight of zero have smaller keys than all others.
+///
+/// [`sample_weighted`]: fn.sample_weighted.html
+#[cfg(feature="std")]
+pub(crate) struct WeightedKey {
+    pub(crate) positive: bool,
+    pub(crate) key: f64,
+    pub(crate) index: usize,
+}
+
+#[cfg(feature="std")]
+impl PartialEq for WeightedKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+#[cfg(feature="std")]
+impl Eq for WeightedKey {}
+
+#[cfg(feature="std")]
+impl PartialOrd for WeightedKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature="std")]
+impl Ord for WeightedKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // The keys are never NaN.
+        (other.positive, other.key).partial_cmp(&(self.positive, self.key))
+            .unwrap()
+    }
+}
+
+/// An index type, `u32` or `usize`.
+trait IndexType: Copy + Ord + Hash + SampleUniform {
+    fn from_usize(x: usize) -> Self;
+    fn as_usize(self) -> usize;
+}
+
+impl IndexType for u32 {
+    #[inline]
+    fn from_usize(x: usize) -> Self { x as u32 }
+    #[inline]
+    fn as_usize(self) -> usize { self as usize }
+}
+
+impl IndexType for usize {
+    #[inline]
+    fn from_usize(x: usize) -> Self { x }
+    #[inline]
+    fn as_usize(self) -> usize { self }
+}
+
+/// Randomly sample exactly `amount` indices from `0..length`, using Floyd's
+/// combination algorithm.
+///
+/// The output values are fully shuffled: inserting `j` at the position of
+/// the duplicate `t` (rather than at the end) makes the order uniformly
+/// random.
+fn sample_floyd<X, R>(rng: &mut R, length: usize, amount: usize) -> Vec<X>
+    where X: IndexType, R: Rng + ?Sized,
+{
+    debug_assert!(amount <= length);
+    let mut indices: Vec<X> = Vec::with_capacity(amount);
+    for j in (length - amount)..length {
+        let t = rng.gen_range(X::from_usize(0), X::from_usize(j + 1));
+        if let Some(pos) = indices.iter().position(|&x| x == t) {
+            indices.insert(pos, X::from_usize(j));
+        } else {
+            indices.push(t);
+        }
+    }
+    debug_assert_eq!(indices.len(), amount);
+    indices
+}
+
+/// Randomly sample exactly `amount` indices from `0..length`, using an
+/// inplace partial Fisher-Yates method.
+///
+/// This allocates the entire `length` of indices and randomizes only the
+/// first `amount`. It then truncates to `amount` and returns.
+fn sample_inplace<X, R>(rng: &mut R, length: usize, amount: usize) -> Vec<X>
+    where X: IndexType, R: Rng + ?Sized,
+{
+    debug_assert!(amount <= length);
+    let mut indices: Vec<X> = Vec::with_capacity(length);
+    indices.extend((0..length).map(X::from_usize));
+    for i in 0..amount {
+        let j = rng.gen_range(X::from_usize(i), X::from_usize(length));
+        indices.swap(i, j.as_usize());
+    }
+    indices.truncate(amount);
+    debug_assert_eq!(indices.len(), amount);
+    indices
+}
+
+/// Randomly sample exactly `amount` indices from `0..length`, using
+/// rejection sampling.
+///
+/// Random indices are drawn from the whole range, and those already sampled
+/// are rejected, using a set to look them up. The indices are returned in the
+/// order they were drawn, which is uniformly random.
+fn sample_rejection<X, R>(rng: &mut R, length: usize, amount: usize) -> Vec<X>
+    where X: IndexType, R: Rng + ?Sized,
+{
+    debug_assert!(amount <= length);
+    if amount == 0 {
+        // `length` may be 0, which is not a valid range to sample from.
+        return Vec::new();
+    }
+    #[cfg(feature="std")] let mut cache = HashSet::with_capacity(amount);
+    #[cfg(not(feature="std"))] let mut cache = BTreeSet::new();
+    let distr = Uniform::new(X::from_usize(0), X::from_usize(length));
+    let mut indices = Vec::with_capacity(amount);
+    for _ in 0..amount {
+        let mut pos = distr.sample(rng);
+        while !cache.insert(pos) {
+            pos = distr.sample(rng);
+        }
+        indices.push(pos);
+    }
+    debug_assert_eq!(indices.len(), amount);
+    indices
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(not(feature="std"))] use alloc::Vec;
+
+    #[test]
+    fn test_sample_boundaries() {
+        let mut r = ::test::rng(404);
+
+        assert_eq!(sample_inplace::<u32, _>(&mut r, 0, 0).len(), 0);
+        assert_eq!(sample_inplace::<u32, _>(&mut r, 1, 0).len(), 0);
+        assert_eq!(sample_inplace::<u32, _>(&mut r, 1, 1), vec![0]);
+
+        assert_eq!(sample_rejection::<u32, _>(&mut r, 0, 0).len(), 0);
+        assert_eq!(sample_rejection::<u32, _>(&mut r, 1, 0).len(), 0);
+        assert_eq!(sample_rejection::<u32, _>(&mut r, 1, 1), vec![0]);
+
+        assert_eq!(sample_floyd::<u32, _>(&mut r, 0, 0).len(), 0);
+        assert_eq!(sample_floyd::<u32, _>(&mut r, 1, 0).len(), 0);
+        assert_eq!(sample_floyd::<u32, _>(&mut r, 1, 1), vec![0]);
+
+        assert!(sample(&mut r, 0, 0).is_empty());
+        assert_eq!(sample(&mut r, 1, 1).into_vec(), vec![0]);
+    }
+
+    fn check_distinct(indices: &IndexVec, length: usize, amount: usize) {
+        assert_eq!(indices.len(), amount);
+        let mut v = indices.clone().into_vec();
+        assert!(v.iter().all(|&i| i < length));
+        v.sort();
+        v.dedup();
+        assert_eq!(v.len(), amount);
+    }
+
+    #[test]
+    fn test_sample_alg() {
+        let seed_rng = ::test::rng;
+
+        // Floyd's algorithm
+        let (length, amount) = (1000, 20);
+        let v1 = sample(&mut seed_rng(420), length, amount);
+        let v2 = IndexVec::from(sample_floyd::<u32, _>(&mut seed_rng(420), length, amount));
+        assert_eq!(v1, v2);
+        check_distinct(&v1, length, amount);
+
+        // in-place shuffle, used when `amount` is close to `length`
+        for &(length, amount) in [(1000, 900), (1000, 49), (100, 40)].iter() {
+            let v1 = sample(&mut seed_rng(421), length, amount);
+            let v2 = IndexVec::from(sample_inplace::<u32, _>(&mut seed_rng(421), length, amount));
+            assert_eq!(v1, v2);
+            check_distinct(&v1, length, amount);
+        }
+
+        // rejection sampling, used when `amount` is much smaller than `length`
+        let (length, amount) = (1_000_000, 100);
+        let v1 = sample(&mut seed_rng(422), length, amount);
+        let v2 = IndexVec::from(sample_rejection::<u32, _>(&mut seed_rng(422), length, amount));
+        assert_eq!(v1, v2);
+        check_distinct(&v1, length, amount);
+    }
+
+    #[test]
+    fn test_sample_uniform() {
+        // For each algorithm, every ordered pair of distinct indices from
+        // `0..4` is equally likely.
+        let algorithms: [fn(&mut ::test::TestRng<::rngs::StdRng>, usize, usize) -> Vec<u32>; 3] =
+            [sample_floyd, sample_inplace, sample_rejection];
+        let mut r = ::test::rng(428);
+        for algorithm in algorithms.iter() {
+            let mut counts = [[0usize; 4]; 4];
+            const N: usize = 12_000;
+            for _ in 0..N {
+                let v = algorithm(&mut r, 4, 2);
+                counts[v[0] as usize][v[1] as usize] += 1;
+            }
+            for (i, row) in counts.iter().enumerate() {
+                for (j, &count) in row.iter().enumerate() {
+                    if i == j {
+                        assert_eq!(count, 0);
+                    } else {
+                        assert!((count as f64 - 1000.0).abs() < 150.0,
+                                "pair ({}, {}) sampled {} times", i, j, count);
+                    }
+                }
+            }
+        }
+
+        // Through `sample`, each tenth of the range gets a tenth of the
+        // indices, with each of the algorithms.
+        for &(length, amount) in [(1000, 20), (1000, 900), (100_000, 60)].iter() {
+            let mut buckets = [0usize; 10];
+            const N: usize = 200;
+            for _ in 0..N {
+                let v = sample(&mut r, length, amount);
+                check_distinct(&v, length, amount);
+                for i in v.iter() {
+                    buckets[i * 10 / length] += 1;
+                }
+            }
+            // Without replacement the variance is smaller than binomial.
+            let (n, p) = ((N * amount) as f64, 0.1);
+            let err = 5.0 * (n * p * (1.0 - p)).sqrt();
+            for &count in buckets.iter() {
+                assert!((count as f64 - n * p).abs() < err,
+                        "count {} sampling {} of {}", count, amount, length);
+            }
+        }
+    }
+
+    #[test]
+    fn test_index_vec_representations() {
+        let mut r = ::test::rng(425);
+        let v = sample(&mut r, 100, 10);
+        match v {
+            IndexVec::U32(_) => (),
+            IndexVec::USize(_) => panic!("expected u32 representation"),
+        }
+        let as_vec: Vec<usize> = v.iter().collect();
+        assert_eq!(v.iter().len(), 10);
+        assert_eq!(v.index(3), as_vec[3]);
+        assert_eq!(v.clone().into_iter().collect::<Vec<usize>>(), as_vec);
+        assert_eq!(v.clone().into_vec(), as_vec);
+        assert_eq!(v, IndexVec::from(as_vec));
+
+        let v = IndexVec::from(vec![3u32, 1, 4]);
+        assert_eq!(v, IndexVec::from(vec![3usize, 1, 4]));
+        assert!(v != IndexVec::from(vec![3usize, 1]));
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn test_index_vec_usize() {
+        let mut r = ::test::rng(426);
+        let length = (::core::u32::MAX as usize) + 100;
+        for &amount in [10, 100].iter() {
+            let v = sample(&mut r, length, amount);
+            match v {
+                IndexVec::USize(_) => (),
+                IndexVec::U32(_) => panic!("expected usize representation"),
+            }
+            check_distinct(&v, length, amount);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sample_too_many() {
+        sample(&mut ::test::rng(427), 10, 11);
+    }
+
+    #[cfg(feature="std")]
+    #[test]
+    fn test_sample_weighted() {
+        let mut r = ::test::rng(429);
+        // The ordered pair `(i, j)` is drawn with probability
+        // `w[i] / W * w[j] / (W - w[i])`. Indices with weight zero are never
+        // drawn, and with six indices the exponential jumps are used.
+        let weights = [1u32, 0, 2, 3, 0, 4];
+        let total = 10.0;
+        let mut counts = [[0usize; 6]; 6];
+        const N: usize = 20_000;
+        for _ in 0..N {
+            let v = sample_weighted(&mut r, 6, |i| weights[i], 2).unwrap();
+            check_distinct(&v, 6, 2);
+            counts[v.index(0)][v.index(1)] += 1;
+        }
+        for (i, row) in counts.iter().enumerate() {
+            for (j, &count) in row.iter().enumerate() {
+                let (wi, wj) = (weights[i] as f64, weights[j] as f64);
+                let p = if i == j { 0.0 } else { wi / total * wj / (total - wi) };
+                let err = 5.0 * (N as f64 * p * (1.0 - p)).sqrt();
+                assert!((count as f64 - N as f64 * p).abs() <= err,
+                        "pair ({}, {}) sampled {} times, expected {}",
+                        i, j, count, N as f64 * p);
+            }
+        }
+
+        // Choosing all indices, the first one has probability `w[i] / W`.
+        let weights = [0.5f64, 1.5, 2.0, 1.0];
+        let mut counts = [0usize; 4];
+        for _ in 0..N {
+            let v = sample_weighted(&mut r, 4, |i| weights[i], 4).unwrap();
+            check_distinct(&v, 4, 4);
+            counts[v.index(0)] += 1;
+        }
+        for (&count, &w) in counts.iter().zip(weights.iter()) {
+            let p = w / 5.0;
+            let err = 5.0 * (N as f64 * p * (1.0 - p)).sqrt();
+            assert!((count as f64 - N as f64 * p).abs() <= err,
+                    "count {} for weight {}", count, w);
+        }
+
+        // Many small weights
+        let v = sample_weighted(&mut r, 10_000, |i| 1e-300 * (i % 7) as f64, 100).unwrap();
+        assert!(v.iter().all(|i| i % 7 != 0));
+        check_distinct(&v, 10_000, 100);
+
+        assert!(sample_weighted(&mut r, 0, |_| 1.0, 0).unwrap().is_empty());
+        assert!(sample_weighted(&mut r, 3, |_| 0.0, 0).unwrap().is_empty());
+        assert_eq!(sample_weighted(&mut r, 3, |i| i as u32, 3).unwrap_err(),
+                   WeightedError::InsufficientNonZero);
+        assert_eq!(sample_weighted(&mut r, 3, |i| 1 - i as i32, 1).unwrap_err(),
+                   WeightedError::NegativeWeight);
+        assert_eq!(sample_weighted(&mut r, 3, |_| ::core::f64::NAN, 1).unwrap_err(),
+                   WeightedError::InvalidWeight);
+    }
+
+    #[cfg(feature="std")]
+    #[test]
+    #[should_panic]
+    fn test_sample_weighted_too_many() {
+        let _ = sample_weighted(&mut ::test::rng(430), 3, |_| 1.0, 4);
+    }
+}
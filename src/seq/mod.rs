@@ -0,0 +1,1824 @@
+// Copyright 2017 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Functions for randomly accessing and sampling sequences.
+//!
+//! The [`SliceRandom`] and [`IteratorRandom`] extension traits add random
+//! sampling methods to slices and iterators. Functions which sample more than
+//! one element, as well as the [`index`] module, require the `alloc` feature.
+//! So do [`Permutation`], a random reordering which can be applied to
+//! several sequences in the same way and inverted, and the [`derangement`]
+//! and [`random_cycle`] functions for permutations with a special structure.
+//!
+//! # Portability
+//!
+//! For a given RNG, the results are the same on 32-bit and 64-bit platforms:
+//! random indices are sampled with [`Rng::gen_index`], which uses a `u32`
+//! for every bound that fits in a `u32`, and [`index::sample`] stores the
+//! indices as `u32` in that case. Only collections longer than `u32::MAX`
+//! use `u64` values, which are not possible on 32-bit platforms.
+//!
+//! [`SliceRandom`]: trait.SliceRandom.html
+//! [`IteratorRandom`]: trait.IteratorRandom.html
+//! [`index`]: index/index.html
+//! [`Permutation`]: struct.Permutation.html
+//! [`derangement`]: fn.derangement.html
+//! [`random_cycle`]: fn.random_cycle.html
+//! [`index::sample`]: index/fn.sample.html
+//! [`Rng::gen_index`]: ../trait.Rng.html#method.gen_index
+
+#[cfg(rustc_1_55)] #[macro_use] mod array;
+#[cfg(feature="alloc")] pub mod index;
+#[cfg(feature="alloc")] mod permutation;
+
+/// Index sampling
+///
+/// Without the `alloc` feature, only [`sample_array`] is available.
+///
+/// [`sample_array`]: fn.sample_array.html
+#[cfg(not(feature="alloc"))]
+pub mod index {
+    #[cfg(rustc_1_55)] pub use super::array::sample_array;
+}
+
+#[cfg(feature="alloc")] pub use self::permutation::{Permutation, derangement, random_cycle};
+
+use super::Rng;
+#[cfg(feature="alloc")] use core::ops::AddAssign;
+#[cfg(feature="alloc")] use distributions::{Distribution, WeightedIndex, WeightedError};
+#[cfg(feature="alloc")] use distributions::uniform::{SampleUniform, SampleBorrow};
+
+#[cfg(feature="std")] use std::collections::BinaryHeap;
+#[cfg(feature="std")] use distributions::OpenClosed01;
+#[cfg(feature="std")] use self::index::WeightedKey;
+#[cfg(all(feature="alloc", not(feature="std")))] use alloc::Vec;
+
+/// Extension trait on slices, providing random mutation and sampling methods.
+///
+/// This replaces the slice methods of [`Rng`], which are deprecated:
+/// `rng.choose(&values)` becomes `values.choose(&mut rng)`.
+///
+/// # Example
+///
+/// ```
+/// use rand::thread_rng;
+/// use rand::seq::SliceRandom;
+///
+/// let mut rng = thread_rng();
+/// let mut cards: Vec<u8> = (1..53).collect();
+/// cards.shuffle(&mut rng);
+/// println!("top card: {:?}", cards.choose(&mut rng));
+/// ```
+///
+/// [`Rng`]: ../trait.Rng.html
+pub trait SliceRandom {
+    /// The element type.
+    type Item;
+
+    /// Return a random element from the slice.
+    ///
+    /// Returns `None` if and only if the slice is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use rand::seq::SliceRandom;
+    ///
+    /// let choices = [1, 2, 4, 8, 16, 32];
+    /// let mut rng = thread_rng();
+    /// println!("{:?}", choices.choose(&mut rng));
+    /// assert_eq!(choices[..0].choose(&mut rng), None);
+    /// ```
+    fn choose<R>(&self, rng: &mut R) -> Option<&Self::Item>
+        where R: Rng + ?Sized;
+
+    /// Return a mutable reference to a random element from the slice.
+    ///
+    /// Returns `None` if and only if the slice is empty.
+    fn choose_mut<R>(&mut self, rng: &mut R) -> Option<&mut Self::Item>
+        where R: Rng + ?Sized;
+
+    /// Return an iterator over `amount` distinct elements chosen at random
+    /// from the slice, in random order.
+    ///
+    /// If the slice has fewer than `amount` elements, all of them are
+    /// returned in random order; for an empty slice the iterator is empty.
+    /// Up to 16 indices are sampled with Floyd's combination algorithm into
+    /// a buffer inside the iterator, without allocating. Larger amounts are
+    /// sampled with [`index::sample`], which takes `O(amount)` memory. See
+    /// also [`sample_slice_fill`], which does not require the `alloc`
+    /// feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use rand::seq::SliceRandom;
+    ///
+    /// let mut rng = thread_rng();
+    /// let sample = "Hello, audience!".as_bytes();
+    ///
+    /// // collect the results into a vector:
+    /// let v: Vec<u8> = sample.choose_multiple(&mut rng, 3).cloned().collect();
+    /// assert_eq!(v.len(), 3);
+    ///
+    /// // store in a buffer:
+    /// let mut buf = [0u8; 5];
+    /// for (b, slot) in sample.choose_multiple(&mut rng, buf.len()).zip(buf.iter_mut()) {
+    ///     *slot = *b;
+    /// }
+    /// ```
+    ///
+    /// [`index::sample`]: index/fn.sample.html
+    /// [`sample_slice_fill`]: fn.sample_slice_fill.html
+    #[cfg(feature="alloc")]
+    fn choose_multiple<'a, R>(&'a self, rng: &mut R, amount: usize)
+        -> SliceChooseIter<'a, Self::Item>
+        where R: Rng + ?Sized;
+
+    #[cfg(rustc_1_55)]
+    choose_multiple_array_decl!();
+
+    /// Return a random element from the slice, with the probability of each
+    /// element proportional to its weight, as returned by `weight`.
+    ///
+    /// This builds a [`WeightedIndex`] over the weights of all elements on
+    /// every call, which takes `O(n)` time and memory. To choose repeatedly
+    /// from the same weights, construct a `WeightedIndex` once and sample
+    /// indices from it instead.
+    ///
+    /// Returns an error under the same conditions as [`WeightedIndex::new`]:
+    /// if the slice is empty, if any weight is negative, if all weights are
+    /// zero, or if a floating point weight is NaN or the total is infinite.
+    /// Elements with a weight of zero are never chosen.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use rand::seq::SliceRandom;
+    ///
+    /// let loot = [("gold", 5), ("potion", 20), ("sword", 1), ("nothing", 74)];
+    /// let mut rng = thread_rng();
+    /// // 5% chance to find gold, 20% a potion, 1% a sword
+    /// println!("{:?}", loot.choose_weighted(&mut rng, |item| item.1).unwrap().0);
+    /// ```
+    ///
+    /// [`WeightedIndex`]: ../distributions/struct.WeightedIndex.html
+    /// [`WeightedIndex::new`]: ../distributions/struct.WeightedIndex.html#method.new
+    #[cfg(feature="alloc")]
+    fn choose_weighted<R, F, B, X>(&self, rng: &mut R, weight: F)
+        -> Result<&Self::Item, WeightedError>
+        where R: Rng + ?Sized,
+              F: Fn(&Self::Item) -> B,
+              B: SampleBorrow<X>,
+              X: SampleUniform + PartialOrd + for<'a> AddAssign<&'a X> +
+                 Clone + Default;
+
+    /// Return a mutable reference to a random element from the slice, with
+    /// the probability of each element proportional to its weight, as
+    /// returned by `weight`.
+    ///
+    /// See [`choose_weighted`] for the errors and complexity.
+    ///
+    /// [`choose_weighted`]: #tymethod.choose_weighted
+    #[cfg(feature="alloc")]
+    fn choose_weighted_mut<R, F, B, X>(&mut self, rng: &mut R, weight: F)
+        -> Result<&mut Self::Item, WeightedError>
+        where R: Rng + ?Sized,
+              F: Fn(&Self::Item) -> B,
+              B: SampleBorrow<X>,
+              X: SampleUniform + PartialOrd + for<'a> AddAssign<&'a X> +
+                 Clone + Default;
+
+    /// Return an iterator over `amount` distinct elements chosen at random
+    /// from the slice, with the probability of choosing each element
+    /// proportional to its weight, as returned by `weight`.
+    ///
+    /// This is weighted sampling without replacement: the elements are
+    /// returned in the order in which they would be drawn one at a time,
+    /// each draw choosing among the remaining elements with probability
+    /// proportional to their weights. For `amount == 1` this is the same
+    /// distribution as that of [`choose_weighted`]. If the slice has fewer
+    /// than `amount` elements, all of them are returned.
+    ///
+    /// Elements with a weight of zero are only chosen after all elements
+    /// with a positive weight, in random order. They are therefore never
+    /// chosen if `amount` is at most the number of positive weights.
+    ///
+    /// The weights can be any type which converts to `f64`, such as `f32`
+    /// or `u32`. Returns an error if the slice is empty, if any weight is
+    /// negative, NaN or infinite, or if all weights are zero.
+    ///
+    /// This uses the algorithm of Efraimidis and Spirakis: each element gets
+    /// the key `u^(1 / weight)` with `u` uniformly distributed, and those
+    /// with the `amount` largest keys are chosen. It takes `O(n log amount)`
+    /// time and `O(amount)` memory for a slice of length `n`, and needs the
+    /// `std` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use rand::seq::SliceRandom;
+    ///
+    /// let ads = [("shoes", 5.0), ("books", 1.0), ("cars", 0.5), ("games", 3.5)];
+    /// let mut rng = thread_rng();
+    /// // Show three different ads, more often those with a higher bid
+    /// let shown = ads.choose_multiple_weighted(&mut rng, 3, |ad| ad.1).unwrap();
+    /// for ad in shown {
+    ///     println!("{}", ad.0);
+    /// }
+    /// ```
+    ///
+    /// P. S. Efraimidis and P. G. Spirakis, *Weighted random sampling with a
+    /// reservoir*, Information Processing Letters 97(5), 2006, 181-185.
+    ///
+    /// [`choose_weighted`]: #tymethod.choose_weighted
+    #[cfg(feature="std")]
+    fn choose_multiple_weighted<'a, R, F, X>(&'a self, rng: &mut R, amount: usize,
+                                             weight: F)
+        -> Result<SliceChooseIter<'a, Self::Item>, WeightedError>
+        where R: Rng + ?Sized,
+              F: Fn(&Self::Item) -> X,
+              X: Into<f64>;
+
+    /// Shuffle the slice in place.
+    ///
+    /// This applies Durstenfeld's algorithm for the [Fisher–Yates shuffle](
+    /// https://en.wikipedia.org/wiki/Fisher%E2%80%93Yates_shuffle#The_modern_algorithm)
+    /// which produces an unbiased permutation. For a given RNG state the
+    /// permutation is the same as that of [`shuffle_by_swap`], which shuffles
+    /// collections other than slices.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use rand::seq::SliceRandom;
+    ///
+    /// let mut rng = thread_rng();
+    /// let mut y = [1, 2, 3];
+    /// y.shuffle(&mut rng);
+    /// println!("{:?}", y);
+    /// ```
+    ///
+    /// [`shuffle_by_swap`]: fn.shuffle_by_swap.html
+    fn shuffle<R>(&mut self, rng: &mut R)
+        where R: Rng + ?Sized;
+
+    /// Shuffle a random selection of `amount` elements of the slice, and
+    /// return them together with the remaining elements.
+    ///
+    /// Returns `(chosen, rest)`, where `chosen` holds `amount` distinct
+    /// elements in random order: every ordered selection of `amount`
+    /// elements is equally likely. `rest` holds the other elements, in an
+    /// unspecified order. If `amount >= len`, this is a full shuffle and
+    /// `rest` is empty.
+    ///
+    /// This performs only `amount` steps of the Fisher–Yates shuffle, taking
+    /// `O(amount)` time instead of the `O(len)` time of `shuffle`. The chosen
+    /// elements are moved to the end of the slice, so `chosen` is a suffix
+    /// of `self` and `rest` a prefix. For the same RNG state, the result for
+    /// `amount >= len` is identical to that of [`shuffle`], and `amount == 0`
+    /// does not use the RNG.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use rand::seq::SliceRandom;
+    ///
+    /// let mut rng = thread_rng();
+    /// let mut deck: Vec<u32> = (1..53).collect();
+    /// // Deal a hand of five cards, without shuffling the whole deck
+    /// let (hand, rest) = deck.partial_shuffle(&mut rng, 5);
+    /// assert_eq!(hand.len(), 5);
+    /// assert_eq!(rest.len(), 47);
+    /// ```
+    ///
+    /// [`shuffle`]: #tymethod.shuffle
+    fn partial_shuffle<R>(&mut self, rng: &mut R, amount: usize)
+        -> (&mut [Self::Item], &mut [Self::Item]) where R: Rng + ?Sized;
+
+    /// Shuffle the slice in place, so that elements with a larger weight, as
+    /// returned by `weight`, tend to come first.
+    ///
+    /// The order is that of repeated weighted sampling without replacement:
+    /// the first element is chosen with probability proportional to its
+    /// weight, the second likewise from the remaining elements, and so on,
+    /// as in a lottery drawing. Elements with a weight of zero come last, in
+    /// uniformly random order. If all weights are equal the result has the
+    /// same distribution as that of [`shuffle`], but not the same value for
+    /// a given RNG.
+    ///
+    /// This uses the keys of Efraimidis and Spirakis, like
+    /// [`choose_multiple_weighted`], followed by an unstable sort of the
+    /// keys. It takes `O(n log n)` time and `O(n)` memory.
+    ///
+    /// Returns an error, leaving the slice unchanged, if any weight is
+    /// negative (`WeightedError::NegativeWeight`) or not finite
+    /// (`WeightedError::InvalidWeight`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use rand::seq::SliceRandom;
+    ///
+    /// // Tickets bought by each player; more tickets, more likely to win.
+    /// let mut players = [("alice", 1), ("bob", 5), ("carol", 2), ("dave", 0)];
+    /// players.shuffle_weighted(&mut thread_rng(), |p| p.1).unwrap();
+    /// // Without tickets, dave never wins a prize.
+    /// assert_eq!(players[3].0, "dave");
+    /// ```
+    ///
+    /// [`shuffle`]: #tymethod.shuffle
+    /// [`choose_multiple_weighted`]: #tymethod.choose_multiple_weighted
+    #[cfg(feature="std")]
+    fn shuffle_weighted<R, F, X>(&mut self, rng: &mut R, weight: F)
+        -> Result<(), WeightedError>
+        where R: Rng + ?Sized,
+              F: Fn(&Self::Item) -> X,
+              X: Into<f64>;
+}
+
+impl<T> SliceRandom for [T] {
+    type Item = T;
+
+    fn choose<R>(&self, rng: &mut R) -> Option<&T>
+        where R: Rng + ?Sized
+    {
+        if self.is_empty() {
+            None
+        } else {
+            Some(&self[rng.gen_index(self.len())])
+        }
+    }
+
+    fn choose_mut<R>(&mut self, rng: &mut R) -> Option<&mut T>
+        where R: Rng + ?Sized
+    {
+        if self.is_empty() {
+            None
+        } else {
+            let len = self.len();
+            Some(&mut self[rng.gen_index(len)])
+        }
+    }
+
+    #[cfg(feature="alloc")]
+    fn choose_multiple<'a, R>(&'a self, rng: &mut R, amount: usize)
+        -> SliceChooseIter<'a, T>
+        where R: Rng + ?Sized
+    {
+        let amount = ::core::cmp::min(amount, self.len());
+        let indices = if amount <= SMALL_AMOUNT &&
+                         self.len() <= ::core::u32::MAX as usize {
+            let mut buf = [0u32; SMALL_AMOUNT];
+            sample_floyd_into(rng, self.len(), &mut buf[..amount]);
+            ChooseIndices::Small(buf, 0..amount)
+        } else {
+            ChooseIndices::Vec(index::sample(rng, self.len(), amount).into_iter())
+        };
+        SliceChooseIter { slice: self, indices }
+    }
+
+    #[cfg(rustc_1_55)]
+    choose_multiple_array_impl!();
+
+    #[cfg(feature="alloc")]
+    fn choose_weighted<R, F, B, X>(&self, rng: &mut R, weight: F)
+        -> Result<&T, WeightedError>
+        where R: Rng + ?Sized,
+              F: Fn(&T) -> B,
+              B: SampleBorrow<X>,
+              X: SampleUniform + PartialOrd + for<'a> AddAssign<&'a X> +
+                 Clone + Default
+    {
+        let distr = WeightedIndex::new(self.iter().map(weight))?;
+        Ok(&self[distr.sample(rng)])
+    }
+
+    #[cfg(feature="alloc")]
+    fn choose_weighted_mut<R, F, B, X>(&mut self, rng: &mut R, weight: F)
+        -> Result<&mut T, WeightedError>
+        where R: Rng + ?Sized,
+              F: Fn(&T) -> B,
+              B: SampleBorrow<X>,
+              X: SampleUniform + PartialOrd + for<'a> AddAssign<&'a X> +
+                 Clone + Default
+    {
+        let distr = WeightedIndex::new(self.iter().map(weight))?;
+        Ok(&mut self[distr.sample(rng)])
+    }
+
+    #[cfg(feature="std")]
+    fn choose_multiple_weighted<'a, R, F, X>(&'a self, rng: &mut R, amount: usize,
+                                             weight: F)
+        -> Result<SliceChooseIter<'a, T>, WeightedError>
+        where R: Rng + ?Sized,
+              F: Fn(&T) -> X,
+              X: Into<f64>
+    {
+        if self.is_empty() {
+            return Err(WeightedError::NoItem);
+        }
+        let amount = ::core::cmp::min(amount, self.len());
+
+        // A min-heap of the `amount` largest keys seen so far
+        let mut heap = BinaryHeap::with_capacity(amount + 1);
+        let mut any_positive = false;
+        for (index, elem) in self.iter().enumerate() {
+            let key = weighted_key(rng, weight(elem).into(), index)?;
+            any_positive |= key.positive;
+            if heap.len() < amount {
+                heap.push(key);
+                continue;
+            }
+            let larger = match heap.peek() {
+                Some(min) => key < *min,
+                None => false,
+            };
+            if larger {
+                heap.pop();
+                heap.push(key);
+            }
+        }
+        if !any_positive {
+            return Err(WeightedError::AllWeightsZero);
+        }
+
+        // The heap is ordered by decreasing keys, so the sorted vector
+        // starts with the largest key.
+        let indices: Vec<usize> = heap.into_sorted_vec().into_iter()
+            .map(|key| key.index).collect();
+        Ok(SliceChooseIter {
+            slice: self,
+            indices: ChooseIndices::Vec(index::IndexVec::from(indices).into_iter()),
+        })
+    }
+
+    fn shuffle<R>(&mut self, rng: &mut R)
+        where R: Rng + ?Sized
+    {
+        shuffle_by_swap(rng, self.len(), |i, j| self.swap(i, j))
+    }
+
+    fn partial_shuffle<R>(&mut self, rng: &mut R, amount: usize)
+        -> (&mut [T], &mut [T]) where R: Rng + ?Sized
+    {
+        // The same algorithm as `shuffle`, stopping after `amount` steps.
+        // The last step of a full shuffle is skipped, as it only has one
+        // choice.
+        let len = self.len();
+        let end = len.saturating_sub(amount);
+        let mut i = len;
+        while i > end && i >= 2 {
+            i -= 1;
+            self.swap(i, rng.gen_index(i + 1));
+        }
+        let (rest, chosen) = self.split_at_mut(end);
+        (chosen, rest)
+    }
+
+    #[cfg(feature="std")]
+    fn shuffle_weighted<R, F, X>(&mut self, rng: &mut R, weight: F)
+        -> Result<(), WeightedError>
+        where R: Rng + ?Sized,
+              F: Fn(&T) -> X,
+              X: Into<f64>
+    {
+        let mut keys = Vec::with_capacity(self.len());
+        for (index, elem) in self.iter().enumerate() {
+            keys.push(weighted_key(rng, weight(elem).into(), index)?);
+        }
+        // `WeightedKey` is ordered by decreasing keys.
+        keys.sort_unstable();
+        let order: Vec<usize> = keys.into_iter().map(|key| key.index).collect();
+        Permutation::from_index_vec(index::IndexVec::from(order)).apply_slice(self);
+        Ok(())
+    }
+}
+
+/// Compute the key of Efraimidis and Spirakis for an element with weight `w`
+/// at `index`; larger keys are chosen first.
+///
+/// Positive weights get the key `ln(u) / w` for `u` uniform in `(0, 1]`,
+/// which has the same order as `u^(1 / w)` but does not underflow for small
+/// weights. Zero weights are ordered after all positive weights, at random.
+#[cfg(feature="std")]
+fn weighted_key<R>(rng: &mut R, w: f64, index: usize)
+    -> Result<WeightedKey, WeightedError>
+    where R: Rng + ?Sized
+{
+    if !w.is_finite() {
+        return Err(WeightedError::InvalidWeight);
+    }
+    if w < 0.0 {
+        return Err(WeightedError::NegativeWeight);
+    }
+    let u: f64 = rng.sample(OpenClosed01);
+    Ok(if w > 0.0 {
+        WeightedKey { positive: true, key: u.ln() / w, index }
+    } else {
+        WeightedKey { positive: false, key: u, index }
+    })
+}
+
+/// Shuffle a collection of `len` elements in place, by calling `swap(i, j)`
+/// to exchange the elements at the indices `i` and `j`.
+///
+/// This is the Fisher–Yates shuffle of [`SliceRandom::shuffle`], for
+/// collections which can swap elements but are not slices, such as a
+/// `VecDeque` or the rows of a matrix. For the same RNG state it produces
+/// the same permutation as `SliceRandom::shuffle` on a slice of length
+/// `len`. `swap` is called `len - 1` times, with `j <= i`; `i` and `j` may
+/// be equal.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::VecDeque;
+/// use rand::thread_rng;
+/// use rand::seq::shuffle_by_swap;
+///
+/// let mut rng = thread_rng();
+/// let mut deque: VecDeque<u32> = (0..10).collect();
+/// let len = deque.len();
+/// shuffle_by_swap(&mut rng, len, |i, j| deque.swap(i, j));
+///
+/// // Shuffle two parallel arrays, keeping them aligned
+/// let mut names = ["a", "b", "c", "d"];
+/// let mut ages = [30, 25, 41, 19];
+/// shuffle_by_swap(&mut rng, names.len(), |i, j| {
+///     names.swap(i, j);
+///     ages.swap(i, j);
+/// });
+/// ```
+///
+/// [`SliceRandom::shuffle`]: trait.SliceRandom.html#tymethod.shuffle
+pub fn shuffle_by_swap<R, F>(rng: &mut R, len: usize, mut swap: F)
+    where R: Rng + ?Sized, F: FnMut(usize, usize)
+{
+    let mut i = len;
+    while i >= 2 {
+        // invariant: elements with index >= i have been locked in place.
+        i -= 1;
+        // lock element i in place.
+        swap(i, rng.gen_index(i + 1));
+    }
+}
+
+/// An iterator over elements chosen at random from a slice.
+///
+/// This `struct` is created by [`SliceRandom::choose_multiple`] and
+/// [`SliceRandom::choose_multiple_weighted`].
+///
+/// [`SliceRandom::choose_multiple`]: trait.SliceRandom.html#tymethod.choose_multiple
+/// [`SliceRandom::choose_multiple_weighted`]: trait.SliceRandom.html#tymethod.choose_multiple_weighted
+#[cfg(feature="alloc")]
+#[derive(Debug)]
+pub struct SliceChooseIter<'a, T: 'a> {
+    slice: &'a [T],
+    indices: ChooseIndices,
+}
+
+/// The indices of a `SliceChooseIter`: small amounts are stored inline, so
+/// that choosing them does not allocate.
+#[cfg(feature="alloc")]
+#[derive(Debug)]
+enum ChooseIndices {
+    Small([u32; SMALL_AMOUNT], ::core::ops::Range<usize>),
+    Vec(index::IndexVecIntoIter),
+}
+
+#[cfg(feature="alloc")]
+impl<'a, T> Iterator for SliceChooseIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let i = match self.indices {
+            ChooseIndices::Small(ref buf, ref mut range) =>
+                range.next().map(|k| buf[k] as usize),
+            ChooseIndices::Vec(ref mut iter) => iter.next(),
+        };
+        i.map(|i| &self.slice[i])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.indices {
+            ChooseIndices::Small(_, ref range) => range.size_hint(),
+            ChooseIndices::Vec(ref iter) => iter.size_hint(),
+        }
+    }
+}
+
+#[cfg(feature="alloc")]
+impl<'a, T> ExactSizeIterator for SliceChooseIter<'a, T> {}
+
+/// Extension trait on iterators, providing random sampling methods.
+///
+/// The iterator is consumed in a single pass, without collecting its
+/// elements. `choose` uses `size_hint` to skip over elements which can not
+/// be chosen, but treats it only as a hint: results are uniform for any
+/// iterator whose lower bound does not exceed the number of remaining
+/// elements, as required by the `Iterator` documentation, and an upper bound
+/// which is too small is ignored. If the lower bound is too large, `choose`
+/// can not go back to elements it skipped over, so they may be chosen with a
+/// lower probability, but an element is still returned.
+pub trait IteratorRandom: Iterator + Sized {
+    /// Choose one element at random from the iterator.
+    ///
+    /// Returns `None` if and only if the iterator is empty.
+    ///
+    /// This uses reservoir sampling. Whenever `size_hint` reports a lower
+    /// bound of `n` more elements, the choice among the next `n` elements
+    /// and those seen before is made with a single random index, and the
+    /// other elements of the batch are skipped with `nth`. This is `O(1)`
+    /// for iterators with a known length that support skipping, such as
+    /// ranges and slice iterators. Iterators without a lower bound sample
+    /// one random index per element.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use rand::seq::IteratorRandom;
+    ///
+    /// let mut rng = thread_rng();
+    /// // the length of a range is known
+    /// println!("{:?}", (1..101).choose(&mut rng));
+    /// // the number of words is not known in advance
+    /// let words = "the quick brown fox jumps over the lazy dog".split(' ');
+    /// println!("{:?}", words.choose(&mut rng));
+    /// assert_eq!((0..0).choose(&mut rng), None);
+    /// ```
+    fn choose<R>(mut self, rng: &mut R) -> Option<Self::Item>
+        where R: Rng + ?Sized
+    {
+        let mut result = None;
+        let mut consumed = 0;
+        loop {
+            let lower = self.size_hint().0;
+            if lower > 0 {
+                // Each of the next `lower` elements replaces the result with
+                // probability `1 / (consumed + lower)`, so choose at most one.
+                let i = rng.gen_index(consumed + lower);
+                // The first element of the batch is taken with `next`, so it
+                // is not lost if the lower bound turns out to be too large.
+                let first = self.next();
+                if first.is_none() {
+                    return result;
+                }
+                let skip = if i < lower {
+                    if i == 0 {
+                        result = first;
+                    } else {
+                        match self.nth(i - 1) {
+                            Some(elem) => result = Some(elem),
+                            None => {
+                                // The lower bound was too large: fall back to
+                                // reservoir sampling over the elements seen.
+                                if rng.gen_index(consumed + 1) == 0 {
+                                    result = first;
+                                }
+                                return result;
+                            }
+                        }
+                    }
+                    lower - (i + 1)
+                } else {
+                    lower - 1
+                };
+                consumed += lower;
+                if skip > 0 && self.nth(skip - 1).is_none() {
+                    return result;
+                }
+            } else {
+                // Reservoir sampling with a reservoir of size 1: the `n`-th
+                // element replaces the chosen one with probability `1 / n`.
+                let elem = self.next();
+                if elem.is_none() {
+                    return result;
+                }
+                consumed += 1;
+                if rng.gen_index(consumed) == 0 {
+                    result = elem;
+                }
+            }
+        }
+    }
+
+    /// Collect elements chosen at random from the iterator into `buf`,
+    /// returning the number of elements written.
+    ///
+    /// If the iterator has fewer than `buf.len()` elements, all of them are
+    /// written to the start of `buf` in their original order, and the rest of
+    /// `buf` is left unchanged. Otherwise `buf` is filled, and each element
+    /// of the iterator is included with equal probability. The order of the
+    /// chosen elements is not random.
+    ///
+    /// This uses reservoir sampling with a reservoir of size `buf.len()`,
+    /// in `O(n)` time for an iterator of length `n`: the first `buf.len()`
+    /// elements fill the buffer, and then the `i`-th element replaces a
+    /// random one with probability `buf.len() / i`. It does not allocate,
+    /// and is available without the `alloc` and `std` features.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use rand::seq::IteratorRandom;
+    ///
+    /// let mut rng = thread_rng();
+    /// let mut buf = [0; 3];
+    /// assert_eq!((1..100).choose_multiple_fill(&mut rng, &mut buf), 3);
+    /// println!("{:?}", buf);
+    /// assert_eq!((1..3).choose_multiple_fill(&mut rng, &mut buf), 2);
+    /// assert_eq!(&buf[..2], &[1, 2]);
+    /// ```
+    fn choose_multiple_fill<R>(mut self, rng: &mut R, buf: &mut [Self::Item])
+        -> usize where R: Rng + ?Sized
+    {
+        let amount = buf.len();
+        let mut len = 0;
+        while len < amount {
+            match self.next() {
+                Some(elem) => buf[len] = elem,
+                None => return len,
+            }
+            len += 1;
+        }
+
+        // The `n`-th element replaces one of the chosen elements with
+        // probability `amount / n`.
+        for (i, elem) in self.enumerate() {
+            let k = rng.gen_index(i + 1 + amount);
+            if let Some(slot) = buf.get_mut(k) {
+                *slot = elem;
+            }
+        }
+        len
+    }
+
+    /// Collect `amount` elements chosen at random from the iterator into a
+    /// new vector.
+    ///
+    /// If the iterator has fewer than `amount` elements, all of them are
+    /// returned in their original order. Otherwise each element of the
+    /// iterator is included with equal probability. The order of the chosen
+    /// elements is not random.
+    ///
+    /// This uses reservoir sampling, in `O(n)` time for an iterator of
+    /// length `n` and `O(amount)` memory. See [`choose_multiple_fill`] for a
+    /// variant which does not allocate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use rand::seq::IteratorRandom;
+    ///
+    /// let mut rng = thread_rng();
+    /// let text = "the quick brown fox jumps over the lazy dog";
+    /// let words = text.split(' ').choose_multiple(&mut rng, 3);
+    /// assert_eq!(words.len(), 3);
+    /// println!("{:?}", words);
+    /// ```
+    ///
+    /// [`choose_multiple_fill`]: #method.choose_multiple_fill
+    #[cfg(feature="alloc")]
+    fn choose_multiple<R>(mut self, rng: &mut R, amount: usize)
+        -> Vec<Self::Item> where R: Rng + ?Sized
+    {
+        let mut reservoir = Vec::with_capacity(amount);
+        reservoir.extend(self.by_ref().take(amount));
+
+        // Continue unless the iterator was exhausted
+        //
+        // note: this prevents iterators that "restart" from causing problems.
+        // If the iterator stops once, then so do we.
+        if reservoir.len() == amount {
+            for (i, elem) in self.enumerate() {
+                let k = rng.gen_index(i + 1 + amount);
+                if let Some(slot) = reservoir.get_mut(k) {
+                    *slot = elem;
+                }
+            }
+        } else {
+            // Don't hang onto extra memory. There is a corner case where
+            // `amount` was much less than `len(iterable)`.
+            reservoir.shrink_to_fit();
+        }
+        reservoir
+    }
+}
+
+impl<I> IteratorRandom for I where I: Iterator + Sized {}
+
+/// Randomly sample `amount` elements from a finite iterator.
+///
+/// The following can be returned:
+///
+/// - `Ok`: `Vec` of `amount` non-repeating randomly sampled elements. The order is not random.
+/// - `Err`: `Vec` of all the elements from `iterable` in sequential order. This happens when the
+///   length of `iterable` was less than `amount`. This is considered an error since exactly
+///   `amount` elements is typically expected.
+///
+/// This implementation uses `O(len(iterable))` time and `O(amount)` memory.
+///
+/// # Example
+///
+/// ```
+/// use rand::{thread_rng, seq};
+///
+/// let mut rng = thread_rng();
+/// let sample = seq::sample_iter(&mut rng, 1..100, 5).unwrap();
+/// println!("{:?}", sample);
+/// ```
+#[cfg(feature="alloc")]
+pub fn sample_iter<T, I, R>(rng: &mut R, iterable: I, amount: usize) -> Result<Vec<T>, Vec<T>>
+    where I: IntoIterator<Item=T>,
+          R: Rng + ?Sized,
+{
+    let mut iter = iterable.into_iter();
+    let mut reservoir = Vec::with_capacity(amount);
+    reservoir.extend(iter.by_ref().take(amount));
+
+    // Continue unless the iterator was exhausted
+    //
+    // note: this prevents iterators that "restart" from causing problems.
+    // If the iterator stops once, then so do we.
+    if reservoir.len() == amount {
+        for (i, elem) in iter.enumerate() {
+            let k = rng.gen_index(i + 1 + amount);
+            if let Some(spot) = reservoir.get_mut(k) {
+                *spot = elem;
+            }
+        }
+        Ok(reservoir)
+    } else {
+        // Don't hang onto extra memory. There is a corner case where
+        // `amount` was much less than `len(iterable)`.
+        reservoir.shrink_to_fit();
+        Err(reservoir)
+    }
+}
+
+/// Randomly sample exactly `amount` values from `slice`.
+///
+/// The values are non-repeating and in random order.
+///
+/// This implementation uses `O(amount)` time and memory.
+///
+/// Panics if `amount > slice.len()`
+///
+/// # Example
+///
+/// ```
+/// use rand::{thread_rng, seq};
+///
+/// let mut rng = thread_rng();
+/// let values = vec![5, 6, 1, 3, 4, 6, 7];
+/// println!("{:?}", seq::sample_slice(&mut rng, &values, 3));
+/// ```
+#[cfg(feature="alloc")]
+pub fn sample_slice<R, T>(rng: &mut R, slice: &[T], amount: usize) -> Vec<T>
+    where R: Rng + ?Sized,
+          T: Clone
+{
+    let indices = sample_indices(rng, slice.len(), amount);
+
+    let mut out = Vec::with_capacity(amount);
+    out.extend(indices.iter().map(|i| slice[*i].clone()));
+    out
+}
+
+/// Randomly sample exactly `amount` references from `slice`.
+///
+/// The references are non-repeating and in random order.
+///
+/// This implementation uses `O(amount)` time and memory.
+///
+/// Panics if `amount > slice.len()`
+///
+/// # Example
+///
+/// ```
+/// use rand::{thread_rng, seq};
+///
+/// let mut rng = thread_rng();
+/// let values = vec![5, 6, 1, 3, 4, 6, 7];
+/// println!("{:?}", seq::sample_slice_ref(&mut rng, &values, 3));
+/// ```
+#[cfg(feature="alloc")]
+pub fn sample_slice_ref<'a, R, T>(rng: &mut R, slice: &'a [T], amount: usize) -> Vec<&'a T>
+    where R: Rng + ?Sized
+{
+    let indices = sample_indices(rng, slice.len(), amount);
+
+    let mut out = Vec::with_capacity(amount);
+    out.extend(indices.iter().map(|i| &slice[*i]));
+    out
+}
+
+/// Randomly sample `out.len()` distinct values from `slice` into `out`,
+/// without allocating.
+///
+/// The values are non-repeating and in random order. Up to 16 values are
+/// sampled with Floyd's combination algorithm in `O(amount²)` time, using
+/// a buffer of indices on the stack. Larger amounts are sampled by selection
+/// sampling followed by a shuffle of `out`, which takes `O(slice.len())`
+/// time; for a large `amount` from a much larger slice [`sample_slice`] may
+/// be faster.
+///
+/// Panics if `out.len() > slice.len()`, like [`sample_slice`].
+///
+/// # Example
+///
+/// ```
+/// use rand::{thread_rng, seq};
+///
+/// let mut rng = thread_rng();
+/// let values = [5, 6, 1, 3, 4, 6, 7];
+/// let mut out = [0; 3];
+/// // The buffer can be reused for many samples.
+/// for _ in 0..10 {
+///     seq::sample_slice_fill(&mut rng, &values, &mut out);
+///     println!("{:?}", out);
+/// }
+/// ```
+///
+/// [`sample_slice`]: fn.sample_slice.html
+pub fn sample_slice_fill<R, T>(rng: &mut R, slice: &[T], out: &mut [T])
+    where R: Rng + ?Sized,
+          T: Copy
+{
+    let amount = out.len();
+    let length = slice.len();
+    assert!(amount <= length,
+            "`amount` of samples must be less than or equal to `length`");
+
+    if amount <= SMALL_AMOUNT && length <= ::core::u32::MAX as usize {
+        let mut indices = [0u32; SMALL_AMOUNT];
+        sample_floyd_into(rng, length, &mut indices[..amount]);
+        for (x, &i) in out.iter_mut().zip(indices.iter()) {
+            *x = slice[i as usize];
+        }
+    } else {
+        // Selection sampling: each element is chosen with probability
+        // `needed / remaining`, which yields the values in slice order.
+        let mut filled = 0;
+        for (i, &x) in slice.iter().enumerate() {
+            if filled == amount {
+                break;
+            }
+            if rng.gen_index(length - i) < amount - filled {
+                out[filled] = x;
+                filled += 1;
+            }
+        }
+        debug_assert_eq!(filled, amount);
+        out.shuffle(rng);
+    }
+}
+
+// The largest amount sampled into a buffer on the stack by `choose_multiple`
+// and `sample_slice_fill`.
+const SMALL_AMOUNT: usize = 16;
+
+/// Randomly sample `indices.len()` distinct indices from `0..length` into
+/// `indices`, in random order, using Floyd's combination algorithm.
+///
+/// This is the same algorithm as `index::sample` uses for small amounts,
+/// with insertions done by shifting within the buffer. Requires
+/// `length <= u32::MAX`.
+fn sample_floyd_into<R>(rng: &mut R, length: usize, indices: &mut [u32])
+    where R: Rng + ?Sized
+{
+    let amount = indices.len();
+    debug_assert!(amount <= length && length <= ::core::u32::MAX as usize);
+    for (n, j) in ((length - amount)..length).enumerate() {
+        let t = rng.gen_range(0, j as u32 + 1);
+        match indices[..n].iter().position(|&x| x == t) {
+            Some(pos) => {
+                // Insert `j` at `pos`, as `t` was already chosen.
+                for k in (pos..n).rev() {
+                    indices[k + 1] = indices[k];
+                }
+                indices[pos] = j as u32;
+            }
+            None => indices[n] = t,
+        }
+    }
+}
+
+/// Randomly sample exactly `amount` indices from `0..length`.
+///
+/// The values are non-repeating and in random order.
+///
+/// This method is used internally by the slice sampling methods, but it can sometimes be useful to
+/// have the indices themselves so this is provided as an alternative.
+///
+/// This is [`index::sample`] collected into a `Vec<usize>`; see there for
+/// the algorithms used. `index::sample` stores the indices more compactly.
+///
+/// Panics if `amount > length`
+///
+/// [`index::sample`]: index/fn.sample.html
+#[cfg(feature="alloc")]
+pub fn sample_indices<R>(rng: &mut R, length: usize, amount: usize) -> Vec<usize>
+    where R: Rng + ?Sized,
+{
+    index::sample(rng, length, amount).into_vec()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use {XorShiftRng, Rng, SeedableRng};
+    #[cfg(all(feature="alloc", not(feature="std")))]
+    use alloc::Vec;
+
+    // An iterator which does not know its length.
+    struct UnhintedIterator<I> {
+        iter: I,
+    }
+
+    impl<I: Iterator> Iterator for UnhintedIterator<I> {
+        type Item = I::Item;
+        fn next(&mut self) -> Option<Self::Item> {
+            self.iter.next()
+        }
+    }
+
+    // An iterator which reports an exact length of at most `hint` elements,
+    // while it may have more.
+    struct LyingHintIterator<I> {
+        iter: I,
+        hint: usize,
+    }
+
+    impl<I: ExactSizeIterator> Iterator for LyingHintIterator<I> {
+        type Item = I::Item;
+        fn next(&mut self) -> Option<Self::Item> {
+            self.iter.next()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let n = ::core::cmp::min(self.iter.len(), self.hint);
+            (n, Some(n))
+        }
+    }
+
+    // An iterator which reports an exact length of `extra` more elements than
+    // it has, which violates the contract of `size_hint`.
+    struct OverHintIterator<I> {
+        iter: I,
+        extra: usize,
+    }
+
+    impl<I: ExactSizeIterator> Iterator for OverHintIterator<I> {
+        type Item = I::Item;
+        fn next(&mut self) -> Option<Self::Item> {
+            self.iter.next()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let n = self.iter.len() + self.extra;
+            (n, Some(n))
+        }
+    }
+
+    // Check that `counts` are consistent with equal probabilities, with a
+    // chi-square test at the 0.1% significance level.
+    fn check_uniform(counts: &[usize]) {
+        // critical values of the chi-square distribution for 1 to 20 degrees
+        // of freedom
+        const CRITICAL: [f64; 20] = [10.83, 13.82, 16.27, 18.47, 20.52,
+                                     22.46, 24.32, 26.12, 27.88, 29.59,
+                                     31.26, 32.91, 34.53, 36.12, 37.70,
+                                     39.25, 40.79, 42.31, 43.82, 45.31];
+        let n: usize = counts.iter().sum();
+        let expected = n as f64 / counts.len() as f64;
+        let chi2: f64 = counts.iter()
+            .map(|&c| (c as f64 - expected).powi(2) / expected)
+            .sum();
+        assert!(chi2 < CRITICAL[counts.len() - 2],
+                "chi-square {} for counts {:?}", chi2, counts);
+    }
+
+    #[test]
+    fn test_slice_choose() {
+        let mut r = ::test::rng(404);
+        let chars = ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j'];
+        let mut chosen = [0usize; 10];
+        for _ in 0..1000 {
+            let c = *chars.choose(&mut r).unwrap();
+            chosen[(c as usize) - ('a' as usize)] += 1;
+        }
+        for &count in chosen.iter() {
+            // each element is chosen with probability 1/10, so the count has
+            // mean 100 and standard deviation 9.5
+            assert!(count > 52 && count < 148, "count {}", count);
+        }
+
+        let mut nums = [1, 2, 3];
+        *nums.choose_mut(&mut r).unwrap() = 7;
+        assert_eq!(nums.iter().filter(|&&x| x == 7).count(), 1);
+
+        let empty: [u8; 0] = [];
+        let mut empty_mut: [u8; 0] = [];
+        assert_eq!(empty.choose(&mut r), None);
+        assert_eq!(empty_mut.choose_mut(&mut r), None);
+    }
+
+    #[test]
+    #[cfg(feature="alloc")]
+    fn test_slice_choose_multiple() {
+        let mut r = ::test::rng(405);
+        let values: Vec<usize> = (0..100).collect();
+        for &amount in [0, 1, 10, 99, 100].iter() {
+            let iter = values.choose_multiple(&mut r, amount);
+            assert_eq!(iter.len(), amount);
+            let mut chosen: Vec<usize> = iter.cloned().collect();
+            assert_eq!(chosen.len(), amount);
+            chosen.sort();
+            chosen.dedup();
+            assert_eq!(chosen.len(), amount);
+        }
+
+        // At most all elements are returned.
+        let mut all: Vec<usize> = values.choose_multiple(&mut r, 150).cloned().collect();
+        assert_eq!(all.len(), 100);
+        all.sort();
+        assert_eq!(all, values);
+
+        let empty: [u8; 0] = [];
+        assert_eq!(empty.choose_multiple(&mut r, 0).next(), None);
+        assert_eq!(empty.choose_multiple(&mut r, 5).next(), None);
+
+        // Each element is chosen with probability `amount / len`.
+        let mut counts = [0usize; 10];
+        const N: usize = 10_000;
+        for _ in 0..N {
+            for &x in values[..10].choose_multiple(&mut r, 3) {
+                counts[x] += 1;
+            }
+        }
+        let p = 0.3;
+        let err = 5.0 * (N as f64 * p * (1.0 - p)).sqrt();
+        for &count in counts.iter() {
+            assert!((count as f64 - N as f64 * p).abs() < err, "count {}", count);
+        }
+    }
+
+    #[test]
+    #[cfg(feature="alloc")]
+    fn test_slice_choose_weighted() {
+        let mut r = ::test::rng(407);
+        let items = [('a', 1u32), ('b', 0), ('c', 4), ('d', 0), ('e', 3)];
+        let mut counts = [0usize; 5];
+        const N: usize = 8_000;
+        for _ in 0..N {
+            let item = items.choose_weighted(&mut r, |item| item.1).unwrap();
+            counts[(item.0 as usize) - ('a' as usize)] += 1;
+        }
+        // zero weights are never chosen
+        assert_eq!(counts[1], 0);
+        assert_eq!(counts[3], 0);
+        for (&count, item) in counts.iter().zip(items.iter()) {
+            let p = item.1 as f64 / 8.0;
+            let err = 5.0 * (N as f64 * p * (1.0 - p)).sqrt();
+            assert!((count as f64 - N as f64 * p).abs() <= err,
+                    "count {} for weight {}", count, item.1);
+        }
+
+        // floating point weights
+        let floats = [(0.5f64, 'x'), (0.0, 'y'), (1.5, 'z')];
+        for _ in 0..100 {
+            let c = floats.choose_weighted(&mut r, |item| item.0).unwrap().1;
+            assert!(c == 'x' || c == 'z');
+        }
+
+        let mut counters = [(0u32, 0.0), (0, 2.0), (0, 0.0)];
+        for _ in 0..100 {
+            counters.choose_weighted_mut(&mut r, |c| c.1).unwrap().0 += 1;
+        }
+        assert_eq!(counters[0].0 + counters[2].0, 0);
+        assert_eq!(counters[1].0, 100);
+
+        let empty: [(u8, u32); 0] = [];
+        assert_eq!(empty.choose_weighted(&mut r, |x| x.1).unwrap_err(),
+                   WeightedError::NoItem);
+        let mut zeros = [(1u8, 0u32), (2, 0)];
+        assert_eq!(zeros.choose_weighted(&mut r, |x| x.1).unwrap_err(),
+                   WeightedError::AllWeightsZero);
+        assert_eq!(zeros.choose_weighted_mut(&mut r, |x| x.1).unwrap_err(),
+                   WeightedError::AllWeightsZero);
+        assert_eq!([1, -1, 2].choose_weighted(&mut r, |&x| x).unwrap_err(),
+                   WeightedError::NegativeWeight);
+        assert_eq!([1.0, ::core::f64::NAN].choose_weighted(&mut r, |&x| x).unwrap_err(),
+                   WeightedError::InvalidWeight);
+    }
+
+    #[test]
+    #[cfg(feature="std")]
+    fn test_slice_choose_multiple_weighted() {
+        let mut r = ::test::rng(408);
+        const N: usize = 8_000;
+
+        // With one element this is the distribution of `choose_weighted`.
+        let items = [('a', 1u32), ('b', 0), ('c', 4), ('d', 0), ('e', 3)];
+        let mut counts = [0usize; 5];
+        for _ in 0..N {
+            let mut iter = items.choose_multiple_weighted(&mut r, 1, |item| item.1)
+                .unwrap();
+            let item = iter.next().unwrap();
+            assert!(iter.next().is_none());
+            counts[(item.0 as usize) - ('a' as usize)] += 1;
+        }
+        assert_eq!(counts[1], 0);
+        assert_eq!(counts[3], 0);
+        for (&count, item) in counts.iter().zip(items.iter()) {
+            let p = item.1 as f64 / 8.0;
+            let err = 5.0 * (N as f64 * p * (1.0 - p)).sqrt();
+            assert!((count as f64 - N as f64 * p).abs() <= err,
+                    "count {} for weight {}", count, item.1);
+        }
+
+        // Choosing two elements, element `i` is included if it is drawn
+        // first, or if another element `j` is drawn first and `i` second.
+        let weights = [1.0f64, 2.0, 3.0, 4.0];
+        let total = 10.0;
+        let mut counts = [0usize; 4];
+        for _ in 0..N {
+            let chosen: Vec<&f64> = weights.choose_multiple_weighted(&mut r, 2, |&w| w)
+                .unwrap().collect();
+            assert_eq!(chosen.len(), 2);
+            assert!(chosen[0] != chosen[1]);
+            for &w in chosen {
+                counts[w as usize - 1] += 1;
+            }
+        }
+        for (i, &count) in counts.iter().enumerate() {
+            let wi = weights[i];
+            let p = wi / total + weights.iter().enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, &wj)| wj / total * wi / (total - wj))
+                .sum::<f64>();
+            let err = 5.0 * (N as f64 * p * (1.0 - p)).sqrt();
+            assert!((count as f64 - N as f64 * p).abs() <= err,
+                    "count {} for weight {}, expected {}", count, wi, N as f64 * p);
+        }
+
+        // Zero weights are only chosen after all positive weights.
+        let values = [0u8, 1, 0, 2];
+        for _ in 0..100 {
+            let mut two: Vec<u8> = values.choose_multiple_weighted(&mut r, 2, |&w| w)
+                .unwrap().cloned().collect();
+            two.sort();
+            assert_eq!(two, vec![1, 2]);
+            let three: Vec<u8> = values.choose_multiple_weighted(&mut r, 3, |&w| w)
+                .unwrap().cloned().collect();
+            assert_eq!(three.len(), 3);
+            assert_eq!(three[2], 0);
+        }
+        // At most all elements are returned.
+        let all = values.choose_multiple_weighted(&mut r, 10, |&w| w).unwrap();
+        assert_eq!(all.len(), 4);
+
+        let empty: [f32; 0] = [];
+        assert_eq!(empty.choose_multiple_weighted(&mut r, 1, |&w| w).unwrap_err(),
+                   WeightedError::NoItem);
+        assert_eq!([0u32, 0].choose_multiple_weighted(&mut r, 1, |&w| w).unwrap_err(),
+                   WeightedError::AllWeightsZero);
+        assert_eq!([1i32, -1].choose_multiple_weighted(&mut r, 1, |&w| w).unwrap_err(),
+                   WeightedError::NegativeWeight);
+        assert_eq!([1.0, ::core::f64::NAN].choose_multiple_weighted(&mut r, 1, |&w| w)
+                   .unwrap_err(), WeightedError::InvalidWeight);
+        assert_eq!([1.0, ::core::f64::INFINITY].choose_multiple_weighted(&mut r, 1, |&w| w)
+                   .unwrap_err(), WeightedError::InvalidWeight);
+    }
+
+    #[test]
+    fn test_slice_shuffle() {
+        let mut r = ::test::rng(406);
+        let empty: &mut [isize] = &mut [];
+        empty.shuffle(&mut r);
+        let mut one = [1];
+        one.shuffle(&mut r);
+        assert_eq!(one, [1]);
+
+        // All 6 permutations of 3 elements are equally likely.
+        let mut counts = [0usize; 6];
+        const N: usize = 6_000;
+        for _ in 0..N {
+            let mut x = [0, 1, 2];
+            x.shuffle(&mut r);
+            let permutation = match x {
+                [0, 1, 2] => 0,
+                [0, 2, 1] => 1,
+                [1, 0, 2] => 2,
+                [1, 2, 0] => 3,
+                [2, 0, 1] => 4,
+                [2, 1, 0] => 5,
+                _ => unreachable!(),
+            };
+            counts[permutation] += 1;
+        }
+        let p = 1.0 / 6.0;
+        let err = 5.0 * (N as f64 * p * (1.0 - p)).sqrt();
+        for &count in counts.iter() {
+            assert!((count as f64 - N as f64 * p).abs() < err, "count {}", count);
+        }
+    }
+
+    #[test]
+    fn test_shuffle_by_swap() {
+        // The same permutation as `SliceRandom::shuffle`
+        for len in 0..40 {
+            let mut a = [0usize; 40];
+            for (i, x) in a.iter_mut().enumerate() {
+                *x = i;
+            }
+            let mut b = a;
+            a[..len].shuffle(&mut ::test::rng(409));
+            shuffle_by_swap(&mut ::test::rng(409), len, |i, j| b.swap(i, j));
+            assert_eq!(&a[..], &b[..]);
+        }
+
+        // Parallel arrays stay aligned.
+        let mut r = ::test::rng(410);
+        let mut keys = [0u8, 1, 2, 3, 4, 5, 6, 7];
+        let mut values = ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'];
+        shuffle_by_swap(&mut r, keys.len(), |i, j| {
+            keys.swap(i, j);
+            values.swap(i, j);
+        });
+        for (&k, &v) in keys.iter().zip(values.iter()) {
+            assert_eq!(v as u8 - b'a', k);
+        }
+        assert!(keys != [0, 1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    #[cfg(feature="std")]
+    fn test_shuffle_weighted() {
+        let mut r = ::test::rng(462);
+        let weights = [1.0, 2.0, 3.0, 4.0, 0.0, 0.0];
+        const N: usize = 20_000;
+        let mut first = [0usize; 6];
+        let mut second_after_3 = [0usize; 6];
+        let mut after_3 = 0;
+        for _ in 0..N {
+            let mut items = [0usize, 1, 2, 3, 4, 5];
+            items.shuffle_weighted(&mut r, |&i| weights[i]).unwrap();
+            first[items[0]] += 1;
+            if items[0] == 3 {
+                after_3 += 1;
+                second_after_3[items[1]] += 1;
+            }
+            // Zero weights come last.
+            assert!(items[4] >= 4 && items[5] >= 4);
+        }
+        // The first element is chosen with probability `w / sum(w)`, and the
+        // second from the remaining weights.
+        for i in 0..6 {
+            let p = weights[i] / 10.0;
+            let err = 5.0 * (N as f64 * p * (1.0 - p)).sqrt();
+            assert!((first[i] as f64 - N as f64 * p).abs() <= err,
+                    "first {:?}", first);
+        }
+        for i in 0..3 {
+            let p = weights[i] / 6.0;
+            let n = after_3 as f64;
+            let err = 5.0 * (n * p * (1.0 - p)).sqrt();
+            assert!((second_after_3[i] as f64 - n * p).abs() < err,
+                    "second {:?}", second_after_3);
+        }
+
+        // With equal weights, all orders are equally likely, as for
+        // `shuffle`. The same holds if all weights are zero.
+        for &w in [1.0, 1e-300, 0.0].iter() {
+            let mut counts = [0usize; 27];
+            const M: usize = 6_000;
+            for _ in 0..M {
+                let mut items = [0usize, 1, 2];
+                items.shuffle_weighted(&mut r, |_| w).unwrap();
+                counts[items[0] * 9 + items[1] * 3 + items[2]] += 1;
+            }
+            let p = 1.0 / 6.0;
+            let err = 5.0 * (M as f64 * p * (1.0 - p)).sqrt();
+            for &count in counts.iter().filter(|&&c| c > 0) {
+                assert!((count as f64 - M as f64 * p).abs() < err, "{:?}", counts);
+            }
+            assert_eq!(counts.iter().filter(|&&c| c > 0).count(), 6);
+        }
+    }
+
+    #[test]
+    #[cfg(feature="std")]
+    fn test_shuffle_weighted_invalid() {
+        let mut r = ::test::rng(463);
+        let mut empty: [f64; 0] = [];
+        assert_eq!(empty.shuffle_weighted(&mut r, |&w| w), Ok(()));
+        let mut one = [5];
+        assert_eq!(one.shuffle_weighted(&mut r, |&w| w), Ok(()));
+        assert_eq!(one, [5]);
+
+        let mut values = [1.0, 2.0, -1.0, 3.0];
+        assert_eq!(values.shuffle_weighted(&mut r, |&w| w),
+                   Err(WeightedError::NegativeWeight));
+        assert_eq!(values, [1.0, 2.0, -1.0, 3.0]);
+        let mut values = [1.0, ::core::f64::NAN];
+        assert_eq!(values.shuffle_weighted(&mut r, |&w| w),
+                   Err(WeightedError::InvalidWeight));
+        let mut values = [::core::f64::INFINITY, 1.0];
+        assert_eq!(values.shuffle_weighted(&mut r, |&w| w),
+                   Err(WeightedError::InvalidWeight));
+        assert_eq!(values[1], 1.0);
+    }
+
+    #[test]
+    #[cfg(feature="std")]
+    fn test_shuffle_vec_deque() {
+        use std::collections::VecDeque;
+        // A deque which wraps around its buffer, so it is not one slice
+        let mut deque = VecDeque::with_capacity(16);
+        for i in 0..12 {
+            deque.push_back(i);
+        }
+        for _ in 0..8 {
+            let x = deque.pop_front().unwrap();
+            deque.push_back(x);
+        }
+        assert!(!deque.as_slices().1.is_empty());
+        let mut slice: Vec<u32> = deque.iter().cloned().collect();
+
+        let len = deque.len();
+        shuffle_by_swap(&mut ::test::rng(411), len, |i, j| deque.swap(i, j));
+        slice.shuffle(&mut ::test::rng(411));
+        assert_eq!(deque.into_iter().collect::<Vec<u32>>(), slice);
+    }
+
+    #[test]
+    fn test_slice_partial_shuffle() {
+        use RngCore;
+        let mut r = ::test::rng(410);
+
+        // Every ordered selection of 2 out of 4 elements is equally likely.
+        const N: usize = 12_000;
+        let mut counts = [0usize; 12];
+        for _ in 0..N {
+            let mut x = [0usize, 1, 2, 3];
+            let index = {
+                let (chosen, rest) = x.partial_shuffle(&mut r, 2);
+                assert_eq!((chosen.len(), rest.len()), (2, 2));
+                assert!(chosen[0] != chosen[1]);
+                chosen[0] * 3 + chosen[1] - (chosen[1] > chosen[0]) as usize
+            };
+            counts[index] += 1;
+            // the elements are preserved
+            x.sort();
+            assert_eq!(x, [0, 1, 2, 3]);
+        }
+        check_uniform(&counts);
+
+        // `amount >= len` is a full shuffle, identical to `shuffle`
+        for &amount in [6, 7, 100].iter() {
+            let mut r1 = ::test::rng(411);
+            let mut r2 = ::test::rng(411);
+            let mut x = [0u32, 1, 2, 3, 4, 5];
+            let mut y = x;
+            {
+                let (chosen, rest) = x.partial_shuffle(&mut r1, amount);
+                assert_eq!((chosen.len(), rest.len()), (6, 0));
+            }
+            y.shuffle(&mut r2);
+            assert_eq!(x, y);
+            assert_eq!(r1.next_u64(), r2.next_u64());
+        }
+
+        // `amount == 0` does nothing and does not use the RNG
+        let mut step = ::rngs::mock::StepRng::new(0, 1);
+        let mut x = [1, 2, 3];
+        {
+            let (chosen, rest) = x.partial_shuffle(&mut step, 0);
+            assert!(chosen.is_empty());
+            assert_eq!(rest, [1, 2, 3]);
+        }
+        assert_eq!(step.next_u64(), 0);
+
+        let empty: &mut [u8] = &mut [];
+        assert_eq!(empty.partial_shuffle(&mut r, 3).0.len(), 0);
+        let mut one = [1];
+        assert_eq!(one.partial_shuffle(&mut r, 1), (&mut [1][..], &mut [][..]));
+    }
+
+    #[test]
+    fn test_iterator_choose() {
+        let mut r = ::test::rng(402);
+        const N: usize = 9_000;
+        let mut known = [0usize; 9];
+        let mut unknown = [0usize; 9];
+        let mut filtered = [0usize; 9];
+        let mut lying = [0usize; 9];
+        let mut chunked = [0usize; 9];
+        for _ in 0..N {
+            known[(0..9).choose(&mut r).unwrap()] += 1;
+            unknown[UnhintedIterator { iter: 0..9 }.choose(&mut r).unwrap()] += 1;
+            filtered[(0..18).filter(|x| x % 2 == 0).choose(&mut r).unwrap() / 2] += 1;
+            lying[LyingHintIterator { iter: 0..9, hint: 1 }.choose(&mut r).unwrap()] += 1;
+            chunked[LyingHintIterator { iter: 0..9, hint: 4 }.choose(&mut r).unwrap()] += 1;
+        }
+        for counts in [known, unknown, filtered, lying, chunked].iter() {
+            check_uniform(counts);
+        }
+
+        assert_eq!((0..0).choose(&mut r), None);
+        assert_eq!(UnhintedIterator { iter: 0..0 }.choose(&mut r), None);
+        assert_eq!(LyingHintIterator { iter: 0..0, hint: 0 }.choose(&mut r), None);
+        assert_eq!((5..6).choose(&mut r), Some(5));
+        assert_eq!(UnhintedIterator { iter: 5..6 }.choose(&mut r), Some(5));
+        assert_eq!(LyingHintIterator { iter: 5..6, hint: 0 }.choose(&mut r), Some(5));
+        // partially known length
+        let v = (0..100).filter(|x| x % 10 == 3).choose(&mut r).unwrap();
+        assert!(v % 10 == 3);
+    }
+
+    #[test]
+    fn test_iterator_choose_over_hint() {
+        let mut r = ::test::rng(467);
+        for _ in 0..1000 {
+            assert_eq!(OverHintIterator { iter: 5..6, extra: 9 }.choose(&mut r), Some(5));
+            assert_eq!(OverHintIterator { iter: 0..0, extra: 3 }.choose(&mut r), None);
+        }
+        // Elements can not be chosen uniformly, but each of them is chosen
+        // sometimes.
+        let mut counts = [0usize; 5];
+        for _ in 0..1000 {
+            counts[OverHintIterator { iter: 0..5, extra: 5 }.choose(&mut r).unwrap()] += 1;
+        }
+        assert!(counts.iter().all(|&c| c > 0));
+    }
+
+    #[test]
+    fn test_iterator_choose_skips() {
+        use RngCore;
+        // An exact length needs only one random index
+        let mut r = ::rngs::mock::StepRng::new(0, 1);
+        assert_eq!((0..1_000_000_000_000u64).choose(&mut r), Some(0));
+        assert_eq!(r.next_u64(), 1);
+    }
+
+    #[test]
+    fn test_iterator_choose_multiple_fill() {
+        let mut r = ::test::rng(408);
+        let mut buf = [0usize; 3];
+        assert_eq!((0..0).choose_multiple_fill(&mut r, &mut buf), 0);
+        assert_eq!((0..2).choose_multiple_fill(&mut r, &mut buf), 2);
+        assert_eq!(buf, [0, 1, 0]);
+        assert_eq!((5..8).choose_multiple_fill(&mut r, &mut buf), 3);
+        assert_eq!(buf, [5, 6, 7]);
+        assert_eq!((0..10).choose_multiple_fill(&mut r, &mut []), 0);
+
+        // Each of the 10 subsets of 2 out of 5 elements is equally likely
+        const N: usize = 10_000;
+        let mut subsets = [0usize; 10];
+        let mut lying = [0usize; 10];
+        let index = |buf: [usize; 2]| {
+            let (a, b) = if buf[0] < buf[1] { (buf[0], buf[1]) } else { (buf[1], buf[0]) };
+            assert!(a < b && b < 5);
+            // index of the pair in 01 02 03 04 12 13 14 23 24 34
+            a * (7 - a) / 2 + b - 1
+        };
+        for _ in 0..N {
+            let mut buf = [0; 2];
+            assert_eq!((0..5).choose_multiple_fill(&mut r, &mut buf), 2);
+            subsets[index(buf)] += 1;
+            let iter = LyingHintIterator { iter: 0..5, hint: 2 };
+            assert_eq!(iter.choose_multiple_fill(&mut r, &mut buf), 2);
+            lying[index(buf)] += 1;
+        }
+        check_uniform(&subsets);
+        check_uniform(&lying);
+
+        // Much longer than the buffer, every position is retained with
+        // probability `k / n`, also without a size hint.
+        let mut retained = [0usize; 100];
+        let mut buf = [0usize; 5];
+        for _ in 0..N {
+            let iter = UnhintedIterator { iter: 0..100 };
+            assert_eq!(iter.choose_multiple_fill(&mut r, &mut buf), 5);
+            for &i in buf.iter() {
+                retained[i] += 1;
+            }
+        }
+        let p = 5.0 / 100.0;
+        let err = 5.0 * (N as f64 * p * (1.0 - p)).sqrt();
+        for (i, &count) in retained.iter().enumerate() {
+            assert!((count as f64 - N as f64 * p).abs() < err,
+                    "position {} retained {} times", i, count);
+        }
+    }
+
+    #[test]
+    #[cfg(feature="alloc")]
+    fn test_iterator_choose_multiple() {
+        let mut r = ::test::rng(409);
+        assert_eq!((0..0).choose_multiple(&mut r, 3), vec![]);
+        assert_eq!((0..5).choose_multiple(&mut r, 0), vec![]);
+        // no randomization happens when amount >= len
+        assert_eq!((0..5).choose_multiple(&mut r, 7), vec![0, 1, 2, 3, 4]);
+        assert_eq!(UnhintedIterator { iter: 0..5 }.choose_multiple(&mut r, 5),
+                   vec![0, 1, 2, 3, 4]);
+
+        // each element is included with probability 3/8
+        const N: usize = 8_000;
+        let mut counts = [0usize; 8];
+        for _ in 0..N {
+            let chosen = UnhintedIterator { iter: 0..8 }.choose_multiple(&mut r, 3);
+            assert_eq!(chosen.len(), 3);
+            for i in chosen {
+                counts[i] += 1;
+            }
+        }
+        check_uniform(&counts);
+    }
+
+    #[test]
+    #[cfg(feature="alloc")]
+    fn test_sample_iter() {
+        let min_val = 1;
+        let max_val = 100;
+
+        let mut r = ::test::rng(401);
+        let vals = (min_val..max_val).collect::<Vec<i32>>();
+        let small_sample = sample_iter(&mut r, vals.iter(), 5).unwrap();
+        let large_sample = sample_iter(&mut r, vals.iter(), vals.len() + 5).unwrap_err();
+
+        assert_eq!(small_sample.len(), 5);
+        assert_eq!(large_sample.len(), vals.len());
+        // no randomization happens when amount >= len
+        assert_eq!(large_sample, vals.iter().collect::<Vec<_>>());
+
+        assert!(small_sample.iter().all(|e| {
+            **e >= min_val && **e <= max_val
+        }));
+    }
+    #[test]
+    #[cfg(feature="alloc")]
+    fn test_sample_slice_boundaries() {
+        let empty: &[u8] = &[];
+
+        let mut r = ::test::rng(402);
+
+        // sample 0 items
+        assert_eq!(&sample_slice(&mut r, empty, 0)[..], [0u8; 0]);
+        assert_eq!(&sample_slice(&mut r, &[42, 2, 42], 0)[..], [0u8; 0]);
+
+        // sample 1 item
+        assert_eq!(&sample_slice(&mut r, &[42], 1)[..], [42]);
+        let v = sample_slice(&mut r, &[1, 42], 1)[0];
+        assert!(v == 1 || v == 42);
+
+        // sample "all" the items
+        let v = sample_slice(&mut r, &[42, 133], 2);
+        assert!(&v[..] == [42, 133] || v[..] == [133, 42]);
+
+        assert_eq!(&sample_indices(&mut r, 0, 0)[..], [0usize; 0]);
+        assert_eq!(&sample_indices(&mut r, 1, 0)[..], [0usize; 0]);
+        assert_eq!(&sample_indices(&mut r, 1, 1)[..], [0]);
+
+        // Make sure lucky 777's aren't lucky
+        let slice = &[42, 777];
+        let mut num_42 = 0;
+        let total = 1000;
+        for _ in 0..total {
+            let v = sample_slice(&mut r, slice, 1);
+            assert_eq!(v.len(), 1);
+            let v = v[0];
+            assert!(v == 42 || v == 777);
+            if v == 42 {
+                num_42 += 1;
+            }
+        }
+        let ratio_42 = num_42 as f64 / 1000 as f64;
+        assert!(0.4 <= ratio_42 || ratio_42 <= 0.6, "{}", ratio_42);
+    }
+
+    #[test]
+    #[cfg(feature="alloc")]
+    fn test_sample_slice() {
+        let xor_rng = XorShiftRng::from_seed;
+
+        let max_range = 100;
+        let mut r = ::test::rng(403);
+
+        for length in 1usize..max_range {
+            let amount = r.gen_range(0, length);
+            let mut seed = [0u8; 16];
+            r.fill(&mut seed);
+
+            // assert the basics work
+            let regular = sample_indices(
+                &mut xor_rng(seed), length, amount);
+            assert_eq!(regular.len(), amount);
+            assert!(regular.iter().all(|e| *e < length));
+            assert_eq!(regular, index::sample(&mut xor_rng(seed), length, amount).into_vec());
+
+            // also test that sampling the slice works
+            let vec: Vec<usize> = (0..length).collect();
+            {
+                let result = sample_slice(&mut xor_rng(seed), &vec, amount);
+                assert_eq!(result, regular);
+            }
+
+            {
+                let result = sample_slice_ref(&mut xor_rng(seed), &vec, amount);
+                let expected = regular.iter().map(|v| v).collect::<Vec<_>>();
+                assert_eq!(result, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sample_slice_fill() {
+        let mut r = ::test::rng(454);
+        let mut values = [0usize; 100];
+        for (i, x) in values.iter_mut().enumerate() {
+            *x = i;
+        }
+        // Both the small and the large path
+        for &amount in [0, 1, 5, 16, 17, 60, 100].iter() {
+            let mut out = [0usize; 100];
+            let out = &mut out[..amount];
+            let mut seen = [false; 100];
+            sample_slice_fill(&mut r, &values, out);
+            for &x in out.iter() {
+                assert!(!seen[x]);
+                seen[x] = true;
+            }
+        }
+
+        // Each element is chosen with probability `amount / len`, and the
+        // positions in the output are uniform too.
+        for &amount in [3, 20].iter() {
+            const N: usize = 5_000;
+            let mut counts = [0usize; 25];
+            let mut first = [0usize; 25];
+            let mut out = [0usize; 20];
+            let out = &mut out[..amount];
+            for _ in 0..N {
+                sample_slice_fill(&mut r, &values[..25], out);
+                for &x in out.iter() {
+                    counts[x] += 1;
+                }
+                first[out[0]] += 1;
+            }
+            let p = amount as f64 / 25.0;
+            let err = 5.0 * (N as f64 * p * (1.0 - p)).sqrt();
+            for &count in counts.iter() {
+                assert!((count as f64 - N as f64 * p).abs() < err, "count {}", count);
+            }
+            let p = 1.0 / 25.0;
+            let err = 5.0 * (N as f64 * p * (1.0 - p)).sqrt();
+            for &count in first.iter() {
+                assert!((count as f64 - N as f64 * p).abs() < err, "first {}", count);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature="alloc")]
+    fn test_sample_floyd_into() {
+        // The same indices as `index::sample` when it uses Floyd's algorithm
+        let mut r = ::test::rng(455);
+        for &(length, amount) in [(1000, 10), (1000, 16), (20, 1), (5, 5)].iter() {
+            let mut seed = [0u8; 16];
+            r.fill(&mut seed);
+            let mut buf = [0u32; SMALL_AMOUNT];
+            sample_floyd_into(&mut XorShiftRng::from_seed(seed), length, &mut buf[..amount]);
+            let expected = index::sample(&mut XorShiftRng::from_seed(seed), length, amount);
+            let buf: Vec<usize> = buf[..amount].iter().map(|&i| i as usize).collect();
+            assert_eq!(buf, expected.into_vec());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "`amount` of samples must be less than or equal to `length`")]
+    fn test_sample_slice_fill_too_many() {
+        let mut r = ::test::rng(456);
+        sample_slice_fill(&mut r, &[1, 2, 3], &mut [0; 4]);
+    }
+}
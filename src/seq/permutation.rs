@@ -0,0 +1,437 @@
+// Copyright 2018 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// https://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Permutations of indices
+
+#[cfg(not(feature="std"))] use alloc::vec::Vec;
+
+use Rng;
+use super::shuffle_by_swap;
+use super::index::{IndexVec, IndexVecIter};
+
+/// A permutation of the indices `0..len`, which can be applied to several
+/// sequences to reorder them consistently.
+///
+/// Applying a permutation `perm` to a sequence `v` moves the element at index
+/// `perm.index(i)` to index `i`, so that afterwards `v[i]` is the old
+/// `v[perm.index(i)]`. Like [`IndexVec`], the mapping is stored as `u32`
+/// values when the length fits in a `u32`.
+///
+/// # Example
+///
+/// ```
+/// use rand::thread_rng;
+/// use rand::seq::Permutation;
+///
+/// // Shuffle two parallel arrays the same way, and restore them afterwards.
+/// let mut names = ["a", "b", "c", "d"];
+/// let mut ages = [1, 2, 3, 4];
+/// let perm = Permutation::random(&mut thread_rng(), names.len());
+/// perm.apply_slice(&mut names);
+/// perm.apply_slice(&mut ages);
+/// assert!(names.iter().zip(ages.iter())
+///         .all(|(&n, &a)| n.as_bytes()[0] - b'a' + 1 == a as u8));
+///
+/// let inverse = perm.inverse();
+/// inverse.apply_slice(&mut names);
+/// inverse.apply_slice(&mut ages);
+/// assert_eq!(names, ["a", "b", "c", "d"]);
+/// assert_eq!(ages, [1, 2, 3, 4]);
+/// ```
+///
+/// [`IndexVec`]: index/enum.IndexVec.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct Permutation {
+    map: IndexVec,
+}
+
+impl Permutation {
+    /// The identity permutation of length `len`, which leaves sequences
+    /// unchanged.
+    pub fn identity(len: usize) -> Permutation {
+        Permutation::from_fn(len, |i| i)
+    }
+
+    /// A random permutation of length `len`, chosen uniformly from all
+    /// `len!` permutations.
+    ///
+    /// For the same state of `rng`, applying the result to a slice gives the
+    /// same order as [`SliceRandom::shuffle`] would, and consumes the same
+    /// random values.
+    ///
+    /// [`SliceRandom::shuffle`]: trait.SliceRandom.html#tymethod.shuffle
+    pub fn random<R: Rng + ?Sized>(rng: &mut R, len: usize) -> Permutation {
+        let mut perm = Permutation::identity(len);
+        match perm.map {
+            IndexVec::U32(ref mut v) => shuffle_by_swap(rng, len, |i, j| v.swap(i, j)),
+            IndexVec::USize(ref mut v) => shuffle_by_swap(rng, len, |i, j| v.swap(i, j)),
+        }
+        perm
+    }
+
+    /// Construct a permutation of length `len` from the values of `f` for
+    /// `0..len`, which must be a permutation.
+    fn from_fn<F: FnMut(usize) -> usize>(len: usize, mut f: F) -> Permutation {
+        let map = if len <= ::core::u32::MAX as usize {
+            IndexVec::U32((0..len).map(|i| f(i) as u32).collect())
+        } else {
+            IndexVec::USize((0..len).map(f).collect())
+        };
+        Permutation { map }
+    }
+
+    /// Construct a permutation from its mapping, which must be a
+    /// permutation of `0..map.len()`.
+    pub(crate) fn from_index_vec(map: IndexVec) -> Permutation {
+        Permutation { map }
+    }
+
+    /// Returns the number of indices permuted.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the permutation is empty.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Return the index of the element which is moved to index `i`.
+    ///
+    /// (Note: like `IndexVec`, we cannot implement `std::ops::Index` because
+    /// the indices may be stored as `u32`.)
+    pub fn index(&self, i: usize) -> usize {
+        self.map.index(i)
+    }
+
+    /// Iterate over the mapping as a sequence of `usize` values; the `i`-th
+    /// value is `self.index(i)`.
+    pub fn iter<'a>(&'a self) -> IndexVecIter<'a> {
+        self.map.iter()
+    }
+
+    /// Apply the permutation to `slice` in place.
+    ///
+    /// The elements are moved along the cycles of the permutation with
+    /// swaps. To use only `O(1)` extra memory, each cycle is processed from
+    /// its smallest index, which is found by following the cycle; this takes
+    /// `O(n log n)` time on average for a random permutation, and `O(n^2)`
+    /// in the worst case. Use [`apply_to_vec`] for a linear time copy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slice.len() != self.len()`.
+    ///
+    /// [`apply_to_vec`]: #method.apply_to_vec
+    pub fn apply_slice<T>(&self, slice: &mut [T]) {
+        assert_eq!(slice.len(), self.len(), "Permutation: length mismatch");
+        for start in 0..self.len() {
+            // Only process the cycle if `start` is its smallest index.
+            let mut j = self.index(start);
+            while j > start {
+                j = self.index(j);
+            }
+            if j < start {
+                continue;
+            }
+
+            let mut i = start;
+            loop {
+                let j = self.index(i);
+                if j == start {
+                    break;
+                }
+                slice.swap(i, j);
+                i = j;
+            }
+        }
+    }
+
+    /// Return a copy of `slice` with the permutation applied, in `O(n)` time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slice.len() != self.len()`.
+    pub fn apply_to_vec<T: Clone>(&self, slice: &[T]) -> Vec<T> {
+        assert_eq!(slice.len(), self.len(), "Permutation: length mismatch");
+        self.iter().map(|i| slice[i].clone()).collect()
+    }
+
+    /// Return the inverse permutation, which undoes the effect of `self`.
+    pub fn inverse(&self) -> Permutation {
+        let mut inverse = Permutation::identity(self.len());
+        match inverse.map {
+            IndexVec::U32(ref mut v) => {
+                for (i, j) in self.iter().enumerate() {
+                    v[j] = i as u32;
+                }
+            }
+            IndexVec::USize(ref mut v) => {
+                for (i, j) in self.iter().enumerate() {
+                    v[j] = i;
+                }
+            }
+        }
+        inverse
+    }
+
+    /// Return the permutation which applies `self` first and `other` second.
+    ///
+    /// Applying the result to a sequence has the same effect as applying
+    /// `self` and then `other`, so `perm.compose(&perm.inverse())` is the
+    /// identity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other.len() != self.len()`.
+    pub fn compose(&self, other: &Permutation) -> Permutation {
+        assert_eq!(other.len(), self.len(), "Permutation: length mismatch");
+        Permutation::from_fn(self.len(), |i| self.index(other.index(i)))
+    }
+
+    /// Return the mapping as an `IndexVec`.
+    pub fn into_index_vec(self) -> IndexVec {
+        self.map
+    }
+}
+
+/// Return a random derangement of `0..len`: a permutation without fixed
+/// points, so that `result[i] != i` for every `i`.
+///
+/// The result is chosen uniformly from all derangements of `len` elements,
+/// for example for a "secret santa" assignment where `result[i]` is the
+/// recipient of `i`. Returns `None` if `len == 1`, as the only permutation
+/// of one element has a fixed point; for `len == 0` the result is empty.
+///
+/// This is a Fisher-Yates shuffle which is restarted as soon as an element
+/// is placed at its own index. About `e ≈ 2.72` passes are needed on
+/// average, and most failing passes are aborted early.
+///
+/// # Example
+///
+/// ```
+/// use rand::thread_rng;
+/// use rand::seq::derangement;
+///
+/// let santas = derangement(&mut thread_rng(), 5).unwrap();
+/// assert!(santas.iter().enumerate().all(|(giver, &receiver)| giver != receiver));
+/// ```
+pub fn derangement<R: Rng + ?Sized>(rng: &mut R, len: usize) -> Option<Vec<usize>> {
+    if len == 1 {
+        return None;
+    }
+    let mut result: Vec<usize> = (0..len).collect();
+    'restart: loop {
+        for (i, x) in result.iter_mut().enumerate() {
+            *x = i;
+        }
+        let mut i = len;
+        while i >= 2 {
+            i -= 1;
+            let j = rng.gen_index(i + 1);
+            result.swap(i, j);
+            // Index `i` is final, so a fixed point cannot be undone.
+            if result[i] == i {
+                continue 'restart;
+            }
+        }
+        if len == 0 || result[0] != 0 {
+            return Some(result);
+        }
+    }
+}
+
+/// Return a random cyclic permutation of `0..len`, which consists of a
+/// single cycle of length `len`.
+///
+/// Following `i`, `result[i]`, `result[result[i]]` and so on visits every
+/// index before returning to `i`. The result is chosen uniformly from all
+/// `(len - 1)!` such permutations using Sattolo's algorithm. For `len <= 1`
+/// this is the identity.
+///
+/// # Example
+///
+/// ```
+/// use rand::thread_rng;
+/// use rand::seq::random_cycle;
+///
+/// // A random round trip through 6 cities, starting anywhere
+/// let next = random_cycle(&mut thread_rng(), 6);
+/// let mut city = 0;
+/// for _ in 0..6 {
+///     city = next[city];
+/// }
+/// assert_eq!(city, 0);
+/// ```
+///
+/// # References
+///
+/// S. Sattolo, *An algorithm to generate a random cyclic permutation*,
+/// Information Processing Letters 22(6), 1986, 315-317.
+pub fn random_cycle<R: Rng + ?Sized>(rng: &mut R, len: usize) -> Vec<usize> {
+    let mut result: Vec<usize> = (0..len).collect();
+    let mut i = len;
+    while i >= 2 {
+        i -= 1;
+        // Unlike Fisher-Yates, the element is never swapped with itself.
+        let j = rng.gen_index(i);
+        result.swap(i, j);
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use seq::SliceRandom;
+    #[cfg(not(feature="std"))] use alloc::vec::Vec;
+
+    #[test]
+    fn test_permutation_matches_shuffle() {
+        for &len in [0, 1, 2, 7, 100].iter() {
+            let mut rng1 = ::test::rng(446);
+            let mut rng2 = ::test::rng(446);
+            let mut shuffled: Vec<usize> = (0..len).collect();
+            shuffled.shuffle(&mut rng1);
+            let perm = Permutation::random(&mut rng2, len);
+            assert_eq!(perm.iter().collect::<Vec<_>>(), shuffled);
+
+            let mut data: Vec<usize> = (100..100 + len).collect();
+            perm.apply_slice(&mut data);
+            let expected: Vec<usize> = shuffled.iter().map(|&i| i + 100).collect();
+            assert_eq!(data, expected);
+            assert_eq!(perm.apply_to_vec(&(100..100 + len).collect::<Vec<_>>()), expected);
+            // Both generators are in the same state afterwards.
+            assert_eq!(rng1.gen::<u64>(), rng2.gen::<u64>());
+        }
+    }
+
+    #[test]
+    fn test_permutation_round_trip() {
+        let mut rng = ::test::rng(447);
+        for len in 0..50 {
+            let perm = Permutation::random(&mut rng, len);
+            let inverse = perm.inverse();
+            let original: Vec<u32> = (0..len).map(|_| rng.gen()).collect();
+
+            let mut data = original.clone();
+            perm.apply_slice(&mut data);
+            assert_eq!(data, perm.apply_to_vec(&original));
+            inverse.apply_slice(&mut data);
+            assert_eq!(data, original);
+
+            assert_eq!(perm.compose(&inverse), Permutation::identity(len));
+            assert_eq!(inverse.compose(&perm), Permutation::identity(len));
+            assert_eq!(inverse.inverse(), perm);
+        }
+    }
+
+    #[test]
+    fn test_permutation_compose() {
+        let mut rng = ::test::rng(448);
+        for len in 0..8 {
+            for _ in 0..20 {
+                let a = Permutation::random(&mut rng, len);
+                let b = Permutation::random(&mut rng, len);
+                let c = Permutation::random(&mut rng, len);
+                assert_eq!(a.compose(&b).compose(&c), a.compose(&b.compose(&c)));
+
+                // Composing is the same as applying one after the other.
+                let mut data: Vec<usize> = (0..len).collect();
+                a.apply_slice(&mut data);
+                b.apply_slice(&mut data);
+                assert_eq!(data, a.compose(&b).iter().collect::<Vec<_>>());
+            }
+        }
+    }
+
+    #[test]
+    fn test_derangement() {
+        let mut rng = ::test::rng(450);
+        assert_eq!(derangement(&mut rng, 0), Some(Vec::new()));
+        assert_eq!(derangement(&mut rng, 1), None);
+        assert_eq!(derangement(&mut rng, 2), Some(vec![1, 0]));
+        for len in 2..40 {
+            for _ in 0..20 {
+                let d = derangement(&mut rng, len).unwrap();
+                let mut sorted = d.clone();
+                sorted.sort();
+                assert_eq!(sorted, (0..len).collect::<Vec<_>>());
+                assert!(d.iter().enumerate().all(|(i, &j)| i != j));
+            }
+        }
+    }
+
+    #[test]
+    fn test_derangement_uniform() {
+        // The 9 derangements of 4 elements, in lexicographic order
+        let all = [
+            [1, 0, 3, 2], [1, 2, 3, 0], [1, 3, 0, 2],
+            [2, 0, 3, 1], [2, 3, 0, 1], [2, 3, 1, 0],
+            [3, 0, 1, 2], [3, 2, 0, 1], [3, 2, 1, 0],
+        ];
+        let mut rng = ::test::rng(451);
+        const N: usize = 90_000;
+        let mut counts = [0usize; 9];
+        for _ in 0..N {
+            let d = derangement(&mut rng, 4).unwrap();
+            let k = all.iter().position(|p| p[..] == d[..]).unwrap();
+            counts[k] += 1;
+        }
+        let p = 1.0 / 9.0;
+        let err = 5.0 * (p * (1.0 - p) / N as f64).sqrt();
+        for &count in counts.iter() {
+            assert!((count as f64 / N as f64 - p).abs() < err, "{:?}", counts);
+        }
+    }
+
+    #[test]
+    fn test_random_cycle() {
+        let mut rng = ::test::rng(452);
+        assert_eq!(random_cycle(&mut rng, 0), Vec::<usize>::new());
+        assert_eq!(random_cycle(&mut rng, 1), vec![0]);
+        for len in 2..40 {
+            for _ in 0..20 {
+                let cycle = random_cycle(&mut rng, len);
+                // Following the cycle from 0 visits every index once.
+                let mut seen = vec![false; len];
+                let mut i = 0;
+                for _ in 0..len {
+                    assert!(!seen[i]);
+                    seen[i] = true;
+                    i = cycle[i];
+                }
+                assert_eq!(i, 0);
+            }
+        }
+
+        // All (4 - 1)! = 6 cycles of 4 elements are equally likely.
+        const N: usize = 60_000;
+        let mut counts = [0usize; 6];
+        for _ in 0..N {
+            let c = random_cycle(&mut rng, 4);
+            // A cycle is determined by the order in which it visits 1, 2, 3.
+            let (a, b) = (c[0], c[c[0]]);
+            counts[(a - 1) * 2 + if b < 6 - a - b { 0 } else { 1 }] += 1;
+        }
+        let p = 1.0 / 6.0;
+        let err = 5.0 * (p * (1.0 - p) / N as f64).sqrt();
+        for &count in counts.iter() {
+            assert!((count as f64 / N as f64 - p).abs() < err, "{:?}", counts);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Permutation: length mismatch")]
+    fn test_permutation_length_mismatch() {
+        let perm = Permutation::identity(3);
+        perm.apply_slice(&mut [1, 2]);
+    }
+}
@@ -14,7 +14,7 @@ use rand::distributions::{Distribution, Bernoulli};
 fn large_probability() {
     let p = 1. - ::core::f64::EPSILON / 2.;
     assert!(p < 1.);
-    let d = Bernoulli::new(p);
+    let d = Bernoulli::new(p).unwrap();
     let mut rng = SmallRng::from_seed(
         [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]);
     for _ in 0..10 {
@@ -0,0 +1,127 @@
+#![cfg(feature = "derive")]
+
+extern crate rand;
+
+use rand::{Rng, SeedableRng};
+use rand::rngs::mock::StepRng;
+use rand::prng::XorShiftRng;
+use rand::distributions::{Distribution, Standard, Uniform};
+
+#[derive(Debug, PartialEq, Distribution)]
+struct Inner {
+    a: u32,
+    b: u32,
+}
+
+#[derive(Debug, PartialEq, Distribution)]
+struct Outer {
+    first: u64,
+    inner: Inner,
+    #[rand(dist = "Uniform::new(0, 10)")]
+    small: u8,
+    flag: bool,
+}
+
+#[derive(Debug, PartialEq, Distribution)]
+struct Tuple(u32, #[rand(dist = "Uniform::new_inclusive(-1.0, 1.0)")] f64);
+
+#[derive(Debug, PartialEq, Distribution)]
+struct Unit;
+
+#[derive(Debug, PartialEq, Distribution)]
+struct Generic<T> {
+    value: T,
+    count: u16,
+}
+
+fn rng() -> XorShiftRng {
+    XorShiftRng::from_seed([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16])
+}
+
+#[test]
+fn field_order() {
+    // Each `u32` is a separate `next_u32`, so the fields see the counter in
+    // the order of declaration.
+    let mut rng = StepRng::new(1, 1);
+    let inner: Inner = rng.gen();
+    assert_eq!(inner, Inner { a: 1, b: 2 });
+
+    let mut rng = StepRng::new(10, 1);
+    let outer: Outer = rng.gen();
+    assert_eq!(outer.first, 10);
+    assert_eq!(outer.inner, Inner { a: 11, b: 12 });
+}
+
+#[test]
+fn matches_manual_sampling() {
+    let mut rng1 = rng();
+    let mut rng2 = rng();
+    for _ in 0..100 {
+        let outer: Outer = rng1.gen();
+        let expected = Outer {
+            first: rng2.gen(),
+            inner: Inner { a: rng2.gen(), b: rng2.gen() },
+            small: rng2.sample(Uniform::new(0, 10)),
+            flag: rng2.gen(),
+        };
+        assert_eq!(outer, expected);
+        assert!(outer.small < 10);
+    }
+}
+
+#[test]
+fn tuple_unit_and_generic() {
+    let mut rng1 = rng();
+    let mut rng2 = rng();
+    for _ in 0..100 {
+        let t: Tuple = rng1.gen();
+        assert_eq!(t, Tuple(rng2.gen(), rng2.sample(Uniform::new_inclusive(-1.0, 1.0))));
+        let _: Unit = Standard.sample(&mut rng1);
+        let g: Generic<Inner> = rng1.gen();
+        let expected = Generic {
+            value: Inner { a: rng2.gen(), b: rng2.gen() },
+            count: rng2.gen(),
+        };
+        assert_eq!(g, expected);
+    }
+}
+
+mod money {
+    use rand::distributions::uniform::SampleUniform;
+
+    /// An amount in cents.
+    #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, SampleUniform)]
+    pub struct Money(pub i64);
+
+    #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, SampleUniform)]
+    pub struct Weight {
+        pub kg: f64,
+    }
+}
+
+use money::{Money, UniformMoney, Weight};
+
+#[test]
+fn sample_uniform_newtype() {
+    use rand::distributions::uniform::UniformSampler;
+
+    let mut rng1 = rng();
+    let mut rng2 = rng();
+    for _ in 0..100 {
+        let x = rng1.gen_range(Money(-500), Money(1000));
+        assert_eq!(x, Money(rng2.gen_range(-500, 1000)));
+
+        let x = rng1.sample(Uniform::new_inclusive(Money(0), Money(3)));
+        assert_eq!(x, Money(rng2.sample(Uniform::new_inclusive(0, 3))));
+
+        let x = Uniform::sample_single(Money(7), Money(8), &mut rng1);
+        assert_eq!(x, Money(Uniform::sample_single(7, 8, &mut rng2)));
+
+        let x = UniformMoney::new(Money(::std::i64::MIN), Money(0)).sample(&mut rng1);
+        assert_eq!(x, Money(Uniform::new(::std::i64::MIN, 0).sample(&mut rng2)));
+
+        let w = rng1.gen_range(Weight { kg: 1.5 }, Weight { kg: 2.5 });
+        assert_eq!(w, Weight { kg: rng2.gen_range(1.5, 2.5) });
+    }
+    assert!(Uniform::try_new(Money(3), Money(3)).is_err());
+}
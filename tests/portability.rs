@@ -0,0 +1,67 @@
+//! Results which must be the same on 32-bit and 64-bit platforms.
+//!
+//! The expected values are computed on a 64-bit platform; CI runs these tests
+//! on 32-bit targets as well.
+
+#![cfg(feature="std")]
+
+extern crate rand;
+
+use rand::{Rng, SeedableRng};
+use rand::prng::ChaChaRng;
+use rand::distributions::{Distribution, Uniform};
+use rand::seq::{SliceRandom, IteratorRandom, index};
+
+fn rng() -> ChaChaRng {
+    ChaChaRng::from_seed([7; 32])
+}
+
+#[test]
+fn uniform_usize() {
+    let mut r = rng();
+    let v: Vec<usize> = (0..10).map(|_| r.gen_range(0usize, 1000)).collect();
+    assert_eq!(v, [257, 439, 342, 897, 135, 463, 669, 421, 988, 401]);
+    let v: Vec<isize> = (0..10).map(|_| r.gen_range(-500isize, 500)).collect();
+    assert_eq!(v, [-259, -467, 452, 229, -18, -336, -294, 368, -209, -283]);
+    let distr = Uniform::new_inclusive(0usize, 1 << 20);
+    let v: Vec<usize> = (0..10).map(|_| distr.sample(&mut r)).collect();
+    assert_eq!(v, [657232, 131241, 658360, 673868, 449542,
+                   967336, 390913, 587642, 58030, 268924]);
+
+    // `usize` and `isize` are sampled like `u64` and `i64`.
+    let (mut r1, mut r2) = (rng(), rng());
+    for _ in 0..100 {
+        assert_eq!(r1.gen_range(3usize, 12345) as u64, r2.gen_range(3u64, 12345));
+        assert_eq!(r1.gen_range(-7isize, 99) as i64, r2.gen_range(-7i64, 99));
+    }
+}
+
+#[test]
+fn slice() {
+    let mut r = rng();
+    let mut v: Vec<u32> = (0..12).collect();
+    v.shuffle(&mut r);
+    assert_eq!(v, [1, 9, 11, 8, 4, 0, 10, 6, 3, 7, 2, 5]);
+    let chosen: Vec<u32> = (0..10).map(|_| *v.choose(&mut r).unwrap()).collect();
+    assert_eq!(chosen, [0, 11, 3, 4, 0, 1, 5, 1, 4, 7]);
+    let mut v: Vec<u32> = (0..12).collect();
+    let (chosen, _) = v.partial_shuffle(&mut r, 4);
+    assert_eq!(chosen, [5, 0, 8, 2]);
+}
+
+#[test]
+fn indices_and_iterators() {
+    let mut r = rng();
+    // Floyd's algorithm, the in-place shuffle and rejection sampling
+    assert_eq!(index::sample(&mut r, 1000, 8).into_vec(),
+               [467, 256, 724, 437, 859, 341, 10, 897]);
+    assert_eq!(index::sample(&mut r, 100, 80).into_vec()[..8],
+               [85, 14, 38, 47, 27, 68, 44, 46]);
+    assert_eq!(index::sample(&mut r, 1_000_000, 100).into_vec()[..8],
+               [52507, 478724, 720329, 934280, 758478, 204884, 507517, 440756]);
+
+    assert_eq!((0..1000).choose(&mut r), Some(504));
+    let mut buf = [0; 5];
+    assert_eq!((0..1000).choose_multiple_fill(&mut r, &mut buf), 5);
+    assert_eq!(buf, [549, 364, 70, 644, 445]);
+}
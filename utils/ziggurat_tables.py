@@ -26,6 +26,7 @@
 
 from math import exp, sqrt, log, floor
 import random
+import struct
 
 # The order should match the return value of `tables`
 TABLE_NAMES = ['X', 'F']
@@ -89,19 +90,41 @@ def render_static(name, type, value):
     # no space or
     return 'pub static %s: %s =%s;\n' % (name, type, value)
 
-# static `name`: [`type`, .. `len(values)`] =
+def render_const(name, type, value):
+    return 'pub const %s: %s =%s;\n' % (name, type, value)
+
+def to_f32(x):
+    return struct.unpack('f', struct.pack('f', x))[0]
+
+# The shortest decimal representation which rounds to the same `f32`.
+def format_f32(x):
+    x = to_f32(x)
+    for precision in range(1, 10):
+        s = '%.*g' % (precision, x)
+        if to_f32(float(s)) == x:
+            break
+    if '.' not in s and 'e' not in s:
+        s += '.0'
+    return s
+
+def format_value(type, x):
+    if type == 'f32':
+        return format_f32(x)
+    return '%.18f' % x
+
+# static `name`: [`type`; `len(values)`] =
 #     [values[0], ..., values[3],
 #      values[4], ..., values[7],
 #      ... ];
-def render_table(name, values):
+def render_table(name, type, values):
     rows = []
     # 4 values on each row
     for i in range(0, len(values), 4):
         row = values[i:i+4]
-        rows.append(', '.join('%.18f' % f for f in row))
+        rows.append(', '.join(format_value(type, f) for f in row))
 
     rendered = '\n    [%s]' % ',\n     '.join(rows)
-    return render_static(name, '[f64, .. %d]' % len(values), rendered)
+    return render_static(name, '[%s; %d]' % (type, len(values)), rendered)
 
 
 with open('ziggurat_tables.rs', 'w') as f:
@@ -118,10 +141,15 @@ with open('ziggurat_tables.rs', 'w') as f:
 // Tables for distributions which are sampled using the ziggurat
 // algorithm. Autogenerated by `ziggurat_tables.py`.
 
-pub type ZigTable = &\'static [f64, .. %d];
-'''  % (TABLE_LEN + 1))
-    for name, tables, r in [('NORM', NORM, NORM_R),
-                            ('EXP', EXP, EXP_R)]:
-        f.write(render_static('ZIG_%s_R' % name, 'f64', ' %.18f' % r))
-        for (tabname, table) in zip(TABLE_NAMES, tables):
-            f.write(render_table('ZIG_%s_%s' % (name, tabname), table))
+pub type ZigTable = &\'static [f64; %d];
+pub type ZigTableF32 = &\'static [f32; %d];
+'''  % (TABLE_LEN + 1, TABLE_LEN + 1))
+    # The `f32` tables are the same tables rounded to single precision.
+    for type, suffix in [('f64', ''), ('f32', '_F32')]:
+        for name, tables, r in [('NORM', NORM, NORM_R),
+                                ('EXP', EXP, EXP_R)]:
+            f.write(render_const('ZIG_%s_R%s' % (name, suffix), type,
+                                 ' ' + format_value(type, r)))
+            for (tabname, table) in zip(TABLE_NAMES, tables):
+                f.write(render_table('ZIG_%s_%s%s' % (name, tabname, suffix),
+                                     type, table))